@@ -13,8 +13,15 @@ pub mod prelude {
 
 pub mod types {
     pub use pedronauck_streams_domains::{
+        abis::types::*,
+        accounts::types::*,
+        balances::types::*,
         blocks::types::*,
+        bundles::types::*,
+        coins::types::*,
+        contracts::types::*,
         inputs::types::*,
+        messages::types::*,
         outputs::types::*,
         receipts::types::*,
         transactions::types::*,
@@ -27,8 +34,14 @@ pub mod types {
 
 pub mod subjects {
     pub use pedronauck_streams_domains::{
+        accounts::subjects::*,
+        balances::subjects::*,
         blocks::subjects::*,
+        bundles::subjects::*,
+        coins::subjects::*,
+        contracts::subjects::*,
         inputs::subjects::*,
+        messages::subjects::*,
         outputs::subjects::*,
         receipts::subjects::*,
         transactions::subjects::*,
@@ -45,8 +58,14 @@ macro_rules! export_module {
     };
 }
 
+export_module!(accounts);
+export_module!(balances);
 export_module!(blocks);
+export_module!(bundles);
+export_module!(coins);
+export_module!(contracts);
 export_module!(inputs);
+export_module!(messages);
 export_module!(outputs);
 export_module!(receipts);
 export_module!(transactions);
@@ -1,14 +1,23 @@
-use pedronauck_streams_types::BlockHeight;
+use pedronauck_streams_store::record::RecordPointer;
+use pedronauck_streams_types::{BlockHeight, BlockTimestamp};
 use serde::{self, Deserialize, Deserializer, Serialize};
 
 #[derive(Debug, thiserror::Error)]
 pub enum DeliverPolicyError {
-    #[error("Invalid deliver policy format. Expected 'new', 'from_block:<height>', or 'from_block=<height>'")]
+    #[error("Invalid deliver policy format. Expected 'new', 'from_block:<height>', 'from_to:<from>-<to>', 'last_blocks:<count>' or 'from_timestamp:<unix_secs>' or 'resume:<pointer>' (':' can also be '=')")]
     InvalidFormat,
     #[error("Block height cannot be empty")]
     EmptyBlockHeight,
     #[error("Invalid block height '{0}': must be a positive number")]
     InvalidBlockHeight(String),
+    #[error("Invalid block range '{0}': 'from' must not be greater than 'to'")]
+    InvalidBlockRange(String),
+    #[error("Invalid block count '{0}': must be a positive number")]
+    InvalidBlockCount(String),
+    #[error("Invalid timestamp '{0}': must be unix seconds")]
+    InvalidTimestamp(String),
+    #[error("Invalid resume pointer '{0}': expected '<height>[,tx_index=<n>][,input_index=<n>|,output_index=<n>|,receipt_index=<n>][,fanout_index=<n>]'")]
+    InvalidPointer(String),
 }
 
 #[derive(Hash, Debug, Default, Serialize, Clone, PartialEq, Eq, Copy)]
@@ -20,6 +29,45 @@ pub enum DeliverPolicy {
         #[serde(rename = "blockHeight")]
         block_height: BlockHeight,
     },
+    FromTo {
+        from: BlockHeight,
+        to: BlockHeight,
+    },
+    LastBlocks(u64),
+    FromTimestamp(BlockTimestamp),
+    /// Resumes strictly after the given pointer, as received in the
+    /// `pointer` field of a previous response.
+    Resume(RecordPointer),
+}
+
+impl DeliverPolicy {
+    /// Last block delivered by this policy, if the stream is bounded.
+    pub fn to_block(&self) -> Option<BlockHeight> {
+        match self {
+            DeliverPolicy::FromTo { to, .. } => Some(*to),
+            _ => None,
+        }
+    }
+
+    /// Pointer the stream has to start strictly after, if resuming.
+    pub fn after_pointer(&self) -> Option<RecordPointer> {
+        match self {
+            DeliverPolicy::Resume(pointer) => Some(*pointer),
+            _ => None,
+        }
+    }
+
+    fn from_to(
+        from: BlockHeight,
+        to: BlockHeight,
+    ) -> Result<Self, DeliverPolicyError> {
+        if from > to {
+            return Err(DeliverPolicyError::InvalidBlockRange(format!(
+                "{from}-{to}"
+            )));
+        }
+        Ok(DeliverPolicy::FromTo { from, to })
+    }
 }
 
 impl std::fmt::Display for DeliverPolicy {
@@ -29,40 +77,77 @@ impl std::fmt::Display for DeliverPolicy {
             DeliverPolicy::FromBlock { block_height } => {
                 write!(f, "from_block:{}", block_height)
             }
+            DeliverPolicy::FromTo { from, to } => {
+                write!(f, "from_to:{}-{}", from, to)
+            }
+            DeliverPolicy::LastBlocks(count) => {
+                write!(f, "last_blocks:{}", count)
+            }
+            DeliverPolicy::FromTimestamp(timestamp) => {
+                write!(f, "from_timestamp:{}", timestamp)
+            }
+            DeliverPolicy::Resume(pointer) => write!(f, "resume:{}", pointer),
         }
     }
 }
 
+fn strip_policy_prefix<'a>(value: &'a str, name: &str) -> Option<&'a str> {
+    value
+        .strip_prefix(name)
+        .and_then(|rest| {
+            rest.strip_prefix(':').or_else(|| rest.strip_prefix('='))
+        })
+        .map(str::trim)
+}
+
+fn parse_block_height(value: &str) -> Result<BlockHeight, DeliverPolicyError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(DeliverPolicyError::EmptyBlockHeight);
+    }
+    value
+        .parse::<BlockHeight>()
+        .map_err(|_| DeliverPolicyError::InvalidBlockHeight(value.to_string()))
+}
+
 impl std::str::FromStr for DeliverPolicy {
     type Err = DeliverPolicyError;
     fn from_str(value: &str) -> Result<Self, Self::Err> {
-        match value {
-            "new" => Ok(DeliverPolicy::New),
-            value
-                if value.starts_with("from_block:")
-                    || value.starts_with("from_block=") =>
-            {
-                let block_height = value
-                    .strip_prefix("from_block:")
-                    .or_else(|| value.strip_prefix("from_block="))
-                    .ok_or(DeliverPolicyError::InvalidFormat)?
-                    .trim();
-
-                if block_height.is_empty() {
-                    return Err(DeliverPolicyError::EmptyBlockHeight);
-                }
-
-                let block_height =
-                    block_height.parse::<BlockHeight>().map_err(|_| {
-                        DeliverPolicyError::InvalidBlockHeight(
-                            block_height.to_string(),
-                        )
-                    })?;
-
-                Ok(DeliverPolicy::FromBlock { block_height })
-            }
-            _ => Err(DeliverPolicyError::InvalidFormat),
+        if value == "new" {
+            return Ok(DeliverPolicy::New);
+        }
+        if let Some(block_height) = strip_policy_prefix(value, "from_block") {
+            let block_height = parse_block_height(block_height)?;
+            return Ok(DeliverPolicy::FromBlock { block_height });
+        }
+        if let Some(range) = strip_policy_prefix(value, "from_to") {
+            let (from, to) = range.split_once('-').ok_or_else(|| {
+                DeliverPolicyError::InvalidBlockRange(range.to_string())
+            })?;
+            let from = parse_block_height(from)?;
+            let to = parse_block_height(to)?;
+            return DeliverPolicy::from_to(from, to);
+        }
+        if let Some(count) = strip_policy_prefix(value, "last_blocks") {
+            let count = count.parse::<u64>().map_err(|_| {
+                DeliverPolicyError::InvalidBlockCount(count.to_string())
+            })?;
+            return Ok(DeliverPolicy::LastBlocks(count));
+        }
+        if let Some(timestamp) = strip_policy_prefix(value, "from_timestamp") {
+            let timestamp =
+                timestamp.parse::<BlockTimestamp>().map_err(|_| {
+                    DeliverPolicyError::InvalidTimestamp(timestamp.to_string())
+                })?;
+            return Ok(DeliverPolicy::FromTimestamp(timestamp));
+        }
+        if let Some(pointer) = strip_policy_prefix(value, "resume") {
+            let pointer = pointer.parse::<RecordPointer>().map_err(|_| {
+                DeliverPolicyError::InvalidPointer(pointer.to_string())
+            })?;
+            return Ok(DeliverPolicy::Resume(pointer));
         }
+        Err(DeliverPolicyError::InvalidFormat)
     }
 }
 
@@ -84,6 +169,21 @@ impl<'de> Deserialize<'de> for DeliverPolicy {
                 #[serde(rename = "fromBlock")]
                 from_block: BlockHeightStr,
             },
+            FromTo {
+                #[serde(rename = "fromTo")]
+                from_to: BlockRange,
+            },
+            LastBlocks {
+                #[serde(rename = "lastBlocks")]
+                last_blocks: NumberOrString,
+            },
+            FromTimestamp {
+                #[serde(rename = "fromTimestamp")]
+                from_timestamp: NumberOrString,
+            },
+            Resume {
+                resume: RecordPointer,
+            },
         }
 
         #[derive(Deserialize)]
@@ -98,6 +198,28 @@ impl<'de> Deserialize<'de> for DeliverPolicy {
             block_height: String,
         }
 
+        #[derive(Deserialize)]
+        struct BlockRange {
+            from: NumberOrString,
+            to: NumberOrString,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum NumberOrString {
+            Number(u64),
+            String(String),
+        }
+
+        impl NumberOrString {
+            fn into_string(self) -> String {
+                match self {
+                    NumberOrString::Number(value) => value.to_string(),
+                    NumberOrString::String(value) => value,
+                }
+            }
+        }
+
         let helper = PolicyHelper::deserialize(deserializer)?;
         match helper {
             PolicyHelper::String(s) => {
@@ -116,6 +238,36 @@ impl<'de> Deserialize<'de> for DeliverPolicy {
                         .map_err(serde::de::Error::custom)?,
                 })
             }
+            PolicyHelper::FromTo { from_to } => {
+                let from = parse_block_height(&from_to.from.into_string())
+                    .map_err(serde::de::Error::custom)?;
+                let to = parse_block_height(&from_to.to.into_string())
+                    .map_err(serde::de::Error::custom)?;
+                DeliverPolicy::from_to(from, to)
+                    .map_err(serde::de::Error::custom)
+            }
+            PolicyHelper::LastBlocks { last_blocks } => {
+                let count = last_blocks.into_string();
+                let count = count.parse::<u64>().map_err(|_| {
+                    serde::de::Error::custom(
+                        DeliverPolicyError::InvalidBlockCount(count),
+                    )
+                })?;
+                Ok(DeliverPolicy::LastBlocks(count))
+            }
+            PolicyHelper::FromTimestamp { from_timestamp } => {
+                let timestamp = from_timestamp.into_string();
+                let timestamp =
+                    timestamp.parse::<BlockTimestamp>().map_err(|_| {
+                        serde::de::Error::custom(
+                            DeliverPolicyError::InvalidTimestamp(timestamp),
+                        )
+                    })?;
+                Ok(DeliverPolicy::FromTimestamp(timestamp))
+            }
+            PolicyHelper::Resume { resume } => {
+                Ok(DeliverPolicy::Resume(resume))
+            }
         }
     }
 }
@@ -158,6 +310,139 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_bounded_string_deserialization() {
+        let json = r#""from_to:10-20""#;
+        let policy: DeliverPolicy = serde_json::from_str(json).unwrap();
+        assert_eq!(policy, DeliverPolicy::FromTo {
+            from: 10.into(),
+            to: 20.into()
+        });
+
+        let json = r#""last_blocks=50""#;
+        let policy: DeliverPolicy = serde_json::from_str(json).unwrap();
+        assert_eq!(policy, DeliverPolicy::LastBlocks(50));
+
+        let json = r#""from_timestamp:1234567890""#;
+        let policy: DeliverPolicy = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            policy,
+            DeliverPolicy::FromTimestamp(BlockTimestamp::from_secs(1234567890))
+        );
+    }
+
+    #[test]
+    fn test_bounded_object_deserialization() {
+        let json = r#"{"fromTo": {"from": 10, "to": "20"}}"#;
+        let policy: DeliverPolicy = serde_json::from_str(json).unwrap();
+        assert_eq!(policy, DeliverPolicy::FromTo {
+            from: 10.into(),
+            to: 20.into()
+        });
+
+        let json = r#"{"lastBlocks": 50}"#;
+        let policy: DeliverPolicy = serde_json::from_str(json).unwrap();
+        assert_eq!(policy, DeliverPolicy::LastBlocks(50));
+
+        let json = r#"{"fromTimestamp": 1234567890}"#;
+        let policy: DeliverPolicy = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            policy,
+            DeliverPolicy::FromTimestamp(BlockTimestamp::from_secs(1234567890))
+        );
+    }
+
+    #[test]
+    fn test_bounded_roundtrip() {
+        let policies = [
+            DeliverPolicy::FromTo {
+                from: 10.into(),
+                to: 20.into(),
+            },
+            DeliverPolicy::LastBlocks(50),
+            DeliverPolicy::FromTimestamp(BlockTimestamp::from_secs(1234567890)),
+        ];
+        for policy in policies {
+            let parsed: DeliverPolicy = policy.to_string().parse().unwrap();
+            assert_eq!(parsed, policy);
+            let json = serde_json::to_string(&policy).unwrap();
+            let parsed: DeliverPolicy = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, policy);
+        }
+    }
+
+    #[test]
+    fn test_invalid_bounded_formats() {
+        // `from` greater than `to`
+        let result = "from_to:20-10".parse::<DeliverPolicy>();
+        assert!(matches!(
+            result,
+            Err(DeliverPolicyError::InvalidBlockRange(_))
+        ));
+
+        let json = r#"{"fromTo": {"from": 20, "to": 10}}"#;
+        let result: Result<DeliverPolicy, _> = serde_json::from_str(json);
+        assert!(result.is_err());
+
+        // Missing range separator
+        let result = "from_to:10".parse::<DeliverPolicy>();
+        assert!(result.is_err());
+
+        let result = "last_blocks:-1".parse::<DeliverPolicy>();
+        assert!(matches!(
+            result,
+            Err(DeliverPolicyError::InvalidBlockCount(_))
+        ));
+
+        let result = "from_timestamp:yesterday".parse::<DeliverPolicy>();
+        assert!(matches!(
+            result,
+            Err(DeliverPolicyError::InvalidTimestamp(_))
+        ));
+    }
+
+    #[test]
+    fn test_resume_deserialization() {
+        let expected = DeliverPolicy::Resume(RecordPointer {
+            block_height: 120.into(),
+            tx_index: Some(3),
+            receipt_index: Some(7),
+            ..Default::default()
+        });
+
+        let json = r#""resume:120,tx_index=3,receipt_index=7""#;
+        let policy: DeliverPolicy = serde_json::from_str(json).unwrap();
+        assert_eq!(policy, expected);
+
+        // Same shape as the `pointer` field of a response
+        let json = r#"{"resume": {"block_height": "120", "tx_index": 3, "receipt_index": 7}}"#;
+        let policy: DeliverPolicy = serde_json::from_str(json).unwrap();
+        assert_eq!(policy, expected);
+
+        let parsed: DeliverPolicy = expected.to_string().parse().unwrap();
+        assert_eq!(parsed, expected);
+        let json = serde_json::to_string(&expected).unwrap();
+        let parsed: DeliverPolicy = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, expected);
+    }
+
+    #[test]
+    fn test_invalid_resume_formats() {
+        for value in [
+            "resume:",
+            "resume:abc",
+            "resume:120,tx_index",
+            "resume:120,tx_index=x",
+            "resume:120,unknown_index=1",
+        ] {
+            let result = value.parse::<DeliverPolicy>();
+            assert!(
+                matches!(result, Err(DeliverPolicyError::InvalidPointer(_))),
+                "expected {value} to be rejected"
+            );
+        }
+    }
+
     #[test]
     fn test_invalid_formats() {
         // Test invalid string format
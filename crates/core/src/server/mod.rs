@@ -1,9 +1,11 @@
 mod deliver_policy;
+mod projection;
 mod requests;
 mod responses;
 mod subscription;
 
 pub use deliver_policy::*;
+pub use projection::*;
 pub use requests::*;
 pub use responses::*;
 pub use subscription::*;
@@ -0,0 +1,172 @@
+use std::{str::FromStr, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::{MessagePayload, MessagePayloadError};
+
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProjectionProfile {
+    /// The whole entity, same as sending no projection
+    Full,
+    /// The entity without its heavy fields, such as the raw payload,
+    /// witnesses and nested records of a transaction
+    Compact,
+}
+
+/// Fields of the payload sent in stream responses, either a predefined
+/// profile or a list of JSON paths such as `txPointer.blockHeight`.
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PayloadProjection {
+    Profile(ProjectionProfile),
+    Fields(Vec<String>),
+}
+
+impl PayloadProjection {
+    pub fn compact() -> Self {
+        Self::Profile(ProjectionProfile::Compact)
+    }
+
+    pub fn is_full(&self) -> bool {
+        matches!(self, Self::Profile(ProjectionProfile::Full))
+    }
+
+    pub fn project(
+        &self,
+        payload: &MessagePayload,
+    ) -> Result<MessagePayload, MessagePayloadError> {
+        if self.is_full() {
+            return Ok(payload.to_owned());
+        }
+        let Value::Object(mut value) = serde_json::to_value(payload)? else {
+            return Ok(payload.to_owned());
+        };
+        let projected = match self {
+            Self::Profile(_) => {
+                for field in payload.heavy_fields() {
+                    value.remove(*field);
+                }
+                value
+            }
+            Self::Fields(fields) => {
+                let mut projected = Map::new();
+                for field in fields {
+                    copy_path(&value, &mut projected, field);
+                }
+                projected
+            }
+        };
+        Ok(MessagePayload::Projected(Arc::new(Value::Object(
+            projected,
+        ))))
+    }
+}
+
+/// Copies the value at a dot separated path into the target object,
+/// creating the intermediate objects. Missing paths are skipped.
+fn copy_path(
+    source: &Map<String, Value>,
+    target: &mut Map<String, Value>,
+    path: &str,
+) {
+    let Some((key, rest)) = path.split_once('.') else {
+        if let Some(value) = source.get(path) {
+            target.insert(path.to_string(), value.to_owned());
+        }
+        return;
+    };
+    let Some(Value::Object(source)) = source.get(key) else {
+        return;
+    };
+    let entry = target
+        .entry(key.to_string())
+        .or_insert_with(|| Value::Object(Map::new()));
+    if let Value::Object(target) = entry {
+        copy_path(source, target, rest);
+    }
+}
+
+impl FromStr for PayloadProjection {
+    type Err = std::convert::Infallible;
+    /// Parses `full`, `compact` or a comma separated list of fields, as
+    /// used by the REST `projection` query param
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let projection = match value.trim() {
+            "full" => Self::Profile(ProjectionProfile::Full),
+            "compact" => Self::Profile(ProjectionProfile::Compact),
+            fields => Self::Fields(
+                fields
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|field| !field.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            ),
+        };
+        Ok(projection)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+    use crate::types::{Transaction, Utxo};
+
+    #[test]
+    fn test_compact_projection_drops_heavy_fields() {
+        let payload = MessagePayload::Transaction(Arc::new(Transaction {
+            witnesses: vec![Default::default()],
+            ..Default::default()
+        }));
+        let projected = PayloadProjection::compact().project(&payload).unwrap();
+        let MessagePayload::Projected(value) = projected else {
+            panic!("expected a projected payload");
+        };
+
+        assert!(value.get("id").is_some());
+        assert!(value.get("status").is_some());
+        for field in payload.heavy_fields() {
+            assert!(value.get(field).is_none(), "field: {field}");
+        }
+    }
+
+    #[test]
+    fn test_fields_projection_keeps_paths() {
+        let payload = MessagePayload::Utxo(Arc::new(Utxo::default()));
+        let projection: PayloadProjection =
+            "txId, utxoId.output_index, missing.field".parse().unwrap();
+        let projected = projection.project(&payload).unwrap();
+
+        let json = serde_json::to_value(&projected).unwrap();
+        let expected = serde_json::to_value(&payload).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "txId": expected["txId"],
+                "utxoId": { "output_index": expected["utxoId"]["output_index"] },
+            })
+        );
+    }
+
+    #[test]
+    fn test_projection_deserialization() {
+        let compact: PayloadProjection =
+            serde_json::from_str(r#""compact""#).unwrap();
+        assert_eq!(compact, PayloadProjection::compact());
+
+        let fields: PayloadProjection =
+            serde_json::from_str(r#"["id", "status"]"#).unwrap();
+        assert_eq!(
+            fields,
+            PayloadProjection::Fields(vec!["id".into(), "status".into()])
+        );
+
+        let full: PayloadProjection = "full".parse().unwrap();
+        assert!(full.is_full());
+    }
+}
@@ -2,13 +2,33 @@ use pedronauck_streams_subject::subject::SubjectPayload;
 use pedronauck_web_utils::api_key::ApiKey;
 use serde::{Deserialize, Serialize};
 
-use crate::types::*;
+use crate::{stream::StreamRateLimit, types::*};
 
 #[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SubscribeRequest {
     pub deliver_policy: DeliverPolicy,
     pub subscribe: Vec<SubjectPayload>,
+    /// Wraps the items of each block between `BlockStart` and `BlockEnd`
+    /// responses. Implies `merge_ordered`, so the items of a block arrive
+    /// together.
+    #[serde(default)]
+    pub block_framing: bool,
+    /// Payload filter applied to every subject, e.g. `amount > 1000`
+    #[serde(default)]
+    pub filter: Option<String>,
+    /// Fields of the payload to send, either `"compact"` or a list of
+    /// JSON paths
+    #[serde(default)]
+    pub projection: Option<PayloadProjection>,
+    /// Slower throughput than the API key's role allows, e.g. to replay
+    /// history at a pace the client can keep up with
+    #[serde(default)]
+    pub rate_limit: Option<StreamRateLimit>,
+    /// Merges the historical replays of all subjects by record pointer,
+    /// so replayed records arrive in chain order and only once
+    #[serde(default)]
+    pub merge_ordered: bool,
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
@@ -16,6 +36,9 @@ pub struct SubscribeRequest {
 pub struct UnsubscribeRequest {
     pub deliver_policy: DeliverPolicy,
     pub unsubscribe: Vec<SubjectPayload>,
+    /// Filter of the subscriptions to remove, as they were subscribed with
+    #[serde(default)]
+    pub filter: Option<String>,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -32,6 +55,36 @@ pub enum ServerRequest {
 }
 
 impl ServerRequest {
+    pub fn block_framing(&self) -> bool {
+        match self {
+            ServerRequest::Subscribe(req) => req.block_framing,
+            ServerRequest::Unsubscribe(_) => false,
+        }
+    }
+
+    pub fn merge_ordered(&self) -> bool {
+        match self {
+            ServerRequest::Subscribe(req) => {
+                req.merge_ordered || req.block_framing
+            }
+            ServerRequest::Unsubscribe(_) => false,
+        }
+    }
+
+    pub fn projection(&self) -> Option<&PayloadProjection> {
+        match self {
+            ServerRequest::Subscribe(req) => req.projection.as_ref(),
+            ServerRequest::Unsubscribe(_) => None,
+        }
+    }
+
+    pub fn rate_limit(&self) -> Option<StreamRateLimit> {
+        match self {
+            ServerRequest::Subscribe(req) => req.rate_limit,
+            ServerRequest::Unsubscribe(_) => None,
+        }
+    }
+
     pub fn subscriptions(&self, api_key: &ApiKey) -> Vec<Subscription> {
         let payload = match self {
             ServerRequest::Subscribe(req) => &req.subscribe,
@@ -41,6 +94,10 @@ impl ServerRequest {
             ServerRequest::Subscribe(req) => req.deliver_policy,
             ServerRequest::Unsubscribe(req) => req.deliver_policy,
         };
+        let filter = match self {
+            ServerRequest::Subscribe(req) => &req.filter,
+            ServerRequest::Unsubscribe(req) => &req.filter,
+        };
 
         let subjects = payload.clone();
         if subjects.is_empty() {
@@ -52,6 +109,9 @@ impl ServerRequest {
             .into_iter()
             .map(|payload| {
                 Subscription::new(api_key, &deliver_policy, &payload)
+                    .with_filter(filter.clone())
+                    .with_projection(self.projection().cloned())
+                    .with_rate_limit(self.rate_limit())
             })
             .collect()
     }
@@ -1,8 +1,14 @@
 use std::sync::Arc;
 
 use pedronauck_streams_domains::{
+    accounts::AccountDbItem,
+    balances::BalanceChangeDbItem,
     blocks::BlockDbItem,
+    bundles::BundleDbItem,
+    coins::CoinDbItem,
+    contracts::{ContractDbItem, ContractTransactionDbItem},
     inputs::InputDbItem,
+    messages::MessageDbItem,
     outputs::OutputDbItem,
     receipts::ReceiptDbItem,
     transactions::TransactionDbItem,
@@ -33,6 +39,8 @@ pub enum MessagePayloadError {
     RecordEntity(#[from] RecordEntityError),
     #[error(transparent)]
     Decode(#[from] DbError),
+    #[error("Failed to project payload: {0}")]
+    Projection(#[from] serde_json::Error),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +52,15 @@ pub enum MessagePayload {
     Transaction(Arc<Transaction>),
     Receipt(Arc<Receipt>),
     Utxo(Arc<Utxo>),
+    Bundle(Arc<TransactionBundle>),
+    Account(Arc<AccountActivity>),
+    Contract(Arc<ContractActivity>),
+    Coin(Arc<Coin>),
+    BalanceChange(Arc<BalanceChange>),
+    Message(Arc<Message>),
+    ContractCreated(Arc<Contract>),
+    /// Payload narrowed down by a [`PayloadProjection`]
+    Projected(Arc<serde_json::Value>),
 }
 
 impl utoipa::ToSchema for MessagePayload {
@@ -65,6 +82,18 @@ impl utoipa::PartialSchema for MessagePayload {
         one_of.items.push(Transaction::schema());
         one_of.items.push(Receipt::schema());
         one_of.items.push(Utxo::schema());
+        one_of.items.push(TransactionBundle::schema());
+        one_of.items.push(AccountActivity::schema());
+        one_of.items.push(ContractActivity::schema());
+        one_of.items.push(Coin::schema());
+        one_of.items.push(BalanceChange::schema());
+        one_of.items.push(Message::schema());
+        one_of.items.push(Contract::schema());
+        one_of.items.push(
+            utoipa::openapi::schema::ObjectBuilder::new()
+                .description(Some("Payload narrowed down by a projection"))
+                .into(),
+        );
 
         // Build the oneOf schema with a description
         let schema = utoipa::openapi::schema::Schema::OneOf(one_of);
@@ -99,6 +128,58 @@ impl MessagePayload {
             RecordEntity::Utxo => {
                 Ok(MessagePayload::Utxo(Arc::new(Utxo::decode_json(value)?)))
             }
+            RecordEntity::Bundle => Ok(MessagePayload::Bundle(Arc::new(
+                TransactionBundle::decode_json(value)?,
+            ))),
+            RecordEntity::Account => Ok(MessagePayload::Account(Arc::new(
+                AccountActivity::decode_json(value)?,
+            ))),
+            RecordEntity::ContractTransaction => Ok(MessagePayload::Contract(
+                Arc::new(ContractActivity::decode_json(value)?),
+            )),
+            RecordEntity::Coin => {
+                Ok(MessagePayload::Coin(Arc::new(Coin::decode_json(value)?)))
+            }
+            RecordEntity::BalanceChange => Ok(MessagePayload::BalanceChange(
+                Arc::new(BalanceChange::decode_json(value)?),
+            )),
+            RecordEntity::Message => Ok(MessagePayload::Message(Arc::new(
+                Message::decode_json(value)?,
+            ))),
+            RecordEntity::Contract => Ok(MessagePayload::ContractCreated(
+                Arc::new(Contract::decode_json(value)?),
+            )),
+        }
+    }
+
+    /// Fields left out by the compact projection profile
+    pub fn heavy_fields(&self) -> &'static [&'static str] {
+        match self {
+            MessagePayload::Block(_) => &["consensus", "transactionIds"],
+            MessagePayload::Input(_) => &["predicate", "predicateData", "data"],
+            MessagePayload::Output(_) => &[],
+            MessagePayload::Transaction(_) => &[
+                "inputs",
+                "outputs",
+                "receipts",
+                "rawPayload",
+                "witnesses",
+                "proofSet",
+                "storageSlots",
+                "script",
+                "scriptData",
+                "policies",
+            ],
+            MessagePayload::Receipt(_) => &["data"],
+            MessagePayload::Utxo(_) => &["data"],
+            MessagePayload::Bundle(_) => &[],
+            MessagePayload::Account(_) => &[],
+            MessagePayload::Contract(_) => &[],
+            MessagePayload::Coin(_) => &[],
+            MessagePayload::BalanceChange(_) => &[],
+            MessagePayload::Message(_) => &["data"],
+            MessagePayload::ContractCreated(_) => &["storageSlots"],
+            MessagePayload::Projected(_) => &[],
         }
     }
 
@@ -147,6 +228,69 @@ impl MessagePayload {
             _ => Err(MessagePayloadError::InvalidData("utxo".to_string())),
         }
     }
+
+    pub fn as_bundle(
+        &self,
+    ) -> Result<Arc<TransactionBundle>, MessagePayloadError> {
+        match self {
+            MessagePayload::Bundle(bundle) => Ok(bundle.clone()),
+            _ => Err(MessagePayloadError::InvalidData("bundle".to_string())),
+        }
+    }
+
+    pub fn as_account(
+        &self,
+    ) -> Result<Arc<AccountActivity>, MessagePayloadError> {
+        match self {
+            MessagePayload::Account(account) => Ok(account.clone()),
+            _ => Err(MessagePayloadError::InvalidData("account".to_string())),
+        }
+    }
+
+    pub fn as_contract(
+        &self,
+    ) -> Result<Arc<ContractActivity>, MessagePayloadError> {
+        match self {
+            MessagePayload::Contract(contract) => Ok(contract.clone()),
+            _ => Err(MessagePayloadError::InvalidData("contract".to_string())),
+        }
+    }
+
+    pub fn as_coin(&self) -> Result<Arc<Coin>, MessagePayloadError> {
+        match self {
+            MessagePayload::Coin(coin) => Ok(coin.clone()),
+            _ => Err(MessagePayloadError::InvalidData("coin".to_string())),
+        }
+    }
+
+    pub fn as_balance_change(
+        &self,
+    ) -> Result<Arc<BalanceChange>, MessagePayloadError> {
+        match self {
+            MessagePayload::BalanceChange(change) => Ok(change.clone()),
+            _ => Err(MessagePayloadError::InvalidData(
+                "balance_change".to_string(),
+            )),
+        }
+    }
+
+    pub fn as_message(&self) -> Result<Arc<Message>, MessagePayloadError> {
+        match self {
+            MessagePayload::Message(message) => Ok(message.clone()),
+            _ => Err(MessagePayloadError::InvalidData("message".to_string())),
+        }
+    }
+
+    pub fn as_contract_created(
+        &self,
+    ) -> Result<Arc<Contract>, MessagePayloadError> {
+        match self {
+            MessagePayload::ContractCreated(contract) => Ok(contract.clone()),
+            _ => Err(MessagePayloadError::InvalidData(
+                "contract_created".to_string(),
+            )),
+        }
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -170,6 +314,10 @@ pub struct StreamResponse {
     pub pointer: RecordPointer,
     pub payload: MessagePayload,
     pub propagation_time_ms: Option<u64>,
+    /// Size of the stored payload, used to charge the byte throttle
+    /// without encoding the response again
+    #[serde(skip)]
+    pub payload_size: usize,
 }
 
 impl StreamResponse {
@@ -188,6 +336,7 @@ impl StreamResponse {
             payload,
             pointer,
             propagation_time_ms: propagation_ms,
+            payload_size: value.len(),
         })
     }
 }
@@ -196,6 +345,14 @@ impl StreamResponse {
     pub fn set_propagation_ms(&mut self, propagation_ms: u64) {
         self.propagation_time_ms = Some(propagation_ms);
     }
+
+    pub fn with_projection(
+        mut self,
+        projection: &PayloadProjection,
+    ) -> Result<Self, StreamResponseError> {
+        self.payload = projection.project(&self.payload)?;
+        Ok(self)
+    }
 }
 
 impl DataEncoder for StreamResponse {
@@ -208,6 +365,18 @@ pub enum ServerResponse {
     Subscribed(Subscription),
     Unsubscribed(Subscription),
     Response(StreamResponse),
+    /// Sent before the items of a block when the subscription asked for
+    /// block framing
+    BlockStart {
+        height: BlockHeight,
+        #[serde(rename = "itemCount")]
+        item_count: usize,
+    },
+    /// Sent after the last item of a block when the subscription asked for
+    /// block framing
+    BlockEnd {
+        height: BlockHeight,
+    },
     Error(String),
 }
 
@@ -274,6 +443,82 @@ impl TryFrom<&RecordPacket> for StreamResponse {
                 response.set_propagation_ms(propagation_ms);
                 Ok(response)
             }
+            RecordEntity::Bundle => {
+                let db_item = BundleDbItem::try_from(packet)?;
+                let mut response =
+                    StreamResponse::try_from((subject_id, db_item))?;
+                response.set_propagation_ms(propagation_ms);
+                Ok(response)
+            }
+            RecordEntity::Account => {
+                let db_item = AccountDbItem::try_from(packet)?;
+                let mut response =
+                    StreamResponse::try_from((subject_id, db_item))?;
+                response.set_propagation_ms(propagation_ms);
+                Ok(response)
+            }
+            RecordEntity::ContractTransaction => {
+                let db_item = ContractTransactionDbItem::try_from(packet)?;
+                let mut response =
+                    StreamResponse::try_from((subject_id, db_item))?;
+                response.set_propagation_ms(propagation_ms);
+                Ok(response)
+            }
+            RecordEntity::Coin => {
+                let db_item = CoinDbItem::try_from(packet)?;
+                let mut response =
+                    StreamResponse::try_from((subject_id, db_item))?;
+                response.set_propagation_ms(propagation_ms);
+                Ok(response)
+            }
+            RecordEntity::BalanceChange => {
+                let db_item = BalanceChangeDbItem::try_from(packet)?;
+                let mut response =
+                    StreamResponse::try_from((subject_id, db_item))?;
+                response.set_propagation_ms(propagation_ms);
+                Ok(response)
+            }
+            RecordEntity::Message => {
+                let db_item = MessageDbItem::try_from(packet)?;
+                let mut response =
+                    StreamResponse::try_from((subject_id, db_item))?;
+                response.set_propagation_ms(propagation_ms);
+                Ok(response)
+            }
+            RecordEntity::Contract => {
+                let db_item = ContractDbItem::try_from(packet)?;
+                let mut response =
+                    StreamResponse::try_from((subject_id, db_item))?;
+                response.set_propagation_ms(propagation_ms);
+                Ok(response)
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_block_frame_serialization() {
+        let start = ServerResponse::BlockStart {
+            height: 10.into(),
+            item_count: 3,
+        };
+        let json = serde_json::to_string(&start).unwrap();
+        assert_eq!(json, r#"{"blockStart":{"height":"10","itemCount":3}}"#);
+
+        let end = ServerResponse::BlockEnd { height: 10.into() };
+        let json = serde_json::to_string(&end).unwrap();
+        assert_eq!(json, r#"{"blockEnd":{"height":"10"}}"#);
+
+        let deserialized: ServerResponse = serde_json::from_str(&json).unwrap();
+        assert!(matches!(
+            deserialized,
+            ServerResponse::BlockEnd { height } if height == 10.into()
+        ));
+    }
+}
@@ -2,7 +2,8 @@ use pedronauck_streams_subject::subject::SubjectPayload;
 use pedronauck_web_utils::api_key::ApiKey;
 use serde::{Deserialize, Serialize};
 
-use super::DeliverPolicy;
+use super::{DeliverPolicy, PayloadProjection};
+use crate::stream::StreamRateLimit;
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -10,6 +11,15 @@ pub struct Subscription {
     pub id: String,
     pub deliver_policy: DeliverPolicy,
     pub payload: SubjectPayload,
+    /// Payload filter expression applied to the subscription's messages
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    /// Fields of the payload sent for the subscription's messages
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub projection: Option<PayloadProjection>,
+    /// Throughput asked for the subscription, capped by the key's role
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<StreamRateLimit>,
 }
 
 impl Subscription {
@@ -22,9 +32,36 @@ impl Subscription {
             id: Self::create_subscription_id(api_key, payload),
             deliver_policy: deliver_policy.to_owned(),
             payload: payload.to_owned(),
+            filter: None,
+            projection: None,
+            rate_limit: None,
         }
     }
 
+    pub fn with_filter(mut self, filter: Option<String>) -> Self {
+        if let Some(filter) = &filter {
+            self.id = format!("{}[{}]", self.id, filter);
+        }
+        self.filter = filter;
+        self
+    }
+
+    pub fn with_projection(
+        mut self,
+        projection: Option<PayloadProjection>,
+    ) -> Self {
+        self.projection = projection;
+        self
+    }
+
+    pub fn with_rate_limit(
+        mut self,
+        rate_limit: Option<StreamRateLimit>,
+    ) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
     fn create_subscription_id(
         api_key: &ApiKey,
         payload: &SubjectPayload,
@@ -91,4 +128,23 @@ mod tests {
         let deserialized: Subscription = serde_json::from_str(&json).unwrap();
         assert_eq!(deserialized, subscription);
     }
+
+    #[test]
+    fn test_subscription_with_filter() {
+        let api_key = MockApiKey::builder(2.into()).into_inner();
+        let payload = SubjectPayload {
+            subject: "test_subject".into(),
+            params: json!({}),
+        };
+        let subscription =
+            Subscription::new(&api_key, &DeliverPolicy::New, &payload)
+                .with_filter(Some("amount > 1000".to_string()));
+
+        let json = serde_json::to_string(&subscription).unwrap();
+        let expected = r#"{"id":"2-builder-test_subject:{}[amount > 1000]","deliverPolicy":"new","payload":{"subject":"test_subject","params":{}},"filter":"amount > 1000"}"#;
+        assert_eq!(json, expected);
+
+        let deserialized: Subscription = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, subscription);
+    }
 }
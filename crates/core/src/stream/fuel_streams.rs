@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use pedronauck_message_broker::NatsMessageBroker;
+use pedronauck_message_broker::MessageBroker;
 use pedronauck_streams_store::{
     db::Db,
     record::{RecordEntity, RecordPacket},
@@ -17,12 +17,19 @@ pub struct FuelStreams {
     pub outputs: Stream<Output>,
     pub receipts: Stream<Receipt>,
     pub utxos: Stream<Utxo>,
-    pub msg_broker: Arc<NatsMessageBroker>,
+    pub bundles: Stream<TransactionBundle>,
+    pub accounts: Stream<AccountActivity>,
+    pub contracts: Stream<ContractActivity>,
+    pub coins: Stream<Coin>,
+    pub balances: Stream<BalanceChange>,
+    pub messages: Stream<Message>,
+    pub contracts_created: Stream<Contract>,
+    pub msg_broker: Arc<dyn MessageBroker>,
     pub db: Arc<Db>,
 }
 
 impl FuelStreams {
-    pub async fn new(broker: &Arc<NatsMessageBroker>, db: &Arc<Db>) -> Self {
+    pub async fn new(broker: &Arc<dyn MessageBroker>, db: &Arc<Db>) -> Self {
         Self {
             blocks: Stream::<Block>::get_or_init(broker, db).await,
             transactions: Stream::<Transaction>::get_or_init(broker, db).await,
@@ -30,6 +37,15 @@ impl FuelStreams {
             outputs: Stream::<Output>::get_or_init(broker, db).await,
             receipts: Stream::<Receipt>::get_or_init(broker, db).await,
             utxos: Stream::<Utxo>::get_or_init(broker, db).await,
+            bundles: Stream::<TransactionBundle>::get_or_init(broker, db).await,
+            accounts: Stream::<AccountActivity>::get_or_init(broker, db).await,
+            contracts: Stream::<ContractActivity>::get_or_init(broker, db)
+                .await,
+            coins: Stream::<Coin>::get_or_init(broker, db).await,
+            balances: Stream::<BalanceChange>::get_or_init(broker, db).await,
+            messages: Stream::<Message>::get_or_init(broker, db).await,
+            contracts_created: Stream::<Contract>::get_or_init(broker, db)
+                .await,
             msg_broker: Arc::clone(broker),
             db: Arc::clone(db),
         }
@@ -39,7 +55,7 @@ impl FuelStreams {
         Arc::new(self.clone())
     }
 
-    pub fn broker(&self) -> Arc<NatsMessageBroker> {
+    pub fn broker(&self) -> Arc<dyn MessageBroker> {
         self.msg_broker.clone()
     }
 
@@ -69,6 +85,25 @@ impl FuelStreams {
                 self.outputs.publish(&subject, &response).await
             }
             RecordEntity::Utxo => self.utxos.publish(&subject, &response).await,
+            RecordEntity::Bundle => {
+                self.bundles.publish(&subject, &response).await
+            }
+            RecordEntity::Account => {
+                self.accounts.publish(&subject, &response).await
+            }
+            RecordEntity::ContractTransaction => {
+                self.contracts.publish(&subject, &response).await
+            }
+            RecordEntity::Coin => self.coins.publish(&subject, &response).await,
+            RecordEntity::BalanceChange => {
+                self.balances.publish(&subject, &response).await
+            }
+            RecordEntity::Message => {
+                self.messages.publish(&subject, &response).await
+            }
+            RecordEntity::Contract => {
+                self.contracts_created.publish(&subject, &response).await
+            }
         }
     }
 }
@@ -0,0 +1,218 @@
+use std::collections::{HashSet, VecDeque};
+
+use futures::{
+    stream::{self, BoxStream},
+    FutureExt,
+    StreamExt,
+};
+
+use super::{BoxedEventStream, StreamError, StreamEvent};
+use crate::types::StreamResponse;
+
+/// How many delivered subjects are remembered to drop the records matched
+/// by more than one subscription
+const DEDUP_CAPACITY: usize = 4096;
+
+/// How many live messages already received are sorted together at most
+const LIVE_BATCH_SIZE: usize = 256;
+
+/// Combines the streams of several subscriptions into one. Their historical
+/// replays are k-way merged by [`RecordPointer`], so the replayed records
+/// come out globally ordered, with a single [`StreamEvent::ReplayEnd`]
+/// after them. Live messages already received by several subscriptions are
+/// sorted the same way before being sent, so the items of a block aren't
+/// split by the ones of the next block. Records matched by overlapping
+/// subjects are only sent once.
+///
+/// [`RecordPointer`]: pedronauck_streams_store::record::RecordPointer
+pub fn merge_ordered(
+    mut streams: Vec<BoxedEventStream>,
+) -> BoxStream<'static, Result<StreamEvent, StreamError>> {
+    let stream = async_stream::try_stream! {
+        let mut dedup = Dedup::default();
+        let mut heads: Vec<Option<StreamResponse>> = vec![None; streams.len()];
+        let mut replaying = vec![true; streams.len()];
+
+        loop {
+            // Every stream still replaying needs a head before the lowest
+            // one can be sent, otherwise a lower record could still come
+            for (index, stream) in streams.iter_mut().enumerate() {
+                if !replaying[index] || heads[index].is_some() {
+                    continue;
+                }
+                match stream.next().await.transpose()? {
+                    Some(StreamEvent::Response(response)) => {
+                        heads[index] = Some(response);
+                    }
+                    Some(StreamEvent::ReplayEnd) | None => {
+                        replaying[index] = false;
+                    }
+                }
+            }
+            let lowest = heads
+                .iter()
+                .enumerate()
+                .filter_map(|(index, head)| Some((index, head.as_ref()?)))
+                .min_by(|(_, a), (_, b)| {
+                    a.pointer.cmp(&b.pointer).then(a.subject.cmp(&b.subject))
+                })
+                .map(|(index, _)| index);
+            let Some(index) = lowest else {
+                break;
+            };
+            if let Some(response) = heads[index].take() {
+                if dedup.insert(&response) {
+                    yield StreamEvent::Response(response);
+                }
+            }
+        }
+        yield StreamEvent::ReplayEnd;
+
+        let mut live = stream::select_all(streams);
+        while let Some(event) = live.next().await {
+            let event = event?;
+            let mut ready = vec![event];
+            while ready.len() < LIVE_BATCH_SIZE {
+                match live.next().now_or_never() {
+                    Some(Some(event)) => ready.push(event?),
+                    _ => break,
+                }
+            }
+            let mut responses = ready
+                .into_iter()
+                .filter_map(|event| match event {
+                    StreamEvent::Response(response) => Some(response),
+                    StreamEvent::ReplayEnd => None,
+                })
+                .collect::<Vec<_>>();
+            responses.sort_by(|a, b| {
+                a.pointer.cmp(&b.pointer).then(a.subject.cmp(&b.subject))
+            });
+            for response in responses {
+                if dedup.insert(&response) {
+                    yield StreamEvent::Response(response);
+                }
+            }
+        }
+    };
+    Box::pin(stream)
+}
+
+/// Bounded set of the last delivered subjects. A subject identifies a
+/// single record, so the same subject sent twice is a duplicate.
+#[derive(Debug, Default)]
+struct Dedup {
+    seen: HashSet<String>,
+    order: VecDeque<String>,
+}
+
+impl Dedup {
+    /// Remembers the response, returning whether it wasn't delivered yet
+    fn insert(&mut self, response: &StreamResponse) -> bool {
+        if !self.seen.insert(response.subject.to_owned()) {
+            return false;
+        }
+        self.order.push_back(response.subject.to_owned());
+        if self.order.len() > DEDUP_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use pedronauck_streams_store::record::RecordPointer;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::types::{MessagePayload, Utxo};
+
+    fn response(subject: &str, height: u32, tx_index: u32) -> StreamEvent {
+        StreamEvent::Response(StreamResponse {
+            version: "1".to_string(),
+            ty: "utxos".to_string(),
+            subject: subject.to_string(),
+            pointer: RecordPointer {
+                block_height: height.into(),
+                tx_index: Some(tx_index),
+                ..Default::default()
+            },
+            payload: MessagePayload::Utxo(Arc::new(Utxo::default())),
+            propagation_time_ms: None,
+            payload_size: 0,
+        })
+    }
+
+    fn event_stream(events: Vec<StreamEvent>) -> BoxedEventStream {
+        Box::new(stream::iter(events.into_iter().map(Ok)))
+    }
+
+    #[tokio::test]
+    async fn test_merge_orders_replays_and_drops_duplicates() {
+        let first = event_stream(vec![
+            response("a.1.0", 1, 0),
+            response("a.3.0", 3, 0),
+            StreamEvent::ReplayEnd,
+            response("a.9.0", 9, 0),
+        ]);
+        let second = event_stream(vec![
+            response("a.1.0", 1, 0),
+            response("b.2.1", 2, 1),
+            response("b.3.1", 3, 1),
+            StreamEvent::ReplayEnd,
+        ]);
+        let third = event_stream(vec![StreamEvent::ReplayEnd]);
+
+        let subjects = merge_ordered(vec![first, second, third])
+            .map(|result| match result.unwrap() {
+                StreamEvent::Response(response) => response.subject,
+                StreamEvent::ReplayEnd => "replay_end".to_string(),
+            })
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(subjects, vec![
+            "a.1.0",
+            "b.2.1",
+            "a.3.0",
+            "b.3.1",
+            "replay_end",
+            "a.9.0"
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_merge_orders_ready_live_messages() {
+        let first = event_stream(vec![
+            StreamEvent::ReplayEnd,
+            response("a.2.0", 2, 0),
+            response("a.3.0", 3, 0),
+        ]);
+        let second = event_stream(vec![
+            StreamEvent::ReplayEnd,
+            response("b.1.0", 1, 0),
+            response("b.2.1", 2, 1),
+        ]);
+
+        let subjects = merge_ordered(vec![first, second])
+            .map(|result| match result.unwrap() {
+                StreamEvent::Response(response) => response.subject,
+                StreamEvent::ReplayEnd => "replay_end".to_string(),
+            })
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(subjects, vec![
+            "replay_end",
+            "b.1.0",
+            "a.2.0",
+            "b.2.1",
+            "a.3.0"
+        ]);
+    }
+}
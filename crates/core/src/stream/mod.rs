@@ -1,8 +1,11 @@
-pub(crate) mod config;
 mod error;
 mod fuel_streams;
+mod merge;
 mod stream_impl;
+mod throttle;
 
 pub use error::*;
 pub use fuel_streams::*;
+pub use merge::*;
 pub use stream_impl::*;
+pub use throttle::*;
@@ -1,31 +1,78 @@
-use std::{sync::Arc, time::Duration};
+use std::sync::Arc;
 
 pub use async_nats::Subscriber as StreamLiveSubscriber;
 use futures::{
     stream::{BoxStream, Stream as FStream},
     StreamExt,
 };
-use pedronauck_streams_subject::subject::IntoSubject;
-use pedronauck_message_broker::NatsMessageBroker;
+use pedronauck_message_broker::{MessageBroker, MessageStream};
 use pedronauck_streams_store::{
     db::{Db, DbItem},
-    record::{DataEncoder, QueryOptions, Record},
-    store::{find_last_block_height, Store},
+    record::{DataEncoder, PayloadFilter, QueryOptions, Record, RecordPointer},
+    store::{find_block_height_by_timestamp, find_last_block_height, Store},
 };
+use pedronauck_streams_subject::subject::IntoSubject;
 use pedronauck_streams_types::BlockHeight;
 use pedronauck_web_utils::api_key::{ApiKeyRole, ApiKeyRoleScope};
-use tokio::{sync::OnceCell, task::spawn_blocking, time::sleep};
+use tokio::{
+    sync::{mpsc, OnceCell},
+    task::spawn_blocking,
+};
 
-use super::{config, StreamError};
+use super::{StreamError, StreamRateLimit, StreamThrottle};
 use crate::{server::DeliverPolicy, types::StreamResponse};
 
+/// Most live messages buffered per subscription while it replays from the
+/// database, or while its subscriber is slower than the chain
+const LIVE_BUFFER_CAPACITY: usize = 1024;
+
 pub type BoxedStoreItem = Result<StreamResponse, StreamError>;
 pub type BoxedStream = Box<dyn FStream<Item = BoxedStoreItem> + Send + Unpin>;
+pub type BoxedEventStream =
+    Box<dyn FStream<Item = Result<StreamEvent, StreamError>> + Send + Unpin>;
+
+/// Item of [`Stream::subscribe_events`], telling where the historical
+/// replay of a subscription ends and its live messages begin
+#[derive(Debug, Clone)]
+pub enum StreamEvent {
+    Response(StreamResponse),
+    /// Sent once after the last replayed response, even when the deliver
+    /// policy has no historical part
+    ReplayEnd,
+}
+
+impl StreamEvent {
+    pub fn into_response(self) -> Option<StreamResponse> {
+        match self {
+            StreamEvent::Response(response) => Some(response),
+            StreamEvent::ReplayEnd => None,
+        }
+    }
+}
+
+/// Item of the live buffer of a subscription
+#[derive(Debug)]
+enum LiveItem {
+    Response(Result<StreamResponse, StreamError>),
+    /// The buffer was full, so the live subscription was dropped and what
+    /// came after the buffered messages has to be caught up from the
+    /// database
+    Overflow,
+}
+
+/// Per subscription options of [`Stream::subscribe_with_opts`]
+#[derive(Debug, Clone, Default)]
+pub struct SubscribeOpts {
+    /// Only delivers the messages whose payload matches the filter
+    pub filter: Option<Arc<PayloadFilter>>,
+    /// Throughput asked by the subscriber, capped by its role
+    pub rate_limit: Option<StreamRateLimit>,
+}
 
 #[derive(Debug, Clone)]
 pub struct Stream<S: Record> {
     store: Arc<Store<S>>,
-    broker: Arc<NatsMessageBroker>,
+    broker: Arc<dyn MessageBroker>,
     namespace: Option<String>,
     _marker: std::marker::PhantomData<S>,
 }
@@ -35,7 +82,7 @@ impl<R: Record> Stream<R> {
     const INSTANCE: OnceCell<Self> = OnceCell::const_new();
 
     pub async fn get_or_init(
-        broker: &Arc<NatsMessageBroker>,
+        broker: &Arc<dyn MessageBroker>,
         db: &Arc<Db>,
     ) -> Self {
         let cell = Self::INSTANCE;
@@ -44,7 +91,7 @@ impl<R: Record> Stream<R> {
             .to_owned()
     }
 
-    pub async fn new(broker: &Arc<NatsMessageBroker>, db: &Arc<Db>) -> Self {
+    pub async fn new(broker: &Arc<dyn MessageBroker>, db: &Arc<Db>) -> Self {
         let store = Arc::new(Store::new(db));
         let broker = Arc::clone(broker);
         Self {
@@ -57,7 +104,7 @@ impl<R: Record> Stream<R> {
 
     #[cfg(any(test, feature = "test-helpers"))]
     pub fn with_namespace(
-        broker: &Arc<NatsMessageBroker>,
+        broker: &Arc<dyn MessageBroker>,
         db: &Arc<Db>,
         namespace: String,
     ) -> Self {
@@ -113,96 +160,344 @@ impl<R: Record> Stream<R> {
         deliver_policy: DeliverPolicy,
         api_key_role: &ApiKeyRole,
     ) -> BoxStream<'static, Result<StreamResponse, StreamError>> {
-        let broker = self.broker.clone();
+        let opts = SubscribeOpts::default();
+        self.subscribe_with_opts(subject, deliver_policy, opts, api_key_role)
+            .await
+    }
+
+    /// Subscribes to a subject with per subscription options. Replayed
+    /// records are narrowed in the database first when the filter has
+    /// predicates on indexed columns, and messages are throttled by the
+    /// role's throughput caps or the lower rate asked by the subscriber.
+    pub async fn subscribe_with_opts(
+        &self,
+        subject: Arc<dyn IntoSubject>,
+        deliver_policy: DeliverPolicy,
+        opts: SubscribeOpts,
+        api_key_role: &ApiKeyRole,
+    ) -> BoxStream<'static, Result<StreamResponse, StreamError>> {
+        self.subscribe_events(subject, deliver_policy, opts, api_key_role)
+            .await
+            .filter_map(|result| async move {
+                result.map(StreamEvent::into_response).transpose()
+            })
+            .boxed()
+    }
+
+    /// Same as [`Stream::subscribe_with_opts`], but also sends a
+    /// [`StreamEvent::ReplayEnd`] between the replayed and live messages
+    pub async fn subscribe_events(
+        &self,
+        subject: Arc<dyn IntoSubject>,
+        deliver_policy: DeliverPolicy,
+        opts: SubscribeOpts,
+        api_key_role: &ApiKeyRole,
+    ) -> BoxStream<'static, Result<StreamEvent, StreamError>> {
+        let SubscribeOpts { filter, rate_limit } = opts;
+        let mut throttle = StreamThrottle::new(api_key_role, rate_limit);
         let subject = subject.clone();
         let stream = self.clone();
+        let db = self.store().db.clone();
         let role = api_key_role.clone();
+
+        // The live subscription is opened before the historical replay so
+        // nothing published while replaying is lost. Live messages are
+        // buffered until the replay is done.
+        let live = self.subscribe_live(&subject, &role).await;
+
         let stream = async_stream::try_stream! {
-            match role.has_scopes(&[ApiKeyRoleScope::HistoricalData]) {
-                Ok(_) => {
-                    if let DeliverPolicy::FromBlock { block_height } = deliver_policy {
-                        let mut historical = stream.historical_streaming(subject.to_owned(), Some(block_height), &role);
-                        while let Some(result) = historical.next().await {
-                            yield result?;
-                            let throttle_time = *config::STREAM_THROTTLE_HISTORICAL;
-                            sleep(Duration::from_millis(throttle_time as u64)).await;
-                        }
-                    }
-                }
-                Err(e) => {
-                    tracing::error!("Error subscribing to stream: {}", e);
-                    Err(StreamError::from(e))?;
-                }
+            if let Err(e) = role.has_scopes(&[ApiKeyRoleScope::HistoricalData]) {
+                tracing::error!("Error subscribing to stream: {}", e);
+                Err(StreamError::from(e))?;
             }
 
-            match role.has_scopes(&[ApiKeyRoleScope::LiveData]) {
-                Ok(_) => {
-                    let mut live = broker.subscribe(&subject.parse()).await?;
-                    while let Some(msg) = live.next().await {
-                        let msg = msg?;
-                        let stream_response = spawn_blocking(move || StreamResponse::decode_json(&msg)).await??;
-                        yield stream_response;
-                        let throttle_time = *config::STREAM_THROTTLE_LIVE;
-                        sleep(Duration::from_millis(throttle_time as u64)).await;
-                    }
+            let mut last_pointer = deliver_policy.after_pointer();
+            let mut historical = stream.historical_streaming(subject.to_owned(), deliver_policy, filter.clone(), &role);
+            while let Some(result) = historical.next().await {
+                let result = result?;
+                last_pointer = Some(result.pointer.to_owned());
+                throttle.acquire(&result).await;
+                yield StreamEvent::Response(result);
+            }
+            yield StreamEvent::ReplayEnd;
+
+            // A closed range is complete once the chain is past its end,
+            // otherwise the rest of it comes from the live subscription
+            let to_block = deliver_policy.to_block();
+            let range_completed = match to_block {
+                Some(to_block) => {
+                    let last_height = find_last_block_height(&db, stream.query_options()).await?;
+                    last_height >= to_block
                 }
-                Err(e) => {
+                None => false,
+            };
+
+            if !range_completed {
+                let mut live = live.inspect_err(|e| {
                     tracing::error!("Error subscribing to stream: {}", e);
-                    Err(StreamError::from(e))?;
+                })?;
+                'live: while let Some(item) = live.recv().await {
+                    let stream_response = match item {
+                        LiveItem::Response(stream_response) => stream_response?,
+                        LiveItem::Overflow => {
+                            tracing::warn!("Live buffer of subscription to {} is full, catching up from the database", subject.parse());
+                            // Subscribed again before catching up, so nothing
+                            // published meanwhile is lost. The buffer only
+                            // overflows once messages were received, so the
+                            // last pointer is set by then.
+                            live = stream.subscribe_live(&subject, &role).await?;
+                            let catch_up_policy = match last_pointer {
+                                Some(pointer) => DeliverPolicy::Resume(pointer),
+                                None => deliver_policy,
+                            };
+                            let mut catch_up = stream.historical_streaming(subject.to_owned(), catch_up_policy, filter.clone(), &role);
+                            while let Some(result) = catch_up.next().await {
+                                let result = result?;
+                                if let Some(to_block) = to_block {
+                                    if result.pointer.block_height > to_block {
+                                        break 'live;
+                                    }
+                                }
+                                last_pointer = Some(result.pointer.to_owned());
+                                throttle.acquire(&result).await;
+                                yield StreamEvent::Response(result);
+                            }
+                            continue;
+                        }
+                    };
+                    // Skip anything the historical replay already delivered
+                    if let Some(last_pointer) = &last_pointer {
+                        if stream_response.pointer <= *last_pointer {
+                            continue;
+                        }
+                    }
+                    if let Some(to_block) = to_block {
+                        if stream_response.pointer.block_height > to_block {
+                            break;
+                        }
+                    }
+                    last_pointer = Some(stream_response.pointer.to_owned());
+                    if !Self::matches_filter(&stream_response, filter.as_deref()) {
+                        continue;
+                    }
+                    throttle.acquire(&stream_response).await;
+                    yield StreamEvent::Response(stream_response);
                 }
             }
         };
         Box::pin(stream)
     }
 
+    /// Opens a live subscription to the subject, buffered by
+    /// [`Stream::buffer_live`]
+    async fn subscribe_live(
+        &self,
+        subject: &Arc<dyn IntoSubject>,
+        role: &ApiKeyRole,
+    ) -> Result<mpsc::Receiver<LiveItem>, StreamError> {
+        role.has_scopes(&[ApiKeyRoleScope::LiveData])?;
+        let live = self.broker.subscribe(&subject.parse()).await?;
+        Ok(Self::buffer_live(live, LIVE_BUFFER_CAPACITY))
+    }
+
+    /// Buffers up to `capacity` live messages. Once the buffer is full the
+    /// live subscription is dropped and [`LiveItem::Overflow`] is sent
+    /// after the buffered messages, rather than holding on to everything
+    /// published while the subscriber can't keep up.
+    fn buffer_live(
+        mut live: MessageStream,
+        capacity: usize,
+    ) -> mpsc::Receiver<LiveItem> {
+        let (tx, rx) = mpsc::channel(capacity);
+        tokio::spawn(async move {
+            loop {
+                let msg = tokio::select! {
+                    _ = tx.closed() => break,
+                    msg = live.next() => match msg {
+                        Some(msg) => msg,
+                        None => break,
+                    },
+                };
+                let result = match msg {
+                    Ok(msg) => spawn_blocking(move || {
+                        StreamResponse::decode_json(&msg)
+                    })
+                    .await
+                    .map_err(StreamError::from)
+                    .and_then(|res| res.map_err(StreamError::from)),
+                    Err(e) => Err(StreamError::from(e)),
+                };
+                match tx.try_send(LiveItem::Response(result)) {
+                    Ok(()) => {}
+                    Err(mpsc::error::TrySendError::Full(_)) => {
+                        let _ = tx.send(LiveItem::Overflow).await;
+                        break;
+                    }
+                    Err(mpsc::error::TrySendError::Closed(_)) => break,
+                }
+            }
+        });
+        rx
+    }
+
     pub fn historical_streaming(
         &self,
         subject: Arc<dyn IntoSubject>,
-        from_block: Option<BlockHeight>,
+        deliver_policy: DeliverPolicy,
+        filter: Option<Arc<PayloadFilter>>,
         role: &ApiKeyRole,
     ) -> BoxStream<'static, Result<StreamResponse, StreamError>> {
         let store = self.store().clone();
         let db = self.store().db.clone();
         let role = role.clone();
-        let opts = if cfg!(any(test, feature = "test-helpers")) {
-            QueryOptions::default()
-        } else {
-            QueryOptions::default().with_namespace(self.namespace.clone())
-        };
+        let opts = self.query_options().with_filter(filter.clone());
 
         let stream = async_stream::try_stream! {
-            let mut current_height = from_block.unwrap_or_default();
-            let mut opts = opts.with_from_block(Some(current_height));
-            let mut last_height = find_last_block_height(&db, opts.clone()).await?;
-            while current_height <= last_height {
-                let items = store.find_many_by_subject(&subject, opts.clone()).await?;
-                for item in items {
-                    let subject = item.subject_str();
-                    let subject_id = item.subject_id();
-                    let block_height = item.block_height();
-                    role.validate_historical_limit(last_height, block_height)?;
-                    let value = item.encoded_value().to_vec();
-                    let pointer = item.into();
-                    let response = StreamResponse::new(subject, subject_id, &value, pointer.to_owned(), None)?;
-                    yield response;
-                    current_height = pointer.block_height;
-                }
-                opts.increment_offset();
-                // When we reach the last known height, we need to check if any new blocks
-                // were produced while we were processing the previous ones
-                if current_height == last_height {
-                    let new_last_height = find_last_block_height(&db, opts.clone()).await?;
-                    if new_last_height > last_height {
-                        // Reset current_height back to process the blocks we haven't seen yet
-                        current_height = last_height;
-                        last_height = new_last_height;
-                    } else {
-                        tracing::debug!("No new blocks found, stopping historical streaming on block {}", current_height);
-                        break
+            let range = Self::historical_range(&db, deliver_policy, opts.clone()).await?;
+            if let Some((from_block, to_block)) = range {
+                let mut opts = opts
+                    .with_from_block(Some(from_block))
+                    .with_to_block(to_block)
+                    .with_after_pointer(deliver_policy.after_pointer());
+                let mut last_height = find_last_block_height(&db, opts.clone()).await?;
+                loop {
+                    let items = store.find_many_by_subject(&subject, opts.clone()).await?;
+                    let page_len = items.len() as i64;
+                    for item in items {
+                        let subject = item.subject_str();
+                        let subject_id = item.subject_id();
+                        let block_height = item.block_height();
+                        role.validate_historical_limit(last_height, block_height)?;
+                        let value = item.encoded_value().to_vec();
+                        let pointer: RecordPointer = item.into();
+                        let response = StreamResponse::new(subject, subject_id, &value, pointer, None)?;
+                        // Only some predicates can be pushed down to the
+                        // query, so the whole filter is checked again here
+                        if Self::matches_filter(&response, filter.as_deref()) {
+                            yield response;
+                        }
+                        opts.seek_after(pointer);
+                    }
+                    // A full page can end anywhere, even in the last block,
+                    // so only a short page means everything stored was sent
+                    if page_len < opts.limit {
+                        // Blocks produced while processing the previous
+                        // ones are still replayed
+                        let new_last_height = find_last_block_height(&db, opts.clone()).await?;
+                        if new_last_height > last_height {
+                            last_height = new_last_height;
+                        } else {
+                            tracing::debug!("No new blocks found, stopping historical streaming on block {}", last_height);
+                            break
+                        }
                     }
                 }
             }
         };
         Box::pin(stream)
     }
+
+    fn matches_filter(
+        response: &StreamResponse,
+        filter: Option<&PayloadFilter>,
+    ) -> bool {
+        let Some(filter) = filter else {
+            return true;
+        };
+        serde_json::to_value(&response.payload)
+            .is_ok_and(|payload| filter.matches(&payload))
+    }
+
+    /// Resolves the block range a deliver policy replays from the database,
+    /// or `None` when the policy has no historical part.
+    async fn historical_range(
+        db: &Db,
+        deliver_policy: DeliverPolicy,
+        opts: QueryOptions,
+    ) -> Result<Option<(BlockHeight, Option<BlockHeight>)>, StreamError> {
+        let range = match deliver_policy {
+            DeliverPolicy::New => None,
+            DeliverPolicy::FromBlock { block_height } => {
+                Some((block_height, None))
+            }
+            DeliverPolicy::FromTo { from, to } => Some((from, Some(to))),
+            DeliverPolicy::LastBlocks(count) => {
+                let last_height = find_last_block_height(db, opts).await?;
+                let from_block = (*last_height + 1).saturating_sub(count);
+                Some((from_block.into(), None))
+            }
+            DeliverPolicy::FromTimestamp(timestamp) => {
+                find_block_height_by_timestamp(db, timestamp, opts)
+                    .await?
+                    .map(|block_height| (block_height, None))
+            }
+            DeliverPolicy::Resume(pointer) => {
+                Some((pointer.block_height, None))
+            }
+        };
+        Ok(range)
+    }
+
+    fn query_options(&self) -> QueryOptions {
+        if cfg!(any(test, feature = "test-helpers")) {
+            QueryOptions::default()
+        } else {
+            QueryOptions::default().with_namespace(self.namespace.clone())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use pedronauck_message_broker::MemoryMessageBroker;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::types::{MessagePayload, Utxo};
+
+    fn response(height: u32) -> StreamResponse {
+        StreamResponse {
+            version: "1".to_string(),
+            ty: "utxos".to_string(),
+            subject: format!("utxos.{height}"),
+            pointer: RecordPointer {
+                block_height: height.into(),
+                ..Default::default()
+            },
+            payload: MessagePayload::Utxo(Arc::new(Utxo::default())),
+            propagation_time_ms: None,
+            payload_size: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_buffer_live_overflow() {
+        let broker = MemoryMessageBroker::new();
+        let live = broker.subscribe("utxos.>").await.unwrap();
+        for height in 0..5 {
+            let payload = response(height).encode_json().unwrap();
+            broker
+                .publish(&format!("utxos.{height}"), payload.into())
+                .await
+                .unwrap();
+        }
+
+        // Nothing is read until the buffer is full
+        let mut rx = Stream::<Utxo>::buffer_live(live, 2);
+        while rx.len() < 2 {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        for height in 0..2 {
+            let Some(LiveItem::Response(Ok(item))) = rx.recv().await else {
+                panic!("expected a buffered response");
+            };
+            assert_eq!(item.pointer.block_height, height.into());
+        }
+        assert!(matches!(rx.recv().await, Some(LiveItem::Overflow)));
+        // The live subscription is dropped on overflow
+        assert!(rx.recv().await.is_none());
+    }
 }
@@ -0,0 +1,141 @@
+use std::time::Duration;
+
+use pedronauck_web_utils::api_key::ApiKeyRole;
+use serde::{Deserialize, Serialize};
+use tokio::time::{sleep, Instant};
+
+use crate::types::StreamResponse;
+
+/// Throughput a subscription asks for. It can only lower the limits of the
+/// API key's role, never raise them.
+#[derive(
+    Debug, Clone, Copy, Default, Hash, Eq, PartialEq, Serialize, Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamRateLimit {
+    pub msgs_per_sec: Option<u32>,
+    pub bytes_per_sec: Option<u32>,
+}
+
+/// Token bucket throttle of a single subscription, limiting both the
+/// messages and the bytes sent per second.
+#[derive(Debug)]
+pub struct StreamThrottle {
+    msgs: Option<TokenBucket>,
+    bytes: Option<TokenBucket>,
+}
+
+impl StreamThrottle {
+    pub fn new(role: &ApiKeyRole, requested: Option<StreamRateLimit>) -> Self {
+        let requested = requested.unwrap_or_default();
+        let msgs_per_sec = min_limit(
+            role.stream_msgs_per_sec().map(|limit| limit.into_inner()),
+            requested.msgs_per_sec,
+        );
+        let bytes_per_sec = min_limit(
+            role.stream_bytes_per_sec().map(|limit| limit.into_inner()),
+            requested.bytes_per_sec,
+        );
+        Self {
+            msgs: msgs_per_sec.map(TokenBucket::new),
+            bytes: bytes_per_sec.map(TokenBucket::new),
+        }
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.msgs.is_none() && self.bytes.is_none()
+    }
+
+    /// Waits until the response can be sent without going over the limits.
+    /// Bytes are charged by the size of the stored payload, so responses
+    /// aren't encoded once here and again when sent.
+    pub async fn acquire(&mut self, response: &StreamResponse) {
+        let mut wait = Duration::ZERO;
+        if let Some(bucket) = &mut self.msgs {
+            wait = wait.max(bucket.take(1.0));
+        }
+        if let Some(bucket) = &mut self.bytes {
+            wait = wait.max(bucket.take(response.payload_size as f64));
+        }
+        if !wait.is_zero() {
+            sleep(wait).await;
+        }
+    }
+}
+
+fn min_limit(role: Option<u32>, requested: Option<u32>) -> Option<u32> {
+    match (role, requested) {
+        (Some(role), Some(requested)) => Some(role.min(requested)),
+        (role, requested) => role.or(requested),
+    }
+}
+
+/// Bucket refilled at `rate` tokens per second, holding up to one second
+/// worth of tokens. Taking more tokens than available leaves the bucket in
+/// debt, so a message bigger than the rate is still sent, just later.
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u32) -> Self {
+        let rate = rate.max(1) as f64;
+        Self {
+            rate,
+            tokens: rate,
+            updated_at: Instant::now(),
+        }
+    }
+
+    /// Takes the tokens, returning how long to wait until they're refilled
+    fn take(&mut self, cost: f64) -> Duration {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.updated_at = now;
+        self.tokens -= cost;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_min_limit() {
+        assert_eq!(min_limit(None, None), None);
+        assert_eq!(min_limit(Some(50), None), Some(50));
+        assert_eq!(min_limit(None, Some(10)), Some(10));
+        assert_eq!(min_limit(Some(50), Some(10)), Some(10));
+        assert_eq!(min_limit(Some(50), Some(100)), Some(50));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_token_bucket_waits_once_empty() {
+        let mut bucket = TokenBucket::new(2);
+        assert_eq!(bucket.take(1.0), Duration::ZERO);
+        assert_eq!(bucket.take(1.0), Duration::ZERO);
+        assert_eq!(bucket.take(1.0), Duration::from_millis(500));
+
+        tokio::time::advance(Duration::from_millis(500)).await;
+        assert_eq!(bucket.take(1.0), Duration::from_millis(500));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_token_bucket_caps_burst() {
+        let mut bucket = TokenBucket::new(10);
+        tokio::time::advance(Duration::from_secs(60)).await;
+        assert_eq!(bucket.take(10.0), Duration::ZERO);
+        assert_eq!(bucket.take(5.0), Duration::from_millis(500));
+    }
+}
@@ -0,0 +1,96 @@
+use pedronauck_streams_types::{BlockTimestamp, ContractId};
+use sqlx::PgExecutor;
+
+use super::{AbiDecoder, AbiError, ContractAbi, ProgramAbi};
+
+/// Row of the `contract_abis` table, with the ABI read as text
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct ContractAbiDbItem {
+    pub contract_id: String,
+    pub abi: String,
+    pub created_at: BlockTimestamp,
+    pub updated_at: BlockTimestamp,
+}
+
+impl TryFrom<ContractAbiDbItem> for ContractAbi {
+    type Error = AbiError;
+    fn try_from(item: ContractAbiDbItem) -> Result<Self, Self::Error> {
+        Ok(ContractAbi {
+            contract_id: item.contract_id.as_str().into(),
+            abi: serde_json::from_str(&item.abi)?,
+            created_at: item.created_at,
+            updated_at: item.updated_at,
+        })
+    }
+}
+
+impl ContractAbi {
+    /// Stores the ABI of a contract, replacing the one it had. ABIs whose
+    /// logged types can't be decoded are rejected.
+    pub async fn upsert<'c, E>(
+        executor: E,
+        contract_id: &ContractId,
+        abi: &serde_json::Value,
+    ) -> Result<Self, AbiError>
+    where
+        E: PgExecutor<'c>,
+    {
+        let program: ProgramAbi = serde_json::from_value(abi.to_owned())?;
+        AbiDecoder::new(&program)?;
+        sqlx::query_as::<_, ContractAbiDbItem>(
+            "INSERT INTO contract_abis (contract_id, abi)
+             VALUES ($1, $2::JSONB)
+             ON CONFLICT (contract_id) DO UPDATE SET
+                abi = EXCLUDED.abi,
+                updated_at = NOW()
+             RETURNING contract_id, abi::TEXT AS abi, created_at, updated_at",
+        )
+        .bind(contract_id.to_string())
+        .bind(abi.to_string())
+        .fetch_one(executor)
+        .await?
+        .try_into()
+    }
+
+    pub async fn find<'c, E>(
+        executor: E,
+        contract_id: &ContractId,
+    ) -> Result<Option<Self>, AbiError>
+    where
+        E: PgExecutor<'c>,
+    {
+        sqlx::query_as::<_, ContractAbiDbItem>(
+            "SELECT contract_id, abi::TEXT AS abi, created_at, updated_at
+             FROM contract_abis
+             WHERE contract_id = $1",
+        )
+        .bind(contract_id.to_string())
+        .fetch_optional(executor)
+        .await?
+        .map(TryInto::try_into)
+        .transpose()
+    }
+
+    /// Rows of the contracts among the given ones that have an ABI
+    pub async fn find_many<'c, E>(
+        executor: E,
+        contract_ids: &[ContractId],
+    ) -> Result<Vec<ContractAbiDbItem>, AbiError>
+    where
+        E: PgExecutor<'c>,
+    {
+        let contract_ids = contract_ids
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        let items = sqlx::query_as::<_, ContractAbiDbItem>(
+            "SELECT contract_id, abi::TEXT AS abi, created_at, updated_at
+             FROM contract_abis
+             WHERE contract_id = ANY($1)",
+        )
+        .bind(contract_ids)
+        .fetch_all(executor)
+        .await?;
+        Ok(items)
+    }
+}
@@ -0,0 +1,584 @@
+use std::collections::HashMap;
+
+use pedronauck_streams_types::HexData;
+use serde_json::{Map, Value};
+
+use super::types::*;
+use crate::receipts::DecodedLog;
+
+/// Deepest nesting of types an ABI can declare, so a malformed ABI
+/// referring to itself can't recurse forever
+const MAX_TYPE_DEPTH: usize = 32;
+
+#[derive(Debug, thiserror::Error)]
+pub enum AbiError {
+    #[error("Invalid ABI: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Unsupported ABI encoding version {0}, expected 1")]
+    UnsupportedEncoding(String),
+    #[error("Invalid log ID {0}")]
+    InvalidLogId(String),
+    #[error("Type {0} is not declared in the ABI")]
+    UnknownType(String),
+    #[error("Type {0} can't be decoded")]
+    UnsupportedType(String),
+    #[error("Types are nested more than {} levels deep", MAX_TYPE_DEPTH)]
+    TooDeep,
+    #[error("No type is logged under log ID {0}")]
+    UnknownLogId(u64),
+    #[error("Log data ended before the value was decoded")]
+    UnexpectedEnd,
+    #[error("Log data has {0} bytes left after the value")]
+    TrailingBytes(usize),
+    #[error("Invalid {0} in log data")]
+    InvalidValue(&'static str),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+/// Layout of a Sway type in the ABI encoding (version 1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AbiType {
+    Unit,
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U256,
+    B256,
+    /// Length prefixed string, such as `str` or `String`
+    Str,
+    StrArray(usize),
+    /// Length prefixed bytes, such as `Bytes` or `raw untyped slice`
+    Bytes,
+    Vec(Box<AbiType>),
+    Array(Box<AbiType>, usize),
+    Tuple(Vec<AbiType>),
+    Struct(Vec<(String, AbiType)>),
+    Enum(Vec<(String, AbiType)>),
+}
+
+#[derive(Debug, Clone)]
+struct LoggedType {
+    type_name: String,
+    ty: AbiType,
+}
+
+/// Decodes the values a contract logs, with the types its ABI declares
+/// for each log ID
+#[derive(Debug, Clone)]
+pub struct AbiDecoder {
+    logged_types: HashMap<u64, LoggedType>,
+}
+
+impl AbiDecoder {
+    /// Resolves the logged types of an ABI, failing if any of them can't
+    /// be decoded
+    pub fn new(abi: &ProgramAbi) -> Result<Self, AbiError> {
+        if abi.encoding_version != "1" {
+            return Err(AbiError::UnsupportedEncoding(
+                abi.encoding_version.to_owned(),
+            ));
+        }
+        let resolver = TypeResolver::new(abi);
+        let logged_types = abi
+            .logged_types
+            .iter()
+            .map(|logged| {
+                let log_id = logged.log_id.parse::<u64>().map_err(|_| {
+                    AbiError::InvalidLogId(logged.log_id.clone())
+                })?;
+                let concrete = resolver.concrete(&logged.concrete_type_id)?;
+                let ty = resolver.resolve_concrete(concrete, 0)?;
+                let type_name = concrete.type_name.to_owned();
+                Ok((log_id, LoggedType { type_name, ty }))
+            })
+            .collect::<Result<_, AbiError>>()?;
+        Ok(Self { logged_types })
+    }
+
+    pub fn from_json(abi: &str) -> Result<Self, AbiError> {
+        let abi: ProgramAbi = serde_json::from_str(abi)?;
+        Self::new(&abi)
+    }
+
+    /// Decodes the data of a `LogData` receipt, logged under the ID held
+    /// in its `rb` register
+    pub fn decode_log(
+        &self,
+        log_id: u64,
+        data: &[u8],
+    ) -> Result<DecodedLog, AbiError> {
+        let logged = self
+            .logged_types
+            .get(&log_id)
+            .ok_or(AbiError::UnknownLogId(log_id))?;
+        let mut reader = AbiReader { data };
+        let value = reader.read(&logged.ty)?;
+        if !reader.data.is_empty() {
+            return Err(AbiError::TrailingBytes(reader.data.len()));
+        }
+        Ok(DecodedLog {
+            log_id: log_id.to_string(),
+            type_name: logged.type_name.to_owned(),
+            value,
+        })
+    }
+}
+
+/// Types bound to the generic parameters of a metadata type, by the
+/// metadata ID of the parameter
+type Generics = HashMap<u32, AbiType>;
+
+struct TypeResolver<'a> {
+    concrete_types: HashMap<&'a str, &'a AbiConcreteType>,
+    metadata_types: HashMap<u32, &'a AbiMetadataType>,
+}
+
+impl<'a> TypeResolver<'a> {
+    fn new(abi: &'a ProgramAbi) -> Self {
+        Self {
+            concrete_types: abi
+                .concrete_types
+                .iter()
+                .map(|ty| (ty.concrete_type_id.as_str(), ty))
+                .collect(),
+            metadata_types: abi
+                .metadata_types
+                .iter()
+                .map(|ty| (ty.metadata_type_id, ty))
+                .collect(),
+        }
+    }
+
+    fn concrete(&self, id: &str) -> Result<&'a AbiConcreteType, AbiError> {
+        self.concrete_types
+            .get(id)
+            .copied()
+            .ok_or_else(|| AbiError::UnknownType(id.to_string()))
+    }
+
+    fn metadata(&self, id: u32) -> Result<&'a AbiMetadataType, AbiError> {
+        self.metadata_types
+            .get(&id)
+            .copied()
+            .ok_or_else(|| AbiError::UnknownType(id.to_string()))
+    }
+
+    fn resolve_concrete(
+        &self,
+        concrete: &AbiConcreteType,
+        depth: usize,
+    ) -> Result<AbiType, AbiError> {
+        let depth = nested(depth)?;
+        if let Some(ty) = builtin_type(&concrete.type_name) {
+            return Ok(ty);
+        }
+        let Some(metadata_id) = concrete.metadata_type_id else {
+            return Err(AbiError::UnsupportedType(concrete.type_name.clone()));
+        };
+        let metadata = self.metadata(metadata_id)?;
+        let generics = metadata
+            .type_parameters
+            .iter()
+            .zip(&concrete.type_arguments)
+            .map(|(param, argument)| {
+                let argument = self.concrete(argument)?;
+                Ok((*param, self.resolve_concrete(argument, depth)?))
+            })
+            .collect::<Result<Generics, AbiError>>()?;
+        self.resolve_metadata(metadata, &generics, depth)
+    }
+
+    fn resolve_application(
+        &self,
+        application: &AbiTypeApplication,
+        generics: &Generics,
+        depth: usize,
+    ) -> Result<AbiType, AbiError> {
+        let depth = nested(depth)?;
+        let metadata_id = match &application.type_id {
+            AbiTypeId::Concrete(id) => {
+                return self.resolve_concrete(self.concrete(id)?, depth)
+            }
+            AbiTypeId::Metadata(id) => *id,
+        };
+        let metadata = self.metadata(metadata_id)?;
+        if application.type_arguments.is_empty() {
+            return self.resolve_metadata(metadata, generics, depth);
+        }
+        let arguments = metadata
+            .type_parameters
+            .iter()
+            .zip(&application.type_arguments)
+            .map(|(param, argument)| {
+                let ty = self.resolve_application(argument, generics, depth)?;
+                Ok((*param, ty))
+            })
+            .collect::<Result<Generics, AbiError>>()?;
+        self.resolve_metadata(metadata, &arguments, depth)
+    }
+
+    fn resolve_metadata(
+        &self,
+        metadata: &AbiMetadataType,
+        generics: &Generics,
+        depth: usize,
+    ) -> Result<AbiType, AbiError> {
+        let name = metadata.type_name.as_str();
+        let unresolved = || AbiError::UnsupportedType(name.to_string());
+        let components = || {
+            metadata
+                .components
+                .iter()
+                .map(|component| {
+                    let ty =
+                        self.resolve_application(component, generics, depth)?;
+                    Ok((component.name.to_owned(), ty))
+                })
+                .collect::<Result<Vec<_>, AbiError>>()
+        };
+
+        if let Some(ty) = builtin_type(name) {
+            return Ok(ty);
+        }
+        if name.starts_with("generic ") {
+            return generics
+                .get(&metadata.metadata_type_id)
+                .cloned()
+                .ok_or_else(unresolved);
+        }
+        if name == "struct std::vec::Vec" {
+            let element = metadata
+                .type_parameters
+                .first()
+                .and_then(|param| generics.get(param))
+                .ok_or_else(unresolved)?;
+            return Ok(AbiType::Vec(Box::new(element.to_owned())));
+        }
+        if name.starts_with("struct ") {
+            return Ok(AbiType::Struct(components()?));
+        }
+        if name.starts_with("enum ") {
+            return Ok(AbiType::Enum(components()?));
+        }
+        if name.starts_with('(') {
+            let types = components()?.into_iter().map(|(_, ty)| ty);
+            return Ok(AbiType::Tuple(types.collect()));
+        }
+        if let Some(len) = array_len(name) {
+            let (_, element) =
+                components()?.into_iter().next().ok_or_else(unresolved)?;
+            return Ok(AbiType::Array(Box::new(element), len));
+        }
+        Err(unresolved())
+    }
+}
+
+fn nested(depth: usize) -> Result<usize, AbiError> {
+    match depth < MAX_TYPE_DEPTH {
+        true => Ok(depth + 1),
+        false => Err(AbiError::TooDeep),
+    }
+}
+
+/// Types with a fixed layout, including the standard library types that
+/// are encoded as length prefixed data rather than as their fields
+fn builtin_type(name: &str) -> Option<AbiType> {
+    let ty = match name {
+        "()" => AbiType::Unit,
+        "bool" => AbiType::Bool,
+        "u8" => AbiType::U8,
+        "u16" => AbiType::U16,
+        "u32" => AbiType::U32,
+        "u64" | "raw untyped ptr" => AbiType::U64,
+        "u256" => AbiType::U256,
+        "b256" => AbiType::B256,
+        "str" | "struct std::string::String" => AbiType::Str,
+        "raw untyped slice" | "struct std::bytes::Bytes" => AbiType::Bytes,
+        _ => {
+            let len = name.strip_prefix("str[")?.strip_suffix(']')?;
+            AbiType::StrArray(len.parse().ok()?)
+        }
+    };
+    Some(ty)
+}
+
+/// Length of an array type, named like `[_; 4]`
+fn array_len(name: &str) -> Option<usize> {
+    let inner = name.strip_prefix('[')?.strip_suffix(']')?;
+    let (_, len) = inner.rsplit_once(';')?;
+    len.trim().parse().ok()
+}
+
+struct AbiReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> AbiReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], AbiError> {
+        if len > self.data.len() {
+            return Err(AbiError::UnexpectedEnd);
+        }
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], AbiError> {
+        let mut bytes = [0u8; N];
+        bytes.copy_from_slice(self.take(N)?);
+        Ok(bytes)
+    }
+
+    fn read_u64(&mut self) -> Result<u64, AbiError> {
+        Ok(u64::from_be_bytes(self.take_array()?))
+    }
+
+    /// Length prefix of dynamic data. Every item takes at least one byte,
+    /// except for units, so lengths past the end of the data are invalid.
+    fn read_len(&mut self) -> Result<usize, AbiError> {
+        let len = self.read_u64()?;
+        usize::try_from(len)
+            .ok()
+            .filter(|len| *len <= self.data.len())
+            .ok_or(AbiError::UnexpectedEnd)
+    }
+
+    fn read_str(&mut self, len: usize) -> Result<Value, AbiError> {
+        let bytes = self.take(len)?.to_vec();
+        let text = String::from_utf8(bytes)
+            .map_err(|_| AbiError::InvalidValue("str"))?;
+        Ok(Value::String(text))
+    }
+
+    fn read_hex(&mut self, len: usize) -> Result<Value, AbiError> {
+        let bytes = self.take(len)?.to_vec();
+        Ok(Value::String(HexData::from(bytes).to_string()))
+    }
+
+    /// Reads a value as JSON. Integers of up to 32 bits are numbers, while
+    /// `u64` is a decimal string like the words of receipts, and 256 bit
+    /// values are hex strings.
+    fn read(&mut self, ty: &AbiType) -> Result<Value, AbiError> {
+        let value = match ty {
+            AbiType::Unit => Value::Null,
+            AbiType::Bool => match self.take_array::<1>()? {
+                [0] => Value::Bool(false),
+                [1] => Value::Bool(true),
+                _ => return Err(AbiError::InvalidValue("bool")),
+            },
+            AbiType::U8 => u8::from_be_bytes(self.take_array()?).into(),
+            AbiType::U16 => u16::from_be_bytes(self.take_array()?).into(),
+            AbiType::U32 => u32::from_be_bytes(self.take_array()?).into(),
+            AbiType::U64 => Value::String(self.read_u64()?.to_string()),
+            AbiType::U256 | AbiType::B256 => self.read_hex(32)?,
+            AbiType::Str => {
+                let len = self.read_len()?;
+                self.read_str(len)?
+            }
+            AbiType::StrArray(len) => self.read_str(*len)?,
+            AbiType::Bytes => {
+                let len = self.read_len()?;
+                self.read_hex(len)?
+            }
+            AbiType::Vec(element) => {
+                let len = self.read_len()?;
+                self.read_items(std::iter::repeat_n(element.as_ref(), len))?
+            }
+            AbiType::Array(element, len) => {
+                self.read_items(std::iter::repeat_n(element.as_ref(), *len))?
+            }
+            AbiType::Tuple(types) => self.read_items(types.iter())?,
+            AbiType::Struct(fields) => {
+                let mut object = Map::new();
+                for (name, ty) in fields {
+                    object.insert(name.to_owned(), self.read(ty)?);
+                }
+                Value::Object(object)
+            }
+            AbiType::Enum(variants) => {
+                let discriminant = self.read_u64()?;
+                let (name, ty) = usize::try_from(discriminant)
+                    .ok()
+                    .and_then(|index| variants.get(index))
+                    .ok_or(AbiError::InvalidValue("enum variant"))?;
+                let mut object = Map::new();
+                object.insert(name.to_owned(), self.read(ty)?);
+                Value::Object(object)
+            }
+        };
+        Ok(value)
+    }
+
+    fn read_items<'t>(
+        &mut self,
+        types: impl Iterator<Item = &'t AbiType>,
+    ) -> Result<Value, AbiError> {
+        let items = types
+            .map(|ty| self.read(ty))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Value::Array(items))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    const TRANSFER_LOG_ID: u64 = 1515152261580153489;
+
+    /// ABI of a contract logging a `Transfer` struct, as `forc` emits it
+    fn abi() -> String {
+        json!({
+            "programType": "contract",
+            "specVersion": "1",
+            "encodingVersion": "1",
+            "concreteTypes": [
+                { "type": "()", "concreteTypeId": "unit" },
+                { "type": "u32", "concreteTypeId": "u32" },
+                { "type": "u64", "concreteTypeId": "u64" },
+                { "type": "b256", "concreteTypeId": "b256" },
+                { "type": "str", "concreteTypeId": "str" },
+                { "type": "u8", "concreteTypeId": "u8" },
+                {
+                    "type": "struct Transfer",
+                    "concreteTypeId": "transfer",
+                    "metadataTypeId": 0
+                },
+                {
+                    "type": "struct std::vec::Vec<u8>",
+                    "concreteTypeId": "vec_u8",
+                    "metadataTypeId": 3,
+                    "typeArguments": ["u8"]
+                }
+            ],
+            "metadataTypes": [
+                {
+                    "type": "struct Transfer",
+                    "metadataTypeId": 0,
+                    "components": [
+                        { "name": "to", "typeId": "b256" },
+                        { "name": "amount", "typeId": "u64" },
+                        {
+                            "name": "memo",
+                            "typeId": 1,
+                            "typeArguments": [{ "name": "", "typeId": "str" }]
+                        },
+                        {
+                            "name": "fee",
+                            "typeId": 1,
+                            "typeArguments": [{ "name": "", "typeId": "u32" }]
+                        },
+                        { "name": "tags", "typeId": "vec_u8" }
+                    ]
+                },
+                {
+                    "type": "enum std::option::Option",
+                    "metadataTypeId": 1,
+                    "components": [
+                        { "name": "None", "typeId": "unit" },
+                        { "name": "Some", "typeId": 2 }
+                    ],
+                    "typeParameters": [2]
+                },
+                { "type": "generic T", "metadataTypeId": 2 },
+                {
+                    "type": "struct std::vec::Vec",
+                    "metadataTypeId": 3,
+                    "components": [
+                        { "name": "buf", "typeId": 4 },
+                        { "name": "len", "typeId": "u64" }
+                    ],
+                    "typeParameters": [2]
+                },
+                { "type": "struct std::vec::RawVec", "metadataTypeId": 4 }
+            ],
+            "loggedTypes": [
+                {
+                    "logId": TRANSFER_LOG_ID.to_string(),
+                    "concreteTypeId": "transfer"
+                },
+                { "logId": "8", "concreteTypeId": "u64" }
+            ]
+        })
+        .to_string()
+    }
+
+    fn encoded_transfer() -> Vec<u8> {
+        let mut data = vec![7u8; 32];
+        data.extend(1_000_000u64.to_be_bytes());
+        // memo: Some("hi")
+        data.extend(1u64.to_be_bytes());
+        data.extend(2u64.to_be_bytes());
+        data.extend(b"hi");
+        // fee: None
+        data.extend(0u64.to_be_bytes());
+        // tags: [1, 2]
+        data.extend(2u64.to_be_bytes());
+        data.extend([1, 2]);
+        data
+    }
+
+    #[test]
+    fn test_decode_logged_struct() {
+        let decoder = AbiDecoder::from_json(&abi()).unwrap();
+        let decoded = decoder
+            .decode_log(TRANSFER_LOG_ID, &encoded_transfer())
+            .unwrap();
+        assert_eq!(decoded.log_id, TRANSFER_LOG_ID.to_string());
+        assert_eq!(decoded.type_name, "struct Transfer");
+        assert_eq!(
+            decoded.value,
+            json!({
+                "to": format!("0x{}", "07".repeat(32)),
+                "amount": "1000000",
+                "memo": { "Some": "hi" },
+                "fee": { "None": null },
+                "tags": [1, 2]
+            })
+        );
+
+        let decoded = decoder.decode_log(8, &42u64.to_be_bytes()).unwrap();
+        assert_eq!(decoded.type_name, "u64");
+        assert_eq!(decoded.value, json!("42"));
+    }
+
+    #[test]
+    fn test_decode_invalid_log() {
+        let decoder = AbiDecoder::from_json(&abi()).unwrap();
+        let data = encoded_transfer();
+        assert!(matches!(
+            decoder.decode_log(1, &data),
+            Err(AbiError::UnknownLogId(1))
+        ));
+        assert!(matches!(
+            decoder.decode_log(TRANSFER_LOG_ID, &data[..40]),
+            Err(AbiError::UnexpectedEnd)
+        ));
+        assert!(matches!(
+            decoder.decode_log(8, &data[..9]),
+            Err(AbiError::TrailingBytes(1))
+        ));
+    }
+
+    #[test]
+    fn test_reject_unsupported_abi() {
+        let legacy = json!({ "encodingVersion": "0", "types": [] });
+        assert!(matches!(
+            AbiDecoder::from_json(&legacy.to_string()),
+            Err(AbiError::UnsupportedEncoding(_))
+        ));
+
+        let mut abi: ProgramAbi = serde_json::from_str(&abi()).unwrap();
+        abi.logged_types[0].concrete_type_id = "missing".to_string();
+        assert!(matches!(
+            AbiDecoder::new(&abi),
+            Err(AbiError::UnknownType(_))
+        ));
+    }
+}
@@ -0,0 +1,9 @@
+mod db_item;
+mod decoder;
+mod registry;
+pub mod types;
+
+pub use db_item::*;
+pub use decoder::*;
+pub use registry::*;
+pub use types::*;
@@ -0,0 +1,139 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, PoisonError, RwLock},
+    time::{Duration, Instant},
+};
+
+use pedronauck_streams_store::db::Db;
+use pedronauck_streams_types::ContractId;
+
+use super::{AbiDecoder, AbiError, ContractAbi};
+use crate::{
+    receipts::{LogDataReceipt, Receipt},
+    MsgPayload,
+};
+
+/// How long the ABI of a contract, or the lack of one, is cached, so ABIs
+/// uploaded while the consumer runs are picked up
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone)]
+struct CachedDecoder {
+    decoder: Option<Arc<AbiDecoder>>,
+    loaded_at: Instant,
+}
+
+/// ABIs registered for contracts, used to decode the values they log
+#[derive(Debug)]
+pub struct AbiRegistry {
+    db: Arc<Db>,
+    cache: RwLock<HashMap<ContractId, CachedDecoder>>,
+}
+
+impl AbiRegistry {
+    pub fn new(db: &Arc<Db>) -> Self {
+        Self {
+            db: db.clone(),
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn arc(self) -> Arc<Self> {
+        Arc::new(self)
+    }
+
+    /// Attaches the decoded value to the `LogData` receipts of contracts
+    /// with a registered ABI, returning how many were decoded. Logs that
+    /// don't match the ABI are left as they are.
+    pub async fn decode_logs(
+        &self,
+        msg_payload: &mut MsgPayload,
+    ) -> Result<usize, AbiError> {
+        let contract_ids = msg_payload
+            .transactions
+            .iter()
+            .flat_map(|tx| tx.receipts.iter())
+            .filter_map(|receipt| match receipt {
+                Receipt::LogData(LogDataReceipt {
+                    id, data: Some(_), ..
+                }) => Some(id.to_owned()),
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+        if contract_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let decoders = self.decoders(contract_ids).await?;
+        let mut decoded = 0;
+        let receipts = msg_payload
+            .transactions
+            .iter_mut()
+            .flat_map(|tx| tx.receipts.iter_mut());
+        for receipt in receipts {
+            let Receipt::LogData(receipt) = receipt else {
+                continue;
+            };
+            let (Some(decoder), Some(data)) =
+                (decoders.get(&receipt.id), &receipt.data)
+            else {
+                continue;
+            };
+            if let Ok(log) = decoder.decode_log(*receipt.rb, data.0.as_ref()) {
+                receipt.decoded = Some(log);
+                decoded += 1;
+            }
+        }
+        Ok(decoded)
+    }
+
+    /// Decoders of the contracts that have an ABI, read from the cache or
+    /// loaded in a single query for the contracts missing from it
+    async fn decoders(
+        &self,
+        contract_ids: HashSet<ContractId>,
+    ) -> Result<HashMap<ContractId, Arc<AbiDecoder>>, AbiError> {
+        let mut decoders = HashMap::new();
+        let mut missing = vec![];
+        {
+            let cache =
+                self.cache.read().unwrap_or_else(PoisonError::into_inner);
+            for contract_id in contract_ids {
+                match cache.get(&contract_id) {
+                    Some(cached) if cached.loaded_at.elapsed() < CACHE_TTL => {
+                        if let Some(decoder) = &cached.decoder {
+                            decoders.insert(contract_id, decoder.clone());
+                        }
+                    }
+                    _ => missing.push(contract_id),
+                }
+            }
+        }
+        if missing.is_empty() {
+            return Ok(decoders);
+        }
+
+        let mut loaded = ContractAbi::find_many(&self.db.pool, &missing)
+            .await?
+            .into_iter()
+            .filter_map(|item| {
+                // ABIs are checked when uploaded, so this only skips ABIs
+                // stored before the decoder supported them
+                let decoder = AbiDecoder::from_json(&item.abi).ok()?;
+                Some((item.contract_id, Arc::new(decoder)))
+            })
+            .collect::<HashMap<_, _>>();
+        let loaded_at = Instant::now();
+        let mut cache =
+            self.cache.write().unwrap_or_else(PoisonError::into_inner);
+        cache.retain(|_, cached| cached.loaded_at.elapsed() < CACHE_TTL);
+        for contract_id in missing {
+            let decoder = loaded.remove(&contract_id.to_string());
+            if let Some(decoder) = &decoder {
+                decoders.insert(contract_id.to_owned(), decoder.clone());
+            }
+            cache.insert(contract_id, CachedDecoder { decoder, loaded_at });
+        }
+        Ok(decoders)
+    }
+}
@@ -0,0 +1,85 @@
+use pedronauck_streams_types::{BlockTimestamp, ContractId};
+use serde::{Deserialize, Serialize};
+
+/// Sway JSON ABI of a program, as emitted by `forc build`. Only the parts
+/// needed to decode logged values are read, the rest of the document is
+/// kept as uploaded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgramAbi {
+    #[serde(default)]
+    pub program_type: String,
+    #[serde(default)]
+    pub spec_version: String,
+    pub encoding_version: String,
+    #[serde(default)]
+    pub concrete_types: Vec<AbiConcreteType>,
+    #[serde(default)]
+    pub metadata_types: Vec<AbiMetadataType>,
+    #[serde(default)]
+    pub logged_types: Vec<AbiLoggedType>,
+}
+
+/// Type used by the program, with its generic arguments applied
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AbiConcreteType {
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub concrete_type_id: String,
+    pub metadata_type_id: Option<u32>,
+    #[serde(default)]
+    pub type_arguments: Vec<String>,
+}
+
+/// Declaration of a struct, enum, tuple, array or generic type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AbiMetadataType {
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub metadata_type_id: u32,
+    #[serde(default)]
+    pub components: Vec<AbiTypeApplication>,
+    #[serde(default)]
+    pub type_parameters: Vec<u32>,
+}
+
+/// Field of a metadata type, or argument of a generic one
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AbiTypeApplication {
+    #[serde(default)]
+    pub name: String,
+    pub type_id: AbiTypeId,
+    #[serde(default)]
+    pub type_arguments: Vec<AbiTypeApplication>,
+}
+
+/// Components refer to concrete types by their hash ID, and to metadata
+/// types, such as generic parameters, by their index
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AbiTypeId {
+    Concrete(String),
+    Metadata(u32),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AbiLoggedType {
+    pub log_id: String,
+    pub concrete_type_id: String,
+}
+
+/// ABI registered for a contract
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractAbi {
+    pub contract_id: ContractId,
+    /// The Sway JSON ABI, as uploaded
+    #[schema(value_type = Object)]
+    pub abi: serde_json::Value,
+    pub created_at: BlockTimestamp,
+    pub updated_at: BlockTimestamp,
+}
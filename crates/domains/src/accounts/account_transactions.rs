@@ -0,0 +1,129 @@
+use pedronauck_streams_store::{
+    db::{DbError, DbResult},
+    record::DbTransaction,
+};
+use pedronauck_streams_types::*;
+use sqlx::{Postgres, QueryBuilder};
+
+use super::types::*;
+use crate::{transactions::Transaction, MsgPayload};
+
+/// Row of the `account_transactions` table, linking an account to a
+/// transaction it takes part in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountTransaction {
+    pub address: Address,
+    pub tx_id: TxId,
+    pub block_height: BlockHeight,
+    pub tx_index: u32,
+    pub role: AccountRole,
+}
+
+impl AccountTransaction {
+    /// Links of a transaction, from its coin and message inputs and
+    /// outputs and its transfer receipts. An account sending and receiving
+    /// in the same transaction gets one link for each role.
+    pub fn from_transaction(
+        block_height: BlockHeight,
+        tx_index: u32,
+        tx: &Transaction,
+    ) -> Vec<Self> {
+        let inputs = tx.inputs.iter().cloned().map(AccountRecord::Input);
+        let outputs = tx.outputs.iter().cloned().map(AccountRecord::Output);
+        let receipts = tx.receipts.iter().cloned().map(AccountRecord::Receipt);
+        let mut links: Vec<Self> = vec![];
+        for (address, role) in inputs
+            .chain(outputs)
+            .chain(receipts)
+            .flat_map(|record| record.roles())
+        {
+            let exists = links
+                .iter()
+                .any(|link| link.address == address && link.role == role);
+            if !exists {
+                links.push(Self {
+                    address,
+                    tx_id: tx.id.to_owned(),
+                    block_height,
+                    tx_index,
+                    role,
+                });
+            }
+        }
+        links
+    }
+
+    pub fn from_msg_payload(msg_payload: &MsgPayload) -> Vec<Self> {
+        let block_height = msg_payload.block_height();
+        msg_payload
+            .transactions
+            .iter()
+            .enumerate()
+            .flat_map(|(tx_index, tx)| {
+                Self::from_transaction(block_height, tx_index as u32, tx)
+            })
+            .collect()
+    }
+
+    /// Inserts the links, skipping the ones already stored when a block is
+    /// processed again
+    pub async fn insert_many(
+        db_tx: &mut DbTransaction,
+        links: &[Self],
+    ) -> DbResult<()> {
+        if links.is_empty() {
+            return Ok(());
+        }
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
+            "INSERT INTO account_transactions (
+                address, tx_id, block_height, tx_index, role
+            ) ",
+        );
+        query_builder.push_values(links, |mut row, link| {
+            row.push_bind(link.address.to_string())
+                .push_bind(link.tx_id.to_string())
+                .push_bind(link.block_height)
+                .push_bind(link.tx_index as i32)
+                .push_bind(link.role.to_string());
+        });
+        query_builder.push(" ON CONFLICT (address, tx_id, role) DO NOTHING");
+        query_builder
+            .build()
+            .execute(&mut **db_tx)
+            .await
+            .map_err(DbError::Insert)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{mocks::MockTransaction, outputs::types::*};
+
+    #[test]
+    fn test_links_one_row_per_account_and_role() {
+        let owner = Address::from([1u8; 32]);
+        let outputs = vec![
+            Output::Coin(OutputCoin {
+                to: owner.to_owned(),
+                ..Default::default()
+            }),
+            Output::Change(OutputChange {
+                to: owner.to_owned(),
+                ..Default::default()
+            }),
+        ];
+        let tx = MockTransaction::script(vec![], outputs, vec![]);
+        let links = AccountTransaction::from_transaction(10.into(), 2, &tx);
+        assert_eq!(links, vec![AccountTransaction {
+            address: owner,
+            tx_id: tx.id.to_owned(),
+            block_height: 10.into(),
+            tx_index: 2,
+            role: AccountRole::Recipient,
+        }]);
+    }
+}
@@ -0,0 +1,160 @@
+use std::cmp::Ordering;
+
+use pedronauck_streams_store::{
+    db::{DbError, DbItem},
+    record::{
+        DataEncoder,
+        RecordEntity,
+        RecordPacket,
+        RecordPacketError,
+        RecordPointer,
+    },
+};
+use pedronauck_streams_types::{BlockHeight, BlockTimestamp};
+use serde::{Deserialize, Serialize};
+
+use super::{subjects::*, types::*};
+use crate::Subjects;
+
+/// Index columns a record doesn't have are stored as `-1`, so they sort
+/// before any real index, as unset pointer indexes do
+const NO_INDEX: i32 = -1;
+
+#[derive(
+    Debug, Clone, Serialize, Deserialize, PartialEq, Eq, sqlx::FromRow,
+)]
+pub struct AccountDbItem {
+    pub subject: String,
+    pub value: Vec<u8>,
+    pub block_height: i64,
+    pub tx_id: String,
+    pub tx_index: i32,
+    pub address: String,
+    pub activity_type: String,
+    pub record_index: i32,
+    pub input_index: i32,
+    pub output_index: i32,
+    pub receipt_index: i32,
+    /// Position among the rows sharing the same record index, see
+    /// [`AccountActivity::fanout_index`]
+    pub fanout_index: i32,
+    pub created_at: BlockTimestamp,
+    pub published_at: BlockTimestamp,
+}
+
+impl DataEncoder for AccountDbItem {
+    type Err = DbError;
+}
+
+impl DbItem for AccountDbItem {
+    fn entity(&self) -> &RecordEntity {
+        &RecordEntity::Account
+    }
+
+    fn encoded_value(&self) -> &[u8] {
+        &self.value
+    }
+
+    fn subject_str(&self) -> String {
+        self.subject.clone()
+    }
+
+    fn subject_id(&self) -> String {
+        AccountsSubject::ID.to_string()
+    }
+
+    fn created_at(&self) -> BlockTimestamp {
+        self.created_at
+    }
+
+    fn published_at(&self) -> BlockTimestamp {
+        self.published_at
+    }
+
+    fn block_height(&self) -> BlockHeight {
+        self.block_height.into()
+    }
+}
+
+impl TryFrom<&RecordPacket> for AccountDbItem {
+    type Error = RecordPacketError;
+    fn try_from(packet: &RecordPacket) -> Result<Self, Self::Error> {
+        let subject: Subjects = packet
+            .subject_payload
+            .to_owned()
+            .try_into()
+            .map_err(|_| RecordPacketError::SubjectMismatch)?;
+
+        match subject {
+            Subjects::Accounts(subject) => {
+                let activity: AccountActivity = AccountActivity::data_parser()
+                    .decode_json(&packet.value)?;
+                let activity_type = subject.activity_type.unwrap();
+                let record_index = subject.record_index.unwrap() as i32;
+                let index_for = |types: &[AccountActivityType]| {
+                    if types.contains(&activity_type) {
+                        record_index
+                    } else {
+                        NO_INDEX
+                    }
+                };
+                Ok(AccountDbItem {
+                    subject: packet.subject_str(),
+                    value: packet.value.to_owned(),
+                    block_height: subject.block_height.unwrap().into(),
+                    tx_id: subject.tx_id.unwrap().to_string(),
+                    tx_index: subject.tx_index.unwrap() as i32,
+                    address: subject.address.unwrap().to_string(),
+                    input_index: index_for(&[
+                        AccountActivityType::Input,
+                        AccountActivityType::Utxo,
+                    ]),
+                    output_index: index_for(&[AccountActivityType::Output]),
+                    receipt_index: index_for(&[AccountActivityType::Receipt]),
+                    fanout_index: activity.fanout_index() as i32,
+                    activity_type: activity_type.to_string(),
+                    record_index,
+                    created_at: packet.block_timestamp,
+                    published_at: packet.block_timestamp,
+                })
+            }
+            _ => Err(RecordPacketError::SubjectMismatch),
+        }
+    }
+}
+
+impl PartialOrd for AccountDbItem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for AccountDbItem {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Order by block height first
+        self.block_height
+            .cmp(&other.block_height)
+            // Then by transaction index within the block
+            .then(self.tx_index.cmp(&other.tx_index))
+            // Then by the position of the record within the transaction
+            .then(self.input_index.cmp(&other.input_index))
+            .then(self.output_index.cmp(&other.output_index))
+            .then(self.receipt_index.cmp(&other.receipt_index))
+            // Then by the position among the rows of the same record
+            .then(self.fanout_index.cmp(&other.fanout_index))
+    }
+}
+
+impl From<AccountDbItem> for RecordPointer {
+    fn from(val: AccountDbItem) -> Self {
+        let index = |value: i32| u32::try_from(value).ok();
+        RecordPointer {
+            block_height: val.block_height.into(),
+            tx_index: Some(val.tx_index as u32),
+            input_index: index(val.input_index),
+            output_index: index(val.output_index),
+            receipt_index: index(val.receipt_index),
+            fanout_index: Some(val.fanout_index as u32),
+        }
+    }
+}
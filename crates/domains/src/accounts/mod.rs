@@ -0,0 +1,12 @@
+mod account_transactions;
+mod db_item;
+mod packets;
+pub mod queryable;
+mod record_impl;
+pub mod subjects;
+pub mod types;
+
+pub use account_transactions::*;
+pub use db_item::*;
+pub use subjects::*;
+pub use types::*;
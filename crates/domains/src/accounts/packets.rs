@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+use pedronauck_streams_store::record::{PacketBuilder, Record, RecordPacket};
+use rayon::prelude::*;
+
+use super::{subjects::*, types::*};
+use crate::{utxos::DynUtxoSubject, MsgPayload};
+
+#[async_trait]
+impl PacketBuilder for AccountActivity {
+    type Opts = MsgPayload;
+
+    fn build_packets(msg_payload: &Self::Opts) -> Vec<RecordPacket> {
+        let block_height = msg_payload.block_height();
+        let timestamp = msg_payload.timestamp();
+        msg_payload
+            .transactions
+            .par_iter()
+            .enumerate()
+            .flat_map_iter(|(tx_index, tx)| {
+                let tx_index = tx_index as u32;
+                let inputs =
+                    tx.inputs.iter().enumerate().map(|(index, input)| {
+                        (index, AccountRecord::Input(input.to_owned()))
+                    });
+                let outputs =
+                    tx.outputs.iter().enumerate().map(|(index, output)| {
+                        (index, AccountRecord::Output(output.to_owned()))
+                    });
+                let receipts =
+                    tx.receipts.iter().enumerate().map(|(index, receipt)| {
+                        (index, AccountRecord::Receipt(receipt.to_owned()))
+                    });
+                let utxos =
+                    tx.inputs.iter().enumerate().map(|(index, input)| {
+                        let utxo = DynUtxoSubject::from((
+                            input,
+                            block_height,
+                            tx.id.to_owned(),
+                            tx_index,
+                            index as u32,
+                        ));
+                        (index, AccountRecord::Utxo(utxo.utxo().to_owned()))
+                    });
+                inputs
+                    .chain(outputs)
+                    .chain(receipts)
+                    .chain(utxos)
+                    .flat_map(move |(record_index, record)| {
+                        record.addresses().into_iter().map(move |address| {
+                            let activity = AccountActivity {
+                                address: address.to_owned(),
+                                tx_id: tx.id.to_owned(),
+                                record: record.to_owned(),
+                            };
+                            let subject = AccountsSubject {
+                                address: Some(address),
+                                block_height: Some(block_height),
+                                tx_id: Some(tx.id.to_owned()),
+                                tx_index: Some(tx_index),
+                                activity_type: Some(record.activity_type()),
+                                record_index: Some(record_index as u32),
+                            }
+                            .dyn_arc();
+                            let packet =
+                                activity.to_packet(&subject, timestamp);
+                            match msg_payload.namespace.clone() {
+                                Some(ns) => packet.with_namespace(&ns),
+                                _ => packet,
+                            }
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,18 @@
+use sea_query::Iden;
+
+#[allow(dead_code)]
+#[derive(Iden)]
+pub enum AccountTransactions {
+    #[iden = "account_transactions"]
+    Table,
+    #[iden = "address"]
+    Address,
+    #[iden = "tx_id"]
+    TxId,
+    #[iden = "block_height"]
+    BlockHeight,
+    #[iden = "tx_index"]
+    TxIndex,
+    #[iden = "role"]
+    Role,
+}
@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+use pedronauck_streams_store::{
+    db::{DbError, DbResult},
+    record::{DataEncoder, FilterColumn, Record, RecordEntity},
+};
+use pedronauck_streams_types::BlockTimestamp;
+use sqlx::PgExecutor;
+
+use super::{AccountActivity, AccountDbItem};
+
+impl DataEncoder for AccountActivity {
+    type Err = DbError;
+}
+
+#[async_trait]
+impl Record for AccountActivity {
+    type DbItem = AccountDbItem;
+
+    const ENTITY: RecordEntity = RecordEntity::Account;
+    const ORDER_PROPS: &'static [&'static str] = &[
+        "tx_index",
+        "input_index",
+        "output_index",
+        "receipt_index",
+        "fanout_index",
+    ];
+    const FILTER_COLUMNS: &'static [FilterColumn] =
+        &[FilterColumn::text("txId", "tx_id")];
+
+    async fn insert<'e, 'c: 'e, E>(
+        executor: E,
+        db_item: Self::DbItem,
+    ) -> DbResult<Self::DbItem>
+    where
+        'c: 'e,
+        E: PgExecutor<'c>,
+    {
+        let published_at = BlockTimestamp::now();
+        let record = sqlx::query_as::<_, AccountDbItem>(
+            "WITH upsert AS (
+                INSERT INTO accounts (
+                    subject, value, block_height, tx_id, tx_index, address,
+                    activity_type, record_index, input_index, output_index,
+                    receipt_index, fanout_index, created_at, published_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+                ON CONFLICT (subject) DO UPDATE SET
+                    value = EXCLUDED.value,
+                    block_height = EXCLUDED.block_height,
+                    tx_id = EXCLUDED.tx_id,
+                    tx_index = EXCLUDED.tx_index,
+                    address = EXCLUDED.address,
+                    activity_type = EXCLUDED.activity_type,
+                    record_index = EXCLUDED.record_index,
+                    input_index = EXCLUDED.input_index,
+                    output_index = EXCLUDED.output_index,
+                    receipt_index = EXCLUDED.receipt_index,
+                    fanout_index = EXCLUDED.fanout_index,
+                    created_at = EXCLUDED.created_at,
+                    published_at = $14
+                RETURNING *
+            )
+            SELECT * FROM upsert",
+        )
+        .bind(db_item.subject)
+        .bind(db_item.value)
+        .bind(db_item.block_height)
+        .bind(db_item.tx_id)
+        .bind(db_item.tx_index)
+        .bind(db_item.address)
+        .bind(db_item.activity_type)
+        .bind(db_item.record_index)
+        .bind(db_item.input_index)
+        .bind(db_item.output_index)
+        .bind(db_item.receipt_index)
+        .bind(db_item.fanout_index)
+        .bind(db_item.created_at)
+        .bind(published_at)
+        .fetch_one(executor)
+        .await
+        .map_err(DbError::Insert)?;
+
+        Ok(record)
+    }
+}
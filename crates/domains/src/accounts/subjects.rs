@@ -0,0 +1,35 @@
+use pedronauck_streams_subject::subject::*;
+use pedronauck_streams_types::*;
+use serde::{Deserialize, Serialize};
+
+use super::types::*;
+
+#[derive(Subject, Debug, Clone, Default, Serialize, Deserialize)]
+#[subject(id = "accounts")]
+#[subject(entity = "AccountActivity")]
+#[subject(query_all = "accounts.>")]
+#[subject(
+    format = "accounts.{address}.{block_height}.{tx_id}.{tx_index}.{activity_type}.{record_index}"
+)]
+pub struct AccountsSubject {
+    #[subject(
+        description = "The address of the account touched by the record (32 byte string prefixed by 0x)"
+    )]
+    pub address: Option<Address>,
+    #[subject(description = "The height of the block containing this record")]
+    pub block_height: Option<BlockHeight>,
+    #[subject(
+        description = "The ID of the transaction containing this record (32 byte string prefixed by 0x)"
+    )]
+    pub tx_id: Option<TxId>,
+    #[subject(description = "The index of the transaction within the block")]
+    pub tx_index: Option<u32>,
+    #[subject(
+        description = "The kind of record touching the account (input, output, receipt, or utxo)"
+    )]
+    pub activity_type: Option<AccountActivityType>,
+    #[subject(
+        description = "The index of the record within its transaction's inputs, outputs or receipts"
+    )]
+    pub record_index: Option<u32>,
+}
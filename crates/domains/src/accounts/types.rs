@@ -0,0 +1,259 @@
+use pedronauck_streams_types::primitives::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    inputs::types::*,
+    outputs::types::*,
+    receipts::types::*,
+    utxos::types::*,
+};
+
+/// Record touching an account, as sent in its own stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum AccountRecord {
+    Input(Input),
+    Output(Output),
+    Receipt(Receipt),
+    Utxo(Utxo),
+}
+
+impl AccountRecord {
+    pub fn activity_type(&self) -> AccountActivityType {
+        match self {
+            AccountRecord::Input(_) => AccountActivityType::Input,
+            AccountRecord::Output(_) => AccountActivityType::Output,
+            AccountRecord::Receipt(_) => AccountActivityType::Receipt,
+            AccountRecord::Utxo(_) => AccountActivityType::Utxo,
+        }
+    }
+
+    /// Accounts the record touches, with the role each one plays, such as
+    /// the owner of a coin input sending it or both sides of a message
+    pub fn roles(&self) -> Vec<(Address, AccountRole)> {
+        use AccountRole::*;
+        match self {
+            AccountRecord::Input(Input::Coin(input)) => {
+                vec![(input.owner.to_owned(), Sender)]
+            }
+            AccountRecord::Input(Input::Message(input)) => vec![
+                (input.sender.to_owned(), Sender),
+                (input.recipient.to_owned(), Recipient),
+            ],
+            AccountRecord::Output(Output::Coin(output)) => {
+                vec![(output.to.to_owned(), Recipient)]
+            }
+            AccountRecord::Output(Output::Change(output)) => {
+                vec![(output.to.to_owned(), Recipient)]
+            }
+            AccountRecord::Output(Output::Variable(output)) => {
+                vec![(output.to.to_owned(), Recipient)]
+            }
+            AccountRecord::Receipt(Receipt::TransferOut(receipt)) => {
+                vec![(receipt.to.to_owned(), Recipient)]
+            }
+            AccountRecord::Receipt(Receipt::MessageOut(receipt)) => vec![
+                (receipt.sender.to_owned(), Sender),
+                (receipt.recipient.to_owned(), Recipient),
+            ],
+            AccountRecord::Utxo(utxo) => utxo
+                .sender
+                .iter()
+                .map(|sender| (sender.to_owned(), Sender))
+                .chain(
+                    utxo.recipient
+                        .iter()
+                        .map(|recipient| (recipient.to_owned(), Recipient)),
+                )
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Addresses of the accounts the record touches
+    pub fn addresses(&self) -> Vec<Address> {
+        let mut addresses = self
+            .roles()
+            .into_iter()
+            .map(|(address, _)| address)
+            .collect::<Vec<_>>();
+        addresses.dedup();
+        addresses
+    }
+}
+
+/// A record touching an account, sent once for each account it touches
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountActivity {
+    pub address: Address,
+    pub tx_id: TxId,
+    pub record: AccountRecord,
+}
+
+/// Most accounts a single record touches: a sender and a recipient
+const MAX_RECORD_ACCOUNTS: u32 = 2;
+
+impl AccountActivity {
+    /// Position of the activity among the rows sharing its input, output
+    /// or receipt index, so each one has a pointer of its own. The accounts
+    /// of a UTXO come after the ones of the input spending it, as both use
+    /// the index of the input.
+    pub fn fanout_index(&self) -> u32 {
+        let position = self
+            .record
+            .addresses()
+            .iter()
+            .position(|address| *address == self.address)
+            .unwrap_or_default() as u32;
+        match self.record {
+            AccountRecord::Utxo(_) => MAX_RECORD_ACCOUNTS + position,
+            _ => position,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccountActivityType {
+    Input,
+    Output,
+    Receipt,
+    Utxo,
+}
+
+impl AccountActivityType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AccountActivityType::Input => "input",
+            AccountActivityType::Output => "output",
+            AccountActivityType::Receipt => "receipt",
+            AccountActivityType::Utxo => "utxo",
+        }
+    }
+}
+
+impl std::fmt::Display for AccountActivityType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for AccountActivityType {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            s if s == Self::Input.as_str() => Ok(Self::Input),
+            s if s == Self::Output.as_str() => Ok(Self::Output),
+            s if s == Self::Receipt.as_str() => Ok(Self::Receipt),
+            s if s == Self::Utxo.as_str() => Ok(Self::Utxo),
+            _ => Err(format!("Invalid account activity type: {s}")),
+        }
+    }
+}
+
+/// Role an account plays in a transaction: sending funds or messages, or
+/// receiving them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccountRole {
+    Sender,
+    Recipient,
+}
+
+impl AccountRole {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AccountRole::Sender => "sender",
+            AccountRole::Recipient => "recipient",
+        }
+    }
+}
+
+impl std::fmt::Display for AccountRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for AccountRole {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            s if s == Self::Sender.as_str() => Ok(Self::Sender),
+            s if s == Self::Recipient.as_str() => Ok(Self::Recipient),
+            _ => Err(format!("Invalid account role: {s}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_message_input_touches_both_sides() {
+        let sender = Address::from([1u8; 32]);
+        let recipient = Address::from([2u8; 32]);
+        let record = AccountRecord::Input(Input::Message(InputMessage {
+            sender: sender.to_owned(),
+            recipient: recipient.to_owned(),
+            ..Default::default()
+        }));
+        assert_eq!(record.addresses(), vec![sender.to_owned(), recipient]);
+        assert_eq!(record.activity_type(), AccountActivityType::Input);
+
+        let record = AccountRecord::Input(Input::Message(InputMessage {
+            sender: sender.to_owned(),
+            recipient: sender.to_owned(),
+            ..Default::default()
+        }));
+        assert_eq!(record.addresses(), vec![sender]);
+
+        let record = AccountRecord::Input(MockInput::contract());
+        assert!(record.addresses().is_empty());
+    }
+
+    #[test]
+    fn test_fanout_index() {
+        let sender = Address::from([1u8; 32]);
+        let recipient = Address::from([2u8; 32]);
+        let record = AccountRecord::Input(Input::Message(InputMessage {
+            sender: sender.to_owned(),
+            recipient: recipient.to_owned(),
+            ..Default::default()
+        }));
+        let activity =
+            |address: &Address, record: &AccountRecord| AccountActivity {
+                address: address.to_owned(),
+                tx_id: TxId::default(),
+                record: record.to_owned(),
+            };
+        assert_eq!(activity(&sender, &record).fanout_index(), 0);
+        assert_eq!(activity(&recipient, &record).fanout_index(), 1);
+
+        let utxo = AccountRecord::Utxo(Utxo {
+            sender: Some(sender.to_owned()),
+            recipient: Some(recipient.to_owned()),
+            ..Default::default()
+        });
+        assert_eq!(activity(&sender, &utxo).fanout_index(), 2);
+        assert_eq!(activity(&recipient, &utxo).fanout_index(), 3);
+    }
+
+    #[test]
+    fn test_activity_type_parsing() {
+        for activity_type in [
+            AccountActivityType::Input,
+            AccountActivityType::Output,
+            AccountActivityType::Receipt,
+            AccountActivityType::Utxo,
+        ] {
+            let parsed = activity_type.to_string().parse().unwrap();
+            assert_eq!(activity_type, parsed);
+        }
+        assert!("block".parse::<AccountActivityType>().is_err());
+    }
+}
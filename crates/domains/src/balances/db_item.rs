@@ -0,0 +1,188 @@
+use std::cmp::Ordering;
+
+use pedronauck_streams_store::{
+    db::{DbError, DbItem},
+    record::{
+        DataEncoder,
+        RecordEntity,
+        RecordPacket,
+        RecordPacketError,
+        RecordPointer,
+    },
+};
+use pedronauck_streams_types::{BlockHeight, BlockTimestamp};
+use serde::{Deserialize, Serialize};
+
+use super::{subjects::*, types::*};
+use crate::Subjects;
+
+/// Index columns a change doesn't come from are stored as `-1`, so they
+/// sort before any real index, as unset pointer indexes do
+const NO_INDEX: i32 = -1;
+
+/// Row of the `balance_changes` table. The amount is kept as a decimal
+/// string, as amounts don't fit in a `BIGINT`.
+#[derive(
+    Debug, Clone, Serialize, Deserialize, PartialEq, Eq, sqlx::FromRow,
+)]
+pub struct BalanceChangeDbItem {
+    pub subject: String,
+    pub value: Vec<u8>,
+    pub block_height: i64,
+    pub tx_id: String,
+    pub tx_index: i32,
+    pub record_index: i32,
+    pub source: String,
+    pub input_index: i32,
+    pub output_index: i32,
+    pub receipt_index: i32,
+    /// Side of the change, as a transfer debits one owner and credits
+    /// another from the same receipt: `0` for debits and `1` for credits
+    pub fanout_index: i32,
+    pub owner: String,
+    pub asset_id: String,
+    pub kind: String,
+    pub amount: String,
+    pub created_at: BlockTimestamp,
+    pub published_at: BlockTimestamp,
+}
+
+impl DataEncoder for BalanceChangeDbItem {
+    type Err = DbError;
+}
+
+impl DbItem for BalanceChangeDbItem {
+    fn entity(&self) -> &RecordEntity {
+        &RecordEntity::BalanceChange
+    }
+
+    fn encoded_value(&self) -> &[u8] {
+        &self.value
+    }
+
+    fn subject_str(&self) -> String {
+        self.subject.clone()
+    }
+
+    fn subject_id(&self) -> String {
+        BalancesSubject::ID.to_string()
+    }
+
+    fn created_at(&self) -> BlockTimestamp {
+        self.created_at
+    }
+
+    fn published_at(&self) -> BlockTimestamp {
+        self.published_at
+    }
+
+    fn block_height(&self) -> BlockHeight {
+        self.block_height.into()
+    }
+}
+
+impl TryFrom<&RecordPacket> for BalanceChangeDbItem {
+    type Error = RecordPacketError;
+    fn try_from(packet: &RecordPacket) -> Result<Self, Self::Error> {
+        let subject: Subjects = packet
+            .subject_payload
+            .to_owned()
+            .try_into()
+            .map_err(|_| RecordPacketError::SubjectMismatch)?;
+
+        match subject {
+            Subjects::Balances(subject) => {
+                let change: BalanceChange =
+                    BalanceChange::data_parser().decode_json(&packet.value)?;
+                let source = subject.source.unwrap();
+                let record_index = subject.record_index.unwrap() as i32;
+                let index_for = |expected: BalanceChangeSource| {
+                    if source == expected {
+                        record_index
+                    } else {
+                        NO_INDEX
+                    }
+                };
+                Ok(BalanceChangeDbItem {
+                    subject: packet.subject_str(),
+                    value: packet.value.to_owned(),
+                    block_height: subject.block_height.unwrap().into(),
+                    tx_id: subject.tx_id.unwrap().to_string(),
+                    tx_index: subject.tx_index.unwrap() as i32,
+                    record_index,
+                    source: source.to_string(),
+                    input_index: index_for(BalanceChangeSource::Input),
+                    output_index: index_for(BalanceChangeSource::Output),
+                    receipt_index: index_for(BalanceChangeSource::Receipt),
+                    fanout_index: match change.kind {
+                        BalanceChangeKind::Debit => 0,
+                        BalanceChangeKind::Credit => 1,
+                    },
+                    owner: subject.owner.unwrap().to_string(),
+                    asset_id: subject.asset_id.unwrap().to_string(),
+                    kind: subject.kind.unwrap().to_string(),
+                    amount: change.amount.to_string(),
+                    created_at: packet.block_timestamp,
+                    published_at: packet.block_timestamp,
+                })
+            }
+            _ => Err(RecordPacketError::SubjectMismatch),
+        }
+    }
+}
+
+impl PartialOrd for BalanceChangeDbItem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BalanceChangeDbItem {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Order by block height first
+        self.block_height
+            .cmp(&other.block_height)
+            // Then by transaction index within the block
+            .then(self.tx_index.cmp(&other.tx_index))
+            // Then by the position of the record within the transaction
+            .then(self.input_index.cmp(&other.input_index))
+            .then(self.output_index.cmp(&other.output_index))
+            .then(self.receipt_index.cmp(&other.receipt_index))
+            // Then debits before credits of the same record
+            .then(self.fanout_index.cmp(&other.fanout_index))
+    }
+}
+
+impl From<BalanceChangeDbItem> for RecordPointer {
+    fn from(val: BalanceChangeDbItem) -> Self {
+        let index = |value: i32| u32::try_from(value).ok();
+        RecordPointer {
+            block_height: val.block_height.into(),
+            tx_index: Some(val.tx_index as u32),
+            input_index: index(val.input_index),
+            output_index: index(val.output_index),
+            receipt_index: index(val.receipt_index),
+            fanout_index: Some(val.fanout_index as u32),
+        }
+    }
+}
+
+/// Row of the `balances` table, with the amount read as text
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct BalanceDbItem {
+    pub owner: String,
+    pub asset_id: String,
+    pub amount: String,
+    pub last_block_height: i64,
+}
+
+impl From<BalanceDbItem> for Balance {
+    fn from(val: BalanceDbItem) -> Self {
+        Balance {
+            owner: val.owner.as_str().into(),
+            asset_id: val.asset_id.as_str().into(),
+            amount: val.amount,
+            last_block_height: val.last_block_height.into(),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+mod db_item;
+mod packets;
+pub mod queryable;
+mod record_impl;
+pub mod subjects;
+pub mod types;
+
+pub use db_item::*;
+pub use subjects::*;
+pub use types::*;
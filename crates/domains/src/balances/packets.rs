@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use pedronauck_streams_store::record::{PacketBuilder, Record, RecordPacket};
+use rayon::prelude::*;
+
+use super::{subjects::*, types::*};
+use crate::MsgPayload;
+
+#[async_trait]
+impl PacketBuilder for BalanceChange {
+    type Opts = MsgPayload;
+
+    fn build_packets(msg_payload: &Self::Opts) -> Vec<RecordPacket> {
+        let block_height = msg_payload.block_height();
+        let timestamp = msg_payload.timestamp();
+        msg_payload
+            .transactions
+            .par_iter()
+            .enumerate()
+            .flat_map_iter(|(tx_index, tx)| {
+                let tx_index = tx_index as u32;
+                BalanceChange::from_transaction(tx)
+                    .into_iter()
+                    .map(|(record_index, change)| {
+                        let subject = BalancesSubject {
+                            owner: Some(change.owner.to_owned()),
+                            asset_id: Some(change.asset_id.to_owned()),
+                            kind: Some(change.kind),
+                            block_height: Some(block_height),
+                            tx_id: Some(tx.id.to_owned()),
+                            tx_index: Some(tx_index),
+                            source: Some(change.source),
+                            record_index: Some(record_index as u32),
+                        }
+                        .dyn_arc();
+                        let packet = change.to_packet(&subject, timestamp);
+                        match msg_payload.namespace.clone() {
+                            Some(ns) => packet.with_namespace(&ns),
+                            _ => packet,
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,158 @@
+use pedronauck_streams_types::*;
+use sea_query::{
+    Alias,
+    Condition,
+    Expr,
+    Iden,
+    Order,
+    PostgresQueryBuilder,
+    Query,
+    SelectStatement,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{Balance, BalanceDbItem};
+use crate::queryable::{HasPagination, QueryPagination};
+
+#[allow(dead_code)]
+#[derive(Iden)]
+pub enum Balances {
+    #[iden = "balances"]
+    Table,
+    #[iden = "owner"]
+    Owner,
+    #[iden = "asset_id"]
+    AssetId,
+    #[iden = "amount"]
+    Amount,
+    #[iden = "last_block_height"]
+    LastBlockHeight,
+}
+
+/// Query on the `balances` table. Its rows aren't records, so it doesn't
+/// implement [`crate::queryable::Queryable`] and returns [`Balance`]s.
+#[derive(
+    Debug, Clone, Default, Serialize, Deserialize, PartialEq, utoipa::ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct BalancesQuery {
+    pub asset_id: Option<AssetId>,
+    #[serde(flatten)]
+    pub pagination: QueryPagination,
+    pub owner: Option<Address>, // for the accounts and contracts endpoints
+}
+
+impl BalancesQuery {
+    pub fn set_owner(&mut self, owner: &str) {
+        self.owner = Some(Address::from(owner));
+    }
+
+    fn build_condition(&self) -> Condition {
+        let mut condition = Condition::all();
+
+        if let Some(owner) = &self.owner {
+            condition =
+                condition.add(Expr::col(Balances::Owner).eq(owner.to_string()));
+        }
+
+        if let Some(asset_id) = &self.asset_id {
+            condition = condition
+                .add(Expr::col(Balances::AssetId).eq(asset_id.to_string()));
+        }
+
+        condition
+    }
+
+    /// Selects the amount as text, as `NUMERIC` columns can't be decoded
+    pub fn build_query(&self) -> SelectStatement {
+        let mut condition = self.build_condition();
+        let pagination = &self.pagination;
+
+        if let Some(after) = pagination.after() {
+            condition =
+                condition.add(Expr::col(Balances::LastBlockHeight).gt(after));
+        }
+
+        if let Some(before) = pagination.before() {
+            condition =
+                condition.add(Expr::col(Balances::LastBlockHeight).lt(before));
+        }
+
+        let mut query_builder = Query::select();
+        let mut query = query_builder
+            .columns([Balances::Owner, Balances::AssetId])
+            .expr_as(
+                Expr::col(Balances::Amount).cast_as(Alias::new("TEXT")),
+                Balances::Amount,
+            )
+            .column(Balances::LastBlockHeight)
+            .from(Balances::Table)
+            .cond_where(condition);
+
+        if let Some(first) = pagination.first() {
+            query = query
+                .order_by(Balances::LastBlockHeight, Order::Asc)
+                .limit(first as u64);
+        } else if let Some(last) = pagination.last() {
+            query = query
+                .order_by(Balances::LastBlockHeight, Order::Desc)
+                .limit(last as u64);
+        }
+
+        query.to_owned()
+    }
+
+    pub fn query_to_string(&self) -> String {
+        self.build_query().to_string(PostgresQueryBuilder)
+    }
+
+    pub async fn execute<'c, E>(
+        &self,
+        executor: E,
+    ) -> Result<Vec<Balance>, sqlx::Error>
+    where
+        E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+    {
+        let sql = self.query_to_string();
+        let rows = sqlx::query_as::<_, BalanceDbItem>(&sql)
+            .fetch_all(executor)
+            .await?;
+        Ok(rows.into_iter().map(Balance::from).collect())
+    }
+}
+
+impl HasPagination for BalancesQuery {
+    fn pagination(&self) -> &QueryPagination {
+        &self.pagination
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pedronauck_streams_types::AssetId;
+    use pretty_assertions::assert_eq;
+
+    use crate::balances::queryable::BalancesQuery;
+
+    const FIRST_POINTER: i32 = 100;
+    const TEST_OWNER: &str =
+        "0x0101010101010101010101010101010101010101010101010101010101010101";
+    const TEST_ASSET_ID: &str =
+        "0x0202020202020202020202020202020202020202020202020202020202020202";
+
+    #[test]
+    fn test_sql_with_balances_of_owner() {
+        let mut query = BalancesQuery {
+            asset_id: Some(AssetId::from(TEST_ASSET_ID)),
+            pagination: (None, None, Some(FIRST_POINTER), None).into(),
+            ..Default::default()
+        };
+        query.set_owner(TEST_OWNER);
+
+        assert_eq!(
+            query.query_to_string(),
+            format!("SELECT \"owner\", \"asset_id\", CAST(\"amount\" AS TEXT) AS \"amount\", \"last_block_height\" FROM \"balances\" WHERE \"owner\" = '{}' AND \"asset_id\" = '{}' ORDER BY \"last_block_height\" ASC LIMIT {}",
+                TEST_OWNER, TEST_ASSET_ID, FIRST_POINTER)
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use async_trait::async_trait;
+use pedronauck_streams_store::{
+    db::{DbError, DbResult},
+    record::{DataEncoder, FilterColumn, Record, RecordEntity},
+};
+use pedronauck_streams_types::BlockTimestamp;
+use sqlx::PgExecutor;
+
+use super::{BalanceChange, BalanceChangeDbItem};
+
+impl DataEncoder for BalanceChange {
+    type Err = DbError;
+}
+
+#[async_trait]
+impl Record for BalanceChange {
+    type DbItem = BalanceChangeDbItem;
+
+    const ENTITY: RecordEntity = RecordEntity::BalanceChange;
+    const ORDER_PROPS: &'static [&'static str] = &[
+        "tx_index",
+        "input_index",
+        "output_index",
+        "receipt_index",
+        "fanout_index",
+    ];
+    const FILTER_COLUMNS: &'static [FilterColumn] =
+        &[FilterColumn::text("txId", "tx_id")];
+
+    /// Changes are stored once per subject and applied to the `balances`
+    /// table in the same statement, only when they are new. Storing a block
+    /// again, or out of order, leaves balances right, as additions commute.
+    async fn insert<'e, 'c: 'e, E>(
+        executor: E,
+        db_item: Self::DbItem,
+    ) -> DbResult<Self::DbItem>
+    where
+        'c: 'e,
+        E: PgExecutor<'c>,
+    {
+        let published_at = BlockTimestamp::now();
+        let record = sqlx::query_as::<_, BalanceChangeDbItem>(
+            "WITH inserted AS (
+                INSERT INTO balance_changes (
+                    subject, value, block_height, tx_id, tx_index,
+                    record_index, source, input_index, output_index,
+                    receipt_index, fanout_index, owner, asset_id, kind, amount,
+                    created_at, published_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+                ON CONFLICT (subject) DO NOTHING
+                RETURNING *
+            ),
+            applied AS (
+                INSERT INTO balances (owner, asset_id, amount, last_block_height)
+                SELECT
+                    owner,
+                    asset_id,
+                    CASE kind
+                        WHEN 'debit' THEN -(amount::NUMERIC)
+                        ELSE amount::NUMERIC
+                    END,
+                    block_height
+                FROM inserted
+                ON CONFLICT (owner, asset_id) DO UPDATE SET
+                    amount = balances.amount + EXCLUDED.amount,
+                    last_block_height = GREATEST(
+                        balances.last_block_height,
+                        EXCLUDED.last_block_height
+                    )
+            )
+            SELECT * FROM inserted
+            UNION ALL
+            SELECT * FROM balance_changes
+            WHERE subject = $1 AND NOT EXISTS (SELECT 1 FROM inserted)",
+        )
+        .bind(db_item.subject)
+        .bind(db_item.value)
+        .bind(db_item.block_height)
+        .bind(db_item.tx_id)
+        .bind(db_item.tx_index)
+        .bind(db_item.record_index)
+        .bind(db_item.source)
+        .bind(db_item.input_index)
+        .bind(db_item.output_index)
+        .bind(db_item.receipt_index)
+        .bind(db_item.fanout_index)
+        .bind(db_item.owner)
+        .bind(db_item.asset_id)
+        .bind(db_item.kind)
+        .bind(db_item.amount)
+        .bind(db_item.created_at)
+        .bind(published_at)
+        .fetch_one(executor)
+        .await
+        .map_err(DbError::Insert)?;
+
+        Ok(record)
+    }
+}
@@ -0,0 +1,41 @@
+use pedronauck_streams_subject::subject::*;
+use pedronauck_streams_types::*;
+use serde::{Deserialize, Serialize};
+
+use super::types::*;
+
+#[derive(Subject, Debug, Clone, Default, Serialize, Deserialize)]
+#[subject(id = "balances")]
+#[subject(entity = "BalanceChange")]
+#[subject(query_all = "balances.>")]
+#[subject(
+    format = "balances.{owner}.{asset_id}.{kind}.{block_height}.{tx_id}.{tx_index}.{source}.{record_index}"
+)]
+pub struct BalancesSubject {
+    #[subject(
+        description = "The address of the account, or the ID of the contract, whose balance changes (32 byte string prefixed by 0x)"
+    )]
+    pub owner: Option<Address>,
+    #[subject(
+        description = "The asset ID of the balance (32 byte string prefixed by 0x)"
+    )]
+    pub asset_id: Option<AssetId>,
+    #[subject(description = "The direction of the change (credit or debit)")]
+    pub kind: Option<BalanceChangeKind>,
+    #[subject(description = "The height of the block containing the change")]
+    pub block_height: Option<BlockHeight>,
+    #[subject(
+        description = "The ID of the transaction making the change (32 byte string prefixed by 0x)"
+    )]
+    pub tx_id: Option<TxId>,
+    #[subject(description = "The index of the transaction within the block")]
+    pub tx_index: Option<u32>,
+    #[subject(
+        description = "The kind of record making the change (input, output, or receipt)"
+    )]
+    pub source: Option<BalanceChangeSource>,
+    #[subject(
+        description = "The index of the record within its transaction's inputs, outputs or receipts"
+    )]
+    pub record_index: Option<u32>,
+}
@@ -0,0 +1,369 @@
+use pedronauck_streams_types::primitives::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    coins::types::*,
+    inputs::types::*,
+    receipts::types::*,
+    transactions::types::*,
+};
+
+/// Change to the balance an account or a contract holds of an asset, sent
+/// in the `balances` stream as a delta of the amount stored in the
+/// `balances` table
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceChange {
+    pub owner: Address,
+    pub asset_id: AssetId,
+    pub amount: Amount,
+    pub kind: BalanceChangeKind,
+    pub source: BalanceChangeSource,
+    pub tx_id: TxId,
+}
+
+impl BalanceChange {
+    fn new(
+        owner: Address,
+        asset_id: &AssetId,
+        amount: u64,
+        kind: BalanceChangeKind,
+        source: BalanceChangeSource,
+        tx_id: &TxId,
+    ) -> Self {
+        Self {
+            owner,
+            asset_id: asset_id.to_owned(),
+            amount: amount.into(),
+            kind,
+            source,
+            tx_id: tx_id.to_owned(),
+        }
+    }
+
+    /// Changes made by a transaction, each one with the index of the input,
+    /// output or receipt it comes from. Coins spent by inputs are debited
+    /// and coins created by outputs credited. Receipts move assets between
+    /// contracts, or mint and burn them, so they only count when the
+    /// transaction succeeded. Coins a contract sends to an address already
+    /// show up as variable outputs.
+    pub fn from_transaction(tx: &Transaction) -> Vec<(usize, Self)> {
+        use BalanceChangeKind::*;
+        use BalanceChangeSource as Source;
+
+        let spent =
+            tx.inputs.iter().enumerate().filter_map(|(index, input)| {
+                let Input::Coin(input) = input else {
+                    return None;
+                };
+                let change = Self::new(
+                    input.owner.to_owned(),
+                    &input.asset_id,
+                    *input.amount,
+                    Debit,
+                    Source::Input,
+                    &tx.id,
+                );
+                Some((index, change))
+            });
+        let created =
+            tx.outputs.iter().enumerate().filter_map(|(index, output)| {
+                let coin = Coin::from_output(output, &tx.id, index as u16)?;
+                let change = Self::new(
+                    coin.owner,
+                    &coin.asset_id,
+                    *coin.amount,
+                    Credit,
+                    Source::Output,
+                    &tx.id,
+                );
+                Some((index, change))
+            });
+        let receipts = match tx.status {
+            TransactionStatus::Failed => &[][..],
+            _ => &tx.receipts[..],
+        };
+        let moved = receipts.iter().enumerate().flat_map(|(index, receipt)| {
+            Self::from_receipt(receipt, &tx.id)
+                .into_iter()
+                .map(move |change| (index, change))
+        });
+        spent.chain(created).chain(moved).collect()
+    }
+
+    /// Changes to the balances of the contracts a receipt involves. The
+    /// zero ID of calls and transfers made by scripts is left out, as the
+    /// coins they forward come from the inputs of the transaction.
+    fn from_receipt(receipt: &Receipt, tx_id: &TxId) -> Vec<Self> {
+        use BalanceChangeKind::*;
+
+        let change = |contract_id: &ContractId, asset_id, amount, kind| {
+            Self::new(
+                Address::from(Bytes32::from(contract_id)),
+                asset_id,
+                amount,
+                kind,
+                BalanceChangeSource::Receipt,
+                tx_id,
+            )
+        };
+        let (from, to, asset_id, amount) = match receipt {
+            Receipt::Call(CallReceipt {
+                id,
+                to,
+                amount,
+                asset_id,
+                ..
+            })
+            | Receipt::Transfer(TransferReceipt {
+                id,
+                to,
+                amount,
+                asset_id,
+                ..
+            }) => (id, Some(to), asset_id.to_owned(), **amount),
+            Receipt::TransferOut(receipt) => (
+                &receipt.id,
+                None,
+                receipt.asset_id.to_owned(),
+                *receipt.amount,
+            ),
+            Receipt::Mint(receipt) => {
+                let asset_id =
+                    mint_asset_id(&receipt.contract_id, &receipt.sub_id);
+                return vec![change(
+                    &receipt.contract_id,
+                    &asset_id,
+                    *receipt.val,
+                    Credit,
+                )];
+            }
+            Receipt::Burn(receipt) => {
+                let asset_id =
+                    mint_asset_id(&receipt.contract_id, &receipt.sub_id);
+                return vec![change(
+                    &receipt.contract_id,
+                    &asset_id,
+                    *receipt.val,
+                    Debit,
+                )];
+            }
+            _ => return vec![],
+        };
+        if amount == 0 || to == Some(from) {
+            return vec![];
+        }
+        let debit = (*from != ContractId::default())
+            .then(|| change(from, &asset_id, amount, Debit));
+        let credit = to.map(|to| change(to, &asset_id, amount, Credit));
+        debit.into_iter().chain(credit).collect()
+    }
+}
+
+/// ID of the asset a contract mints or burns under a sub ID
+fn mint_asset_id(contract_id: &ContractId, sub_id: &Bytes32) -> AssetId {
+    let sub_id = sub_id.to_owned().into_inner();
+    AssetId::from(contract_id.to_owned().into_inner().asset_id(&sub_id))
+}
+
+/// Balance of an asset held by an account or a contract. The amount is a
+/// decimal string, as it is summed from the stored changes and a spend
+/// stored before the coin it consumes leaves it below zero for a while.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Balance {
+    pub owner: Address,
+    pub asset_id: AssetId,
+    pub amount: String,
+    pub last_block_height: BlockHeight,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum BalanceChangeKind {
+    Credit,
+    Debit,
+}
+
+impl BalanceChangeKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BalanceChangeKind::Credit => "credit",
+            BalanceChangeKind::Debit => "debit",
+        }
+    }
+}
+
+impl std::fmt::Display for BalanceChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for BalanceChangeKind {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            s if s == Self::Credit.as_str() => Ok(Self::Credit),
+            s if s == Self::Debit.as_str() => Ok(Self::Debit),
+            _ => Err(format!("Invalid balance change kind: {s}")),
+        }
+    }
+}
+
+/// Kind of record a balance change comes from
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum BalanceChangeSource {
+    Input,
+    Output,
+    Receipt,
+}
+
+impl BalanceChangeSource {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BalanceChangeSource::Input => "input",
+            BalanceChangeSource::Output => "output",
+            BalanceChangeSource::Receipt => "receipt",
+        }
+    }
+}
+
+impl std::fmt::Display for BalanceChangeSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for BalanceChangeSource {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            s if s == Self::Input.as_str() => Ok(Self::Input),
+            s if s == Self::Output.as_str() => Ok(Self::Output),
+            s if s == Self::Receipt.as_str() => Ok(Self::Receipt),
+            _ => Err(format!("Invalid balance change source: {s}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use BalanceChangeKind::*;
+    use BalanceChangeSource as Source;
+
+    use super::*;
+    use crate::outputs::types::*;
+
+    fn contract_address(contract_id: &ContractId) -> Address {
+        Address::from(Bytes32::from(contract_id))
+    }
+
+    #[test]
+    fn test_changes_from_coins_and_transfers() {
+        let owner = Address::from([1u8; 32]);
+        let contract_a = ContractId::from([2u8; 32]);
+        let contract_b = ContractId::from([3u8; 32]);
+        let transfer = Receipt::Transfer(TransferReceipt {
+            id: contract_a.to_owned(),
+            to: contract_b.to_owned(),
+            amount: 30.into(),
+            ..Default::default()
+        });
+        let transfer_out = Receipt::TransferOut(TransferOutReceipt {
+            id: contract_b.to_owned(),
+            to: owner.to_owned(),
+            amount: 10.into(),
+            ..Default::default()
+        });
+        let outputs = vec![Output::Change(OutputChange {
+            to: owner.to_owned(),
+            amount: 50.into(),
+            ..Default::default()
+        })];
+        let mut tx = MockTransaction::script(
+            vec![MockInput::coin_signed()],
+            outputs,
+            vec![transfer, transfer_out],
+        );
+        let Input::Coin(input) = tx.inputs[0].to_owned() else {
+            panic!("expected a coin input");
+        };
+
+        let changes = BalanceChange::from_transaction(&tx)
+            .into_iter()
+            .map(|(index, change)| {
+                (
+                    index,
+                    change.source,
+                    change.kind,
+                    change.owner,
+                    *change.amount,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(changes, vec![
+            (0, Source::Input, Debit, input.owner, *input.amount),
+            (0, Source::Output, Credit, owner, 50),
+            (0, Source::Receipt, Debit, contract_address(&contract_a), 30),
+            (
+                0,
+                Source::Receipt,
+                Credit,
+                contract_address(&contract_b),
+                30
+            ),
+            (1, Source::Receipt, Debit, contract_address(&contract_b), 10),
+        ]);
+
+        // Receipts of failed transactions are reverted
+        tx.status = TransactionStatus::Failed;
+        let changes = BalanceChange::from_transaction(&tx);
+        assert_eq!(changes.len(), 2);
+        assert!(changes
+            .iter()
+            .all(|(_, change)| change.source != Source::Receipt));
+    }
+
+    #[test]
+    fn test_changes_from_mints_and_script_calls() {
+        let tx_id = TxId::random();
+        let contract_id = ContractId::from([2u8; 32]);
+        let sub_id = Bytes32::from([4u8; 32]);
+        let mint = Receipt::Mint(MintReceipt {
+            sub_id: sub_id.to_owned(),
+            contract_id: contract_id.to_owned(),
+            val: 100.into(),
+            ..Default::default()
+        });
+        let changes = BalanceChange::from_receipt(&mint, &tx_id);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, Credit);
+        assert_eq!(changes[0].owner, contract_address(&contract_id));
+        assert_eq!(changes[0].asset_id, mint_asset_id(&contract_id, &sub_id));
+        assert_ne!(changes[0].asset_id, AssetId::default());
+
+        // Coins a script forwards come from its inputs, so only the called
+        // contract changes
+        let call = Receipt::Call(CallReceipt {
+            id: ContractId::default(),
+            to: contract_id.to_owned(),
+            amount: 5.into(),
+            ..Default::default()
+        });
+        let changes = BalanceChange::from_receipt(&call, &tx_id);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, Credit);
+        assert_eq!(changes[0].owner, contract_address(&contract_id));
+    }
+}
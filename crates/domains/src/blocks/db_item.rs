@@ -108,6 +108,7 @@ impl From<BlockDbItem> for RecordPointer {
             input_index: None,
             output_index: None,
             receipt_index: None,
+            fanout_index: None,
         }
     }
 }
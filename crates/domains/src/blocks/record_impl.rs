@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use pedronauck_streams_store::{
     db::{DbError, DbResult},
-    record::{DataEncoder, Record, RecordEntity},
+    record::{DataEncoder, FilterColumn, Record, RecordEntity},
 };
 use pedronauck_streams_types::BlockTimestamp;
 use sqlx::PgExecutor;
@@ -18,6 +18,10 @@ impl Record for Block {
 
     const ENTITY: RecordEntity = RecordEntity::Block;
     const ORDER_PROPS: &'static [&'static str] = &["block_height"];
+    const FILTER_COLUMNS: &'static [FilterColumn] = &[
+        FilterColumn::int("height", "block_height"),
+        FilterColumn::text("producer", "producer_address"),
+    ];
 
     async fn insert<'e, 'c: 'e, E>(
         executor: E,
@@ -0,0 +1,122 @@
+use std::cmp::Ordering;
+
+use pedronauck_streams_store::{
+    db::{DbError, DbItem},
+    record::{
+        DataEncoder,
+        RecordEntity,
+        RecordPacket,
+        RecordPacketError,
+        RecordPointer,
+    },
+};
+use pedronauck_streams_types::{BlockHeight, BlockTimestamp};
+use serde::{Deserialize, Serialize};
+
+use super::subjects::*;
+use crate::Subjects;
+
+#[derive(
+    Debug, Clone, Serialize, Deserialize, PartialEq, Eq, sqlx::FromRow,
+)]
+pub struct BundleDbItem {
+    pub subject: String,
+    pub value: Vec<u8>,
+    pub block_height: i64,
+    pub tx_id: String,
+    pub tx_index: i32,
+    pub tx_status: String,
+    #[serde(rename = "type")]
+    pub r#type: String,
+    pub created_at: BlockTimestamp,
+    pub published_at: BlockTimestamp,
+}
+
+impl DataEncoder for BundleDbItem {
+    type Err = DbError;
+}
+
+impl DbItem for BundleDbItem {
+    fn entity(&self) -> &RecordEntity {
+        &RecordEntity::Bundle
+    }
+
+    fn encoded_value(&self) -> &[u8] {
+        &self.value
+    }
+
+    fn subject_str(&self) -> String {
+        self.subject.clone()
+    }
+
+    fn subject_id(&self) -> String {
+        BundlesSubject::ID.to_string()
+    }
+
+    fn created_at(&self) -> BlockTimestamp {
+        self.created_at
+    }
+
+    fn published_at(&self) -> BlockTimestamp {
+        self.published_at
+    }
+
+    fn block_height(&self) -> BlockHeight {
+        self.block_height.into()
+    }
+}
+
+impl TryFrom<&RecordPacket> for BundleDbItem {
+    type Error = RecordPacketError;
+    fn try_from(packet: &RecordPacket) -> Result<Self, Self::Error> {
+        let subject: Subjects = packet
+            .subject_payload
+            .to_owned()
+            .try_into()
+            .map_err(|_| RecordPacketError::SubjectMismatch)?;
+
+        match subject {
+            Subjects::Bundles(subject) => Ok(BundleDbItem {
+                subject: packet.subject_str(),
+                value: packet.value.to_owned(),
+                block_height: subject.block_height.unwrap().into(),
+                tx_id: subject.tx_id.unwrap().to_string(),
+                tx_index: subject.tx_index.unwrap() as i32,
+                tx_status: subject.tx_status.unwrap().to_string(),
+                r#type: subject.tx_type.unwrap().to_string(),
+                created_at: packet.block_timestamp,
+                published_at: packet.block_timestamp,
+            }),
+            _ => Err(RecordPacketError::SubjectMismatch),
+        }
+    }
+}
+
+impl PartialOrd for BundleDbItem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BundleDbItem {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Order by block height first
+        self.block_height
+            .cmp(&other.block_height)
+            // Then by transaction index within the block
+            .then(self.tx_index.cmp(&other.tx_index))
+    }
+}
+
+impl From<BundleDbItem> for RecordPointer {
+    fn from(val: BundleDbItem) -> Self {
+        RecordPointer {
+            block_height: val.block_height.into(),
+            tx_index: Some(val.tx_index as u32),
+            input_index: None,
+            output_index: None,
+            receipt_index: None,
+            fanout_index: None,
+        }
+    }
+}
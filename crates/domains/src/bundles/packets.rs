@@ -0,0 +1,38 @@
+use async_trait::async_trait;
+use pedronauck_streams_store::record::{PacketBuilder, Record, RecordPacket};
+use rayon::prelude::*;
+
+use super::{subjects::*, TransactionBundle};
+use crate::MsgPayload;
+
+#[async_trait]
+impl PacketBuilder for TransactionBundle {
+    type Opts = MsgPayload;
+
+    fn build_packets(msg_payload: &Self::Opts) -> Vec<RecordPacket> {
+        let block_height = msg_payload.block_height();
+        let timestamps = msg_payload.timestamp();
+        msg_payload
+            .transactions
+            .par_iter()
+            .enumerate()
+            .map(|(tx_index, tx)| {
+                let tx_index = tx_index as u32;
+                let bundle = TransactionBundle::new(block_height, tx_index, tx);
+                let subject = BundlesSubject {
+                    block_height: Some(block_height),
+                    tx_id: Some(tx.id.to_owned()),
+                    tx_index: Some(tx_index),
+                    tx_status: Some(tx.status.to_owned()),
+                    tx_type: Some(tx.tx_type.to_owned()),
+                }
+                .dyn_arc();
+                let packet = bundle.to_packet(&subject, timestamps);
+                match msg_payload.namespace.clone() {
+                    Some(ns) => packet.with_namespace(&ns),
+                    _ => packet,
+                }
+            })
+            .collect()
+    }
+}
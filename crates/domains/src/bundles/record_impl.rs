@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+use pedronauck_streams_store::{
+    db::{DbError, DbResult},
+    record::{DataEncoder, FilterColumn, Record, RecordEntity},
+};
+use pedronauck_streams_types::BlockTimestamp;
+use sqlx::PgExecutor;
+
+use super::{BundleDbItem, TransactionBundle};
+
+impl DataEncoder for TransactionBundle {
+    type Err = DbError;
+}
+
+#[async_trait]
+impl Record for TransactionBundle {
+    type DbItem = BundleDbItem;
+
+    const ENTITY: RecordEntity = RecordEntity::Bundle;
+    const ORDER_PROPS: &'static [&'static str] = &["tx_index"];
+    const FILTER_COLUMNS: &'static [FilterColumn] = &[
+        FilterColumn::text("transaction.id", "tx_id"),
+        FilterColumn::text("transaction.status", "tx_status"),
+    ];
+
+    async fn insert<'e, 'c: 'e, E>(
+        executor: E,
+        db_item: Self::DbItem,
+    ) -> DbResult<Self::DbItem>
+    where
+        'c: 'e,
+        E: PgExecutor<'c>,
+    {
+        let published_at = BlockTimestamp::now();
+        let record = sqlx::query_as::<_, BundleDbItem>(
+            "WITH upsert AS (
+                INSERT INTO bundles (
+                    subject, value, block_height, tx_id, tx_index,
+                    tx_status, type, created_at, published_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                ON CONFLICT (subject) DO UPDATE SET
+                    value = EXCLUDED.value,
+                    block_height = EXCLUDED.block_height,
+                    tx_id = EXCLUDED.tx_id,
+                    tx_index = EXCLUDED.tx_index,
+                    tx_status = EXCLUDED.tx_status,
+                    type = EXCLUDED.type,
+                    created_at = EXCLUDED.created_at,
+                    published_at = $9
+                RETURNING *
+            )
+            SELECT * FROM upsert",
+        )
+        .bind(db_item.subject)
+        .bind(db_item.value)
+        .bind(db_item.block_height)
+        .bind(db_item.tx_id)
+        .bind(db_item.tx_index)
+        .bind(db_item.tx_status)
+        .bind(db_item.r#type)
+        .bind(db_item.created_at)
+        .bind(published_at)
+        .fetch_one(executor)
+        .await
+        .map_err(DbError::Insert)?;
+
+        Ok(record)
+    }
+}
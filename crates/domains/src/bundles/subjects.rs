@@ -0,0 +1,41 @@
+use pedronauck_streams_subject::subject::*;
+use pedronauck_streams_types::*;
+use serde::{Deserialize, Serialize};
+
+use crate::transactions::types::*;
+
+#[derive(Subject, Debug, Clone, Default, Serialize, Deserialize)]
+#[subject(id = "bundles")]
+#[subject(entity = "TransactionBundle")]
+#[subject(query_all = "bundles.>")]
+#[subject(
+    format = "bundles.{block_height}.{tx_id}.{tx_index}.{tx_status}.{tx_type}"
+)]
+pub struct BundlesSubject {
+    #[subject(
+        description = "The height of the block containing this transaction"
+    )]
+    pub block_height: Option<BlockHeight>,
+    #[subject(
+        description = "The ID of the transaction (32 byte string prefixed by 0x)"
+    )]
+    pub tx_id: Option<TxId>,
+    #[subject(description = "The index of the transaction within the block")]
+    pub tx_index: Option<u32>,
+    #[subject(
+        description = "The status of the transaction (success, failure, or submitted)"
+    )]
+    pub tx_status: Option<TransactionStatus>,
+    #[subject(description = "The type of transaction (create, mint, script)")]
+    #[subject(sql_column = "type")]
+    pub tx_type: Option<TransactionType>,
+}
+
+impl From<&Transaction> for BundlesSubject {
+    fn from(transaction: &Transaction) -> Self {
+        let subject = BundlesSubject::new();
+        subject
+            .with_tx_id(Some(transaction.id.clone()))
+            .with_tx_type(Some(transaction.tx_type.clone()))
+    }
+}
@@ -0,0 +1,149 @@
+use pedronauck_streams_store::record::RecordPointer;
+use pedronauck_streams_types::BlockHeight;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    inputs::types::*,
+    outputs::types::*,
+    receipts::types::*,
+    transactions::types::*,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BundledInput {
+    pub pointer: RecordPointer,
+    pub input: Input,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BundledOutput {
+    pub pointer: RecordPointer,
+    pub output: Output,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BundledReceipt {
+    pub pointer: RecordPointer,
+    pub receipt: Receipt,
+}
+
+/// A transaction together with its inputs, outputs and receipts, each one
+/// with the pointer it has in its own stream. The nested records of the
+/// transaction itself are left empty, as they're already in the bundle.
+#[derive(
+    Debug, Default, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionBundle {
+    pub pointer: RecordPointer,
+    pub transaction: Transaction,
+    pub inputs: Vec<BundledInput>,
+    pub outputs: Vec<BundledOutput>,
+    pub receipts: Vec<BundledReceipt>,
+}
+
+impl TransactionBundle {
+    pub fn new(
+        block_height: BlockHeight,
+        tx_index: u32,
+        transaction: &Transaction,
+    ) -> Self {
+        let pointer = RecordPointer {
+            block_height,
+            tx_index: Some(tx_index),
+            ..Default::default()
+        };
+        let inputs = transaction
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(index, input)| BundledInput {
+                pointer: RecordPointer {
+                    input_index: Some(index as u32),
+                    ..pointer
+                },
+                input: input.to_owned(),
+            })
+            .collect();
+        let outputs = transaction
+            .outputs
+            .iter()
+            .enumerate()
+            .map(|(index, output)| BundledOutput {
+                pointer: RecordPointer {
+                    output_index: Some(index as u32),
+                    ..pointer
+                },
+                output: output.to_owned(),
+            })
+            .collect();
+        let receipts = transaction
+            .receipts
+            .iter()
+            .enumerate()
+            .map(|(index, receipt)| BundledReceipt {
+                pointer: RecordPointer {
+                    receipt_index: Some(index as u32),
+                    ..pointer
+                },
+                receipt: receipt.to_owned(),
+            })
+            .collect();
+        let transaction = Transaction {
+            inputs: vec![],
+            outputs: vec![],
+            receipts: vec![],
+            ..transaction.to_owned()
+        };
+        Self {
+            pointer,
+            transaction,
+            inputs,
+            outputs,
+            receipts,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_bundle_keeps_child_pointers() {
+        let tx = MockTransaction::script(
+            MockInput::all(),
+            MockOutput::all(),
+            MockReceipt::all(),
+        );
+        let bundle = TransactionBundle::new(10.into(), 2, &tx);
+
+        assert_eq!(bundle.pointer.tx_index, Some(2));
+        assert!(bundle.transaction.inputs.is_empty());
+        assert!(bundle.transaction.outputs.is_empty());
+        assert!(bundle.transaction.receipts.is_empty());
+        assert_eq!(bundle.inputs.len(), tx.inputs.len());
+        assert_eq!(bundle.outputs.len(), tx.outputs.len());
+        assert_eq!(bundle.receipts.len(), tx.receipts.len());
+
+        let last = bundle.receipts.last().unwrap();
+        assert_eq!(last.receipt, *tx.receipts.last().unwrap());
+        assert_eq!(last.pointer, RecordPointer {
+            block_height: 10.into(),
+            tx_index: Some(2),
+            input_index: None,
+            output_index: None,
+            receipt_index: Some(tx.receipts.len() as u32 - 1),
+        });
+
+        let serialized = serde_json::to_string(&bundle).unwrap();
+        let deserialized: TransactionBundle =
+            serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, bundle);
+    }
+}
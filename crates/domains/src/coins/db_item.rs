@@ -0,0 +1,153 @@
+use std::cmp::Ordering;
+
+use pedronauck_streams_store::{
+    db::{DbError, DbItem},
+    record::{
+        DataEncoder,
+        RecordEntity,
+        RecordPacket,
+        RecordPacketError,
+        RecordPointer,
+    },
+};
+use pedronauck_streams_types::{BlockHeight, BlockTimestamp};
+use serde::{Deserialize, Serialize};
+
+use super::{subjects::*, types::*};
+use crate::Subjects;
+
+/// Stored in the index column the state change didn't come from, e.g. the
+/// output index of a coin being spent
+const NO_INDEX: i32 = -1;
+
+/// Current state of a coin. The position columns point to the transaction
+/// that last changed it: the one creating it or, once spent, the one
+/// spending it.
+#[derive(
+    Debug, Clone, Serialize, Deserialize, PartialEq, Eq, sqlx::FromRow,
+)]
+pub struct CoinDbItem {
+    pub subject: String,
+    pub value: Vec<u8>,
+    pub block_height: i64,
+    pub tx_id: String,
+    pub tx_index: i32,
+    pub record_index: i32,
+    pub input_index: i32,
+    pub output_index: i32,
+    pub utxo_id: String,
+    pub owner: String,
+    pub asset_id: String,
+    pub status: String,
+    pub spent_in_tx: Option<String>,
+    pub spent_at_height: Option<i64>,
+    pub created_at: BlockTimestamp,
+    pub published_at: BlockTimestamp,
+}
+
+impl DataEncoder for CoinDbItem {
+    type Err = DbError;
+}
+
+impl DbItem for CoinDbItem {
+    fn entity(&self) -> &RecordEntity {
+        &RecordEntity::Coin
+    }
+
+    fn encoded_value(&self) -> &[u8] {
+        &self.value
+    }
+
+    fn subject_str(&self) -> String {
+        self.subject.clone()
+    }
+
+    fn subject_id(&self) -> String {
+        CoinsSubject::ID.to_string()
+    }
+
+    fn created_at(&self) -> BlockTimestamp {
+        self.created_at
+    }
+
+    fn published_at(&self) -> BlockTimestamp {
+        self.published_at
+    }
+
+    fn block_height(&self) -> BlockHeight {
+        self.block_height.into()
+    }
+}
+
+impl TryFrom<&RecordPacket> for CoinDbItem {
+    type Error = RecordPacketError;
+    fn try_from(packet: &RecordPacket) -> Result<Self, Self::Error> {
+        let subject: Subjects = packet
+            .subject_payload
+            .to_owned()
+            .try_into()
+            .map_err(|_| RecordPacketError::SubjectMismatch)?;
+
+        match subject {
+            Subjects::Coins(subject) => {
+                let status = subject.status.unwrap();
+                let spent = status == CoinStatus::Spent;
+                let block_height: i64 = subject.block_height.unwrap().into();
+                let tx_id = subject.tx_id.unwrap().to_string();
+                let record_index = subject.record_index.unwrap() as i32;
+                Ok(CoinDbItem {
+                    subject: packet.subject_str(),
+                    value: packet.value.to_owned(),
+                    block_height,
+                    tx_id: tx_id.to_owned(),
+                    tx_index: subject.tx_index.unwrap() as i32,
+                    record_index,
+                    input_index: if spent { record_index } else { NO_INDEX },
+                    output_index: if spent { NO_INDEX } else { record_index },
+                    utxo_id: subject.utxo_id.unwrap().to_string(),
+                    owner: subject.owner.unwrap().to_string(),
+                    asset_id: subject.asset_id.unwrap().to_string(),
+                    status: status.to_string(),
+                    spent_in_tx: spent.then_some(tx_id),
+                    spent_at_height: spent.then_some(block_height),
+                    created_at: packet.block_timestamp,
+                    published_at: packet.block_timestamp,
+                })
+            }
+            _ => Err(RecordPacketError::SubjectMismatch),
+        }
+    }
+}
+
+impl PartialOrd for CoinDbItem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CoinDbItem {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Order by block height first
+        self.block_height
+            .cmp(&other.block_height)
+            // Then by transaction index within the block
+            .then(self.tx_index.cmp(&other.tx_index))
+            // Then by the input spending the coin or the output creating it
+            .then(self.input_index.cmp(&other.input_index))
+            .then(self.output_index.cmp(&other.output_index))
+    }
+}
+
+impl From<CoinDbItem> for RecordPointer {
+    fn from(val: CoinDbItem) -> Self {
+        let index = |value: i32| u32::try_from(value).ok();
+        RecordPointer {
+            block_height: val.block_height.into(),
+            tx_index: Some(val.tx_index as u32),
+            input_index: index(val.input_index),
+            output_index: index(val.output_index),
+            receipt_index: None,
+            fanout_index: None,
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use async_trait::async_trait;
+use pedronauck_streams_store::record::{PacketBuilder, Record, RecordPacket};
+use pedronauck_streams_types::HexData;
+use rayon::prelude::*;
+
+use super::{subjects::*, types::*};
+use crate::MsgPayload;
+
+#[async_trait]
+impl PacketBuilder for Coin {
+    type Opts = MsgPayload;
+
+    fn build_packets(msg_payload: &Self::Opts) -> Vec<RecordPacket> {
+        let block_height = msg_payload.block_height();
+        let timestamp = msg_payload.timestamp();
+        msg_payload
+            .transactions
+            .par_iter()
+            .enumerate()
+            .flat_map_iter(|(tx_index, tx)| {
+                let tx_index = tx_index as u32;
+                let spent = tx.inputs.iter().enumerate().filter_map(
+                    |(index, input)| {
+                        Coin::from_input(input, &tx.id, block_height)
+                            .map(|coin| (index, coin))
+                    },
+                );
+                let created = tx.outputs.iter().enumerate().filter_map(
+                    |(index, output)| {
+                        Coin::from_output(output, &tx.id, index as u16)
+                            .map(|coin| (index, coin))
+                    },
+                );
+                spent
+                    .chain(created)
+                    .map(|(record_index, coin)| {
+                        let subject = CoinsSubject {
+                            owner: Some(coin.owner.to_owned()),
+                            asset_id: Some(coin.asset_id.to_owned()),
+                            utxo_id: Some(HexData::from(&coin.utxo_id)),
+                            status: Some(coin.status),
+                            block_height: Some(block_height),
+                            tx_id: Some(tx.id.to_owned()),
+                            tx_index: Some(tx_index),
+                            record_index: Some(record_index as u32),
+                        }
+                        .dyn_arc();
+                        let packet = coin.to_packet(&subject, timestamp);
+                        match msg_payload.namespace.clone() {
+                            Some(ns) => packet.with_namespace(&ns),
+                            _ => packet,
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,186 @@
+use pedronauck_streams_types::*;
+use sea_query::{Condition, Expr, Iden};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+
+use super::{CoinDbItem, CoinStatus};
+use crate::queryable::{HasPagination, QueryPagination, Queryable};
+
+#[allow(dead_code)]
+#[derive(Iden)]
+pub enum Coins {
+    #[iden = "coins"]
+    Table,
+    #[iden = "subject"]
+    Subject,
+    #[iden = "value"]
+    Value,
+    #[iden = "block_height"]
+    BlockHeight,
+    #[iden = "tx_id"]
+    TxId,
+    #[iden = "tx_index"]
+    TxIndex,
+    #[iden = "record_index"]
+    RecordIndex,
+    #[iden = "input_index"]
+    InputIndex,
+    #[iden = "output_index"]
+    OutputIndex,
+    #[iden = "utxo_id"]
+    UtxoId,
+    #[iden = "owner"]
+    Owner,
+    #[iden = "asset_id"]
+    AssetId,
+    #[iden = "status"]
+    Status,
+    #[iden = "spent_in_tx"]
+    SpentInTx,
+    #[iden = "spent_at_height"]
+    SpentAtHeight,
+    #[iden = "created_at"]
+    CreatedAt,
+    #[iden = "published_at"]
+    PublishedAt,
+}
+
+#[serde_as]
+#[derive(
+    Debug, Clone, Default, Serialize, Deserialize, PartialEq, utoipa::ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinsQuery {
+    pub utxo_id: Option<HexData>,
+    pub asset_id: Option<AssetId>,
+    pub block_height: Option<BlockHeight>,
+    pub spent_in_tx: Option<TxId>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub unspent: Option<bool>,
+    #[serde(flatten)]
+    pub pagination: QueryPagination,
+    pub owner: Option<Address>, // for the accounts endpoint
+}
+
+impl CoinsQuery {
+    pub fn set_owner(&mut self, owner: &str) {
+        self.owner = Some(Address::from(owner));
+    }
+}
+
+#[async_trait::async_trait]
+impl Queryable for CoinsQuery {
+    type Record = CoinDbItem;
+    type Table = Coins;
+    type PaginationColumn = Coins;
+
+    fn table() -> Self::Table {
+        Coins::Table
+    }
+
+    fn pagination_column() -> Self::PaginationColumn {
+        Coins::BlockHeight
+    }
+
+    fn pagination(&self) -> &QueryPagination {
+        &self.pagination
+    }
+
+    fn build_condition(&self) -> Condition {
+        let mut condition = Condition::all();
+
+        if let Some(owner) = &self.owner {
+            condition =
+                condition.add(Expr::col(Coins::Owner).eq(owner.to_string()));
+        }
+
+        if let Some(asset_id) = &self.asset_id {
+            condition = condition
+                .add(Expr::col(Coins::AssetId).eq(asset_id.to_string()));
+        }
+
+        if let Some(unspent) = self.unspent {
+            let status = match unspent {
+                true => CoinStatus::Unspent,
+                false => CoinStatus::Spent,
+            };
+            condition =
+                condition.add(Expr::col(Coins::Status).eq(status.to_string()));
+        }
+
+        if let Some(block_height) = &self.block_height {
+            condition =
+                condition.add(Expr::col(Coins::BlockHeight).eq(**block_height));
+        }
+
+        if let Some(spent_in_tx) = &self.spent_in_tx {
+            condition = condition
+                .add(Expr::col(Coins::SpentInTx).eq(spent_in_tx.to_string()));
+        }
+
+        // unique conditions
+        if let Some(utxo_id) = &self.utxo_id {
+            condition =
+                condition.add(Expr::col(Coins::UtxoId).eq(utxo_id.to_string()));
+        }
+
+        condition
+    }
+}
+
+impl HasPagination for CoinsQuery {
+    fn pagination(&self) -> &QueryPagination {
+        &self.pagination
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pedronauck_streams_types::AssetId;
+    use pretty_assertions::assert_eq;
+
+    use crate::{coins::queryable::CoinsQuery, queryable::Queryable};
+
+    const FIRST_POINTER: i32 = 100;
+    const TEST_OWNER: &str =
+        "0x0101010101010101010101010101010101010101010101010101010101010101";
+    const TEST_ASSET_ID: &str =
+        "0x0202020202020202020202020202020202020202020202020202020202020202";
+
+    #[test]
+    fn test_sql_with_unspent_coins_of_owner() {
+        let mut query = CoinsQuery {
+            asset_id: Some(AssetId::from(TEST_ASSET_ID)),
+            unspent: Some(true),
+            pagination: (None, None, Some(FIRST_POINTER), None).into(),
+            ..Default::default()
+        };
+        query.set_owner(TEST_OWNER);
+
+        assert_eq!(
+            query.query_to_string(),
+            format!("SELECT * FROM \"coins\" WHERE \"owner\" = '{}' AND \"asset_id\" = '{}' AND \"status\" = 'unspent' ORDER BY \"block_height\" ASC LIMIT {}",
+                TEST_OWNER, TEST_ASSET_ID, FIRST_POINTER)
+        );
+    }
+
+    #[test]
+    fn test_coins_query_from_query_string() {
+        use serde_urlencoded;
+
+        let query_string = format!(
+            "unspent=false&assetId={TEST_ASSET_ID}&first={FIRST_POINTER}"
+        );
+        let query: CoinsQuery =
+            serde_urlencoded::from_str(&query_string).unwrap();
+
+        assert_eq!(query.unspent, Some(false));
+        assert_eq!(query.asset_id, Some(AssetId::from(TEST_ASSET_ID)));
+        assert_eq!(query.pagination().first, Some(FIRST_POINTER));
+        assert_eq!(
+            query.query_to_string(),
+            format!("SELECT * FROM \"coins\" WHERE \"asset_id\" = '{}' AND \"status\" = 'spent' ORDER BY \"block_height\" ASC LIMIT {}",
+                TEST_ASSET_ID, FIRST_POINTER)
+        );
+    }
+}
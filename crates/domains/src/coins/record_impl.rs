@@ -0,0 +1,94 @@
+use async_trait::async_trait;
+use pedronauck_streams_store::{
+    db::{DbError, DbResult},
+    record::{DataEncoder, FilterColumn, Record, RecordEntity},
+};
+use pedronauck_streams_types::BlockTimestamp;
+use sqlx::PgExecutor;
+
+use super::{Coin, CoinDbItem};
+
+impl DataEncoder for Coin {
+    type Err = DbError;
+}
+
+#[async_trait]
+impl Record for Coin {
+    type DbItem = CoinDbItem;
+
+    const ENTITY: RecordEntity = RecordEntity::Coin;
+    const ORDER_PROPS: &'static [&'static str] =
+        &["tx_index", "input_index", "output_index"];
+    const FILTER_COLUMNS: &'static [FilterColumn] = &[
+        FilterColumn::text("txId", "tx_id"),
+        FilterColumn::text("spentInTx", "spent_in_tx"),
+    ];
+
+    /// Coins are stored once per UTXO ID, so a spend updates the row of the
+    /// coin it consumes. Blocks can be stored out of order, so a spend that
+    /// was stored first is kept when its coin creation comes in later.
+    async fn insert<'e, 'c: 'e, E>(
+        executor: E,
+        db_item: Self::DbItem,
+    ) -> DbResult<Self::DbItem>
+    where
+        'c: 'e,
+        E: PgExecutor<'c>,
+    {
+        let published_at = BlockTimestamp::now();
+        let record = sqlx::query_as::<_, CoinDbItem>(
+            "WITH upsert AS (
+                INSERT INTO coins (
+                    subject, value, block_height, tx_id, tx_index,
+                    record_index, input_index, output_index, utxo_id, owner,
+                    asset_id, status, spent_in_tx, spent_at_height,
+                    created_at, published_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+                ON CONFLICT (utxo_id) DO UPDATE SET
+                    subject = EXCLUDED.subject,
+                    value = EXCLUDED.value,
+                    block_height = EXCLUDED.block_height,
+                    tx_id = EXCLUDED.tx_id,
+                    tx_index = EXCLUDED.tx_index,
+                    record_index = EXCLUDED.record_index,
+                    input_index = EXCLUDED.input_index,
+                    output_index = EXCLUDED.output_index,
+                    owner = EXCLUDED.owner,
+                    asset_id = EXCLUDED.asset_id,
+                    status = EXCLUDED.status,
+                    spent_in_tx = EXCLUDED.spent_in_tx,
+                    spent_at_height = EXCLUDED.spent_at_height,
+                    created_at = EXCLUDED.created_at,
+                    published_at = $16
+                WHERE coins.status = 'unspent'
+                RETURNING *
+            )
+            SELECT * FROM upsert
+            UNION ALL
+            SELECT * FROM coins
+            WHERE utxo_id = $9 AND NOT EXISTS (SELECT 1 FROM upsert)",
+        )
+        .bind(db_item.subject)
+        .bind(db_item.value)
+        .bind(db_item.block_height)
+        .bind(db_item.tx_id)
+        .bind(db_item.tx_index)
+        .bind(db_item.record_index)
+        .bind(db_item.input_index)
+        .bind(db_item.output_index)
+        .bind(db_item.utxo_id)
+        .bind(db_item.owner)
+        .bind(db_item.asset_id)
+        .bind(db_item.status)
+        .bind(db_item.spent_in_tx)
+        .bind(db_item.spent_at_height)
+        .bind(db_item.created_at)
+        .bind(published_at)
+        .fetch_one(executor)
+        .await
+        .map_err(DbError::Insert)?;
+
+        Ok(record)
+    }
+}
@@ -0,0 +1,43 @@
+use pedronauck_streams_subject::subject::*;
+use pedronauck_streams_types::*;
+use serde::{Deserialize, Serialize};
+
+use super::types::*;
+
+#[derive(Subject, Debug, Clone, Default, Serialize, Deserialize)]
+#[subject(id = "coins")]
+#[subject(entity = "Coin")]
+#[subject(query_all = "coins.>")]
+#[subject(
+    format = "coins.{owner}.{asset_id}.{utxo_id}.{status}.{block_height}.{tx_id}.{tx_index}.{record_index}"
+)]
+pub struct CoinsSubject {
+    #[subject(
+        description = "The address owning the coin (32 byte string prefixed by 0x)"
+    )]
+    pub owner: Option<Address>,
+    #[subject(
+        description = "The asset ID of the coin (32 byte string prefixed by 0x)"
+    )]
+    pub asset_id: Option<AssetId>,
+    #[subject(
+        description = "The UTXO ID of the coin, made of the ID of the transaction creating it and the index of its output"
+    )]
+    pub utxo_id: Option<HexData>,
+    #[subject(description = "The state of the coin (unspent or spent)")]
+    pub status: Option<CoinStatus>,
+    #[subject(
+        description = "The height of the block where the coin was created or spent"
+    )]
+    pub block_height: Option<BlockHeight>,
+    #[subject(
+        description = "The ID of the transaction creating or spending the coin (32 byte string prefixed by 0x)"
+    )]
+    pub tx_id: Option<TxId>,
+    #[subject(description = "The index of the transaction within the block")]
+    pub tx_index: Option<u32>,
+    #[subject(
+        description = "The index of the output creating the coin, or of the input spending it"
+    )]
+    pub record_index: Option<u32>,
+}
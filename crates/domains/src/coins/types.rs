@@ -0,0 +1,168 @@
+use pedronauck_streams_types::primitives::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{inputs::types::*, outputs::types::*};
+
+/// A coin of the UTXO set, sent each time its state changes: once when an
+/// output creates it and once more when an input spends it
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Coin {
+    pub utxo_id: UtxoId,
+    pub owner: Address,
+    pub amount: Amount,
+    pub asset_id: AssetId,
+    pub status: CoinStatus,
+    pub spent_in_tx: Option<TxId>,
+    pub spent_at_height: Option<BlockHeight>,
+}
+
+impl Coin {
+    /// Coin created by a coin, change or variable output. Outputs holding
+    /// no amount, such as unused variable outputs, don't create any coin.
+    pub fn from_output(
+        output: &Output,
+        tx_id: &TxId,
+        output_index: u16,
+    ) -> Option<Self> {
+        let (owner, amount, asset_id) = match output {
+            Output::Coin(OutputCoin {
+                to,
+                amount,
+                asset_id,
+            })
+            | Output::Change(OutputChange {
+                to,
+                amount,
+                asset_id,
+            })
+            | Output::Variable(OutputVariable {
+                to,
+                amount,
+                asset_id,
+            }) => (to, amount, asset_id),
+            _ => return None,
+        };
+        if **amount == 0 {
+            return None;
+        }
+        Some(Coin {
+            utxo_id: UtxoId {
+                tx_id: tx_id.into(),
+                output_index,
+            },
+            owner: owner.to_owned(),
+            amount: *amount,
+            asset_id: asset_id.to_owned(),
+            status: CoinStatus::Unspent,
+            spent_in_tx: None,
+            spent_at_height: None,
+        })
+    }
+
+    /// Coin spent by a coin input of the transaction `tx_id`
+    pub fn from_input(
+        input: &Input,
+        tx_id: &TxId,
+        block_height: BlockHeight,
+    ) -> Option<Self> {
+        match input {
+            Input::Coin(input) => Some(Coin {
+                utxo_id: input.utxo_id.to_owned(),
+                owner: input.owner.to_owned(),
+                amount: input.amount,
+                asset_id: input.asset_id.to_owned(),
+                status: CoinStatus::Spent,
+                spent_in_tx: Some(tx_id.to_owned()),
+                spent_at_height: Some(block_height),
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum CoinStatus {
+    #[default]
+    Unspent,
+    Spent,
+}
+
+impl CoinStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CoinStatus::Unspent => "unspent",
+            CoinStatus::Spent => "spent",
+        }
+    }
+}
+
+impl std::fmt::Display for CoinStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for CoinStatus {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            s if s == Self::Unspent.as_str() => Ok(Self::Unspent),
+            s if s == Self::Spent.as_str() => Ok(Self::Spent),
+            _ => Err(format!("Invalid coin status: {s}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_coin_from_output() {
+        let tx_id = TxId::random();
+        let to = Address::from([1u8; 32]);
+        let output = Output::Change(OutputChange {
+            to: to.to_owned(),
+            amount: 50.into(),
+            asset_id: AssetId::default(),
+        });
+        let coin = Coin::from_output(&output, &tx_id, 2).unwrap();
+        assert_eq!(coin.utxo_id.tx_id, Bytes32::from(&tx_id));
+        assert_eq!(coin.utxo_id.output_index, 2);
+        assert_eq!(coin.owner, to);
+        assert_eq!(coin.status, CoinStatus::Unspent);
+        assert_eq!(coin.spent_in_tx, None);
+
+        let output = Output::Variable(OutputVariable::default());
+        assert!(Coin::from_output(&output, &tx_id, 0).is_none());
+    }
+
+    #[test]
+    fn test_coin_from_input() {
+        let tx_id = TxId::random();
+        let coin =
+            Coin::from_input(&MockInput::coin_signed(), &tx_id, 10.into())
+                .unwrap();
+        assert_eq!(coin.status, CoinStatus::Spent);
+        assert_eq!(coin.spent_in_tx, Some(tx_id));
+        assert_eq!(coin.spent_at_height, Some(10.into()));
+        let input = MockInput::contract();
+        assert!(Coin::from_input(&input, &tx_id, 10.into()).is_none());
+    }
+}
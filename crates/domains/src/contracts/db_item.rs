@@ -0,0 +1,275 @@
+use std::cmp::Ordering;
+
+use pedronauck_streams_store::{
+    db::{DbError, DbItem},
+    record::{
+        DataEncoder,
+        RecordEntity,
+        RecordPacket,
+        RecordPacketError,
+        RecordPointer,
+    },
+};
+use pedronauck_streams_types::{BlockHeight, BlockTimestamp};
+use serde::{Deserialize, Serialize};
+
+use super::{subjects::*, types::*};
+use crate::Subjects;
+
+/// Index columns a record doesn't have are stored as `-1`, so they sort
+/// before any real index, as unset pointer indexes do
+const NO_INDEX: i32 = -1;
+
+#[derive(
+    Debug, Clone, Serialize, Deserialize, PartialEq, Eq, sqlx::FromRow,
+)]
+pub struct ContractTransactionDbItem {
+    pub subject: String,
+    pub value: Vec<u8>,
+    pub block_height: i64,
+    pub tx_id: String,
+    pub tx_index: i32,
+    pub contract_id: String,
+    pub activity_type: String,
+    pub record_index: i32,
+    pub input_index: i32,
+    pub output_index: i32,
+    pub receipt_index: i32,
+    /// Position among the rows sharing the same record index, see
+    /// [`ContractActivity::fanout_index`]
+    pub fanout_index: i32,
+    pub created_at: BlockTimestamp,
+    pub published_at: BlockTimestamp,
+}
+
+impl DataEncoder for ContractTransactionDbItem {
+    type Err = DbError;
+}
+
+impl DbItem for ContractTransactionDbItem {
+    fn entity(&self) -> &RecordEntity {
+        &RecordEntity::ContractTransaction
+    }
+
+    fn encoded_value(&self) -> &[u8] {
+        &self.value
+    }
+
+    fn subject_str(&self) -> String {
+        self.subject.clone()
+    }
+
+    fn subject_id(&self) -> String {
+        ContractsSubject::ID.to_string()
+    }
+
+    fn created_at(&self) -> BlockTimestamp {
+        self.created_at
+    }
+
+    fn published_at(&self) -> BlockTimestamp {
+        self.published_at
+    }
+
+    fn block_height(&self) -> BlockHeight {
+        self.block_height.into()
+    }
+}
+
+impl TryFrom<&RecordPacket> for ContractTransactionDbItem {
+    type Error = RecordPacketError;
+    fn try_from(packet: &RecordPacket) -> Result<Self, Self::Error> {
+        let subject: Subjects = packet
+            .subject_payload
+            .to_owned()
+            .try_into()
+            .map_err(|_| RecordPacketError::SubjectMismatch)?;
+
+        match subject {
+            Subjects::Contracts(subject) => {
+                let activity: ContractActivity =
+                    ContractActivity::data_parser()
+                        .decode_json(&packet.value)?;
+                let activity_type = subject.activity_type.unwrap();
+                let record_index = subject.record_index.unwrap() as i32;
+                let index_for = |activity: ContractActivityType| {
+                    if activity == activity_type {
+                        record_index
+                    } else {
+                        NO_INDEX
+                    }
+                };
+                Ok(ContractTransactionDbItem {
+                    subject: packet.subject_str(),
+                    value: packet.value.to_owned(),
+                    block_height: subject.block_height.unwrap().into(),
+                    tx_id: subject.tx_id.unwrap().to_string(),
+                    tx_index: subject.tx_index.unwrap() as i32,
+                    contract_id: subject.contract_id.unwrap().to_string(),
+                    input_index: index_for(ContractActivityType::Input),
+                    output_index: index_for(ContractActivityType::Output),
+                    receipt_index: index_for(ContractActivityType::Receipt),
+                    fanout_index: activity.fanout_index() as i32,
+                    activity_type: activity_type.to_string(),
+                    record_index,
+                    created_at: packet.block_timestamp,
+                    published_at: packet.block_timestamp,
+                })
+            }
+            _ => Err(RecordPacketError::SubjectMismatch),
+        }
+    }
+}
+
+impl PartialOrd for ContractTransactionDbItem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ContractTransactionDbItem {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Order by block height first
+        self.block_height
+            .cmp(&other.block_height)
+            // Then by transaction index within the block
+            .then(self.tx_index.cmp(&other.tx_index))
+            // Then by the position of the record within the transaction
+            .then(self.input_index.cmp(&other.input_index))
+            .then(self.output_index.cmp(&other.output_index))
+            .then(self.receipt_index.cmp(&other.receipt_index))
+            // Then by the position among the rows of the same record
+            .then(self.fanout_index.cmp(&other.fanout_index))
+    }
+}
+
+impl From<ContractTransactionDbItem> for RecordPointer {
+    fn from(val: ContractTransactionDbItem) -> Self {
+        let index = |value: i32| u32::try_from(value).ok();
+        RecordPointer {
+            block_height: val.block_height.into(),
+            tx_index: Some(val.tx_index as u32),
+            input_index: index(val.input_index),
+            output_index: index(val.output_index),
+            receipt_index: index(val.receipt_index),
+            fanout_index: Some(val.fanout_index as u32),
+        }
+    }
+}
+
+/// Entry of the contracts registry, one per deployed contract
+#[derive(
+    Debug, Clone, Serialize, Deserialize, PartialEq, Eq, sqlx::FromRow,
+)]
+pub struct ContractDbItem {
+    pub subject: String,
+    pub value: Vec<u8>,
+    pub block_height: i64,
+    pub tx_id: String,
+    pub tx_index: i32,
+    pub output_index: i32,
+    pub contract_id: String,
+    pub deployer: String,
+    pub bytecode_root: String,
+    pub bytecode_size: i32,
+    pub created_at: BlockTimestamp,
+    pub published_at: BlockTimestamp,
+}
+
+impl DataEncoder for ContractDbItem {
+    type Err = DbError;
+}
+
+impl DbItem for ContractDbItem {
+    fn entity(&self) -> &RecordEntity {
+        &RecordEntity::Contract
+    }
+
+    fn encoded_value(&self) -> &[u8] {
+        &self.value
+    }
+
+    fn subject_str(&self) -> String {
+        self.subject.clone()
+    }
+
+    fn subject_id(&self) -> String {
+        ContractsCreatedSubject::ID.to_string()
+    }
+
+    fn created_at(&self) -> BlockTimestamp {
+        self.created_at
+    }
+
+    fn published_at(&self) -> BlockTimestamp {
+        self.published_at
+    }
+
+    fn block_height(&self) -> BlockHeight {
+        self.block_height.into()
+    }
+}
+
+impl TryFrom<&RecordPacket> for ContractDbItem {
+    type Error = RecordPacketError;
+    fn try_from(packet: &RecordPacket) -> Result<Self, Self::Error> {
+        let subject: Subjects = packet
+            .subject_payload
+            .to_owned()
+            .try_into()
+            .map_err(|_| RecordPacketError::SubjectMismatch)?;
+
+        match subject {
+            Subjects::ContractsCreated(subject) => {
+                let contract: Contract =
+                    Contract::data_parser().decode_json(&packet.value)?;
+                Ok(ContractDbItem {
+                    subject: packet.subject_str(),
+                    value: packet.value.to_owned(),
+                    block_height: subject.block_height.unwrap().into(),
+                    tx_id: subject.tx_id.unwrap().to_string(),
+                    tx_index: subject.tx_index.unwrap() as i32,
+                    output_index: subject.output_index.unwrap() as i32,
+                    contract_id: subject.contract_id.unwrap().to_string(),
+                    deployer: subject.deployer.unwrap().to_string(),
+                    bytecode_root: contract.bytecode_root.to_string(),
+                    bytecode_size: contract.bytecode_size as i32,
+                    created_at: packet.block_timestamp,
+                    published_at: packet.block_timestamp,
+                })
+            }
+            _ => Err(RecordPacketError::SubjectMismatch),
+        }
+    }
+}
+
+impl PartialOrd for ContractDbItem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ContractDbItem {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Order by block height first
+        self.block_height
+            .cmp(&other.block_height)
+            // Then by transaction index within the block
+            .then(self.tx_index.cmp(&other.tx_index))
+            // Then by the output creating the contract
+            .then(self.output_index.cmp(&other.output_index))
+    }
+}
+
+impl From<ContractDbItem> for RecordPointer {
+    fn from(val: ContractDbItem) -> Self {
+        RecordPointer {
+            block_height: val.block_height.into(),
+            tx_index: Some(val.tx_index as u32),
+            input_index: None,
+            output_index: Some(val.output_index as u32),
+            receipt_index: None,
+            fanout_index: None,
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+use pedronauck_streams_store::record::{PacketBuilder, Record, RecordPacket};
+use rayon::prelude::*;
+
+use super::{subjects::*, types::*};
+use crate::MsgPayload;
+
+#[async_trait]
+impl PacketBuilder for ContractActivity {
+    type Opts = MsgPayload;
+
+    fn build_packets(msg_payload: &Self::Opts) -> Vec<RecordPacket> {
+        let block_height = msg_payload.block_height();
+        let timestamp = msg_payload.timestamp();
+        msg_payload
+            .transactions
+            .par_iter()
+            .enumerate()
+            .flat_map_iter(|(tx_index, tx)| {
+                let tx_index = tx_index as u32;
+                let inputs =
+                    tx.inputs.iter().enumerate().map(|(index, input)| {
+                        (index, ContractRecord::Input(input.to_owned()))
+                    });
+                let outputs =
+                    tx.outputs.iter().enumerate().map(|(index, output)| {
+                        (index, ContractRecord::Output(output.to_owned()))
+                    });
+                let receipts =
+                    tx.receipts.iter().enumerate().map(|(index, receipt)| {
+                        (index, ContractRecord::Receipt(receipt.to_owned()))
+                    });
+                inputs
+                    .chain(outputs)
+                    .chain(receipts)
+                    .flat_map(move |(record_index, record)| {
+                        let contract_ids = record.contract_ids(&tx.inputs);
+                        contract_ids.into_iter().map(move |contract_id| {
+                            let activity = ContractActivity {
+                                contract_id: contract_id.to_owned(),
+                                tx_id: tx.id.to_owned(),
+                                record: record.to_owned(),
+                            };
+                            let subject = ContractsSubject {
+                                contract_id: Some(contract_id),
+                                block_height: Some(block_height),
+                                tx_id: Some(tx.id.to_owned()),
+                                tx_index: Some(tx_index),
+                                activity_type: Some(record.activity_type()),
+                                record_index: Some(record_index as u32),
+                            }
+                            .dyn_arc();
+                            let packet =
+                                activity.to_packet(&subject, timestamp);
+                            match msg_payload.namespace.clone() {
+                                Some(ns) => packet.with_namespace(&ns),
+                                _ => packet,
+                            }
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl PacketBuilder for Contract {
+    type Opts = MsgPayload;
+
+    fn build_packets(msg_payload: &Self::Opts) -> Vec<RecordPacket> {
+        let block_height = msg_payload.block_height();
+        let timestamp = msg_payload.timestamp();
+        msg_payload
+            .transactions
+            .par_iter()
+            .enumerate()
+            .filter_map(|(tx_index, tx)| {
+                let (output_index, contract) =
+                    Contract::from_transaction(tx, block_height)?;
+                let subject = ContractsCreatedSubject {
+                    contract_id: Some(contract.contract_id.to_owned()),
+                    deployer: Some(contract.deployer.to_owned()),
+                    block_height: Some(block_height),
+                    tx_id: Some(tx.id.to_owned()),
+                    tx_index: Some(tx_index as u32),
+                    output_index: Some(output_index as u32),
+                }
+                .dyn_arc();
+                let packet = contract.to_packet(&subject, timestamp);
+                Some(match msg_payload.namespace.clone() {
+                    Some(ns) => packet.with_namespace(&ns),
+                    _ => packet,
+                })
+            })
+            .collect()
+    }
+}
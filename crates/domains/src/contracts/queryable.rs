@@ -0,0 +1,187 @@
+use pedronauck_streams_types::*;
+use sea_query::{Condition, Expr, Iden};
+use serde::{Deserialize, Serialize};
+
+use super::ContractDbItem;
+use crate::queryable::{HasPagination, QueryPagination, Queryable};
+
+#[allow(dead_code)]
+#[derive(Iden)]
+pub enum ContractTransactions {
+    #[iden = "contract_transactions"]
+    Table,
+    #[iden = "subject"]
+    Subject,
+    #[iden = "value"]
+    Value,
+    #[iden = "block_height"]
+    BlockHeight,
+    #[iden = "tx_id"]
+    TxId,
+    #[iden = "tx_index"]
+    TxIndex,
+    #[iden = "contract_id"]
+    ContractId,
+    #[iden = "activity_type"]
+    ActivityType,
+    #[iden = "record_index"]
+    RecordIndex,
+    #[iden = "created_at"]
+    CreatedAt,
+    #[iden = "published_at"]
+    PublishedAt,
+}
+
+#[allow(dead_code)]
+#[derive(Iden)]
+pub enum Contracts {
+    #[iden = "contracts"]
+    Table,
+    #[iden = "subject"]
+    Subject,
+    #[iden = "value"]
+    Value,
+    #[iden = "block_height"]
+    BlockHeight,
+    #[iden = "tx_id"]
+    TxId,
+    #[iden = "tx_index"]
+    TxIndex,
+    #[iden = "output_index"]
+    OutputIndex,
+    #[iden = "contract_id"]
+    ContractId,
+    #[iden = "deployer"]
+    Deployer,
+    #[iden = "bytecode_root"]
+    BytecodeRoot,
+    #[iden = "bytecode_size"]
+    BytecodeSize,
+    #[iden = "created_at"]
+    CreatedAt,
+    #[iden = "published_at"]
+    PublishedAt,
+}
+
+#[derive(
+    Debug, Clone, Default, Serialize, Deserialize, PartialEq, utoipa::ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractsQuery {
+    pub contract_id: Option<ContractId>,
+    pub deployer: Option<Address>,
+    pub bytecode_root: Option<Bytes32>,
+    pub tx_id: Option<TxId>,
+    pub block_height: Option<BlockHeight>,
+    #[serde(flatten)]
+    pub pagination: QueryPagination,
+}
+
+impl ContractsQuery {
+    pub fn set_contract_id(&mut self, contract_id: &str) {
+        self.contract_id = Some(ContractId::from(contract_id));
+    }
+}
+
+#[async_trait::async_trait]
+impl Queryable for ContractsQuery {
+    type Record = ContractDbItem;
+    type Table = Contracts;
+    type PaginationColumn = Contracts;
+
+    fn table() -> Self::Table {
+        Contracts::Table
+    }
+
+    fn pagination_column() -> Self::PaginationColumn {
+        Contracts::BlockHeight
+    }
+
+    fn pagination(&self) -> &QueryPagination {
+        &self.pagination
+    }
+
+    fn build_condition(&self) -> Condition {
+        let mut condition = Condition::all();
+
+        if let Some(deployer) = &self.deployer {
+            condition = condition
+                .add(Expr::col(Contracts::Deployer).eq(deployer.to_string()));
+        }
+
+        if let Some(bytecode_root) = &self.bytecode_root {
+            condition = condition.add(
+                Expr::col(Contracts::BytecodeRoot)
+                    .eq(bytecode_root.to_string()),
+            );
+        }
+
+        if let Some(tx_id) = &self.tx_id {
+            condition =
+                condition.add(Expr::col(Contracts::TxId).eq(tx_id.to_string()));
+        }
+
+        if let Some(block_height) = &self.block_height {
+            condition = condition
+                .add(Expr::col(Contracts::BlockHeight).eq(**block_height));
+        }
+
+        // unique conditions
+        if let Some(contract_id) = &self.contract_id {
+            condition = condition.add(
+                Expr::col(Contracts::ContractId).eq(contract_id.to_string()),
+            );
+        }
+
+        condition
+    }
+}
+
+impl HasPagination for ContractsQuery {
+    fn pagination(&self) -> &QueryPagination {
+        &self.pagination
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pedronauck_streams_types::Address;
+    use pretty_assertions::assert_eq;
+
+    use crate::{contracts::queryable::ContractsQuery, queryable::Queryable};
+
+    const FIRST_POINTER: i32 = 100;
+    const TEST_CONTRACT_ID: &str =
+        "0x0101010101010101010101010101010101010101010101010101010101010101";
+    const TEST_DEPLOYER: &str =
+        "0x0202020202020202020202020202020202020202020202020202020202020202";
+
+    #[test]
+    fn test_sql_with_contract_id() {
+        let mut query = ContractsQuery::default();
+        query.set_contract_id(TEST_CONTRACT_ID);
+
+        assert_eq!(
+            query.query_to_string(),
+            format!(
+                "SELECT * FROM \"contracts\" WHERE \"contract_id\" = '{}'",
+                TEST_CONTRACT_ID
+            )
+        );
+    }
+
+    #[test]
+    fn test_sql_with_contracts_of_deployer() {
+        let query = ContractsQuery {
+            deployer: Some(Address::from(TEST_DEPLOYER)),
+            pagination: (None, None, Some(FIRST_POINTER), None).into(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            query.query_to_string(),
+            format!("SELECT * FROM \"contracts\" WHERE \"deployer\" = '{}' ORDER BY \"block_height\" ASC LIMIT {}",
+                TEST_DEPLOYER, FIRST_POINTER)
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use pedronauck_streams_subject::subject::*;
+use pedronauck_streams_types::*;
+use serde::{Deserialize, Serialize};
+
+use super::types::*;
+
+#[derive(Subject, Debug, Clone, Default, Serialize, Deserialize)]
+#[subject(id = "contracts")]
+#[subject(entity = "ContractActivity")]
+#[subject(query_all = "contracts.>")]
+#[subject(
+    format = "contracts.{contract_id}.{block_height}.{tx_id}.{tx_index}.{activity_type}.{record_index}"
+)]
+pub struct ContractsSubject {
+    #[subject(
+        description = "The ID of the contract involved in the record (32 byte string prefixed by 0x)"
+    )]
+    pub contract_id: Option<ContractId>,
+    #[subject(description = "The height of the block containing this record")]
+    pub block_height: Option<BlockHeight>,
+    #[subject(
+        description = "The ID of the transaction containing this record (32 byte string prefixed by 0x)"
+    )]
+    pub tx_id: Option<TxId>,
+    #[subject(description = "The index of the transaction within the block")]
+    pub tx_index: Option<u32>,
+    #[subject(
+        description = "The kind of record involving the contract (input, output, or receipt)"
+    )]
+    pub activity_type: Option<ContractActivityType>,
+    #[subject(
+        description = "The index of the record within its transaction's inputs, outputs or receipts"
+    )]
+    pub record_index: Option<u32>,
+}
+
+/// Contracts deployed by `Create` transactions. Kept apart from the
+/// `contracts.>` subjects, which carry the activity of contracts.
+#[derive(Subject, Debug, Clone, Default, Serialize, Deserialize)]
+#[subject(id = "contracts_created")]
+#[subject(entity = "Contract")]
+#[subject(query_all = "contracts_created.>")]
+#[subject(
+    format = "contracts_created.{contract_id}.{deployer}.{block_height}.{tx_id}.{tx_index}.{output_index}"
+)]
+pub struct ContractsCreatedSubject {
+    #[subject(
+        description = "The ID of the deployed contract (32 byte string prefixed by 0x)"
+    )]
+    pub contract_id: Option<ContractId>,
+    #[subject(
+        description = "The address deploying the contract (32 byte string prefixed by 0x)"
+    )]
+    pub deployer: Option<Address>,
+    #[subject(
+        description = "The height of the block where the contract was deployed"
+    )]
+    pub block_height: Option<BlockHeight>,
+    #[subject(
+        description = "The ID of the transaction deploying the contract (32 byte string prefixed by 0x)"
+    )]
+    pub tx_id: Option<TxId>,
+    #[subject(description = "The index of the transaction within the block")]
+    pub tx_index: Option<u32>,
+    #[subject(description = "The index of the output creating the contract")]
+    pub output_index: Option<u32>,
+}
@@ -0,0 +1,301 @@
+use pedronauck_streams_types::primitives::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    inputs::types::*,
+    outputs::types::*,
+    receipts::types::*,
+    transactions::types::*,
+};
+
+/// Record involving a contract, as sent in its own stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ContractRecord {
+    Input(Input),
+    Output(Output),
+    Receipt(Receipt),
+}
+
+impl ContractRecord {
+    pub fn activity_type(&self) -> ContractActivityType {
+        match self {
+            ContractRecord::Input(_) => ContractActivityType::Input,
+            ContractRecord::Output(_) => ContractActivityType::Output,
+            ContractRecord::Receipt(_) => ContractActivityType::Receipt,
+        }
+    }
+
+    /// IDs of the contracts the record involves. Contract outputs only
+    /// point to their input, so the inputs of the transaction are needed
+    /// to resolve them. The zero ID used for calls made by scripts is
+    /// left out.
+    pub fn contract_ids(&self, tx_inputs: &[Input]) -> Vec<ContractId> {
+        let mut contract_ids = match self {
+            ContractRecord::Input(Input::Contract(input)) => {
+                vec![ContractId::from(&input.contract_id)]
+            }
+            ContractRecord::Output(Output::Contract(output)) => tx_inputs
+                .get(output.input_index as usize)
+                .and_then(|input| match input {
+                    Input::Contract(input) => {
+                        Some(ContractId::from(&input.contract_id))
+                    }
+                    _ => None,
+                })
+                .into_iter()
+                .collect(),
+            ContractRecord::Output(Output::ContractCreated(output)) => {
+                vec![output.contract_id.to_owned()]
+            }
+            ContractRecord::Receipt(Receipt::Call(receipt)) => {
+                vec![receipt.id.to_owned(), receipt.to.to_owned()]
+            }
+            ContractRecord::Receipt(Receipt::Log(receipt)) => {
+                vec![receipt.id.to_owned()]
+            }
+            ContractRecord::Receipt(Receipt::LogData(receipt)) => {
+                vec![receipt.id.to_owned()]
+            }
+            ContractRecord::Receipt(Receipt::Transfer(receipt)) => {
+                vec![receipt.id.to_owned(), receipt.to.to_owned()]
+            }
+            ContractRecord::Receipt(Receipt::TransferOut(receipt)) => {
+                vec![receipt.id.to_owned()]
+            }
+            _ => vec![],
+        };
+        contract_ids
+            .retain(|contract_id| *contract_id != ContractId::default());
+        contract_ids.dedup();
+        contract_ids
+    }
+}
+
+/// A record involving a contract, sent once for each contract it involves
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractActivity {
+    pub contract_id: ContractId,
+    pub tx_id: TxId,
+    pub record: ContractRecord,
+}
+
+impl ContractActivity {
+    /// Position of the contract among the ones its record involves, such as
+    /// the caller and the callee of a call, so each row has a pointer of
+    /// its own. Contract outputs only involve one contract, so they don't
+    /// need the inputs of the transaction here.
+    pub fn fanout_index(&self) -> u32 {
+        self.record
+            .contract_ids(&[])
+            .iter()
+            .position(|contract_id| *contract_id == self.contract_id)
+            .unwrap_or_default() as u32
+    }
+}
+
+/// A contract deployed by a `Create` transaction, as kept in the contracts
+/// registry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Contract {
+    pub contract_id: ContractId,
+    /// Owner of the first coin, or recipient of the first message, the
+    /// transaction spends. Zeroed when it spends neither.
+    pub deployer: Address,
+    pub tx_id: TxId,
+    pub block_height: BlockHeight,
+    pub salt: Salt,
+    /// Merkle root of the bytecode, which the contract ID is derived from
+    pub bytecode_root: Bytes32,
+    /// Size of the bytecode in bytes
+    pub bytecode_size: u32,
+    pub state_root: Bytes32,
+    /// Storage slots the contract was deployed with
+    pub storage_slots: Vec<StorageSlot>,
+}
+
+impl Contract {
+    /// Contract deployed by a `Create` transaction, along with the index of
+    /// its `ContractCreated` output. Transactions that aren't successful
+    /// `Create` ones, or whose bytecode witness is missing, deploy nothing.
+    pub fn from_transaction(
+        tx: &Transaction,
+        block_height: BlockHeight,
+    ) -> Option<(usize, Self)> {
+        if tx.tx_type != TransactionType::Create
+            || tx.status != TransactionStatus::Success
+        {
+            return None;
+        }
+        let (output_index, output) =
+            tx.outputs.iter().enumerate().find_map(|(index, output)| {
+                match output {
+                    Output::ContractCreated(output) => Some((index, output)),
+                    _ => None,
+                }
+            })?;
+        let bytecode = tx
+            .bytecode_witness_index
+            .and_then(|index| tx.witnesses.get(index as usize))?;
+        let bytecode_root =
+            fuel_core_types::fuel_tx::Contract::root_from_code(&bytecode.0);
+        let deployer = tx
+            .inputs
+            .iter()
+            .find_map(|input| match input {
+                Input::Coin(input) => Some(input.owner.to_owned()),
+                Input::Message(input) => Some(input.recipient.to_owned()),
+                _ => None,
+            })
+            .unwrap_or_default();
+        let contract = Contract {
+            contract_id: output.contract_id.to_owned(),
+            deployer,
+            tx_id: tx.id.to_owned(),
+            block_height,
+            salt: tx.salt.to_owned().unwrap_or_default(),
+            bytecode_root: bytecode_root.into(),
+            bytecode_size: bytecode.0.as_ref().len() as u32,
+            state_root: output.state_root.to_owned(),
+            storage_slots: tx.storage_slots.to_owned(),
+        };
+        Some((output_index, contract))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContractActivityType {
+    Input,
+    Output,
+    Receipt,
+}
+
+impl ContractActivityType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ContractActivityType::Input => "input",
+            ContractActivityType::Output => "output",
+            ContractActivityType::Receipt => "receipt",
+        }
+    }
+}
+
+impl std::fmt::Display for ContractActivityType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for ContractActivityType {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            s if s == Self::Input.as_str() => Ok(Self::Input),
+            s if s == Self::Output.as_str() => Ok(Self::Output),
+            s if s == Self::Receipt.as_str() => Ok(Self::Receipt),
+            _ => Err(format!("Invalid contract activity type: {s}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_contract_output_resolves_its_input() {
+        let contract_id = Bytes32::from([1u8; 32]);
+        let Input::Contract(input) = MockInput::contract() else {
+            panic!("expected a contract input");
+        };
+        let inputs = vec![
+            MockInput::coin_signed(),
+            Input::Contract(InputContract {
+                contract_id: contract_id.to_owned(),
+                ..input
+            }),
+        ];
+        let output = Output::Contract(OutputContract {
+            balance_root: Bytes32::default(),
+            input_index: 1,
+            state_root: Bytes32::default(),
+        });
+        let record = ContractRecord::Output(output);
+        assert_eq!(record.contract_ids(&inputs), vec![ContractId::from(
+            &contract_id
+        )]);
+        assert_eq!(record.activity_type(), ContractActivityType::Output);
+    }
+
+    #[test]
+    fn test_script_calls_leave_out_the_zero_id() {
+        let to = ContractId::from([2u8; 32]);
+        let record = ContractRecord::Receipt(Receipt::Call(CallReceipt {
+            id: ContractId::default(),
+            to: to.to_owned(),
+            ..MockReceipt::call().as_call()
+        }));
+        assert_eq!(record.contract_ids(&[]), vec![to]);
+    }
+
+    #[test]
+    fn test_call_fanout_index() {
+        let id = ContractId::from([1u8; 32]);
+        let to = ContractId::from([2u8; 32]);
+        let record = ContractRecord::Receipt(Receipt::Call(CallReceipt {
+            id: id.to_owned(),
+            to: to.to_owned(),
+            ..MockReceipt::call().as_call()
+        }));
+        let activity = |contract_id: &ContractId| ContractActivity {
+            contract_id: contract_id.to_owned(),
+            tx_id: TxId::default(),
+            record: record.to_owned(),
+        };
+        assert_eq!(activity(&id).fanout_index(), 0);
+        assert_eq!(activity(&to).fanout_index(), 1);
+    }
+
+    #[test]
+    fn test_contract_from_create_transaction() {
+        let contract_id = ContractId::from([3u8; 32]);
+        let bytecode = vec![0x24, 0x40, 0x00, 0x00];
+        let mut tx = MockTransaction::create(
+            vec![MockInput::coin_signed()],
+            vec![
+                MockOutput::coin(100),
+                Output::ContractCreated(OutputContractCreated {
+                    contract_id: contract_id.to_owned(),
+                    state_root: Bytes32::default(),
+                }),
+            ],
+            vec![],
+        );
+        tx.bytecode_witness_index = Some(0);
+        tx.witnesses = vec![HexData(bytecode.to_owned().into())];
+
+        let (output_index, contract) =
+            Contract::from_transaction(&tx, 10.into()).unwrap();
+        assert_eq!(output_index, 1);
+        assert_eq!(contract.contract_id, contract_id);
+        assert_eq!(contract.tx_id, tx.id);
+        assert_eq!(contract.block_height, 10.into());
+        assert_eq!(contract.bytecode_size, 4);
+        let bytecode_root =
+            fuel_core_types::fuel_tx::Contract::root_from_code(&bytecode);
+        assert_eq!(contract.bytecode_root, Bytes32::from(bytecode_root));
+        let Input::Coin(input) = MockInput::coin_signed() else {
+            panic!("expected a coin input");
+        };
+        assert_eq!(contract.deployer, input.owner);
+        assert_eq!(contract.storage_slots, tx.storage_slots);
+
+        let script = MockTransaction::script(vec![], tx.outputs, vec![]);
+        assert!(Contract::from_transaction(&script, 10.into()).is_none());
+    }
+}
@@ -165,6 +165,7 @@ impl From<InputDbItem> for RecordPointer {
             input_index: Some(val.input_index as u32),
             output_index: None,
             receipt_index: None,
+            fanout_index: None,
         }
     }
 }
@@ -1,5 +1,12 @@
+pub mod abis;
+pub mod accounts;
+pub mod balances;
 pub mod blocks;
+pub mod bundles;
+pub mod coins;
+pub mod contracts;
 pub mod inputs;
+pub mod messages;
 mod msg_payload;
 pub mod outputs;
 pub mod queryable;
@@ -0,0 +1,161 @@
+use std::cmp::Ordering;
+
+use pedronauck_streams_store::{
+    db::{DbError, DbItem},
+    record::{
+        DataEncoder,
+        RecordEntity,
+        RecordPacket,
+        RecordPacketError,
+        RecordPointer,
+    },
+};
+use pedronauck_streams_types::{BlockHeight, BlockTimestamp};
+use serde::{Deserialize, Serialize};
+
+use super::{subjects::*, types::*};
+use crate::Subjects;
+
+/// Stored in the index column the state change didn't come from, e.g. the
+/// receipt index of a message used by an input
+const NO_INDEX: i32 = -1;
+
+/// Current state of a message. The position columns point to where the
+/// message was first seen, so its ordering key never moves, while the
+/// subject, value, transaction ID and status follow the last state change:
+/// the transaction sending it to L1 or, for messages from L1, the last one
+/// using it.
+#[derive(
+    Debug, Clone, Serialize, Deserialize, PartialEq, Eq, sqlx::FromRow,
+)]
+pub struct MessageDbItem {
+    pub subject: String,
+    pub value: Vec<u8>,
+    pub block_height: i64,
+    pub tx_id: String,
+    pub tx_index: i32,
+    pub record_index: i32,
+    pub input_index: i32,
+    pub receipt_index: i32,
+    pub message_id: String,
+    pub nonce: String,
+    pub direction: String,
+    pub status: String,
+    pub sender: String,
+    pub recipient: String,
+    pub da_height: i64,
+    pub created_at: BlockTimestamp,
+    pub published_at: BlockTimestamp,
+}
+
+impl DataEncoder for MessageDbItem {
+    type Err = DbError;
+}
+
+impl DbItem for MessageDbItem {
+    fn entity(&self) -> &RecordEntity {
+        &RecordEntity::Message
+    }
+
+    fn encoded_value(&self) -> &[u8] {
+        &self.value
+    }
+
+    fn subject_str(&self) -> String {
+        self.subject.clone()
+    }
+
+    fn subject_id(&self) -> String {
+        MessagesSubject::ID.to_string()
+    }
+
+    fn created_at(&self) -> BlockTimestamp {
+        self.created_at
+    }
+
+    fn published_at(&self) -> BlockTimestamp {
+        self.published_at
+    }
+
+    fn block_height(&self) -> BlockHeight {
+        self.block_height.into()
+    }
+}
+
+impl TryFrom<&RecordPacket> for MessageDbItem {
+    type Error = RecordPacketError;
+    fn try_from(packet: &RecordPacket) -> Result<Self, Self::Error> {
+        let subject: Subjects = packet
+            .subject_payload
+            .to_owned()
+            .try_into()
+            .map_err(|_| RecordPacketError::SubjectMismatch)?;
+
+        match subject {
+            Subjects::Messages(subject) => {
+                let direction = subject.direction.unwrap();
+                let from_l1 = direction == MessageDirection::L1ToL2;
+                let record_index = subject.record_index.unwrap() as i32;
+                let message: Message =
+                    Message::data_parser().decode_json(&packet.value)?;
+                Ok(MessageDbItem {
+                    subject: packet.subject_str(),
+                    value: packet.value.to_owned(),
+                    block_height: subject.block_height.unwrap().into(),
+                    tx_id: subject.tx_id.unwrap().to_string(),
+                    tx_index: subject.tx_index.unwrap() as i32,
+                    record_index,
+                    input_index: if from_l1 { record_index } else { NO_INDEX },
+                    receipt_index: if from_l1 {
+                        NO_INDEX
+                    } else {
+                        record_index
+                    },
+                    message_id: subject.message_id.unwrap().to_string(),
+                    nonce: subject.nonce.unwrap().to_string(),
+                    direction: direction.to_string(),
+                    status: subject.status.unwrap().to_string(),
+                    sender: subject.sender.unwrap().to_string(),
+                    recipient: subject.recipient.unwrap().to_string(),
+                    da_height: message.da_height.into(),
+                    created_at: packet.block_timestamp,
+                    published_at: packet.block_timestamp,
+                })
+            }
+            _ => Err(RecordPacketError::SubjectMismatch),
+        }
+    }
+}
+
+impl PartialOrd for MessageDbItem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MessageDbItem {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Order by block height first
+        self.block_height
+            .cmp(&other.block_height)
+            // Then by transaction index within the block
+            .then(self.tx_index.cmp(&other.tx_index))
+            // Then by the input using the message or the receipt sending it
+            .then(self.input_index.cmp(&other.input_index))
+            .then(self.receipt_index.cmp(&other.receipt_index))
+    }
+}
+
+impl From<MessageDbItem> for RecordPointer {
+    fn from(val: MessageDbItem) -> Self {
+        let index = |value: i32| u32::try_from(value).ok();
+        RecordPointer {
+            block_height: val.block_height.into(),
+            tx_index: Some(val.tx_index as u32),
+            input_index: index(val.input_index),
+            output_index: None,
+            receipt_index: index(val.receipt_index),
+            fanout_index: None,
+        }
+    }
+}
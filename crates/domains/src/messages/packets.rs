@@ -0,0 +1,59 @@
+use async_trait::async_trait;
+use pedronauck_streams_store::record::{PacketBuilder, Record, RecordPacket};
+use rayon::prelude::*;
+
+use super::{subjects::*, types::*};
+use crate::MsgPayload;
+
+#[async_trait]
+impl PacketBuilder for Message {
+    type Opts = MsgPayload;
+
+    fn build_packets(msg_payload: &Self::Opts) -> Vec<RecordPacket> {
+        let block_height = msg_payload.block_height();
+        let timestamp = msg_payload.timestamp();
+        let da_height = msg_payload.block.header.da_height;
+        msg_payload
+            .transactions
+            .par_iter()
+            .enumerate()
+            .flat_map_iter(|(tx_index, tx)| {
+                let tx_index = tx_index as u32;
+                let used = tx.inputs.iter().enumerate().filter_map(
+                    |(index, input)| {
+                        Message::from_input(input, tx, da_height)
+                            .map(|message| (index, message))
+                    },
+                );
+                let sent = tx.receipts.iter().enumerate().filter_map(
+                    |(index, receipt)| {
+                        Message::from_receipt(receipt, tx, da_height)
+                            .map(|message| (index, message))
+                    },
+                );
+                used.chain(sent)
+                    .map(|(record_index, message)| {
+                        let subject = MessagesSubject {
+                            direction: Some(message.direction),
+                            status: Some(message.status),
+                            sender: Some(message.sender.to_owned()),
+                            recipient: Some(message.recipient.to_owned()),
+                            nonce: Some(message.nonce.to_owned()),
+                            message_id: Some(message.message_id.to_owned()),
+                            block_height: Some(block_height),
+                            tx_id: Some(tx.id.to_owned()),
+                            tx_index: Some(tx_index),
+                            record_index: Some(record_index as u32),
+                        }
+                        .dyn_arc();
+                        let packet = message.to_packet(&subject, timestamp);
+                        match msg_payload.namespace.clone() {
+                            Some(ns) => packet.with_namespace(&ns),
+                            _ => packet,
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
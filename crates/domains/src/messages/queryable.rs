@@ -0,0 +1,195 @@
+use pedronauck_streams_types::*;
+use sea_query::{Condition, Expr, Iden};
+use serde::{Deserialize, Serialize};
+
+use super::{MessageDbItem, MessageDirection, MessageStatus};
+use crate::queryable::{HasPagination, QueryPagination, Queryable};
+
+#[allow(dead_code)]
+#[derive(Iden)]
+pub enum Messages {
+    #[iden = "messages"]
+    Table,
+    #[iden = "subject"]
+    Subject,
+    #[iden = "value"]
+    Value,
+    #[iden = "block_height"]
+    BlockHeight,
+    #[iden = "tx_id"]
+    TxId,
+    #[iden = "tx_index"]
+    TxIndex,
+    #[iden = "record_index"]
+    RecordIndex,
+    #[iden = "input_index"]
+    InputIndex,
+    #[iden = "receipt_index"]
+    ReceiptIndex,
+    #[iden = "message_id"]
+    MessageId,
+    #[iden = "nonce"]
+    Nonce,
+    #[iden = "direction"]
+    Direction,
+    #[iden = "status"]
+    Status,
+    #[iden = "sender"]
+    Sender,
+    #[iden = "recipient"]
+    Recipient,
+    #[iden = "da_height"]
+    DaHeight,
+    #[iden = "created_at"]
+    CreatedAt,
+    #[iden = "published_at"]
+    PublishedAt,
+}
+
+#[derive(
+    Debug, Clone, Default, Serialize, Deserialize, PartialEq, utoipa::ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct MessagesQuery {
+    pub message_id: Option<MessageId>,
+    pub nonce: Option<Nonce>,
+    pub direction: Option<MessageDirection>,
+    pub status: Option<MessageStatus>,
+    pub sender: Option<Address>,
+    pub recipient: Option<Address>,
+    pub tx_id: Option<TxId>,
+    pub block_height: Option<BlockHeight>,
+    #[serde(flatten)]
+    pub pagination: QueryPagination,
+}
+
+impl MessagesQuery {
+    pub fn set_message_id(&mut self, message_id: &str) {
+        self.message_id = Some(MessageId::from(message_id));
+    }
+}
+
+#[async_trait::async_trait]
+impl Queryable for MessagesQuery {
+    type Record = MessageDbItem;
+    type Table = Messages;
+    type PaginationColumn = Messages;
+
+    fn table() -> Self::Table {
+        Messages::Table
+    }
+
+    fn pagination_column() -> Self::PaginationColumn {
+        Messages::BlockHeight
+    }
+
+    fn pagination(&self) -> &QueryPagination {
+        &self.pagination
+    }
+
+    fn build_condition(&self) -> Condition {
+        let mut condition = Condition::all();
+
+        if let Some(direction) = &self.direction {
+            condition = condition
+                .add(Expr::col(Messages::Direction).eq(direction.to_string()));
+        }
+
+        if let Some(status) = &self.status {
+            condition = condition
+                .add(Expr::col(Messages::Status).eq(status.to_string()));
+        }
+
+        if let Some(sender) = &self.sender {
+            condition = condition
+                .add(Expr::col(Messages::Sender).eq(sender.to_string()));
+        }
+
+        if let Some(recipient) = &self.recipient {
+            condition = condition
+                .add(Expr::col(Messages::Recipient).eq(recipient.to_string()));
+        }
+
+        if let Some(tx_id) = &self.tx_id {
+            condition =
+                condition.add(Expr::col(Messages::TxId).eq(tx_id.to_string()));
+        }
+
+        if let Some(block_height) = &self.block_height {
+            condition = condition
+                .add(Expr::col(Messages::BlockHeight).eq(**block_height));
+        }
+
+        // unique conditions
+        if let Some(nonce) = &self.nonce {
+            condition =
+                condition.add(Expr::col(Messages::Nonce).eq(nonce.to_string()));
+        }
+
+        if let Some(message_id) = &self.message_id {
+            condition = condition
+                .add(Expr::col(Messages::MessageId).eq(message_id.to_string()));
+        }
+
+        condition
+    }
+}
+
+impl HasPagination for MessagesQuery {
+    fn pagination(&self) -> &QueryPagination {
+        &self.pagination
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pedronauck_streams_types::Address;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        messages::{queryable::MessagesQuery, MessageDirection, MessageStatus},
+        queryable::Queryable,
+    };
+
+    const FIRST_POINTER: i32 = 100;
+    const TEST_SENDER: &str =
+        "0x0101010101010101010101010101010101010101010101010101010101010101";
+    const TEST_MESSAGE_ID: &str =
+        "0x0202020202020202020202020202020202020202020202020202020202020202";
+
+    #[test]
+    fn test_sql_with_withdrawals_of_sender() {
+        let query = MessagesQuery {
+            direction: Some(MessageDirection::L2ToL1),
+            sender: Some(Address::from(TEST_SENDER)),
+            pagination: (None, None, Some(FIRST_POINTER), None).into(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            query.query_to_string(),
+            format!("SELECT * FROM \"messages\" WHERE \"direction\" = 'l2_to_l1' AND \"sender\" = '{}' ORDER BY \"block_height\" ASC LIMIT {}",
+                TEST_SENDER, FIRST_POINTER)
+        );
+    }
+
+    #[test]
+    fn test_messages_query_from_query_string() {
+        use serde_urlencoded;
+
+        let query_string =
+            format!("status=relayed&direction=l1_to_l2&first={FIRST_POINTER}");
+        let mut query: MessagesQuery =
+            serde_urlencoded::from_str(&query_string).unwrap();
+        query.set_message_id(TEST_MESSAGE_ID);
+
+        assert_eq!(query.status, Some(MessageStatus::Relayed));
+        assert_eq!(query.direction, Some(MessageDirection::L1ToL2));
+        assert_eq!(query.pagination().first, Some(FIRST_POINTER));
+        assert_eq!(
+            query.query_to_string(),
+            format!("SELECT * FROM \"messages\" WHERE \"direction\" = 'l1_to_l2' AND \"status\" = 'relayed' AND \"message_id\" = '{}' ORDER BY \"block_height\" ASC LIMIT {}",
+                TEST_MESSAGE_ID, FIRST_POINTER)
+        );
+    }
+}
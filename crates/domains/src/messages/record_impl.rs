@@ -0,0 +1,91 @@
+use async_trait::async_trait;
+use pedronauck_streams_store::{
+    db::{DbError, DbResult},
+    record::{DataEncoder, FilterColumn, Record, RecordEntity},
+};
+use pedronauck_streams_types::BlockTimestamp;
+use sqlx::PgExecutor;
+
+use super::{Message, MessageDbItem};
+
+impl DataEncoder for Message {
+    type Err = DbError;
+}
+
+#[async_trait]
+impl Record for Message {
+    type DbItem = MessageDbItem;
+
+    const ENTITY: RecordEntity = RecordEntity::Message;
+    const ORDER_PROPS: &'static [&'static str] =
+        &["tx_index", "input_index", "receipt_index"];
+    const FILTER_COLUMNS: &'static [FilterColumn] = &[
+        FilterColumn::text("messageId", "message_id"),
+        FilterColumn::text("nonce", "nonce"),
+        FilterColumn::text("txId", "tx_id"),
+    ];
+
+    /// Messages are stored once per message ID, so a message from L1 used
+    /// again updates the state columns of its row, while its position
+    /// stays where the message was first seen. Blocks can be stored out of
+    /// order, so a use from a lower block than the last state change, or
+    /// any use once the message is spent, leaves the row as it is.
+    async fn insert<'e, 'c: 'e, E>(
+        executor: E,
+        db_item: Self::DbItem,
+    ) -> DbResult<Self::DbItem>
+    where
+        'c: 'e,
+        E: PgExecutor<'c>,
+    {
+        let published_at = BlockTimestamp::now();
+        let record = sqlx::query_as::<_, MessageDbItem>(
+            "WITH upsert AS (
+                INSERT INTO messages (
+                    subject, value, block_height, tx_id, tx_index,
+                    record_index, input_index, receipt_index, message_id,
+                    nonce, direction, status, sender, recipient, da_height,
+                    created_at, published_at, updated_block_height
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $3)
+                ON CONFLICT (message_id) DO UPDATE SET
+                    subject = EXCLUDED.subject,
+                    value = EXCLUDED.value,
+                    tx_id = EXCLUDED.tx_id,
+                    status = EXCLUDED.status,
+                    da_height = EXCLUDED.da_height,
+                    updated_block_height = EXCLUDED.updated_block_height,
+                    published_at = $17
+                WHERE messages.status <> 'spent'
+                    AND EXCLUDED.updated_block_height >= messages.updated_block_height
+                RETURNING *
+            )
+            SELECT * FROM upsert
+            UNION ALL
+            SELECT * FROM messages
+            WHERE message_id = $9 AND NOT EXISTS (SELECT 1 FROM upsert)",
+        )
+        .bind(db_item.subject)
+        .bind(db_item.value)
+        .bind(db_item.block_height)
+        .bind(db_item.tx_id)
+        .bind(db_item.tx_index)
+        .bind(db_item.record_index)
+        .bind(db_item.input_index)
+        .bind(db_item.receipt_index)
+        .bind(db_item.message_id)
+        .bind(db_item.nonce)
+        .bind(db_item.direction)
+        .bind(db_item.status)
+        .bind(db_item.sender)
+        .bind(db_item.recipient)
+        .bind(db_item.da_height)
+        .bind(db_item.created_at)
+        .bind(published_at)
+        .fetch_one(executor)
+        .await
+        .map_err(DbError::Insert)?;
+
+        Ok(record)
+    }
+}
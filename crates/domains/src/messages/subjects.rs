@@ -0,0 +1,53 @@
+use pedronauck_streams_subject::subject::*;
+use pedronauck_streams_types::*;
+use serde::{Deserialize, Serialize};
+
+use super::types::*;
+
+#[derive(Subject, Debug, Clone, Default, Serialize, Deserialize)]
+#[subject(id = "messages")]
+#[subject(entity = "Message")]
+#[subject(query_all = "messages.>")]
+#[subject(
+    format = "messages.{direction}.{status}.{sender}.{recipient}.{nonce}.{message_id}.{block_height}.{tx_id}.{tx_index}.{record_index}"
+)]
+pub struct MessagesSubject {
+    #[subject(
+        description = "The direction of the message (l1_to_l2 or l2_to_l1)"
+    )]
+    pub direction: Option<MessageDirection>,
+    #[subject(
+        description = "The state of the message (sent, relayed or spent)"
+    )]
+    pub status: Option<MessageStatus>,
+    #[subject(
+        description = "The address sending the message (32 byte string prefixed by 0x)"
+    )]
+    pub sender: Option<Address>,
+    #[subject(
+        description = "The address receiving the message (32 byte string prefixed by 0x)"
+    )]
+    pub recipient: Option<Address>,
+    #[subject(
+        description = "The nonce of the message (32 byte string prefixed by 0x)"
+    )]
+    pub nonce: Option<Nonce>,
+    #[subject(
+        description = "The ID of the message (32 byte string prefixed by 0x)"
+    )]
+    pub message_id: Option<MessageId>,
+    #[subject(
+        description = "The height of the block where the message was created or used"
+    )]
+    pub block_height: Option<BlockHeight>,
+    #[subject(
+        description = "The ID of the transaction creating or using the message (32 byte string prefixed by 0x)"
+    )]
+    pub tx_id: Option<TxId>,
+    #[subject(description = "The index of the transaction within the block")]
+    pub tx_index: Option<u32>,
+    #[subject(
+        description = "The index of the receipt creating the message, or of the input using it"
+    )]
+    pub record_index: Option<u32>,
+}
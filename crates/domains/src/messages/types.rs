@@ -0,0 +1,274 @@
+use pedronauck_streams_types::primitives::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{inputs::types::*, receipts::types::*, transactions::types::*};
+
+/// A message of the bridge, sent each time its state changes. Messages
+/// going to L1 are sent once, when a `MessageOut` receipt creates them.
+/// Messages coming from L1 are sent when a message input uses them, which
+/// spends them unless the transaction fails and they carry data, as such
+/// messages can be retried.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Message {
+    pub message_id: MessageId,
+    pub nonce: Nonce,
+    pub direction: MessageDirection,
+    pub status: MessageStatus,
+    pub sender: Address,
+    pub recipient: Address,
+    pub amount: Amount,
+    pub data: HexData,
+    /// DA height of the block the message was seen in: a message from L1
+    /// was relayed at or below it
+    pub da_height: DaBlockHeight,
+    /// The transaction creating the message or, for messages from L1, the
+    /// last one using it
+    pub tx_id: TxId,
+}
+
+impl Message {
+    /// Message going to L1, created by a `MessageOut` receipt of a
+    /// successful transaction. The receipts of failed transactions are
+    /// reverted, so they don't create any message.
+    pub fn from_receipt(
+        receipt: &Receipt,
+        tx: &Transaction,
+        da_height: DaBlockHeight,
+    ) -> Option<Self> {
+        let Receipt::MessageOut(receipt) = receipt else {
+            return None;
+        };
+        if tx.status != TransactionStatus::Success {
+            return None;
+        }
+        let data = receipt.data.to_owned().unwrap_or_default();
+        Some(Message {
+            message_id: compute_message_id(
+                &receipt.sender,
+                &receipt.recipient,
+                &receipt.nonce,
+                *receipt.amount,
+                &data,
+            ),
+            nonce: receipt.nonce.to_owned(),
+            direction: MessageDirection::L2ToL1,
+            status: MessageStatus::Sent,
+            sender: receipt.sender.to_owned(),
+            recipient: receipt.recipient.to_owned(),
+            amount: (*receipt.amount).into(),
+            data,
+            da_height,
+            tx_id: tx.id.to_owned(),
+        })
+    }
+
+    /// Message coming from L1, used by a message input of the transaction
+    pub fn from_input(
+        input: &Input,
+        tx: &Transaction,
+        da_height: DaBlockHeight,
+    ) -> Option<Self> {
+        let Input::Message(input) = input else {
+            return None;
+        };
+        let retryable = tx.status == TransactionStatus::Failed
+            && !input.data.0.as_ref().is_empty();
+        Some(Message {
+            message_id: input.compute_message_id(),
+            nonce: input.nonce.to_owned(),
+            direction: MessageDirection::L1ToL2,
+            status: match retryable {
+                true => MessageStatus::Relayed,
+                false => MessageStatus::Spent,
+            },
+            sender: input.sender.to_owned(),
+            recipient: input.recipient.to_owned(),
+            amount: input.amount,
+            data: input.data.to_owned(),
+            da_height,
+            tx_id: tx.id.to_owned(),
+        })
+    }
+}
+
+/// ID of a message, the same way the node computes it for message inputs
+pub fn compute_message_id(
+    sender: &Address,
+    recipient: &Address,
+    nonce: &Nonce,
+    amount: u64,
+    data: &HexData,
+) -> MessageId {
+    let hasher = fuel_core_types::fuel_crypto::Hasher::default()
+        .chain(sender.as_ref())
+        .chain(recipient.as_ref())
+        .chain(nonce.as_ref())
+        .chain(amount.to_be_bytes())
+        .chain(data.0.as_ref());
+
+    (*hasher.finalize()).into()
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageDirection {
+    /// Deposits and other messages relayed from L1
+    #[default]
+    L1ToL2,
+    /// Withdrawals and other messages sent to L1
+    L2ToL1,
+}
+
+impl MessageDirection {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MessageDirection::L1ToL2 => "l1_to_l2",
+            MessageDirection::L2ToL1 => "l2_to_l1",
+        }
+    }
+}
+
+impl std::fmt::Display for MessageDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for MessageDirection {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            s if s == Self::L1ToL2.as_str() => Ok(Self::L1ToL2),
+            s if s == Self::L2ToL1.as_str() => Ok(Self::L2ToL1),
+            _ => Err(format!("Invalid message direction: {s}")),
+        }
+    }
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageStatus {
+    /// Sent to L1 by a `MessageOut` receipt
+    #[default]
+    Sent,
+    /// Relayed from L1 and used by a failed transaction, so it can still be
+    /// spent
+    Relayed,
+    /// Relayed from L1 and spent by a message input
+    Spent,
+}
+
+impl MessageStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MessageStatus::Sent => "sent",
+            MessageStatus::Relayed => "relayed",
+            MessageStatus::Spent => "spent",
+        }
+    }
+}
+
+impl std::fmt::Display for MessageStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for MessageStatus {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            s if s == Self::Sent.as_str() => Ok(Self::Sent),
+            s if s == Self::Relayed.as_str() => Ok(Self::Relayed),
+            s if s == Self::Spent.as_str() => Ok(Self::Spent),
+            _ => Err(format!("Invalid message status: {s}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn transaction(status: TransactionStatus) -> Transaction {
+        let mut tx = MockTransaction::script(vec![], vec![], vec![]);
+        tx.status = status;
+        tx
+    }
+
+    #[test]
+    fn test_message_from_receipt() {
+        let tx = transaction(TransactionStatus::Success);
+        let receipt = MockReceipt::message_out();
+        let message = Message::from_receipt(&receipt, &tx, 5.into()).unwrap();
+        assert_eq!(message.direction, MessageDirection::L2ToL1);
+        assert_eq!(message.status, MessageStatus::Sent);
+        assert_eq!(message.data, HexData(vec![7, 8, 9].into()));
+        assert_eq!(message.da_height, 5.into());
+        assert_eq!(message.tx_id, tx.id);
+
+        // The ID is computed the same way as the one of message inputs
+        let Receipt::MessageOut(receipt) = receipt else {
+            panic!("expected a message out receipt");
+        };
+        let input = InputMessage {
+            amount: (*receipt.amount).into(),
+            data: HexData(vec![7, 8, 9].into()),
+            nonce: receipt.nonce,
+            recipient: receipt.recipient,
+            sender: receipt.sender,
+            ..Default::default()
+        };
+        assert_eq!(message.message_id, input.compute_message_id());
+
+        let tx = transaction(TransactionStatus::Failed);
+        let receipt = MockReceipt::message_out();
+        assert!(Message::from_receipt(&receipt, &tx, 5.into()).is_none());
+    }
+
+    #[test]
+    fn test_message_from_input() {
+        let tx = transaction(TransactionStatus::Success);
+        let input = MockInput::message_data_signed();
+        let message = Message::from_input(&input, &tx, 5.into()).unwrap();
+        assert_eq!(message.direction, MessageDirection::L1ToL2);
+        assert_eq!(message.status, MessageStatus::Spent);
+
+        // Messages with data aren't spent by failed transactions, unlike
+        // the ones only holding coins
+        let tx = transaction(TransactionStatus::Failed);
+        let message = Message::from_input(&input, &tx, 5.into()).unwrap();
+        assert_eq!(message.status, MessageStatus::Relayed);
+        let input = MockInput::message_coin_signed();
+        let message = Message::from_input(&input, &tx, 5.into()).unwrap();
+        assert_eq!(message.status, MessageStatus::Spent);
+
+        let input = MockInput::coin_signed();
+        assert!(Message::from_input(&input, &tx, 5.into()).is_none());
+    }
+}
@@ -187,6 +187,7 @@ impl From<OutputDbItem> for RecordPointer {
             input_index: None,
             output_index: Some(val.output_index as u32),
             receipt_index: None,
+            fanout_index: None,
         }
     }
 }
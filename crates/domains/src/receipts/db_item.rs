@@ -13,7 +13,7 @@ use pedronauck_streams_store::{
 use pedronauck_streams_types::{BlockHeight, BlockTimestamp};
 use serde::{Deserialize, Serialize};
 
-use super::subjects::*;
+use super::{subjects::*, types::*};
 use crate::Subjects;
 
 #[derive(
@@ -35,6 +35,10 @@ pub struct ReceiptDbItem {
     pub sub_id: Option<String>,      // for mint/burn
     pub sender_address: Option<String>, // for message_out
     pub recipient_address: Option<String>, // for message_out
+    pub log_id: Option<String>,      // for log_data
+    pub log_type: Option<String>,    // for log_data decoded with an ABI
+    pub panic_reason: Option<String>, // for panic
+    pub revert_signal: Option<String>, // for revert
     pub created_at: BlockTimestamp,
     pub published_at: BlockTimestamp,
 }
@@ -115,6 +119,10 @@ impl TryFrom<&RecordPacket> for ReceiptDbItem {
                 sub_id: None,
                 sender_address: None,
                 recipient_address: None,
+                log_id: None,
+                log_type: None,
+                panic_reason: None,
+                revert_signal: None,
                 created_at: packet.block_timestamp,
                 published_at: packet.block_timestamp,
             }),
@@ -134,6 +142,10 @@ impl TryFrom<&RecordPacket> for ReceiptDbItem {
                 sub_id: None,
                 sender_address: None,
                 recipient_address: None,
+                log_id: None,
+                log_type: None,
+                panic_reason: None,
+                revert_signal: None,
                 created_at: packet.block_timestamp,
                 published_at: packet.block_timestamp,
             }),
@@ -153,6 +165,10 @@ impl TryFrom<&RecordPacket> for ReceiptDbItem {
                 sub_id: None,
                 sender_address: None,
                 recipient_address: None,
+                log_id: None,
+                log_type: None,
+                panic_reason: None,
+                revert_signal: None,
                 created_at: packet.block_timestamp,
                 published_at: packet.block_timestamp,
             }),
@@ -172,6 +188,10 @@ impl TryFrom<&RecordPacket> for ReceiptDbItem {
                 sub_id: None,
                 sender_address: None,
                 recipient_address: None,
+                log_id: None,
+                log_type: None,
+                panic_reason: subject.reason,
+                revert_signal: None,
                 created_at: packet.block_timestamp,
                 published_at: packet.block_timestamp,
             }),
@@ -191,6 +211,10 @@ impl TryFrom<&RecordPacket> for ReceiptDbItem {
                 sub_id: None,
                 sender_address: None,
                 recipient_address: None,
+                log_id: None,
+                log_type: None,
+                panic_reason: None,
+                revert_signal: subject.signal.map(|signal| signal.to_string()),
                 created_at: packet.block_timestamp,
                 published_at: packet.block_timestamp,
             }),
@@ -210,28 +234,48 @@ impl TryFrom<&RecordPacket> for ReceiptDbItem {
                 sub_id: None,
                 sender_address: None,
                 recipient_address: None,
+                log_id: None,
+                log_type: None,
+                panic_reason: None,
+                revert_signal: None,
                 created_at: packet.block_timestamp,
                 published_at: packet.block_timestamp,
             }),
-            Subjects::ReceiptsLogData(subject) => Ok(ReceiptDbItem {
-                subject: packet.subject_str(),
-                value: packet.value.to_owned(),
-                block_height: subject.block_height.unwrap().into(),
-                tx_id: subject.tx_id.unwrap().to_string(),
-                tx_index: subject.tx_index.unwrap() as i32,
-                receipt_index: subject.receipt_index.unwrap() as i32,
-                receipt_type: "log_data".to_string(),
-                from_contract_id: None,
-                to_contract_id: None,
-                to_address: None,
-                asset_id: None,
-                contract_id: Some(subject.contract.unwrap().to_string()),
-                sub_id: None,
-                sender_address: None,
-                recipient_address: None,
-                created_at: packet.block_timestamp,
-                published_at: packet.block_timestamp,
-            }),
+            Subjects::ReceiptsLogData(subject) => {
+                // The type is stored lowercase, like the other filterable
+                // text columns
+                let receipt: Receipt =
+                    Receipt::data_parser().decode_json(&packet.value)?;
+                let log_type = match receipt {
+                    Receipt::LogData(LogDataReceipt { decoded, .. }) => {
+                        decoded.map(|decoded| decoded.type_name.to_lowercase())
+                    }
+                    _ => None,
+                };
+                Ok(ReceiptDbItem {
+                    subject: packet.subject_str(),
+                    value: packet.value.to_owned(),
+                    block_height: subject.block_height.unwrap().into(),
+                    tx_id: subject.tx_id.unwrap().to_string(),
+                    tx_index: subject.tx_index.unwrap() as i32,
+                    receipt_index: subject.receipt_index.unwrap() as i32,
+                    receipt_type: "log_data".to_string(),
+                    from_contract_id: None,
+                    to_contract_id: None,
+                    to_address: None,
+                    asset_id: None,
+                    contract_id: Some(subject.contract.unwrap().to_string()),
+                    sub_id: None,
+                    sender_address: None,
+                    recipient_address: None,
+                    log_id: subject.log_id.map(|log_id| log_id.to_string()),
+                    log_type,
+                    panic_reason: None,
+                    revert_signal: None,
+                    created_at: packet.block_timestamp,
+                    published_at: packet.block_timestamp,
+                })
+            }
             Subjects::ReceiptsTransfer(subject) => Ok(ReceiptDbItem {
                 subject: packet.subject_str(),
                 value: packet.value.to_owned(),
@@ -248,6 +292,10 @@ impl TryFrom<&RecordPacket> for ReceiptDbItem {
                 sender_address: None,
                 recipient_address: None,
                 to_address: None,
+                log_id: None,
+                log_type: None,
+                panic_reason: None,
+                revert_signal: None,
                 created_at: packet.block_timestamp,
                 published_at: packet.block_timestamp,
             }),
@@ -267,6 +315,10 @@ impl TryFrom<&RecordPacket> for ReceiptDbItem {
                 sub_id: None,
                 sender_address: None,
                 recipient_address: None,
+                log_id: None,
+                log_type: None,
+                panic_reason: None,
+                revert_signal: None,
                 created_at: packet.block_timestamp,
                 published_at: packet.block_timestamp,
             }),
@@ -286,6 +338,10 @@ impl TryFrom<&RecordPacket> for ReceiptDbItem {
                 sub_id: None,
                 sender_address: None,
                 recipient_address: None,
+                log_id: None,
+                log_type: None,
+                panic_reason: None,
+                revert_signal: None,
                 created_at: packet.block_timestamp,
                 published_at: packet.block_timestamp,
             }),
@@ -305,6 +361,10 @@ impl TryFrom<&RecordPacket> for ReceiptDbItem {
                 sub_id: None,
                 sender_address: Some(subject.sender.unwrap().to_string()),
                 recipient_address: Some(subject.recipient.unwrap().to_string()),
+                log_id: None,
+                log_type: None,
+                panic_reason: None,
+                revert_signal: None,
                 created_at: packet.block_timestamp,
                 published_at: packet.block_timestamp,
             }),
@@ -324,6 +384,10 @@ impl TryFrom<&RecordPacket> for ReceiptDbItem {
                 sub_id: Some(subject.sub_id.unwrap().to_string()),
                 sender_address: None,
                 recipient_address: None,
+                log_id: None,
+                log_type: None,
+                panic_reason: None,
+                revert_signal: None,
                 created_at: packet.block_timestamp,
                 published_at: packet.block_timestamp,
             }),
@@ -343,6 +407,10 @@ impl TryFrom<&RecordPacket> for ReceiptDbItem {
                 sub_id: Some(subject.sub_id.unwrap().to_string()),
                 sender_address: None,
                 recipient_address: None,
+                log_id: None,
+                log_type: None,
+                panic_reason: None,
+                revert_signal: None,
                 created_at: packet.block_timestamp,
                 published_at: packet.block_timestamp,
             }),
@@ -377,6 +445,7 @@ impl From<ReceiptDbItem> for RecordPointer {
             input_index: None,
             output_index: None,
             receipt_index: Some(val.receipt_index as u32),
+            fanout_index: None,
         }
     }
 }
@@ -3,9 +3,11 @@ mod packets;
 pub mod queryable;
 mod record_impl;
 pub mod subjects;
+pub mod trace;
 pub mod types;
 
 pub use db_item::*;
 pub use packets::*;
 pub use subjects::*;
+pub use trace::*;
 pub use types::*;
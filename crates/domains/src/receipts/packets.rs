@@ -86,21 +86,25 @@ impl From<(&Receipt, BlockHeight, TxId, u32, u32)> for DynReceiptSubject {
                 }
                 .arc()
             }
-            Receipt::Panic(PanicReceipt { id, .. }) => ReceiptsPanicSubject {
-                block_height: Some(block_height),
-                tx_id: Some(tx_id),
-                tx_index: Some(tx_index),
-                receipt_index: Some(receipt_index),
-                contract: Some(id.to_owned()),
+            Receipt::Panic(PanicReceipt { id, reason, .. }) => {
+                ReceiptsPanicSubject {
+                    block_height: Some(block_height),
+                    tx_id: Some(tx_id),
+                    tx_index: Some(tx_index),
+                    receipt_index: Some(receipt_index),
+                    contract: Some(id.to_owned()),
+                    reason: Some(reason.reason_name()),
+                }
+                .arc()
             }
-            .arc(),
-            Receipt::Revert(RevertReceipt { id, .. }) => {
+            Receipt::Revert(RevertReceipt { id, ra, .. }) => {
                 ReceiptsRevertSubject {
                     block_height: Some(block_height),
                     tx_id: Some(tx_id),
                     tx_index: Some(tx_index),
                     receipt_index: Some(receipt_index),
                     contract: Some(id.to_owned()),
+                    signal: Some(RevertSignal::from_code(**ra)),
                 }
                 .arc()
             }
@@ -112,13 +116,14 @@ impl From<(&Receipt, BlockHeight, TxId, u32, u32)> for DynReceiptSubject {
                 contract: Some(id.to_owned()),
             }
             .arc(),
-            Receipt::LogData(LogDataReceipt { id, .. }) => {
+            Receipt::LogData(LogDataReceipt { id, rb, .. }) => {
                 ReceiptsLogDataSubject {
                     block_height: Some(block_height),
                     tx_id: Some(tx_id),
                     tx_index: Some(tx_index),
                     receipt_index: Some(receipt_index),
                     contract: Some(id.to_owned()),
+                    log_id: Some(**rb),
                 }
                 .arc()
             }
@@ -3,7 +3,7 @@ use pedronauck_streams_types::*;
 use sea_query::{Condition, Expr, Iden};
 use serde::{Deserialize, Serialize};
 
-use super::{ReceiptDbItem, ReceiptType};
+use super::{ReceiptDbItem, ReceiptType, RevertSignal};
 use crate::queryable::{HasPagination, QueryPagination, Queryable};
 
 #[allow(dead_code)]
@@ -42,6 +42,14 @@ pub enum Receipts {
     ReceiptSenderAddress,
     #[iden = "recipient_address"] // Address for message_out
     ReceiptRecipientAddress,
+    #[iden = "log_id"] // for log_data
+    LogId,
+    #[iden = "log_type"] // for log_data decoded with an ABI
+    LogType,
+    #[iden = "panic_reason"] // for panic
+    PanicReason,
+    #[iden = "revert_signal"] // for revert
+    RevertSignal,
     #[iden = "created_at"]
     CreatedAt,
     #[iden = "published_at"]
@@ -65,6 +73,10 @@ pub struct ReceiptsQuery {
     pub sender: Option<Address>,
     pub recipient: Option<Address>,
     pub sub_id: Option<Bytes32>,
+    pub log_id: Option<u64>,
+    pub log_type: Option<String>,
+    pub panic_reason: Option<String>,
+    pub revert_signal: Option<RevertSignal>,
     #[serde(flatten)]
     pub pagination: QueryPagination,
     pub address: Option<Address>, // for the accounts endpoint
@@ -228,6 +240,28 @@ impl Queryable for ReceiptsQuery {
             );
         }
 
+        if let Some(log_id) = &self.log_id {
+            condition = condition
+                .add(Expr::col(Receipts::LogId).eq(log_id.to_string()));
+        }
+
+        if let Some(log_type) = &self.log_type {
+            condition = condition
+                .add(Expr::col(Receipts::LogType).eq(log_type.to_lowercase()));
+        }
+
+        if let Some(panic_reason) = &self.panic_reason {
+            condition = condition.add(
+                Expr::col(Receipts::PanicReason).eq(panic_reason.to_owned()),
+            );
+        }
+
+        if let Some(revert_signal) = &self.revert_signal {
+            condition = condition.add(
+                Expr::col(Receipts::RevertSignal).eq(revert_signal.to_string()),
+            );
+        }
+
         condition
     }
 }
@@ -273,6 +307,7 @@ mod test {
         "0x0404040404040404040404040404040404040404040404040404040404040404";
     const TEST_SUB_ID: &str =
         "0x0505050505050505050505050505050505050505050505050505050505050505";
+    const TEST_LOG_ID: u64 = 1515152261580153489;
 
     #[test]
     fn test_sql_with_fixed_conds() {
@@ -290,6 +325,10 @@ mod test {
             sender: None,
             recipient: None,
             sub_id: None,
+            log_id: None,
+            log_type: None,
+            panic_reason: None,
+            revert_signal: None,
             pagination: Default::default(),
             address: None,
         };
@@ -314,6 +353,10 @@ mod test {
             sender: None,
             recipient: None,
             sub_id: None,
+            log_id: None,
+            log_type: None,
+            panic_reason: None,
+            revert_signal: None,
             pagination: (None, None, Some(FIRST_POINTER), None).into(),
             address: None,
         };
@@ -338,6 +381,10 @@ mod test {
             sender: None,
             recipient: None,
             sub_id: None,
+            log_id: None,
+            log_type: None,
+            panic_reason: None,
+            revert_signal: None,
             pagination: (Some(AFTER_POINTER), None, None, Some(LAST_POINTER))
                 .into(),
             address: None,
@@ -363,6 +410,10 @@ mod test {
             sender: Some(Address::from([4u8; 32])),
             recipient: Some(Address::from([4u8; 32])),
             sub_id: None,
+            log_id: None,
+            log_type: None,
+            panic_reason: None,
+            revert_signal: None,
             pagination: (None, Some(BEFORE_POINTER), Some(FIRST_POINTER), None)
                 .into(),
             address: None,
@@ -388,6 +439,10 @@ mod test {
             sender: None,
             recipient: None,
             sub_id: None,
+            log_id: None,
+            log_type: None,
+            panic_reason: None,
+            revert_signal: None,
             pagination: Default::default(),
             address: None,
         };
@@ -397,6 +452,33 @@ mod test {
             format!("SELECT * FROM \"receipts\" WHERE \"tx_id\" = '{}' AND \"tx_index\" = {} AND \"receipt_index\" = {}",
                 TEST_TX_ID, TEST_TX_INDEX, TEST_RECEIPT_INDEX)
         );
+
+        // Test 6: query with decoded log filters
+        let log_query = ReceiptsQuery {
+            receipt_type: Some(ReceiptType::LogData),
+            contract: Some(ContractId::from(TEST_CONTRACT_ID)),
+            log_id: Some(TEST_LOG_ID),
+            log_type: Some("struct Transfer".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            log_query.query_to_string(),
+            format!("SELECT * FROM \"receipts\" WHERE \"receipt_type\" = 'log_data' AND \"contract_id\" = '{}' AND \"log_id\" = '{}' AND \"log_type\" = 'struct transfer'",
+                TEST_CONTRACT_ID, TEST_LOG_ID)
+        );
+
+        // Test 7: query with failure reason filters
+        let failure_query = ReceiptsQuery {
+            panic_reason: Some("NotEnoughBalance".to_string()),
+            revert_signal: Some(RevertSignal::FailedRequire),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            failure_query.query_to_string(),
+            "SELECT * FROM \"receipts\" WHERE \"panic_reason\" = 'NotEnoughBalance' AND \"revert_signal\" = 'failed_require'"
+        );
     }
 
     #[test]
@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use pedronauck_streams_store::{
     db::{DbError, DbResult},
-    record::{DataEncoder, Record, RecordEntity},
+    record::{DataEncoder, FilterColumn, Record, RecordEntity},
 };
 use pedronauck_streams_types::BlockTimestamp;
 use sqlx::PgExecutor;
@@ -18,6 +18,14 @@ impl Record for Receipt {
 
     const ENTITY: RecordEntity = RecordEntity::Receipt;
     const ORDER_PROPS: &'static [&'static str] = &["tx_index", "receipt_index"];
+    const FILTER_COLUMNS: &'static [FilterColumn] = &[
+        FilterColumn::text("assetId", "asset_id"),
+        FilterColumn::text("subId", "sub_id"),
+        FilterColumn::text("sender", "sender_address"),
+        FilterColumn::text("recipient", "recipient_address"),
+        FilterColumn::text("decoded.logId", "log_id"),
+        FilterColumn::text("decoded.type", "log_type"),
+    ];
 
     async fn insert<'e, 'c: 'e, E>(
         executor: E,
@@ -34,9 +42,9 @@ impl Record for Receipt {
                     subject, value, block_height, tx_id, tx_index, receipt_index,
                     receipt_type, from_contract_id, to_contract_id, to_address,
                     asset_id, contract_id, sub_id, sender_address, recipient_address,
-                    created_at, published_at
+                    log_id, log_type, panic_reason, revert_signal, created_at, published_at
                 )
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21)
                 ON CONFLICT (subject) DO UPDATE SET
                     value = EXCLUDED.value,
                     block_height = EXCLUDED.block_height,
@@ -52,8 +60,12 @@ impl Record for Receipt {
                     sub_id = EXCLUDED.sub_id,
                     sender_address = EXCLUDED.sender_address,
                     recipient_address = EXCLUDED.recipient_address,
+                    log_id = EXCLUDED.log_id,
+                    log_type = EXCLUDED.log_type,
+                    panic_reason = EXCLUDED.panic_reason,
+                    revert_signal = EXCLUDED.revert_signal,
                     created_at = EXCLUDED.created_at,
-                    published_at = $17
+                    published_at = $21
                 RETURNING *
             )
             SELECT * FROM upsert"
@@ -73,6 +85,10 @@ impl Record for Receipt {
         .bind(db_item.sub_id)
         .bind(db_item.sender_address)
         .bind(db_item.recipient_address)
+        .bind(db_item.log_id)
+        .bind(db_item.log_type)
+        .bind(db_item.panic_reason)
+        .bind(db_item.revert_signal)
         .bind(db_item.created_at)
         .bind(published_at)
         .fetch_one(executor)
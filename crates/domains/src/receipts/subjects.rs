@@ -2,7 +2,7 @@ use pedronauck_streams_subject::subject::*;
 use pedronauck_streams_types::*;
 use serde::{Deserialize, Serialize};
 
-use super::ReceiptType;
+use super::{ReceiptType, RevertSignal};
 
 #[derive(Subject, Debug, Clone, Default, Serialize, Deserialize)]
 #[subject(id = "receipts_call")]
@@ -110,7 +110,7 @@ pub struct ReceiptsReturnDataSubject {
 #[subject(query_all = "receipts.panic.>")]
 #[subject(custom_where = "receipt_type = 'panic'")]
 #[subject(
-    format = "receipts.panic.{block_height}.{tx_id}.{tx_index}.{receipt_index}.{contract}"
+    format = "receipts.panic.{block_height}.{tx_id}.{tx_index}.{receipt_index}.{contract}.{reason}"
 )]
 pub struct ReceiptsPanicSubject {
     #[subject(
@@ -132,6 +132,11 @@ pub struct ReceiptsPanicSubject {
         description = "The ID of the contract that panicked (32 byte string prefixed by 0x)"
     )]
     pub contract: Option<ContractId>,
+    #[subject(
+        sql_column = "panic_reason",
+        description = "The name of the panic reason, e.g. NotEnoughBalance"
+    )]
+    pub reason: Option<String>,
 }
 
 #[derive(Subject, Debug, Clone, Default, Serialize, Deserialize)]
@@ -140,7 +145,7 @@ pub struct ReceiptsPanicSubject {
 #[subject(query_all = "receipts.revert.>")]
 #[subject(custom_where = "receipt_type = 'revert'")]
 #[subject(
-    format = "receipts.revert.{block_height}.{tx_id}.{tx_index}.{receipt_index}.{contract}"
+    format = "receipts.revert.{block_height}.{tx_id}.{tx_index}.{receipt_index}.{contract}.{signal}"
 )]
 pub struct ReceiptsRevertSubject {
     #[subject(
@@ -162,6 +167,11 @@ pub struct ReceiptsRevertSubject {
         description = "The ID of the contract that reverted (32 byte string prefixed by 0x)"
     )]
     pub contract: Option<ContractId>,
+    #[subject(
+        sql_column = "revert_signal",
+        description = "The Sway revert signal, e.g. failed_require, or custom for codes the contract reverted with itself"
+    )]
+    pub signal: Option<RevertSignal>,
 }
 
 #[derive(Subject, Debug, Clone, Default, Serialize, Deserialize)]
@@ -200,7 +210,7 @@ pub struct ReceiptsLogSubject {
 #[subject(query_all = "receipts.log_data.>")]
 #[subject(custom_where = "receipt_type = 'log_data'")]
 #[subject(
-    format = "receipts.log_data.{block_height}.{tx_id}.{tx_index}.{receipt_index}.{contract}"
+    format = "receipts.log_data.{block_height}.{tx_id}.{tx_index}.{receipt_index}.{contract}.{log_id}"
 )]
 pub struct ReceiptsLogDataSubject {
     #[subject(
@@ -222,6 +232,10 @@ pub struct ReceiptsLogDataSubject {
         description = "The ID of the contract that emitted the log data (32 byte string prefixed by 0x)"
     )]
     pub contract: Option<ContractId>,
+    #[subject(
+        description = "The ID the value was logged under, matching the `logId` of the contract's ABI"
+    )]
+    pub log_id: Option<u64>,
 }
 
 #[derive(Subject, Debug, Clone, Default, Serialize, Deserialize)]
@@ -0,0 +1,337 @@
+use pedronauck_streams_store::{
+    db::{DbError, DbResult},
+    record::Record,
+};
+use pedronauck_streams_types::{
+    AssetId,
+    BlockHeight,
+    ContractId,
+    HexData,
+    PanicInstruction,
+    ScriptExecutionResult,
+    TxId,
+    Word,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgExecutor;
+
+use super::{CallReceipt, Receipt, ReceiptDbItem};
+
+/// How a call frame ended
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum CallResult {
+    Return {
+        val: Word,
+    },
+    ReturnData {
+        data: Option<HexData>,
+    },
+    Panic {
+        reason: PanicInstruction,
+    },
+    Revert {
+        ra: Word,
+    },
+    /// The frame never returned, as a call made within it failed
+    Aborted,
+}
+
+impl CallResult {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, CallResult::Panic { .. } | CallResult::Revert { .. })
+    }
+}
+
+/// Contract call made by a transaction, with the calls made within it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    /// Index of the `Call` receipt that opened the frame
+    pub receipt_index: u32,
+    /// The calling contract, zeroed when called by the script
+    pub from: ContractId,
+    pub to: ContractId,
+    /// The `param1` of the call, which holds the function selector
+    pub selector: Word,
+    pub amount: Word,
+    pub asset_id: AssetId,
+    pub gas: Word,
+    pub result: CallResult,
+    #[schema(no_recursion)]
+    pub calls: Vec<CallFrame>,
+}
+
+impl CallFrame {
+    fn new(receipt_index: u32, call: CallReceipt) -> Self {
+        Self {
+            receipt_index,
+            from: call.id,
+            to: call.to,
+            selector: call.param1,
+            amount: call.amount,
+            asset_id: call.asset_id,
+            gas: call.gas,
+            result: CallResult::Aborted,
+            calls: vec![],
+        }
+    }
+}
+
+/// Call tree of a transaction, rebuilt from its call, return, panic and
+/// revert receipts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CallTrace {
+    pub tx_id: TxId,
+    pub block_height: BlockHeight,
+    /// How the script itself ended, if it returned or failed outside calls
+    pub result: Option<CallResult>,
+    /// Outcome of the transaction, from its `ScriptResult` receipt
+    pub script_result: Option<ScriptExecutionResult>,
+    pub gas_used: Option<Word>,
+    /// Receipt index of the call frame that panicked or reverted
+    pub failing_frame: Option<u32>,
+    /// Calls made by the script
+    pub calls: Vec<CallFrame>,
+}
+
+impl CallTrace {
+    /// Nests the calls found in receipts given in execution order. A panic
+    /// or revert ends the frame it happened in and aborts the ones above it.
+    pub fn from_receipts(
+        tx_id: TxId,
+        block_height: BlockHeight,
+        receipts: impl IntoIterator<Item = (u32, Receipt)>,
+    ) -> Self {
+        let mut trace = Self {
+            tx_id,
+            block_height,
+            result: None,
+            script_result: None,
+            gas_used: None,
+            failing_frame: None,
+            calls: vec![],
+        };
+        let mut stack: Vec<CallFrame> = vec![];
+        for (receipt_index, receipt) in receipts {
+            let result = match receipt {
+                Receipt::Call(call) => {
+                    stack.push(CallFrame::new(receipt_index, call));
+                    continue;
+                }
+                Receipt::Return(receipt) => {
+                    CallResult::Return { val: receipt.val }
+                }
+                Receipt::ReturnData(receipt) => {
+                    CallResult::ReturnData { data: receipt.data }
+                }
+                Receipt::Panic(receipt) => CallResult::Panic {
+                    reason: receipt.reason,
+                },
+                Receipt::Revert(receipt) => {
+                    CallResult::Revert { ra: receipt.ra }
+                }
+                Receipt::ScriptResult(receipt) => {
+                    trace.script_result = Some(receipt.result);
+                    trace.gas_used = Some(receipt.gas_used);
+                    continue;
+                }
+                _ => continue,
+            };
+
+            let failed = result.is_failure();
+            match stack.pop() {
+                Some(mut frame) => {
+                    if failed {
+                        trace.failing_frame = Some(frame.receipt_index);
+                    }
+                    frame.result = result;
+                    trace.close_frame(&mut stack, frame);
+                }
+                None => trace.result = Some(result),
+            }
+            if failed {
+                trace.close_all(&mut stack);
+            }
+        }
+        trace.close_all(&mut stack);
+        trace
+    }
+
+    fn close_frame(&mut self, stack: &mut [CallFrame], frame: CallFrame) {
+        match stack.last_mut() {
+            Some(parent) => parent.calls.push(frame),
+            None => self.calls.push(frame),
+        }
+    }
+
+    fn close_all(&mut self, stack: &mut Vec<CallFrame>) {
+        while let Some(frame) = stack.pop() {
+            self.close_frame(stack, frame);
+        }
+    }
+
+    /// Rebuilds the call tree of a stored transaction, or `None` when no
+    /// receipts are stored for it
+    pub async fn find<'c, E>(
+        executor: E,
+        tx_id: &TxId,
+    ) -> DbResult<Option<Self>>
+    where
+        E: PgExecutor<'c>,
+    {
+        let items = sqlx::query_as::<_, ReceiptDbItem>(
+            "SELECT * FROM receipts WHERE tx_id = $1 ORDER BY receipt_index",
+        )
+        .bind(tx_id.to_string())
+        .fetch_all(executor)
+        .await
+        .map_err(DbError::Query)?;
+        let Some(first) = items.first() else {
+            return Ok(None);
+        };
+        let block_height = first.block_height.into();
+        let receipts = items
+            .iter()
+            .map(|item| {
+                let receipt = Receipt::from_db_item(item)?;
+                Ok((item.receipt_index as u32, receipt))
+            })
+            .collect::<DbResult<Vec<_>>>()?;
+        Ok(Some(Self::from_receipts(
+            tx_id.to_owned(),
+            block_height,
+            receipts,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fuel_core_types::{fuel_asm::RawInstruction, fuel_tx::PanicReason};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::receipts::{
+        PanicReceipt,
+        ReturnDataReceipt,
+        ReturnReceipt,
+        ScriptResultReceipt,
+    };
+
+    fn contract(byte: u8) -> ContractId {
+        ContractId::from([byte; 32])
+    }
+
+    fn call(from: u8, to: u8, selector: u64) -> Receipt {
+        Receipt::Call(CallReceipt {
+            id: contract(from),
+            to: contract(to),
+            param1: selector.into(),
+            gas: 1000.into(),
+            ..Default::default()
+        })
+    }
+
+    fn ret(id: u8, val: u64) -> Receipt {
+        Receipt::Return(ReturnReceipt {
+            id: contract(id),
+            val: val.into(),
+            ..Default::default()
+        })
+    }
+
+    fn script_result(result: ScriptExecutionResult) -> Receipt {
+        Receipt::ScriptResult(ScriptResultReceipt {
+            result,
+            gas_used: 500.into(),
+        })
+    }
+
+    fn build(receipts: Vec<Receipt>) -> CallTrace {
+        let receipts = receipts
+            .into_iter()
+            .enumerate()
+            .map(|(index, receipt)| (index as u32, receipt));
+        CallTrace::from_receipts(TxId::default(), 1.into(), receipts)
+    }
+
+    #[test]
+    fn test_nested_calls() {
+        let trace = build(vec![
+            call(0, 1, 10),
+            call(1, 2, 20),
+            ret(2, 2),
+            call(1, 3, 30),
+            ret(3, 3),
+            ret(1, 1),
+            call(0, 4, 40),
+            Receipt::ReturnData(ReturnDataReceipt {
+                id: contract(4),
+                data: Some(vec![1, 2].into()),
+                ..Default::default()
+            }),
+            ret(0, 0),
+            script_result(ScriptExecutionResult::Success),
+        ]);
+
+        assert_eq!(trace.result, Some(CallResult::Return { val: 0.into() }));
+        assert_eq!(trace.script_result, Some(ScriptExecutionResult::Success));
+        assert_eq!(trace.gas_used, Some(500.into()));
+        assert_eq!(trace.failing_frame, None);
+        assert_eq!(trace.calls.len(), 2);
+
+        let first = &trace.calls[0];
+        assert_eq!(first.receipt_index, 0);
+        assert_eq!(first.from, contract(0));
+        assert_eq!(first.to, contract(1));
+        assert_eq!(first.selector, 10.into());
+        assert_eq!(first.result, CallResult::Return { val: 1.into() });
+        let nested = first
+            .calls
+            .iter()
+            .map(|frame| (frame.receipt_index, frame.to.to_owned()))
+            .collect::<Vec<_>>();
+        assert_eq!(nested, vec![(1, contract(2)), (3, contract(3))]);
+
+        let second = &trace.calls[1];
+        assert_eq!(second.receipt_index, 6);
+        assert_eq!(second.result, CallResult::ReturnData {
+            data: Some(vec![1, 2].into())
+        });
+        assert!(second.calls.is_empty());
+    }
+
+    #[test]
+    fn test_failing_frame() {
+        let reason = PanicInstruction {
+            reason: PanicReason::NotEnoughBalance,
+            instruction: RawInstruction::default(),
+        };
+        let trace = build(vec![
+            call(0, 1, 10),
+            call(1, 2, 20),
+            ret(2, 2),
+            call(1, 3, 30),
+            Receipt::Panic(PanicReceipt {
+                id: contract(3),
+                reason: reason.to_owned(),
+                ..Default::default()
+            }),
+            script_result(ScriptExecutionResult::Panic),
+        ]);
+
+        assert_eq!(trace.result, None);
+        assert_eq!(trace.script_result, Some(ScriptExecutionResult::Panic));
+        assert_eq!(trace.failing_frame, Some(3));
+        assert_eq!(trace.calls.len(), 1);
+
+        let outer = &trace.calls[0];
+        assert_eq!(outer.result, CallResult::Aborted);
+        assert_eq!(outer.calls.len(), 2);
+        assert_eq!(outer.calls[0].result, CallResult::Return { val: 2.into() });
+        assert_eq!(outer.calls[1].receipt_index, 3);
+        assert_eq!(outer.calls[1].result, CallResult::Panic { reason });
+    }
+}
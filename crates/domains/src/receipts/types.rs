@@ -20,6 +20,45 @@ pub enum Receipt {
 }
 
 impl Receipt {
+    /// Explains why the transaction failed, when this is the panic or
+    /// revert receipt that ended it
+    pub fn failure_reason(&self) -> Option<String> {
+        let (cause, id) = match self {
+            Receipt::Panic(receipt) => {
+                let name = receipt
+                    .reason_name
+                    .to_owned()
+                    .unwrap_or_else(|| receipt.reason.reason_name());
+                let cause = match receipt
+                    .instruction
+                    .to_owned()
+                    .or_else(|| receipt.reason.decoded_instruction())
+                {
+                    Some(instruction) => {
+                        format!("Panicked with {name} at {instruction}")
+                    }
+                    None => format!("Panicked with {name}"),
+                };
+                (cause, &receipt.id)
+            }
+            Receipt::Revert(receipt) => {
+                let cause = match RevertSignal::from_code(*receipt.ra) {
+                    RevertSignal::Custom => {
+                        format!("Reverted with code {}", *receipt.ra)
+                    }
+                    signal => format!("Reverted with {signal}"),
+                };
+                (cause, &receipt.id)
+            }
+            _ => return None,
+        };
+        if *id == ContractId::zeroed() {
+            Some(format!("{cause} in the script"))
+        } else {
+            Some(format!("{cause} in contract {id}"))
+        }
+    }
+
     #[cfg(any(test, feature = "test-helpers"))]
     pub fn as_call(&self) -> CallReceipt {
         match self {
@@ -81,6 +120,12 @@ pub struct PanicReceipt {
     pub pc: Word,
     pub is: Word,
     pub contract_id: Option<ContractId>,
+    /// Name of the panic reason, e.g. `NotEnoughBalance`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason_name: Option<String>,
+    /// The instruction that panicked, decoded from `reason.instruction`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instruction: Option<String>,
 }
 
 #[derive(
@@ -92,6 +137,96 @@ pub struct RevertReceipt {
     pub ra: Word,
     pub pc: Word,
     pub is: Word,
+    /// The Sway standard library signal the contract reverted with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signal: Option<RevertSignal>,
+}
+
+/// Revert codes of the Sway standard library, raised by `require`,
+/// `assert` and the like. Any other code is a `Custom` one, reverted with
+/// by the contract itself.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum RevertSignal {
+    FailedRequire,
+    FailedTransferToAddress,
+    FailedSendMessage,
+    FailedAssertEq,
+    FailedAssert,
+    FailedAssertNe,
+    RevertWithLog,
+    Custom,
+}
+
+impl RevertSignal {
+    const FAILED_REQUIRE: u64 = 0xffff_ffff_ffff_0000;
+    const FAILED_TRANSFER_TO_ADDRESS: u64 = 0xffff_ffff_ffff_0001;
+    const FAILED_SEND_MESSAGE: u64 = 0xffff_ffff_ffff_0002;
+    const FAILED_ASSERT_EQ: u64 = 0xffff_ffff_ffff_0003;
+    const FAILED_ASSERT: u64 = 0xffff_ffff_ffff_0004;
+    const FAILED_ASSERT_NE: u64 = 0xffff_ffff_ffff_0005;
+    const REVERT_WITH_LOG: u64 = 0xffff_ffff_ffff_0006;
+
+    pub fn from_code(code: u64) -> Self {
+        match code {
+            Self::FAILED_REQUIRE => Self::FailedRequire,
+            Self::FAILED_TRANSFER_TO_ADDRESS => Self::FailedTransferToAddress,
+            Self::FAILED_SEND_MESSAGE => Self::FailedSendMessage,
+            Self::FAILED_ASSERT_EQ => Self::FailedAssertEq,
+            Self::FAILED_ASSERT => Self::FailedAssert,
+            Self::FAILED_ASSERT_NE => Self::FailedAssertNe,
+            Self::REVERT_WITH_LOG => Self::RevertWithLog,
+            _ => Self::Custom,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::FailedRequire => "failed_require",
+            Self::FailedTransferToAddress => "failed_transfer_to_address",
+            Self::FailedSendMessage => "failed_send_message",
+            Self::FailedAssertEq => "failed_assert_eq",
+            Self::FailedAssert => "failed_assert",
+            Self::FailedAssertNe => "failed_assert_ne",
+            Self::RevertWithLog => "revert_with_log",
+            Self::Custom => "custom",
+        }
+    }
+}
+
+impl std::fmt::Display for RevertSignal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for RevertSignal {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Self::FailedRequire,
+            Self::FailedTransferToAddress,
+            Self::FailedSendMessage,
+            Self::FailedAssertEq,
+            Self::FailedAssert,
+            Self::FailedAssertNe,
+            Self::RevertWithLog,
+            Self::Custom,
+        ]
+        .into_iter()
+        .find(|signal| signal.as_str() == s)
+        .ok_or_else(|| format!("Invalid revert signal: {s}"))
+    }
 }
 
 #[derive(
@@ -122,6 +257,24 @@ pub struct LogDataReceipt {
     pub pc: Word,
     pub is: Word,
     pub data: Option<HexData>,
+    /// The logged value, decoded with the ABI registered for the contract
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decoded: Option<DecodedLog>,
+}
+
+/// Sway value logged by a contract, decoded with its JSON ABI
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedLog {
+    /// The log ID the value was logged under, from the `rb` register
+    pub log_id: String,
+    /// The Sway type of the value, e.g. `struct MyEvent`
+    #[serde(rename = "type")]
+    pub type_name: String,
+    #[schema(value_type = Object)]
+    pub value: serde_json::Value,
 }
 
 #[derive(
@@ -252,19 +405,25 @@ impl From<FuelCoreReceipt> for Receipt {
                 pc,
                 is,
                 contract_id,
-            } => Self::Panic(PanicReceipt {
-                id: id.into(),
-                reason: reason.into(),
-                pc: pc.into(),
-                is: is.into(),
-                contract_id: contract_id.map(|id| id.into()),
-            }),
+            } => {
+                let reason = PanicInstruction::from(reason);
+                Self::Panic(PanicReceipt {
+                    id: id.into(),
+                    reason_name: Some(reason.reason_name()),
+                    instruction: reason.decoded_instruction(),
+                    reason,
+                    pc: pc.into(),
+                    is: is.into(),
+                    contract_id: contract_id.map(|id| id.into()),
+                })
+            }
             FuelCoreReceipt::Revert { id, ra, pc, is } => {
                 Self::Revert(RevertReceipt {
                     id: id.into(),
                     ra: ra.into(),
                     pc: pc.into(),
                     is: is.into(),
+                    signal: Some(RevertSignal::from_code(ra)),
                 })
             }
             FuelCoreReceipt::Log {
@@ -304,6 +463,7 @@ impl From<FuelCoreReceipt> for Receipt {
                 pc: pc.into(),
                 is: is.into(),
                 data: data.map(|data| data.into()),
+                decoded: None,
             }),
             FuelCoreReceipt::Transfer {
                 id,
@@ -520,6 +680,8 @@ impl MockReceipt {
             pc: 0.into(),
             is: 0.into(),
             contract_id: None,
+            reason_name: Some(PanicInstruction::default().reason_name()),
+            instruction: None,
         })
     }
 
@@ -529,6 +691,7 @@ impl MockReceipt {
             ra: 0.into(),
             pc: 0.into(),
             is: 0.into(),
+            signal: Some(RevertSignal::Custom),
         })
     }
 
@@ -555,6 +718,7 @@ impl MockReceipt {
             pc: 0.into(),
             is: 0.into(),
             data: Some(vec![4, 5, 6].into()),
+            decoded: None,
         })
     }
 
@@ -637,3 +801,67 @@ impl MockReceipt {
         ]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use fuel_core_types::fuel_tx::PanicReason;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_revert_signal_from_code() {
+        assert_eq!(
+            RevertSignal::from_code(0xffff_ffff_ffff_0000),
+            RevertSignal::FailedRequire
+        );
+        assert_eq!(
+            RevertSignal::from_code(0xffff_ffff_ffff_0003),
+            RevertSignal::FailedAssertEq
+        );
+        assert_eq!(RevertSignal::from_code(42), RevertSignal::Custom);
+        assert_eq!(
+            "failed_assert_ne".parse::<RevertSignal>(),
+            Ok(RevertSignal::FailedAssertNe)
+        );
+        assert!("require".parse::<RevertSignal>().is_err());
+    }
+
+    #[test]
+    fn test_failure_reason() {
+        let contract_id = ContractId::from([1u8; 32]);
+        let revert = Receipt::Revert(RevertReceipt {
+            id: contract_id.to_owned(),
+            ra: 0xffff_ffff_ffff_0000.into(),
+            ..Default::default()
+        });
+        assert_eq!(
+            revert.failure_reason(),
+            Some(format!(
+                "Reverted with failed_require in contract {contract_id}"
+            ))
+        );
+
+        let revert = Receipt::Revert(RevertReceipt {
+            ra: 42.into(),
+            ..Default::default()
+        });
+        assert_eq!(
+            revert.failure_reason(),
+            Some("Reverted with code 42 in the script".to_string())
+        );
+
+        let panic = Receipt::Panic(PanicReceipt {
+            reason: PanicInstruction {
+                reason: PanicReason::NotEnoughBalance,
+                instruction: 0,
+            },
+            ..Default::default()
+        });
+        assert_eq!(
+            panic.failure_reason(),
+            Some("Panicked with NotEnoughBalance in the script".to_string())
+        );
+        assert_eq!(MockReceipt::log().failure_reason(), None);
+    }
+}
@@ -7,8 +7,14 @@ use pedronauck_streams_subject::subject::{
 };
 
 use crate::{
+    accounts::*,
+    balances::*,
     blocks::*,
+    bundles::*,
+    coins::*,
+    contracts::*,
     inputs::*,
+    messages::*,
     outputs::*,
     receipts::*,
     transactions::*,
@@ -52,6 +58,13 @@ pub enum Subjects {
     ReceiptsBurn(ReceiptsBurnSubject),
     Transactions(TransactionsSubject),
     Utxos(UtxosSubject),
+    Bundles(BundlesSubject),
+    Accounts(AccountsSubject),
+    Contracts(ContractsSubject),
+    Coins(CoinsSubject),
+    Balances(BalancesSubject),
+    Messages(MessagesSubject),
+    ContractsCreated(ContractsCreatedSubject),
 }
 
 impl From<Subjects> for Arc<dyn IntoSubject> {
@@ -84,6 +97,13 @@ impl From<Subjects> for Arc<dyn IntoSubject> {
             Subjects::ReceiptsBurn(s) => s.dyn_arc(),
             Subjects::Transactions(s) => s.dyn_arc(),
             Subjects::Utxos(s) => s.dyn_arc(),
+            Subjects::Bundles(s) => s.dyn_arc(),
+            Subjects::Accounts(s) => s.dyn_arc(),
+            Subjects::Contracts(s) => s.dyn_arc(),
+            Subjects::Coins(s) => s.dyn_arc(),
+            Subjects::Balances(s) => s.dyn_arc(),
+            Subjects::Messages(s) => s.dyn_arc(),
+            Subjects::ContractsCreated(s) => s.dyn_arc(),
         }
     }
 }
@@ -139,6 +159,19 @@ impl_try_from_subjects!(
     (TransactionsSubject, Transactions),
     // Utxo subjects
     (UtxosSubject, Utxos),
+    // Bundle subjects
+    (BundlesSubject, Bundles),
+    // Account subjects
+    (AccountsSubject, Accounts),
+    // Contract subjects
+    (ContractsSubject, Contracts),
+    (ContractsCreatedSubject, ContractsCreated),
+    // Coin subjects
+    (CoinsSubject, Coins),
+    // Balance subjects
+    (BalancesSubject, Balances),
+    // Message subjects
+    (MessagesSubject, Messages),
 );
 
 #[allow(clippy::disallowed_macros)]
@@ -214,6 +247,13 @@ mod tests {
     #[test_case("receipts_burn" => Ok(RecordEntity::Receipt); "receipts_burn subject")]
     #[test_case("transactions" => Ok(RecordEntity::Transaction); "transactions subject")]
     #[test_case("utxos" => Ok(RecordEntity::Utxo); "utxos subject")]
+    #[test_case("bundles" => Ok(RecordEntity::Bundle); "bundles subject")]
+    #[test_case("accounts" => Ok(RecordEntity::Account); "accounts subject")]
+    #[test_case("contracts" => Ok(RecordEntity::ContractTransaction); "contracts subject")]
+    #[test_case("contracts_created" => Ok(RecordEntity::Contract); "contracts_created subject")]
+    #[test_case("coins" => Ok(RecordEntity::Coin); "coins subject")]
+    #[test_case("balances" => Ok(RecordEntity::BalanceChange); "balances subject")]
+    #[test_case("messages" => Ok(RecordEntity::Message); "messages subject")]
     // Case variations
     #[test_case("BLOCKS" => Ok(RecordEntity::Block); "uppercase subject")]
     #[test_case("Inputs_Coin" => Ok(RecordEntity::Input); "mixed case subject")]
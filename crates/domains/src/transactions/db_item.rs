@@ -116,6 +116,7 @@ impl From<TransactionDbItem> for RecordPointer {
             input_index: None,
             output_index: None,
             receipt_index: None,
+            fanout_index: None,
         }
     }
 }
@@ -1,10 +1,14 @@
 use pedronauck_streams_subject::subject::*;
 use pedronauck_streams_types::*;
-use sea_query::{Condition, Expr, Iden};
+use sea_query::{Condition, Expr, Iden, Query};
 use serde::{Deserialize, Serialize};
 
 use super::TransactionDbItem;
-use crate::queryable::{HasPagination, QueryPagination, Queryable};
+use crate::{
+    accounts::queryable::AccountTransactions,
+    contracts::queryable::ContractTransactions,
+    queryable::{HasPagination, QueryPagination, Queryable},
+};
 
 #[allow(dead_code)]
 #[derive(Iden)]
@@ -109,6 +113,36 @@ impl Queryable for TransactionsQuery {
             );
         }
 
+        if let Some(contract_id) = &self.contract_id {
+            condition = condition.add(
+                Expr::col(Transactions::TxId).in_subquery(
+                    Query::select()
+                        .column(ContractTransactions::TxId)
+                        .from(ContractTransactions::Table)
+                        .and_where(
+                            Expr::col(ContractTransactions::ContractId)
+                                .eq(contract_id.to_string()),
+                        )
+                        .to_owned(),
+                ),
+            );
+        }
+
+        if let Some(address) = &self.address {
+            condition = condition.add(
+                Expr::col(Transactions::TxId).in_subquery(
+                    Query::select()
+                        .column(AccountTransactions::TxId)
+                        .from(AccountTransactions::Table)
+                        .and_where(
+                            Expr::col(AccountTransactions::Address)
+                                .eq(address.to_string()),
+                        )
+                        .to_owned(),
+                ),
+            );
+        }
+
         condition
     }
 }
@@ -143,6 +177,10 @@ mod test {
     const TEST_TX_INDEX: u32 = 3;
     const TEST_TX_ID: &str =
         "0x0101010101010101010101010101010101010101010101010101010101010101";
+    const TEST_CONTRACT_ID: &str =
+        "0x0202020202020202020202020202020202020202020202020202020202020202";
+    const TEST_ADDRESS: &str =
+        "0x0303030303030303030303030303030303030303030303030303030303030303";
 
     #[test]
     fn test_sql_with_fixed_conds() {
@@ -241,6 +279,37 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_sql_with_contract_id() {
+        let mut query = TransactionsQuery {
+            pagination: (None, None, Some(FIRST_POINTER), None).into(),
+            ..Default::default()
+        };
+        query.set_contract_id(TEST_CONTRACT_ID);
+
+        assert_eq!(
+            query.query_to_string(),
+            format!("SELECT * FROM \"transactions\" WHERE \"tx_id\" IN (SELECT \"tx_id\" FROM \"contract_transactions\" WHERE \"contract_id\" = '{}') ORDER BY \"block_height\" ASC LIMIT {}",
+                TEST_CONTRACT_ID, FIRST_POINTER)
+        );
+    }
+
+    #[test]
+    fn test_sql_with_address() {
+        let mut query = TransactionsQuery {
+            pagination: (Some(AFTER_POINTER), None, None, Some(LAST_POINTER))
+                .into(),
+            ..Default::default()
+        };
+        query.set_address(TEST_ADDRESS);
+
+        assert_eq!(
+            query.query_to_string(),
+            format!("SELECT * FROM \"transactions\" WHERE \"tx_id\" IN (SELECT \"tx_id\" FROM \"account_transactions\" WHERE \"address\" = '{}') AND \"block_height\" > {} ORDER BY \"block_height\" DESC LIMIT {}",
+                TEST_ADDRESS, AFTER_POINTER, LAST_POINTER)
+        );
+    }
+
     #[test]
     fn test_transactions_query_from_query_string() {
         use serde_urlencoded;
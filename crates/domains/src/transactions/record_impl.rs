@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use pedronauck_streams_store::{
     db::{DbError, DbResult},
-    record::{DataEncoder, Record, RecordEntity},
+    record::{DataEncoder, FilterColumn, Record, RecordEntity},
 };
 use pedronauck_streams_types::BlockTimestamp;
 use sqlx::PgExecutor;
@@ -18,6 +18,10 @@ impl Record for Transaction {
 
     const ENTITY: RecordEntity = RecordEntity::Transaction;
     const ORDER_PROPS: &'static [&'static str] = &["tx_index"];
+    const FILTER_COLUMNS: &'static [FilterColumn] = &[
+        FilterColumn::text("id", "tx_id"),
+        FilterColumn::text("status", "tx_status"),
+    ];
 
     async fn insert<'e, 'c: 'e, E>(
         executor: E,
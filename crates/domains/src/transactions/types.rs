@@ -158,6 +158,9 @@ pub struct Transaction {
     pub script_data: Option<HexData>,
     pub script_gas_limit: Option<GasAmount>,
     pub status: TransactionStatus,
+    /// Why the transaction failed, from the panic or revert that ended it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<String>,
     pub storage_slots: Vec<StorageSlot>,
     pub subsection_index: Option<u16>,
     pub subsections_number: Option<u16>,
@@ -471,6 +474,15 @@ impl Transaction {
             }
         };
 
+        let receipts: Vec<Receipt> =
+            receipts.iter().map(|r| r.to_owned().into()).collect();
+        let failure_reason = match status {
+            TransactionStatus::Failed => {
+                receipts.iter().find_map(Receipt::failure_reason)
+            }
+            _ => None,
+        };
+
         Transaction {
             id: id.to_owned().into(),
             tx_type: transaction.into(),
@@ -501,13 +513,14 @@ impl Transaction {
             script_data,
             script_gas_limit: script_gas_limit.map(|amount| amount.into()),
             status: status.to_owned(),
+            failure_reason,
             storage_slots,
             subsection_index,
             subsections_number,
             tx_pointer: Some(tx_pointer.into()),
             upgrade_purpose: upgrade_purpose.map(FuelCoreUpgradePurposeWrapper),
             witnesses,
-            receipts: receipts.iter().map(|r| r.to_owned().into()).collect(),
+            receipts,
         }
     }
 }
@@ -580,6 +593,7 @@ impl MockTransaction {
             script_data: None,
             script_gas_limit: None,
             status: TransactionStatus::Success,
+            failure_reason: None,
             storage_slots: vec![],
             subsection_index: None,
             subsections_number: None,
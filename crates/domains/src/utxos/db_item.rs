@@ -121,6 +121,7 @@ impl From<UtxoDbItem> for RecordPointer {
             input_index: Some(val.input_index as u32),
             output_index: None,
             receipt_index: None,
+            fanout_index: None,
         }
     }
 }
@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use pedronauck_streams_store::{
     db::{DbError, DbResult},
-    record::{DataEncoder, Record, RecordEntity},
+    record::{DataEncoder, FilterColumn, Record, RecordEntity},
 };
 use pedronauck_streams_types::BlockTimestamp;
 use sqlx::PgExecutor;
@@ -18,6 +18,10 @@ impl Record for Utxo {
 
     const ENTITY: RecordEntity = RecordEntity::Utxo;
     const ORDER_PROPS: &'static [&'static str] = &["tx_index", "input_index"];
+    const FILTER_COLUMNS: &'static [FilterColumn] = &[
+        FilterColumn::text("txId", "tx_id"),
+        FilterColumn::text("contractId", "contract_id"),
+    ];
 
     async fn insert<'e, 'c: 'e, E>(
         executor: E,
@@ -1,9 +1,11 @@
+mod memory;
 mod msg_broker;
 mod nats;
 pub mod nats_metrics;
 mod nats_opts;
 mod nats_queue;
 
+pub use memory::*;
 pub use msg_broker::*;
 pub use nats::*;
 pub use nats_opts::*;
@@ -0,0 +1,601 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use async_trait::async_trait;
+use tokio::{
+    sync::{broadcast, Notify},
+    time::Instant,
+};
+
+use crate::{
+    Message,
+    MessageBlockStream,
+    MessageBroker,
+    MessageBrokerError,
+    MessageStream,
+    Namespace,
+    NatsQueue,
+    NatsSubject,
+    QueuedMessage,
+};
+
+const CHANNEL_CAPACITY: usize = 1024;
+const DEFAULT_ACK_WAIT: Duration = Duration::from_secs(30);
+/// How long a queue subscription waits for messages before returning an
+/// empty batch, mirroring a JetStream fetch expiry
+const FETCH_WAIT: Duration = Duration::from_secs(1);
+
+#[derive(Debug)]
+struct QueueEntry {
+    subject: String,
+    payload: bytes::Bytes,
+    published_at: i64,
+    deliveries: u64,
+    available_at: Instant,
+}
+
+impl QueueEntry {
+    fn to_queued(&self, sequence: u64) -> QueuedMessage {
+        QueuedMessage {
+            sequence,
+            subject: self.subject.clone(),
+            payload: self.payload.clone(),
+            published_at: self.published_at,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct QueueState {
+    last_sequence: u64,
+    entries: BTreeMap<u64, QueueEntry>,
+}
+
+type Queues = Arc<Mutex<HashMap<String, QueueState>>>;
+
+/// In-process broker backed by a tokio broadcast channel for pub/sub and
+/// in-memory work queues. Nothing survives a restart, so it is meant for
+/// tests and single-process deployments.
+#[derive(Debug, Clone)]
+pub struct MemoryMessageBroker {
+    namespace: Namespace,
+    sender: broadcast::Sender<(String, bytes::Bytes)>,
+    queues: Queues,
+    notify: Arc<Notify>,
+    ack_wait: Duration,
+    max_deliver: Option<i64>,
+}
+
+impl Default for MemoryMessageBroker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryMessageBroker {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            namespace: Namespace::None,
+            sender,
+            queues: Arc::new(Mutex::new(HashMap::new())),
+            notify: Arc::new(Notify::new()),
+            ack_wait: DEFAULT_ACK_WAIT,
+            max_deliver: None,
+        }
+    }
+
+    pub fn with_namespace(self, namespace: &str) -> Self {
+        let namespace = Namespace::Custom(namespace.to_string());
+        Self { namespace, ..self }
+    }
+
+    pub fn with_ack_wait(self, secs: u64) -> Self {
+        Self {
+            ack_wait: Duration::from_secs(secs),
+            ..self
+        }
+    }
+
+    pub fn with_max_deliver(self, max_deliver: i64) -> Self {
+        Self {
+            max_deliver: Some(max_deliver),
+            ..self
+        }
+    }
+
+    pub fn arc(&self) -> Arc<Self> {
+        Arc::new(self.clone())
+    }
+
+    fn lock_queues(
+        &self,
+    ) -> Result<
+        std::sync::MutexGuard<'_, HashMap<String, QueueState>>,
+        MessageBrokerError,
+    > {
+        self.queues
+            .lock()
+            .map_err(|e| MessageBrokerError::Other(e.to_string().into()))
+    }
+
+    /// Marks up to `batch_size` available messages as delivered
+    fn take_batch(
+        &self,
+        queue: &NatsQueue,
+        batch_size: usize,
+    ) -> Result<Vec<Box<dyn Message>>, MessageBrokerError> {
+        let max_deliver = self.max_deliver(queue) as u64;
+        let queue_name = queue.queue_name();
+        let consumer_subject = queue.consumer_subject();
+        let now = Instant::now();
+        let mut queues = self.lock_queues()?;
+        let state = queues.entry(queue_name.clone()).or_default();
+        let batch = state
+            .entries
+            .iter_mut()
+            .filter(|(_, entry)| {
+                subject_matches(&consumer_subject, &entry.subject)
+                    && entry.available_at <= now
+                    && entry.deliveries < max_deliver
+            })
+            .take(batch_size)
+            .map(|(sequence, entry)| {
+                entry.deliveries += 1;
+                entry.available_at = now + self.ack_wait;
+                Box::new(MemoryMessage {
+                    queues: self.queues.clone(),
+                    notify: self.notify.clone(),
+                    queue_name: queue_name.clone(),
+                    sequence: *sequence,
+                    subject: entry.subject.clone(),
+                    payload: entry.payload.clone(),
+                    delivery_count: entry.deliveries,
+                }) as Box<dyn Message>
+            })
+            .collect();
+        Ok(batch)
+    }
+}
+
+/// NATS-style subject matching, where `*` matches a single token and `>`
+/// matches every remaining token
+fn subject_matches(pattern: &str, subject: &str) -> bool {
+    let mut subject_tokens = subject.split('.');
+    for token in pattern.split('.') {
+        match (token, subject_tokens.next()) {
+            (">", Some(_)) => return true,
+            ("*", Some(_)) => {}
+            (token, Some(value)) if token == value => {}
+            _ => return false,
+        }
+    }
+    subject_tokens.next().is_none()
+}
+
+#[async_trait]
+impl MessageBroker for MemoryMessageBroker {
+    fn namespace(&self) -> &Namespace {
+        &self.namespace
+    }
+
+    fn is_connected(&self) -> bool {
+        true
+    }
+
+    async fn setup_queues(&self) -> Result<(), MessageBrokerError> {
+        let broker: Arc<dyn MessageBroker> = self.arc();
+        let mut queues = self.lock_queues()?;
+        for queue in [
+            NatsQueue::BlockImporter(broker.clone()),
+            NatsQueue::BlockRetrier(broker),
+        ] {
+            queues.entry(queue.queue_name()).or_default();
+        }
+        Ok(())
+    }
+
+    async fn publish(
+        &self,
+        topic: &str,
+        payload: bytes::Bytes,
+    ) -> Result<(), MessageBrokerError> {
+        let subject = self.namespace().subject_name(topic);
+        // Like core NATS, publishing without subscribers is not an error
+        let _ = self.sender.send((subject, payload));
+        Ok(())
+    }
+
+    async fn subscribe(
+        &self,
+        topic: &str,
+    ) -> Result<MessageStream, MessageBrokerError> {
+        let pattern = self.namespace().subject_name(topic);
+        let receiver = self.sender.subscribe();
+        let stream = futures::stream::unfold(
+            (receiver, pattern),
+            |(mut receiver, pattern)| async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok((subject, payload)) => {
+                            if subject_matches(&pattern, &subject) {
+                                return Some((Ok(payload), (receiver, pattern)));
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            let err = MessageBrokerError::Receiving(format!(
+                                "Subscriber lagged behind by {skipped} messages"
+                            ));
+                            return Some((Err(err), (receiver, pattern)));
+                        }
+                        Err(broadcast::error::RecvError::Closed) => return None,
+                    }
+                }
+            },
+        );
+        Ok(Box::new(Box::pin(stream)))
+    }
+
+    async fn flush(&self) -> Result<(), MessageBrokerError> {
+        Ok(())
+    }
+
+    async fn is_healthy(&self) -> bool {
+        true
+    }
+
+    async fn get_health_info(
+        &self,
+        uptime_secs: u64,
+    ) -> Result<serde_json::Value, MessageBrokerError> {
+        let queues = self
+            .lock_queues()?
+            .iter()
+            .map(|(name, state)| {
+                serde_json::json!({
+                    "queue_name": name,
+                    "messages": state.entries.len(),
+                    "last_sequence": state.last_sequence,
+                })
+            })
+            .collect::<Vec<_>>();
+        Ok(serde_json::json!({
+            "uptime_secs": uptime_secs,
+            "is_healthy": self.is_healthy().await,
+            "queues": queues,
+        }))
+    }
+
+    fn max_deliver(&self, queue: &NatsQueue) -> i64 {
+        match (queue, self.max_deliver) {
+            (NatsQueue::BlockImporter(_), Some(max_deliver)) => max_deliver,
+            _ => queue.default_max_deliver(),
+        }
+    }
+
+    async fn queue_publish(
+        &self,
+        queue: &NatsQueue,
+        subject: &NatsSubject,
+        payload: bytes::Bytes,
+    ) -> Result<(), MessageBrokerError> {
+        let subject = subject.to_string(queue);
+        {
+            let mut queues = self.lock_queues()?;
+            let state = queues.entry(queue.queue_name()).or_default();
+            // Stored subjects are deduplicated, like JetStream message ids
+            if state.entries.values().any(|entry| entry.subject == subject) {
+                return Ok(());
+            }
+            state.last_sequence += 1;
+            let published_at = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or_default();
+            state.entries.insert(state.last_sequence, QueueEntry {
+                subject,
+                payload,
+                published_at,
+                deliveries: 0,
+                available_at: Instant::now(),
+            });
+        }
+        self.notify.notify_waiters();
+        Ok(())
+    }
+
+    async fn queue_subscribe(
+        &self,
+        queue: &NatsQueue,
+        batch_size: usize,
+    ) -> Result<MessageBlockStream, MessageBrokerError> {
+        let deadline = Instant::now() + FETCH_WAIT;
+        let mut batch = self.take_batch(queue, batch_size)?;
+        while batch.is_empty() && Instant::now() < deadline {
+            let _ =
+                tokio::time::timeout_at(deadline, self.notify.notified()).await;
+            batch = self.take_batch(queue, batch_size)?;
+        }
+        Ok(Box::new(futures::stream::iter(batch.into_iter().map(Ok))))
+    }
+
+    async fn queue_messages(
+        &self,
+        queue: &NatsQueue,
+        filter: &str,
+        limit: usize,
+    ) -> Result<Vec<QueuedMessage>, MessageBrokerError> {
+        let queues = self.lock_queues()?;
+        let messages = queues
+            .get(&queue.queue_name())
+            .map(|state| {
+                state
+                    .entries
+                    .iter()
+                    .filter(|(_, entry)| {
+                        subject_matches(filter, &entry.subject)
+                    })
+                    .take(limit)
+                    .map(|(sequence, entry)| entry.to_queued(*sequence))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(messages)
+    }
+
+    async fn queue_find_message(
+        &self,
+        queue: &NatsQueue,
+        subject: &NatsSubject,
+    ) -> Result<Option<QueuedMessage>, MessageBrokerError> {
+        let subject = subject.to_string(queue);
+        let queues = self.lock_queues()?;
+        let message = queues.get(&queue.queue_name()).and_then(|state| {
+            state
+                .entries
+                .iter()
+                .rev()
+                .find(|(_, entry)| entry.subject == subject)
+                .map(|(sequence, entry)| entry.to_queued(*sequence))
+        });
+        Ok(message)
+    }
+
+    async fn queue_delete_message(
+        &self,
+        queue: &NatsQueue,
+        sequence: u64,
+    ) -> Result<bool, MessageBrokerError> {
+        let mut queues = self.lock_queues()?;
+        let deleted = queues
+            .get_mut(&queue.queue_name())
+            .and_then(|state| state.entries.remove(&sequence))
+            .is_some();
+        Ok(deleted)
+    }
+}
+
+#[derive(Debug)]
+pub struct MemoryMessage {
+    queues: Queues,
+    notify: Arc<Notify>,
+    queue_name: String,
+    sequence: u64,
+    subject: String,
+    payload: bytes::Bytes,
+    delivery_count: u64,
+}
+
+impl MemoryMessage {
+    fn update_entry(
+        &self,
+        f: impl FnOnce(&mut QueueState),
+    ) -> Result<(), MessageBrokerError> {
+        let mut queues = self
+            .queues
+            .lock()
+            .map_err(|e| MessageBrokerError::Acknowledgment(e.to_string()))?;
+        if let Some(state) = queues.get_mut(&self.queue_name) {
+            f(state);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Message for MemoryMessage {
+    fn payload(&self) -> Vec<u8> {
+        self.payload.to_vec()
+    }
+
+    fn id(&self) -> String {
+        self.subject.clone()
+    }
+
+    async fn ack(&self) -> Result<(), MessageBrokerError> {
+        self.update_entry(|state| {
+            state.entries.remove(&self.sequence);
+        })
+    }
+
+    async fn nak(
+        &self,
+        delay: Option<Duration>,
+    ) -> Result<(), MessageBrokerError> {
+        let available_at = Instant::now() + delay.unwrap_or_default();
+        self.update_entry(|state| {
+            if let Some(entry) = state.entries.get_mut(&self.sequence) {
+                entry.available_at = available_at;
+            }
+        })?;
+        self.notify.notify_waiters();
+        Ok(())
+    }
+
+    fn delivery_count(&self) -> u64 {
+        self.delivery_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn setup_broker() -> Arc<dyn MessageBroker> {
+        MemoryMessageBroker::new()
+            .with_namespace("test")
+            .with_ack_wait(1)
+            .arc()
+    }
+
+    async fn fetch(queue: &NatsQueue) -> Vec<Box<dyn Message>> {
+        let messages = queue.subscribe(10).await.unwrap();
+        messages.map(|msg| msg.unwrap()).collect().await
+    }
+
+    #[test]
+    fn test_subject_matches() {
+        assert!(subject_matches("a.b.c", "a.b.c"));
+        assert!(subject_matches("a.*.c", "a.b.c"));
+        assert!(subject_matches("a.>", "a.b.c"));
+        assert!(!subject_matches("a.>", "a"));
+        assert!(!subject_matches("a.*", "a.b.c"));
+        assert!(!subject_matches("a.b", "a.c"));
+    }
+
+    #[tokio::test]
+    async fn test_pub_sub_wildcard() -> Result<(), MessageBrokerError> {
+        let broker = setup_broker();
+        let mut stream = broker.subscribe("blocks.>").await?;
+        broker.publish("transactions.1", "skipped".into()).await?;
+        broker.publish("blocks.1.a", "first".into()).await?;
+        broker.publish("blocks.2.b", "second".into()).await?;
+
+        let first = stream.next().await.unwrap()?;
+        let second = stream.next().await.unwrap()?;
+        assert_eq!(first, bytes::Bytes::from("first"));
+        assert_eq!(second, bytes::Bytes::from("second"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_queue_ack_and_nak() -> Result<(), MessageBrokerError> {
+        let broker = setup_broker();
+        broker.setup_queues().await?;
+        let queue = NatsQueue::BlockImporter(broker.clone());
+        queue
+            .publish(&NatsSubject::BlockSubmitted(1), "one")
+            .await?;
+        queue
+            .publish(&NatsSubject::BlockSubmitted(2), "two")
+            .await?;
+
+        let messages = fetch(&queue).await;
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].delivery_count(), 1);
+        messages[0].ack().await?;
+        messages[1].nak(None).await?;
+
+        let messages = fetch(&queue).await;
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].payload(), b"two".to_vec());
+        assert_eq!(messages[0].delivery_count(), 2);
+        messages[0].ack().await?;
+
+        let filter = queue.consumer_subject();
+        assert!(queue.messages(&filter, 10).await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_queue_stops_at_max_deliver() -> Result<(), MessageBrokerError>
+    {
+        let broker: Arc<dyn MessageBroker> =
+            MemoryMessageBroker::new().with_max_deliver(2).arc();
+        let queue = NatsQueue::BlockImporter(broker.clone());
+        queue
+            .publish(&NatsSubject::BlockSubmitted(1), "one")
+            .await?;
+
+        for _ in 0..2 {
+            let messages = fetch(&queue).await;
+            assert_eq!(messages.len(), 1);
+            messages[0].nak(None).await?;
+        }
+
+        assert!(fetch(&queue).await.is_empty());
+        assert_eq!(
+            queue.messages(&queue.consumer_subject(), 10).await?.len(),
+            1
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_queue_stored_messages() -> Result<(), MessageBrokerError> {
+        let broker = setup_broker();
+        let queue = NatsQueue::BlockRetrier(broker.clone());
+        for height in 1..=3 {
+            queue
+                .publish(&NatsSubject::BlockFailed(height), "failed")
+                .await?;
+        }
+        // Duplicated subjects are not stored twice
+        queue
+            .publish(&NatsSubject::BlockFailed(2), "failed")
+            .await?;
+
+        let filter = queue.consumer_subject();
+        let messages = queue.messages(&filter, 10).await?;
+        let heights = messages
+            .iter()
+            .map(|msg| msg.block_height())
+            .collect::<Vec<_>>();
+        assert_eq!(heights, vec![Some(1), Some(2), Some(3)]);
+
+        let found = queue
+            .find_message(&NatsSubject::BlockFailed(2))
+            .await?
+            .expect("message should be stored");
+        assert!(queue.delete_message(found.sequence).await?);
+        assert!(queue
+            .find_message(&NatsSubject::BlockFailed(2))
+            .await?
+            .is_none());
+        assert_eq!(queue.messages(&filter, 1).await?.len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_queue_dead_letters() -> Result<(), MessageBrokerError> {
+        let broker = setup_broker();
+        let queue = NatsQueue::BlockRetrier(broker.clone());
+        queue
+            .publish(&NatsSubject::BlockFailed(1), "failed")
+            .await?;
+        queue.publish(&NatsSubject::BlockDead(2), "dead").await?;
+
+        // Dead letters are stored but never delivered to the consumer
+        let messages = fetch(&queue).await;
+        assert_eq!(messages.len(), 1);
+        assert_eq!(
+            messages[0].id(),
+            NatsSubject::BlockFailed(1).to_string(&queue)
+        );
+
+        let dead_letters =
+            queue.messages(&queue.dead_letters_subject(), 10).await?;
+        let heights = dead_letters
+            .iter()
+            .map(|msg| msg.block_height())
+            .collect::<Vec<_>>();
+        assert_eq!(heights, vec![Some(2)]);
+        Ok(())
+    }
+}
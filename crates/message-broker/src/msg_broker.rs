@@ -1,8 +1,10 @@
-use std::fmt;
+use std::{fmt, time::Duration};
 
 use async_trait::async_trait;
 use futures::Stream;
 
+use crate::{NatsQueue, NatsSubject, QueuedMessage};
+
 /// Represents a namespace for message broker subjects/topics
 #[derive(Debug, Clone, Default)]
 pub enum Namespace {
@@ -68,9 +70,73 @@ pub enum MessageBrokerError {
 pub trait Message: std::fmt::Debug + Send + Sync {
     fn payload(&self) -> Vec<u8>;
     async fn ack(&self) -> Result<(), MessageBrokerError>;
+    /// Asks the broker to redeliver the message, optionally after a delay
+    async fn nak(
+        &self,
+        delay: Option<Duration>,
+    ) -> Result<(), MessageBrokerError>;
+    /// How many times this message has been delivered, starting at 1
+    fn delivery_count(&self) -> u64;
     fn id(&self) -> String;
 }
 
+/// Message broker backend: plain pub/sub used for live streaming, plus the
+/// work queues that move blocks between services.
+#[async_trait]
+pub trait MessageBroker: std::fmt::Debug + Send + Sync + 'static {
+    fn namespace(&self) -> &Namespace;
+    fn is_connected(&self) -> bool;
+    /// Creates whatever the work queues need before they can be used
+    async fn setup_queues(&self) -> Result<(), MessageBrokerError>;
+    async fn publish(
+        &self,
+        topic: &str,
+        payload: bytes::Bytes,
+    ) -> Result<(), MessageBrokerError>;
+    async fn subscribe(
+        &self,
+        topic: &str,
+    ) -> Result<MessageStream, MessageBrokerError>;
+    async fn flush(&self) -> Result<(), MessageBrokerError>;
+    async fn is_healthy(&self) -> bool;
+    async fn get_health_info(
+        &self,
+        uptime_secs: u64,
+    ) -> Result<serde_json::Value, MessageBrokerError>;
+
+    /// Deliveries of a queue message before it stops being redelivered
+    fn max_deliver(&self, queue: &NatsQueue) -> i64 {
+        queue.default_max_deliver()
+    }
+    async fn queue_publish(
+        &self,
+        queue: &NatsQueue,
+        subject: &NatsSubject,
+        payload: bytes::Bytes,
+    ) -> Result<(), MessageBrokerError>;
+    async fn queue_subscribe(
+        &self,
+        queue: &NatsQueue,
+        batch_size: usize,
+    ) -> Result<MessageBlockStream, MessageBrokerError>;
+    async fn queue_messages(
+        &self,
+        queue: &NatsQueue,
+        filter: &str,
+        limit: usize,
+    ) -> Result<Vec<QueuedMessage>, MessageBrokerError>;
+    async fn queue_find_message(
+        &self,
+        queue: &NatsQueue,
+        subject: &NatsSubject,
+    ) -> Result<Option<QueuedMessage>, MessageBrokerError>;
+    async fn queue_delete_message(
+        &self,
+        queue: &NatsQueue,
+        sequence: u64,
+    ) -> Result<bool, MessageBrokerError>;
+}
+
 pub type MessageBlockStream = Box<
     dyn Stream<Item = Result<Box<dyn Message>, MessageBrokerError>>
         + Send
@@ -1,6 +1,25 @@
-use std::sync::Arc;
-
-use async_nats::{jetstream::Context, Client};
+use std::{sync::Arc, time::Duration};
+
+use async_nats::{
+    jetstream::{
+        consumer::{
+            self,
+            pull::Config as ConsumerConfig,
+            AckPolicy,
+            PullConsumer,
+        },
+        message::StreamMessage,
+        stream::{
+            Config as StreamConfig,
+            DirectGetErrorKind,
+            RetentionPolicy,
+            Stream as JetStream,
+        },
+        AckKind,
+        Context,
+    },
+    Client,
+};
 use async_trait::async_trait;
 use futures::StreamExt;
 use tracing::info;
@@ -8,13 +27,21 @@ use tracing::info;
 use crate::{
     nats_metrics::{NatsHealthInfo, StreamInfo},
     Message,
+    MessageBlockStream,
+    MessageBroker,
     MessageBrokerError,
     MessageStream,
     Namespace,
     NatsOpts,
     NatsQueue,
+    NatsSubject,
+    QueuedMessage,
 };
 
+/// Ack wait of the queue consumers when none is set, the same as the
+/// JetStream default
+const DEFAULT_ACK_WAIT: Duration = Duration::from_secs(30);
+
 #[derive(Debug)]
 pub struct NatsMessage(pub async_nats::jetstream::Message);
 
@@ -71,14 +98,6 @@ impl NatsMessageBroker {
         Ok(broker.arc())
     }
 
-    async fn setup_queues(&self) -> Result<(), MessageBrokerError> {
-        let block_importer = NatsQueue::BlockImporter(self.arc());
-        let block_retrier = NatsQueue::BlockRetrier(self.arc());
-        block_importer.setup().await?;
-        block_retrier.setup().await?;
-        Ok(())
-    }
-
     pub fn client(&self) -> Arc<Client> {
         Arc::new(self.client.to_owned())
     }
@@ -87,16 +106,136 @@ impl NatsMessageBroker {
         Arc::new(self.clone())
     }
 
-    pub fn namespace(&self) -> &Namespace {
+    pub async fn get_streams_info(
+        &self,
+    ) -> Result<Vec<StreamInfo>, MessageBrokerError> {
+        let mut streams = self.jetstream.streams();
+        let mut infos = vec![];
+        while let Some(stream) = streams.next().await {
+            let stream =
+                stream.map_err(|e| MessageBrokerError::Setup(e.to_string()))?;
+            infos.push(StreamInfo {
+                stream_name: stream.config.name,
+                state: stream.state.into(),
+            });
+        }
+        Ok(infos)
+    }
+
+    pub async fn get_or_create_stream(
+        &self,
+        queue: &NatsQueue,
+    ) -> Result<JetStream, MessageBrokerError> {
+        let config = StreamConfig {
+            name: queue.queue_name(),
+            subjects: queue.subjects(),
+            retention: RetentionPolicy::WorkQueue,
+            duplicate_window: Duration::from_secs(1),
+            allow_direct: true,
+            ..Default::default()
+        };
+        let stream = self
+            .jetstream
+            .get_or_create_stream(config.clone())
+            .await
+            .map_err(|e| MessageBrokerError::Setup(e.to_string()))?;
+        if stream.cached_info().config.subjects == config.subjects {
+            return Ok(stream);
+        }
+
+        // Streams created before the queue gained a subject are updated in
+        // place, keeping their messages
+        self.jetstream
+            .update_stream(&config)
+            .await
+            .map_err(|e| MessageBrokerError::Setup(e.to_string()))?;
+        self.jetstream
+            .get_stream(&config.name)
+            .await
+            .map_err(|e| MessageBrokerError::Setup(e.to_string()))
+    }
+
+    pub async fn get_or_create_consumer(
+        &self,
+        queue: &NatsQueue,
+    ) -> Result<PullConsumer, MessageBrokerError> {
+        let stream = self.get_or_create_stream(queue).await?;
+        let consumer_name = queue.consumer_name();
+        let ack_wait = self.opts.ack_wait_secs.map(Duration::from_secs);
+        let config = ConsumerConfig {
+            durable_name: Some(consumer_name.to_string()),
+            ack_policy: AckPolicy::Explicit,
+            max_deliver: self.max_deliver(queue),
+            filter_subject: queue.consumer_subject(),
+            ack_wait: ack_wait.unwrap_or(DEFAULT_ACK_WAIT),
+            ..Default::default()
+        };
+
+        let consumer = stream
+            .get_or_create_consumer(&consumer_name, config.clone())
+            .await
+            .map_err(|e| MessageBrokerError::Setup(e.to_string()))?;
+        if consumer_config_matches(&consumer.cached_info().config, &config) {
+            return Ok(consumer);
+        }
+
+        // Consumers created with other settings are updated in place, e.g.
+        // ones created without a subject filter would also pull the dead
+        // letters stored in the same stream
+        stream
+            .create_consumer(config)
+            .await
+            .map_err(|e| MessageBrokerError::Setup(e.to_string()))
+    }
+}
+
+/// Whether an existing consumer has the settings the queue asks for. Only
+/// the settings we set are compared, the rest is filled in by the server.
+fn consumer_config_matches(
+    current: &consumer::Config,
+    expected: &ConsumerConfig,
+) -> bool {
+    current.durable_name == expected.durable_name
+        && current.ack_policy == expected.ack_policy
+        && current.max_deliver == expected.max_deliver
+        && current.filter_subject == expected.filter_subject
+        && current.ack_wait == expected.ack_wait
+}
+
+impl From<StreamMessage> for QueuedMessage {
+    fn from(msg: StreamMessage) -> Self {
+        Self {
+            sequence: msg.sequence,
+            subject: msg.subject.to_string(),
+            payload: msg.payload,
+            published_at: msg.time.unix_timestamp(),
+        }
+    }
+}
+
+#[async_trait]
+impl MessageBroker for NatsMessageBroker {
+    fn namespace(&self) -> &Namespace {
         &self.namespace
     }
 
-    pub fn is_connected(&self) -> bool {
+    fn is_connected(&self) -> bool {
         let state = self.client.connection_state();
         state == async_nats::connection::State::Connected
     }
 
-    pub async fn publish(
+    async fn setup_queues(&self) -> Result<(), MessageBrokerError> {
+        let broker: Arc<dyn MessageBroker> = self.arc();
+        for queue in [
+            NatsQueue::BlockImporter(broker.clone()),
+            NatsQueue::BlockRetrier(broker),
+        ] {
+            self.get_or_create_consumer(&queue).await?;
+        }
+        Ok(())
+    }
+
+    async fn publish(
         &self,
         topic: &str,
         payload: bytes::Bytes,
@@ -109,7 +248,7 @@ impl NatsMessageBroker {
         Ok(())
     }
 
-    pub async fn subscribe(
+    async fn subscribe(
         &self,
         topic: &str,
     ) -> Result<MessageStream, MessageBrokerError> {
@@ -123,23 +262,7 @@ impl NatsMessageBroker {
         Ok(Box::new(stream))
     }
 
-    pub async fn get_streams_info(
-        &self,
-    ) -> Result<Vec<StreamInfo>, MessageBrokerError> {
-        let mut streams = self.jetstream.streams();
-        let mut infos = vec![];
-        while let Some(stream) = streams.next().await {
-            let stream =
-                stream.map_err(|e| MessageBrokerError::Setup(e.to_string()))?;
-            infos.push(StreamInfo {
-                stream_name: stream.config.name,
-                state: stream.state.into(),
-            });
-        }
-        Ok(infos)
-    }
-
-    pub async fn flush(&self) -> Result<(), MessageBrokerError> {
+    async fn flush(&self) -> Result<(), MessageBrokerError> {
         self.client.flush().await.map_err(|e| {
             MessageBrokerError::Flush(format!(
                 "Failed to flush NATS client: {}",
@@ -149,11 +272,11 @@ impl NatsMessageBroker {
         Ok(())
     }
 
-    pub async fn is_healthy(&self) -> bool {
+    async fn is_healthy(&self) -> bool {
         self.is_connected()
     }
 
-    pub async fn get_health_info(
+    async fn get_health_info(
         &self,
         uptime_secs: u64,
     ) -> Result<serde_json::Value, MessageBrokerError> {
@@ -165,6 +288,115 @@ impl NatsMessageBroker {
         };
         Ok(serde_json::to_value(health_info)?)
     }
+
+    fn max_deliver(&self, queue: &NatsQueue) -> i64 {
+        match (queue, self.opts.max_deliver) {
+            (NatsQueue::BlockImporter(_), Some(max_deliver)) => max_deliver,
+            _ => queue.default_max_deliver(),
+        }
+    }
+
+    async fn queue_publish(
+        &self,
+        queue: &NatsQueue,
+        subject: &NatsSubject,
+        payload: bytes::Bytes,
+    ) -> Result<(), MessageBrokerError> {
+        let subject_str = subject.to_string(queue);
+        let subject_id = subject.to_id(queue);
+        let publish = async_nats::jetstream::context::Publish::build()
+            .message_id(subject_id)
+            .payload(payload);
+
+        self.jetstream
+            .send_publish(subject_str, publish)
+            .await
+            .map_err(|e| MessageBrokerError::Publishing(e.to_string()))?
+            .await
+            .map_err(|e| MessageBrokerError::Publishing(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn queue_subscribe(
+        &self,
+        queue: &NatsQueue,
+        batch_size: usize,
+    ) -> Result<MessageBlockStream, MessageBrokerError> {
+        let consumer = self.get_or_create_consumer(queue).await?;
+        let stream = consumer
+            .fetch()
+            .max_messages(batch_size)
+            .messages()
+            .await
+            .map_err(|e| MessageBrokerError::Receiving(e.to_string()))?
+            .filter_map(|msg| async {
+                msg.ok()
+                    .map(|m| Ok(Box::new(NatsMessage(m)) as Box<dyn Message>))
+            })
+            .boxed();
+        Ok(Box::new(stream))
+    }
+
+    async fn queue_messages(
+        &self,
+        queue: &NatsQueue,
+        filter: &str,
+        limit: usize,
+    ) -> Result<Vec<QueuedMessage>, MessageBrokerError> {
+        let stream = self.get_or_create_stream(queue).await?;
+        let mut messages = Vec::new();
+        let mut sequence = None;
+        // Each lookup jumps straight to the next stored message matching the
+        // filter, skipping acked and deleted sequences on the server
+        while messages.len() < limit {
+            let msg = match stream
+                .direct_get_next_for_subject(filter, sequence)
+                .await
+            {
+                Ok(msg) => {
+                    StreamMessage::try_from(msg.message).map_err(|e| {
+                        MessageBrokerError::Receiving(e.to_string())
+                    })?
+                }
+                Err(e) if e.kind() == DirectGetErrorKind::NotFound => break,
+                Err(e) => {
+                    return Err(MessageBrokerError::Receiving(e.to_string()))
+                }
+            };
+            sequence = Some(msg.sequence + 1);
+            messages.push(msg.into());
+        }
+        Ok(messages)
+    }
+
+    async fn queue_find_message(
+        &self,
+        queue: &NatsQueue,
+        subject: &NatsSubject,
+    ) -> Result<Option<QueuedMessage>, MessageBrokerError> {
+        let stream = self.get_or_create_stream(queue).await?;
+        match stream
+            .direct_get_last_for_subject(subject.to_string(queue))
+            .await
+        {
+            Ok(msg) => Ok(Some(msg.into())),
+            Err(e) if e.kind() == DirectGetErrorKind::NotFound => Ok(None),
+            Err(e) => Err(MessageBrokerError::Receiving(e.to_string())),
+        }
+    }
+
+    async fn queue_delete_message(
+        &self,
+        queue: &NatsQueue,
+        sequence: u64,
+    ) -> Result<bool, MessageBrokerError> {
+        let stream = self.get_or_create_stream(queue).await?;
+        stream
+            .delete_message(sequence)
+            .await
+            .map_err(|e| MessageBrokerError::Acknowledgment(e.to_string()))
+    }
 }
 
 #[async_trait]
@@ -183,6 +415,23 @@ impl Message for NatsMessage {
             .await
             .map_err(|e| MessageBrokerError::Acknowledgment(e.to_string()))
     }
+
+    async fn nak(
+        &self,
+        delay: Option<Duration>,
+    ) -> Result<(), MessageBrokerError> {
+        self.0
+            .ack_with(AckKind::Nak(delay))
+            .await
+            .map_err(|e| MessageBrokerError::Acknowledgment(e.to_string()))
+    }
+
+    fn delivery_count(&self) -> u64 {
+        self.0
+            .info()
+            .map(|info| info.delivered.max(1) as u64)
+            .unwrap_or(1)
+    }
 }
 
 #[cfg(test)]
@@ -192,6 +441,7 @@ mod tests {
         time::Duration,
     };
 
+    use async_nats::jetstream::consumer::IntoConsumerConfig;
     use pretty_assertions::assert_eq;
     use rand::Rng;
 
@@ -214,6 +464,67 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_consumer_config_matches() {
+        let expected = ConsumerConfig {
+            durable_name: Some("consumer".to_string()),
+            ack_policy: AckPolicy::Explicit,
+            max_deliver: 10,
+            filter_subject: "queue.block_submitted.>".to_string(),
+            ack_wait: Duration::from_secs(30),
+            ..Default::default()
+        };
+        let current = expected.clone().into_consumer_config();
+        let filled_by_server = consumer::Config {
+            max_ack_pending: 1000,
+            ..current.clone()
+        };
+        assert!(consumer_config_matches(&filled_by_server, &expected));
+
+        let other_max_deliver = consumer::Config {
+            max_deliver: 3,
+            ..current.clone()
+        };
+        let other_ack_wait = consumer::Config {
+            ack_wait: Duration::from_secs(1),
+            ..current.clone()
+        };
+        let no_filter = consumer::Config {
+            filter_subject: String::new(),
+            ..current
+        };
+        for current in [other_max_deliver, other_ack_wait, no_filter] {
+            assert!(!consumer_config_matches(&current, &expected));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_consumer_config_is_updated() -> Result<(), MessageBrokerError>
+    {
+        let broker = setup_broker().await?;
+        let queue = NatsQueue::BlockImporter(Arc::new(broker.clone()));
+        let stream = broker.get_or_create_stream(&queue).await?;
+        stream
+            .create_consumer(ConsumerConfig {
+                durable_name: Some(queue.consumer_name()),
+                ack_policy: AckPolicy::Explicit,
+                max_deliver: 1,
+                filter_subject: queue.consumer_subject(),
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| MessageBrokerError::Setup(e.to_string()))?;
+
+        let mut consumer = broker.get_or_create_consumer(&queue).await?;
+        let info = consumer
+            .info()
+            .await
+            .map_err(|e| MessageBrokerError::Setup(e.to_string()))?;
+        assert_eq!(info.config.max_deliver, queue.max_deliver());
+        assert_eq!(info.config.ack_wait, Duration::from_secs(1));
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     async fn test_pub_sub() -> Result<(), MessageBrokerError> {
         let broker = setup_broker().await?;
@@ -305,6 +616,74 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_work_queue_nak_redelivery() -> Result<(), MessageBrokerError>
+    {
+        let broker = setup_broker().await?;
+        let queue = NatsQueue::BlockImporter(broker.arc());
+
+        queue
+            .publish(&NatsSubject::BlockSubmitted(1_u64), vec![1])
+            .await?;
+
+        {
+            let mut message_stream = queue.subscribe(1).await?;
+            let msg = message_stream.next().await.unwrap()?;
+            assert_eq!(msg.delivery_count(), 1);
+            msg.nak(None).await?;
+        }
+
+        // A nak'ed message is redelivered right away, without waiting for
+        // the ack wait to expire
+        let mut message_stream = queue.subscribe(1).await?;
+        let msg = message_stream.next().await.unwrap()?;
+        assert_eq!(msg.payload(), &[1]);
+        assert_eq!(msg.delivery_count(), 2);
+        msg.ack().await?;
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_queue_stored_messages() -> Result<(), MessageBrokerError> {
+        let broker = setup_broker().await?;
+        let queue = NatsQueue::BlockRetrier(broker.arc());
+
+        for height in [3_u64, 7] {
+            queue
+                .publish(&NatsSubject::BlockFailed(height), vec![height as u8])
+                .await?;
+        }
+
+        let filter = queue.consumer_subject();
+        let messages = queue.messages(&filter, 10).await?;
+        let heights: Vec<_> = messages
+            .iter()
+            .filter_map(|msg| msg.block_height())
+            .collect();
+        assert_eq!(heights, vec![3, 7]);
+
+        let msg = queue
+            .find_message(&NatsSubject::BlockFailed(7))
+            .await?
+            .expect("message for block 7");
+        assert_eq!(msg.payload.as_ref(), &[7]);
+        assert!(queue.delete_message(msg.sequence).await?);
+        assert!(queue
+            .find_message(&NatsSubject::BlockFailed(7))
+            .await?
+            .is_none());
+        assert_eq!(queue.messages(&filter, 10).await?.len(), 1);
+
+        // Dead letters are listed apart from the blocks still being retried
+        queue.publish(&NatsSubject::BlockDead(3), vec![3]).await?;
+        let dead_letters =
+            queue.messages(&queue.dead_letters_subject(), 10).await?;
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].block_height(), Some(3));
+        assert_eq!(queue.messages(&filter, 10).await?.len(), 1);
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
     async fn test_work_queue_multiple_consumers(
     ) -> Result<(), MessageBrokerError> {
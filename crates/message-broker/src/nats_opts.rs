@@ -10,6 +10,7 @@ pub struct NatsOpts {
     pub(crate) namespace: Namespace,
     pub(crate) timeout_secs: u64,
     pub(crate) ack_wait_secs: Option<u64>,
+    pub(crate) max_deliver: Option<i64>,
 }
 
 impl NatsOpts {
@@ -18,6 +19,7 @@ impl NatsOpts {
             url,
             timeout_secs: 5,
             ack_wait_secs: None,
+            max_deliver: None,
             namespace: Namespace::None,
         }
     }
@@ -45,6 +47,13 @@ impl NatsOpts {
         }
     }
 
+    pub fn with_max_deliver(self, max_deliver: i64) -> Self {
+        Self {
+            max_deliver: Some(max_deliver),
+            ..self
+        }
+    }
+
     #[cfg(any(test, feature = "test-helpers"))]
     pub fn with_rdn_namespace(self) -> Self {
         let namespace = format!(r"namespace-{}", Self::random_int());
@@ -1,17 +1,36 @@
-use std::{sync::Arc, time::Duration};
-
-use async_nats::jetstream::{
-    consumer::{pull::Config as ConsumerConfig, AckPolicy, PullConsumer},
-    stream::{Config as StreamConfig, RetentionPolicy},
-};
-use futures::StreamExt;
+use std::sync::Arc;
+
+use crate::{MessageBroker, MessageBrokerError};
+
+/// Deliveries of a block before JetStream stops redelivering it to the
+/// block importer consumer
+pub const BLOCK_IMPORTER_MAX_DELIVER: i64 = 10;
+/// Deliveries of a failed block before the retrier gives up on it and moves
+/// it to the dead letters of the retrier stream
+pub const BLOCK_RETRIER_MAX_DELIVER: i64 = 8;
+
+/// A message still stored in a queue's stream
+#[derive(Debug, Clone)]
+pub struct QueuedMessage {
+    pub sequence: u64,
+    pub subject: String,
+    pub payload: bytes::Bytes,
+    pub published_at: i64,
+}
 
-use crate::{MessageBrokerError, NatsMessageBroker};
+impl QueuedMessage {
+    pub fn block_height(&self) -> Option<u64> {
+        NatsSubject::parse_height(&self.subject)
+    }
+}
 
 pub enum NatsSubject {
     BlockSubmitted(u64),
     BlockFailed(u64),
     BlockSuccess(u64),
+    /// Failed block the retrier gave up on, kept until it is replayed or
+    /// dropped
+    BlockDead(u64),
 }
 
 impl NatsSubject {
@@ -27,28 +46,38 @@ impl NatsSubject {
             NatsSubject::BlockSuccess(id) => {
                 format!("{queue_name}.block_success.{id}")
             }
+            NatsSubject::BlockDead(id) => {
+                format!("{queue_name}.block_dead.{id}")
+            }
         }
     }
 
-    fn to_id(&self, queue: &NatsQueue) -> String {
+    pub fn to_id(&self, queue: &NatsQueue) -> String {
         self.to_string(queue).replace(".", "_")
     }
+
+    /// Block height carried by the last token of a block subject
+    pub fn parse_height(subject: &str) -> Option<u64> {
+        subject.rsplit('.').next()?.parse().ok()
+    }
 }
 
+/// Work queues moving blocks between services. The queue operations are
+/// implemented by each [`MessageBroker`] backend.
 pub enum NatsQueue {
-    BlockImporter(Arc<NatsMessageBroker>),
-    BlockRetrier(Arc<NatsMessageBroker>),
+    BlockImporter(Arc<dyn MessageBroker>),
+    BlockRetrier(Arc<dyn MessageBroker>),
 }
 
 impl NatsQueue {
-    fn broker(&self) -> &NatsMessageBroker {
+    fn broker(&self) -> &dyn MessageBroker {
         match self {
-            NatsQueue::BlockImporter(broker) => broker,
-            NatsQueue::BlockRetrier(broker) => broker,
+            NatsQueue::BlockImporter(broker) => broker.as_ref(),
+            NatsQueue::BlockRetrier(broker) => broker.as_ref(),
         }
     }
 
-    fn queue_name(&self) -> String {
+    pub fn queue_name(&self) -> String {
         let value = match self {
             NatsQueue::BlockImporter(_) => "block_importer",
             NatsQueue::BlockRetrier(_) => "block_retrier",
@@ -56,62 +85,48 @@ impl NatsQueue {
         self.broker().namespace().queue_name(value)
     }
 
-    fn subjects(&self) -> Vec<String> {
+    pub fn subjects(&self) -> Vec<String> {
+        match self {
+            NatsQueue::BlockImporter(_) => vec![self.consumer_subject()],
+            NatsQueue::BlockRetrier(_) => {
+                vec![self.consumer_subject(), self.dead_letters_subject()]
+            }
+        }
+    }
+
+    /// Subject filter of the messages delivered to the queue's consumer
+    pub fn consumer_subject(&self) -> String {
         let queue_name = self.queue_name();
         match self {
             NatsQueue::BlockImporter(_) => {
-                vec![format!("{queue_name}.block_submitted.>")]
+                format!("{queue_name}.block_submitted.>")
             }
             NatsQueue::BlockRetrier(_) => {
-                vec![format!("{queue_name}.block_failed.>")]
+                format!("{queue_name}.block_failed.>")
             }
         }
     }
 
-    fn consumer_name(&self) -> String {
-        format!("{}_consumer", self.queue_name())
+    /// Subject filter of the blocks the retrier gave up on. They are stored
+    /// apart from the consumer subject, so they are never delivered again.
+    pub fn dead_letters_subject(&self) -> String {
+        format!("{}.block_dead.>", self.queue_name())
     }
 
-    pub async fn get_or_create_stream(
-        &self,
-    ) -> Result<async_nats::jetstream::stream::Stream, MessageBrokerError> {
-        let broker = self.broker();
-        let subjects = self.subjects();
-        let name = self.queue_name();
-        broker
-            .jetstream
-            .get_or_create_stream(StreamConfig {
-                name,
-                subjects,
-                retention: RetentionPolicy::WorkQueue,
-                duplicate_window: Duration::from_secs(1),
-                allow_direct: true,
-                ..Default::default()
-            })
-            .await
-            .map_err(|e| MessageBrokerError::Setup(e.to_string()))
+    pub fn consumer_name(&self) -> String {
+        format!("{}_consumer", self.queue_name())
     }
 
-    pub async fn get_or_create_consumer(
-        &self,
-    ) -> Result<PullConsumer, MessageBrokerError> {
-        let stream = self.get_or_create_stream().await?;
-        let consumer_name = self.consumer_name();
-        let broker = self.broker();
-        let mut config = ConsumerConfig {
-            durable_name: Some(consumer_name.to_string()),
-            ack_policy: AckPolicy::Explicit,
-            ..Default::default()
-        };
-
-        if let Some(ack_wait) = broker.opts.ack_wait_secs {
-            config.ack_wait = Duration::from_secs(ack_wait);
+    /// Max deliver used when the broker doesn't override it
+    pub fn default_max_deliver(&self) -> i64 {
+        match self {
+            NatsQueue::BlockImporter(_) => BLOCK_IMPORTER_MAX_DELIVER,
+            NatsQueue::BlockRetrier(_) => BLOCK_RETRIER_MAX_DELIVER,
         }
+    }
 
-        stream
-            .get_or_create_consumer(&consumer_name, config)
-            .await
-            .map_err(|e| MessageBrokerError::Setup(e.to_string()))
+    pub fn max_deliver(&self) -> i64 {
+        self.broker().max_deliver(self)
     }
 
     pub async fn publish<T: Into<bytes::Bytes>>(
@@ -119,48 +134,40 @@ impl NatsQueue {
         subject: &NatsSubject,
         payload: T,
     ) -> Result<(), MessageBrokerError> {
-        let broker = self.broker();
-        let subject_str = subject.to_string(self);
-        let subject_id = subject.to_id(self);
-        let publish = async_nats::jetstream::context::Publish::build()
-            .message_id(subject_id)
-            .payload(payload.into());
-
-        broker
-            .jetstream
-            .send_publish(subject_str, publish)
+        self.broker()
+            .queue_publish(self, subject, payload.into())
             .await
-            .map_err(|e| MessageBrokerError::Publishing(e.to_string()))?
-            .await
-            .map_err(|e| MessageBrokerError::Publishing(e.to_string()))?;
-
-        Ok(())
     }
 
     pub async fn subscribe(
         &self,
         batch_size: usize,
     ) -> Result<crate::MessageBlockStream, MessageBrokerError> {
-        let consumer = self.get_or_create_consumer().await?;
-        let stream = consumer
-            .fetch()
-            .max_messages(batch_size)
-            .messages()
-            .await
-            .map_err(|e| MessageBrokerError::Receiving(e.to_string()))?
-            .filter_map(|msg| async {
-                msg.ok().map(|m| {
-                    Ok(Box::new(crate::NatsMessage(m))
-                        as Box<dyn crate::Message>)
-                })
-            })
-            .boxed();
-        Ok(Box::new(stream))
-    }
-
-    pub async fn setup(&self) -> Result<(), MessageBrokerError> {
-        let _ = self.get_or_create_stream().await?;
-        let _ = self.get_or_create_consumer().await?;
-        Ok(())
+        self.broker().queue_subscribe(self, batch_size).await
+    }
+
+    /// Lists up to `limit` messages stored in the queue under the given
+    /// subject filter, oldest first
+    pub async fn messages(
+        &self,
+        filter: &str,
+        limit: usize,
+    ) -> Result<Vec<QueuedMessage>, MessageBrokerError> {
+        self.broker().queue_messages(self, filter, limit).await
+    }
+
+    /// Latest message stored for the given subject, if any
+    pub async fn find_message(
+        &self,
+        subject: &NatsSubject,
+    ) -> Result<Option<QueuedMessage>, MessageBrokerError> {
+        self.broker().queue_find_message(self, subject).await
+    }
+
+    pub async fn delete_message(
+        &self,
+        sequence: u64,
+    ) -> Result<bool, MessageBrokerError> {
+        self.broker().queue_delete_message(self, sequence).await
     }
 }
@@ -6,7 +6,8 @@ use futures::{
 };
 use pedronauck_streams_core::{
     subjects::*,
-    types::{StreamResponse, SubscribeRequest},
+    types::{PayloadProjection, StreamResponse, SubscribeRequest},
+    StreamRateLimit,
 };
 use tokio::sync::RwLock;
 use tokio_tungstenite::{
@@ -80,7 +81,7 @@ impl Connection {
         &mut self,
         message: &SubscribeRequest,
     ) -> Result<
-        impl Stream<Item = Result<StreamResponse, ClientError>> + '_ + Send + Unpin,
+        impl Stream<Item = Result<ServerResponse, ClientError>> + '_ + Send + Unpin,
         ClientError,
     > {
         self.send_client_message(message).await?;
@@ -120,6 +121,147 @@ impl Connection {
         let message = SubscribeRequest {
             deliver_policy,
             subscribe: subjects,
+            block_framing: false,
+            filter: None,
+            projection: None,
+            rate_limit: None,
+            merge_ordered: false,
+        };
+        self.subscribe_with_message(&message).await
+    }
+
+    /// Like [`Connection::subscribe`], but the server only sends the
+    /// messages whose payload matches the filter, e.g.
+    /// `amount > 1000 && assetId in [0x01, 0x02]`.
+    pub async fn subscribe_filtered(
+        &mut self,
+        subjects: Vec<SubjectPayload>,
+        deliver_policy: DeliverPolicy,
+        filter: impl ToString,
+    ) -> Result<
+        impl Stream<Item = Result<StreamResponse, ClientError>> + '_ + Send + Unpin,
+        ClientError,
+    > {
+        let message = SubscribeRequest {
+            deliver_policy,
+            subscribe: subjects,
+            block_framing: false,
+            filter: Some(filter.to_string()),
+            projection: None,
+            rate_limit: None,
+            merge_ordered: false,
+        };
+        self.subscribe_with_message(&message).await
+    }
+
+    /// Like [`Connection::subscribe`], but the server only sends the
+    /// payload fields picked by the projection. Projected payloads are
+    /// received as `MessagePayload::Projected`.
+    pub async fn subscribe_projected(
+        &mut self,
+        subjects: Vec<SubjectPayload>,
+        deliver_policy: DeliverPolicy,
+        projection: PayloadProjection,
+    ) -> Result<
+        impl Stream<Item = Result<StreamResponse, ClientError>> + '_ + Send + Unpin,
+        ClientError,
+    > {
+        let message = SubscribeRequest {
+            deliver_policy,
+            subscribe: subjects,
+            block_framing: false,
+            filter: None,
+            projection: Some(projection),
+            rate_limit: None,
+            merge_ordered: false,
+        };
+        self.subscribe_with_message(&message).await
+    }
+
+    /// Like [`Connection::subscribe`], but the server sends the messages no
+    /// faster than the given rate. The rate can only be lower than the one
+    /// allowed for the API key's role.
+    pub async fn subscribe_with_rate_limit(
+        &mut self,
+        subjects: Vec<SubjectPayload>,
+        deliver_policy: DeliverPolicy,
+        rate_limit: StreamRateLimit,
+    ) -> Result<
+        impl Stream<Item = Result<StreamResponse, ClientError>> + '_ + Send + Unpin,
+        ClientError,
+    > {
+        let message = SubscribeRequest {
+            deliver_policy,
+            subscribe: subjects,
+            block_framing: false,
+            filter: None,
+            projection: None,
+            rate_limit: Some(rate_limit),
+            merge_ordered: false,
+        };
+        self.subscribe_with_message(&message).await
+    }
+
+    /// Like [`Connection::subscribe`], but the historical replays of all
+    /// the subjects are merged in chain order, and records matched by more
+    /// than one subject are only received once.
+    pub async fn subscribe_merged(
+        &mut self,
+        subjects: Vec<SubjectPayload>,
+        deliver_policy: DeliverPolicy,
+    ) -> Result<
+        impl Stream<Item = Result<StreamResponse, ClientError>> + '_ + Send + Unpin,
+        ClientError,
+    > {
+        let message = SubscribeRequest {
+            deliver_policy,
+            subscribe: subjects,
+            block_framing: false,
+            filter: None,
+            projection: None,
+            rate_limit: None,
+            merge_ordered: true,
+        };
+        self.subscribe_with_message(&message).await
+    }
+
+    async fn subscribe_with_message(
+        &mut self,
+        message: &SubscribeRequest,
+    ) -> Result<
+        impl Stream<Item = Result<StreamResponse, ClientError>> + '_ + Send + Unpin,
+        ClientError,
+    > {
+        let stream = self.stream_with_message(message).await?;
+        let stream = stream.filter_map(|msg| async {
+            match msg {
+                Ok(ServerResponse::Response(response)) => Some(Ok(response)),
+                Ok(_) => None,
+                Err(e) => Some(Err(e)),
+            }
+        });
+        Ok(Box::pin(stream))
+    }
+
+    /// Like [`Connection::subscribe`], but every block's items are wrapped
+    /// between [`ServerResponse::BlockStart`] and [`ServerResponse::BlockEnd`]
+    /// so the caller knows when a block is complete.
+    pub async fn subscribe_framed(
+        &mut self,
+        subjects: Vec<SubjectPayload>,
+        deliver_policy: DeliverPolicy,
+    ) -> Result<
+        impl Stream<Item = Result<ServerResponse, ClientError>> + '_ + Send + Unpin,
+        ClientError,
+    > {
+        let message = SubscribeRequest {
+            deliver_policy,
+            subscribe: subjects,
+            block_framing: true,
+            filter: None,
+            projection: None,
+            rate_limit: None,
+            merge_ordered: true,
         };
         self.stream_with_message(&message).await
     }
@@ -127,9 +269,13 @@ impl Connection {
 
 fn handle_binary_message(
     bin: tokio_tungstenite::tungstenite::Bytes,
-) -> Result<Option<StreamResponse>, ClientError> {
+) -> Result<Option<ServerResponse>, ClientError> {
     match serde_json::from_slice::<ServerResponse>(&bin) {
-        Ok(ServerResponse::Response(response)) => Ok(Some(response)),
+        Ok(
+            response @ (ServerResponse::Response(_)
+            | ServerResponse::BlockStart { .. }
+            | ServerResponse::BlockEnd { .. }),
+        ) => Ok(Some(response)),
         Ok(ServerResponse::Error(e)) => Err(ClientError::Server(e)),
         Ok(_) => Ok(None),
         Err(e) => Err(ClientError::Server(e.to_string())),
@@ -1,4 +1,5 @@
 pub use pedronauck_streams_core::{
     server::{DeliverPolicy, ServerRequest, ServerResponse},
     subjects::SubjectPayload,
+    StreamRateLimit,
 };
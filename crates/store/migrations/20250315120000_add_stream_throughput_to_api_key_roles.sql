@@ -0,0 +1,9 @@
+-- Per subscription throughput caps, NULL means unlimited
+ALTER TABLE api_key_roles
+    ADD COLUMN IF NOT EXISTS stream_msgs_per_sec INTEGER,
+    ADD COLUMN IF NOT EXISTS stream_bytes_per_sec INTEGER;
+
+UPDATE api_key_roles SET stream_msgs_per_sec = 50, stream_bytes_per_sec = 1048576
+    WHERE name = 'BUILDER';
+UPDATE api_key_roles SET stream_msgs_per_sec = 20, stream_bytes_per_sec = 262144
+    WHERE name = 'WEB_CLIENT';
@@ -0,0 +1,28 @@
+CREATE TABLE IF NOT EXISTS bundles (
+    id SERIAL PRIMARY KEY,
+    subject TEXT NOT NULL UNIQUE,
+    value BYTEA NOT NULL,       -- transaction with its inputs, outputs and receipts
+    block_height BIGINT NOT NULL,
+    tx_id TEXT NOT NULL UNIQUE,
+    tx_index INTEGER NOT NULL,
+    tx_status TEXT NOT NULL,
+    type TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    published_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_bundles_subject ON bundles (subject);
+CREATE INDEX IF NOT EXISTS idx_bundles_block_height ON bundles (block_height);
+CREATE INDEX IF NOT EXISTS idx_bundles_tx_id ON bundles (tx_id);
+CREATE INDEX IF NOT EXISTS idx_bundles_tx_index ON bundles (tx_index);
+CREATE INDEX IF NOT EXISTS idx_bundles_tx_status ON bundles (tx_status);
+CREATE INDEX IF NOT EXISTS idx_bundles_type ON bundles (type);
+CREATE INDEX IF NOT EXISTS idx_bundles_created_at ON bundles (created_at);
+CREATE INDEX IF NOT EXISTS idx_bundles_published_at ON bundles (published_at);
+
+-- Composite indexes for filtering with "WHERE block_height >= <value>"
+CREATE INDEX IF NOT EXISTS idx_bundles_tx_status_block_height ON bundles (tx_status, block_height);
+CREATE INDEX IF NOT EXISTS idx_bundles_tx_type_block_height ON bundles (type, block_height);
+
+-- Composite index for ordering by (block_height, tx_index)
+CREATE INDEX IF NOT EXISTS idx_bundles_ordering ON bundles (block_height, tx_index);
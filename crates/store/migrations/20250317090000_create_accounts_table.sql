@@ -0,0 +1,37 @@
+CREATE TABLE IF NOT EXISTS accounts (
+    id SERIAL PRIMARY KEY,
+    subject TEXT NOT NULL UNIQUE,
+    value BYTEA NOT NULL,       -- account activity with the record touching the account
+    block_height BIGINT NOT NULL,
+    tx_id TEXT NOT NULL,
+    tx_index INTEGER NOT NULL,
+    address TEXT NOT NULL,
+    activity_type TEXT NOT NULL,
+    record_index INTEGER NOT NULL,
+    -- position of the record within the transaction, -1 when the record
+    -- doesn't have the index so it sorts like an unset pointer index
+    input_index INTEGER NOT NULL DEFAULT -1,
+    output_index INTEGER NOT NULL DEFAULT -1,
+    receipt_index INTEGER NOT NULL DEFAULT -1,
+    -- position of the row among the ones sharing the same record index,
+    -- such as the sender and recipient of a message, or an input and the
+    -- UTXO it spends, so each row has its own ordering key
+    fanout_index INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    published_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_accounts_subject ON accounts (subject);
+CREATE INDEX IF NOT EXISTS idx_accounts_block_height ON accounts (block_height);
+CREATE INDEX IF NOT EXISTS idx_accounts_tx_id ON accounts (tx_id);
+CREATE INDEX IF NOT EXISTS idx_accounts_address ON accounts (address);
+CREATE INDEX IF NOT EXISTS idx_accounts_activity_type ON accounts (activity_type);
+CREATE INDEX IF NOT EXISTS idx_accounts_created_at ON accounts (created_at);
+CREATE INDEX IF NOT EXISTS idx_accounts_published_at ON accounts (published_at);
+
+-- Composite indexes for filtering with "WHERE block_height >= <value>"
+CREATE INDEX IF NOT EXISTS idx_accounts_address_block_height ON accounts (address, block_height);
+CREATE INDEX IF NOT EXISTS idx_accounts_address_activity_type_block_height ON accounts (address, activity_type, block_height);
+
+-- Composite index for ordering by (block_height, tx_index, input_index, output_index, receipt_index, fanout_index)
+CREATE INDEX IF NOT EXISTS idx_accounts_ordering ON accounts (block_height, tx_index, input_index, output_index, receipt_index, fanout_index);
@@ -0,0 +1,39 @@
+CREATE TABLE IF NOT EXISTS contract_transactions (
+    id SERIAL PRIMARY KEY,
+    subject TEXT NOT NULL UNIQUE,
+    value BYTEA NOT NULL,       -- record involving the contract
+    block_height BIGINT NOT NULL,
+    tx_id TEXT NOT NULL,
+    tx_index INTEGER NOT NULL,
+    contract_id TEXT NOT NULL,
+    activity_type TEXT NOT NULL,
+    record_index INTEGER NOT NULL,
+    -- position of the record within the transaction, -1 when the record
+    -- doesn't have the index so it sorts like an unset pointer index
+    input_index INTEGER NOT NULL DEFAULT -1,
+    output_index INTEGER NOT NULL DEFAULT -1,
+    receipt_index INTEGER NOT NULL DEFAULT -1,
+    -- position of the row among the ones sharing the same record index,
+    -- such as the caller and the callee of a call, so each row has its own
+    -- ordering key
+    fanout_index INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    published_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_contract_transactions_subject ON contract_transactions (subject);
+CREATE INDEX IF NOT EXISTS idx_contract_transactions_block_height ON contract_transactions (block_height);
+CREATE INDEX IF NOT EXISTS idx_contract_transactions_tx_id ON contract_transactions (tx_id);
+CREATE INDEX IF NOT EXISTS idx_contract_transactions_contract_id ON contract_transactions (contract_id);
+CREATE INDEX IF NOT EXISTS idx_contract_transactions_activity_type ON contract_transactions (activity_type);
+CREATE INDEX IF NOT EXISTS idx_contract_transactions_created_at ON contract_transactions (created_at);
+CREATE INDEX IF NOT EXISTS idx_contract_transactions_published_at ON contract_transactions (published_at);
+
+-- Composite indexes for filtering with "WHERE block_height >= <value>"
+CREATE INDEX IF NOT EXISTS idx_contract_transactions_contract_id_block_height ON contract_transactions (contract_id, block_height);
+
+-- Composite index for the transactions of a contract, used by "/contracts/{id}/transactions"
+CREATE INDEX IF NOT EXISTS idx_contract_transactions_contract_id_tx_id ON contract_transactions (contract_id, tx_id);
+
+-- Composite index for ordering by (block_height, tx_index, input_index, output_index, receipt_index, fanout_index)
+CREATE INDEX IF NOT EXISTS idx_contract_transactions_ordering ON contract_transactions (block_height, tx_index, input_index, output_index, receipt_index, fanout_index);
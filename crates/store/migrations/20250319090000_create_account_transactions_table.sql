@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS account_transactions (
+    address TEXT NOT NULL,
+    tx_id TEXT NOT NULL,
+    block_height BIGINT NOT NULL,
+    tx_index INTEGER NOT NULL,
+    role TEXT NOT NULL,         -- sender or recipient
+    PRIMARY KEY (address, tx_id, role)
+);
+
+CREATE INDEX IF NOT EXISTS idx_account_transactions_tx_id ON account_transactions (tx_id);
+
+-- Composite index for the transactions of an account, used by "/accounts/{address}/transactions"
+CREATE INDEX IF NOT EXISTS idx_account_transactions_address_block_height ON account_transactions (address, block_height, tx_index);
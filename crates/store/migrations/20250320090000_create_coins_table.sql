@@ -0,0 +1,40 @@
+CREATE TABLE IF NOT EXISTS coins (
+    id SERIAL PRIMARY KEY,
+    subject TEXT NOT NULL,      -- subject of the last state change
+    value BYTEA NOT NULL,       -- coin in its current state
+    -- position of the transaction creating the coin or, once spent, of the
+    -- one spending it
+    block_height BIGINT NOT NULL,
+    tx_id TEXT NOT NULL,
+    tx_index INTEGER NOT NULL,
+    record_index INTEGER NOT NULL,
+    -- index of the spending input or of the creating output, -1 for the
+    -- other one so it sorts like an unset pointer index
+    input_index INTEGER NOT NULL DEFAULT -1,
+    output_index INTEGER NOT NULL DEFAULT -1,
+    utxo_id TEXT NOT NULL UNIQUE, -- hex string of the UTXO identifier
+    owner TEXT NOT NULL,
+    asset_id TEXT NOT NULL,
+    status TEXT NOT NULL,       -- 'unspent' or 'spent'
+    spent_in_tx TEXT,
+    spent_at_height BIGINT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    published_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_coins_subject ON coins (subject);
+CREATE INDEX IF NOT EXISTS idx_coins_block_height ON coins (block_height);
+CREATE INDEX IF NOT EXISTS idx_coins_tx_id ON coins (tx_id);
+CREATE INDEX IF NOT EXISTS idx_coins_owner ON coins (owner);
+CREATE INDEX IF NOT EXISTS idx_coins_asset_id ON coins (asset_id);
+CREATE INDEX IF NOT EXISTS idx_coins_status ON coins (status);
+CREATE INDEX IF NOT EXISTS idx_coins_spent_in_tx ON coins (spent_in_tx);
+CREATE INDEX IF NOT EXISTS idx_coins_created_at ON coins (created_at);
+CREATE INDEX IF NOT EXISTS idx_coins_published_at ON coins (published_at);
+
+-- Composite indexes for the unspent coins of an account
+CREATE INDEX IF NOT EXISTS idx_coins_owner_status_block_height ON coins (owner, status, block_height);
+CREATE INDEX IF NOT EXISTS idx_coins_owner_asset_id_status ON coins (owner, asset_id, status);
+
+-- Composite index for ordering by (block_height, tx_index, input_index, output_index)
+CREATE INDEX IF NOT EXISTS idx_coins_ordering ON coins (block_height, tx_index, input_index, output_index);
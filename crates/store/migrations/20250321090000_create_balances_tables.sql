@@ -0,0 +1,50 @@
+CREATE TABLE IF NOT EXISTS balance_changes (
+    id SERIAL PRIMARY KEY,
+    subject TEXT NOT NULL UNIQUE,
+    value BYTEA NOT NULL,
+    block_height BIGINT NOT NULL,
+    tx_id TEXT NOT NULL,
+    tx_index INTEGER NOT NULL,
+    record_index INTEGER NOT NULL,
+    source TEXT NOT NULL,       -- 'input', 'output' or 'receipt'
+    -- index of the record the change comes from, -1 for the other ones so
+    -- they sort like an unset pointer index
+    input_index INTEGER NOT NULL DEFAULT -1,
+    output_index INTEGER NOT NULL DEFAULT -1,
+    receipt_index INTEGER NOT NULL DEFAULT -1,
+    owner TEXT NOT NULL,        -- account address or contract ID
+    asset_id TEXT NOT NULL,
+    kind TEXT NOT NULL,         -- 'credit' or 'debit'
+    -- side of the change, as a transfer debits one owner and credits
+    -- another from the same receipt: 0 for debits and 1 for credits, so
+    -- both rows have their own ordering key
+    fanout_index INTEGER NOT NULL DEFAULT 0,
+    amount TEXT NOT NULL,       -- decimal string, amounts don't fit in BIGINT
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    published_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_balance_changes_subject ON balance_changes (subject);
+CREATE INDEX IF NOT EXISTS idx_balance_changes_block_height ON balance_changes (block_height);
+CREATE INDEX IF NOT EXISTS idx_balance_changes_tx_id ON balance_changes (tx_id);
+CREATE INDEX IF NOT EXISTS idx_balance_changes_owner ON balance_changes (owner);
+CREATE INDEX IF NOT EXISTS idx_balance_changes_asset_id ON balance_changes (asset_id);
+CREATE INDEX IF NOT EXISTS idx_balance_changes_created_at ON balance_changes (created_at);
+CREATE INDEX IF NOT EXISTS idx_balance_changes_published_at ON balance_changes (published_at);
+
+-- Composite index for ordering by (block_height, tx_index, input_index, output_index, receipt_index, fanout_index)
+CREATE INDEX IF NOT EXISTS idx_balance_changes_ordering ON balance_changes (block_height, tx_index, input_index, output_index, receipt_index, fanout_index);
+
+-- Current balances, summed from the changes as they are stored
+CREATE TABLE IF NOT EXISTS balances (
+    owner TEXT NOT NULL,        -- account address or contract ID
+    asset_id TEXT NOT NULL,
+    -- NUMERIC, as a sum of u64 amounts can go past BIGINT, and below zero
+    -- while a spend is stored before the coin it consumes
+    amount NUMERIC(40, 0) NOT NULL DEFAULT 0,
+    last_block_height BIGINT NOT NULL,
+    PRIMARY KEY (owner, asset_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_balances_last_block_height ON balances (last_block_height);
+CREATE INDEX IF NOT EXISTS idx_balances_owner_last_block_height ON balances (owner, last_block_height);
@@ -0,0 +1,16 @@
+-- Sway JSON ABIs uploaded for contracts, used to decode their logs
+CREATE TABLE IF NOT EXISTS contract_abis (
+    contract_id TEXT PRIMARY KEY,
+    abi JSONB NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- Log ID (the rb register) of log_data receipts, and the type of the value
+-- when it was decoded with the contract's ABI, stored lowercase
+ALTER TABLE receipts ADD COLUMN IF NOT EXISTS log_id TEXT;
+ALTER TABLE receipts ADD COLUMN IF NOT EXISTS log_type TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_receipts_log_id ON receipts (log_id);
+CREATE INDEX IF NOT EXISTS idx_receipts_log_type ON receipts (log_type);
+CREATE INDEX IF NOT EXISTS idx_receipts_contract_id_log_id ON receipts (contract_id, log_id);
@@ -0,0 +1,7 @@
+-- Name of the panic reason of panic receipts, and the Sway revert signal of
+-- revert receipts
+ALTER TABLE receipts ADD COLUMN IF NOT EXISTS panic_reason TEXT;
+ALTER TABLE receipts ADD COLUMN IF NOT EXISTS revert_signal TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_receipts_panic_reason ON receipts (panic_reason);
+CREATE INDEX IF NOT EXISTS idx_receipts_revert_signal ON receipts (revert_signal);
@@ -0,0 +1,46 @@
+CREATE TABLE IF NOT EXISTS messages (
+    id SERIAL PRIMARY KEY,
+    subject TEXT NOT NULL,      -- subject of the last state change
+    value BYTEA NOT NULL,       -- message in its current state
+    -- position where the message was first seen, so its ordering key
+    -- never moves: the transaction sending it to L1 or, for messages from
+    -- L1, the first one seen using it
+    block_height BIGINT NOT NULL,
+    tx_id TEXT NOT NULL,        -- transaction of the last state change
+    tx_index INTEGER NOT NULL,
+    record_index INTEGER NOT NULL,
+    -- index of the input using the message or of the receipt sending it,
+    -- -1 for the other one so it sorts like an unset pointer index
+    input_index INTEGER NOT NULL DEFAULT -1,
+    receipt_index INTEGER NOT NULL DEFAULT -1,
+    message_id TEXT NOT NULL UNIQUE,
+    nonce TEXT NOT NULL,
+    direction TEXT NOT NULL,    -- 'l1_to_l2' or 'l2_to_l1'
+    status TEXT NOT NULL,       -- 'sent', 'relayed' or 'spent'
+    sender TEXT NOT NULL,
+    recipient TEXT NOT NULL,
+    da_height BIGINT NOT NULL,
+    -- height of the block of the last state change, so an older use stored
+    -- late doesn't overwrite a newer one
+    updated_block_height BIGINT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    published_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_messages_subject ON messages (subject);
+CREATE INDEX IF NOT EXISTS idx_messages_block_height ON messages (block_height);
+CREATE INDEX IF NOT EXISTS idx_messages_tx_id ON messages (tx_id);
+CREATE INDEX IF NOT EXISTS idx_messages_nonce ON messages (nonce);
+CREATE INDEX IF NOT EXISTS idx_messages_sender ON messages (sender);
+CREATE INDEX IF NOT EXISTS idx_messages_recipient ON messages (recipient);
+CREATE INDEX IF NOT EXISTS idx_messages_direction ON messages (direction);
+CREATE INDEX IF NOT EXISTS idx_messages_status ON messages (status);
+CREATE INDEX IF NOT EXISTS idx_messages_created_at ON messages (created_at);
+CREATE INDEX IF NOT EXISTS idx_messages_published_at ON messages (published_at);
+
+-- Composite indexes for the messages of a bridge user
+CREATE INDEX IF NOT EXISTS idx_messages_sender_direction_block_height ON messages (sender, direction, block_height);
+CREATE INDEX IF NOT EXISTS idx_messages_recipient_direction_block_height ON messages (recipient, direction, block_height);
+
+-- Composite index for ordering by (block_height, tx_index, input_index, receipt_index)
+CREATE INDEX IF NOT EXISTS idx_messages_ordering ON messages (block_height, tx_index, input_index, receipt_index);
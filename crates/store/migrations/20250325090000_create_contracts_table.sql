@@ -0,0 +1,27 @@
+CREATE TABLE IF NOT EXISTS contracts (
+    id SERIAL PRIMARY KEY,
+    subject TEXT NOT NULL,
+    value BYTEA NOT NULL,       -- contract with its initial storage slots
+    -- position of the output of the `Create` transaction deploying it
+    block_height BIGINT NOT NULL,
+    tx_id TEXT NOT NULL,
+    tx_index INTEGER NOT NULL,
+    output_index INTEGER NOT NULL,
+    contract_id TEXT NOT NULL UNIQUE,
+    deployer TEXT NOT NULL,
+    bytecode_root TEXT NOT NULL, -- Merkle root the contract ID derives from
+    bytecode_size INTEGER NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    published_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_contracts_subject ON contracts (subject);
+CREATE INDEX IF NOT EXISTS idx_contracts_block_height ON contracts (block_height);
+CREATE INDEX IF NOT EXISTS idx_contracts_tx_id ON contracts (tx_id);
+CREATE INDEX IF NOT EXISTS idx_contracts_deployer ON contracts (deployer);
+CREATE INDEX IF NOT EXISTS idx_contracts_bytecode_root ON contracts (bytecode_root);
+CREATE INDEX IF NOT EXISTS idx_contracts_created_at ON contracts (created_at);
+CREATE INDEX IF NOT EXISTS idx_contracts_published_at ON contracts (published_at);
+
+-- Composite index for ordering by (block_height, tx_index, output_index)
+CREATE INDEX IF NOT EXISTS idx_contracts_ordering ON contracts (block_height, tx_index, output_index);
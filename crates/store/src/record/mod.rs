@@ -1,8 +1,10 @@
+mod payload_filter;
 mod query_options;
 mod record_entity;
 mod record_impl;
 mod record_packet;
 
+pub use payload_filter::*;
 pub use query_options::*;
 pub use record_entity::*;
 pub use record_impl::*;
@@ -0,0 +1,476 @@
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use serde_json::Value;
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum PayloadFilterError {
+    #[error("Filter expression is empty")]
+    Empty,
+    #[error("Unexpected end of filter expression, expected {0}")]
+    UnexpectedEnd(&'static str),
+    #[error("Unexpected '{found}' in filter expression, expected {expected}")]
+    Unexpected {
+        found: String,
+        expected: &'static str,
+    },
+    #[error("Unterminated string in filter expression")]
+    UnterminatedString,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterOp {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    In,
+}
+
+impl FilterOp {
+    pub(crate) fn as_sql(&self) -> &'static str {
+        match self {
+            FilterOp::Eq => " = ",
+            FilterOp::Ne => " <> ",
+            FilterOp::Gt => " > ",
+            FilterOp::Gte => " >= ",
+            FilterOp::Lt => " < ",
+            FilterOp::Lte => " <= ",
+            FilterOp::In => " IN ",
+        }
+    }
+
+    fn accepts(&self, ordering: Ordering) -> bool {
+        match self {
+            FilterOp::Eq | FilterOp::In => ordering.is_eq(),
+            FilterOp::Ne => ordering.is_ne(),
+            FilterOp::Gt => ordering.is_gt(),
+            FilterOp::Gte => ordering.is_ge(),
+            FilterOp::Lt => ordering.is_lt(),
+            FilterOp::Lte => ordering.is_le(),
+        }
+    }
+}
+
+impl fmt::Display for FilterOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = match self {
+            FilterOp::Eq => "==",
+            FilterOp::Ne => "!=",
+            FilterOp::Gt => ">",
+            FilterOp::Gte => ">=",
+            FilterOp::Lt => "<",
+            FilterOp::Lte => "<=",
+            FilterOp::In => "in",
+        };
+        f.write_str(op)
+    }
+}
+
+/// Literal a payload field is compared against
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterValue {
+    Number(i128),
+    Text(String),
+    Bool(bool),
+}
+
+impl FilterValue {
+    /// Compares a payload value with this literal. Integers serialized as
+    /// strings are compared as numbers and text is compared ignoring case,
+    /// as ids are hex strings.
+    fn compare(&self, value: &Value) -> Option<Ordering> {
+        match (self, value) {
+            (FilterValue::Number(expected), Value::Number(number)) => number
+                .as_i64()
+                .map(i128::from)
+                .or_else(|| number.as_u64().map(i128::from))
+                .map(|number| number.cmp(expected)),
+            (FilterValue::Number(expected), Value::String(text)) => {
+                text.parse::<i128>().ok().map(|number| number.cmp(expected))
+            }
+            (FilterValue::Text(expected), Value::String(text)) => Some(
+                text.to_ascii_lowercase()
+                    .cmp(&expected.to_ascii_lowercase()),
+            ),
+            (FilterValue::Bool(expected), Value::Bool(value)) => {
+                Some(value.cmp(expected))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for FilterValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterValue::Number(value) => write!(f, "{value}"),
+            FilterValue::Text(value) => write!(f, "{value:?}"),
+            FilterValue::Bool(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// A single `field <op> value` comparison of a filter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterPredicate {
+    pub field: String,
+    pub op: FilterOp,
+    pub values: Vec<FilterValue>,
+}
+
+impl FilterPredicate {
+    /// Whether the payload satisfies the predicate. Missing and null fields
+    /// never match.
+    pub fn matches(&self, payload: &Value) -> bool {
+        let Some(value) = self
+            .field
+            .split('.')
+            .try_fold(payload, |value, key| value.get(key))
+        else {
+            return false;
+        };
+        self.values.iter().any(|expected| {
+            expected
+                .compare(value)
+                .is_some_and(|ordering| self.op.accepts(ordering))
+        })
+    }
+}
+
+impl fmt::Display for FilterPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.op {
+            FilterOp::In => {
+                let values = self
+                    .values
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>();
+                write!(f, "{} in [{}]", self.field, values.join(", "))
+            }
+            op => write!(f, "{} {} {}", self.field, op, self.values[0]),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterColumnKind {
+    Int,
+    /// Text column holding lowercase values, such as hex ids
+    Text,
+}
+
+/// Payload field backed by a column of a record's table, so predicates on
+/// it can be evaluated by Postgres.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FilterColumn {
+    pub field: &'static str,
+    pub column: &'static str,
+    pub kind: FilterColumnKind,
+}
+
+impl FilterColumn {
+    pub const fn int(field: &'static str, column: &'static str) -> Self {
+        Self {
+            field,
+            column,
+            kind: FilterColumnKind::Int,
+        }
+    }
+
+    pub const fn text(field: &'static str, column: &'static str) -> Self {
+        Self {
+            field,
+            column,
+            kind: FilterColumnKind::Text,
+        }
+    }
+}
+
+/// Value of a predicate pushed down to SQL, bound as a parameter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterSqlValue {
+    Int(i64),
+    Text(String),
+}
+
+/// A predicate that can be evaluated by Postgres
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterSqlCondition {
+    pub column: &'static str,
+    pub op: FilterOp,
+    pub values: Vec<FilterSqlValue>,
+}
+
+/// Conjunction of predicates on the decoded payload of a subscription,
+/// e.g. `amount > 1000 && assetId in ["0x01", "0x02"]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayloadFilter {
+    predicates: Vec<FilterPredicate>,
+}
+
+impl PayloadFilter {
+    pub fn predicates(&self) -> &[FilterPredicate] {
+        &self.predicates
+    }
+
+    pub fn matches(&self, payload: &Value) -> bool {
+        self.predicates
+            .iter()
+            .all(|predicate| predicate.matches(payload))
+    }
+
+    /// Predicates on fields backed by one of the given columns, converted
+    /// to SQL conditions. Predicates that can't be expressed in SQL are
+    /// left out, so the filter still has to be applied to every row.
+    pub fn sql_conditions(
+        &self,
+        columns: &[FilterColumn],
+    ) -> Vec<FilterSqlCondition> {
+        self.predicates
+            .iter()
+            .filter_map(|predicate| {
+                let column = columns
+                    .iter()
+                    .find(|column| column.field == predicate.field)?;
+                let values = predicate
+                    .values
+                    .iter()
+                    .map(|value| match (column.kind, value) {
+                        (FilterColumnKind::Int, FilterValue::Number(n)) => {
+                            i64::try_from(*n).ok().map(FilterSqlValue::Int)
+                        }
+                        (FilterColumnKind::Text, FilterValue::Text(text))
+                            if matches!(
+                                predicate.op,
+                                FilterOp::Eq | FilterOp::Ne | FilterOp::In
+                            ) =>
+                        {
+                            Some(FilterSqlValue::Text(text.to_lowercase()))
+                        }
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                Some(FilterSqlCondition {
+                    column: column.column,
+                    op: predicate.op,
+                    values,
+                })
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for PayloadFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let predicates = self
+            .predicates
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        f.write_str(&predicates.join(" && "))
+    }
+}
+
+impl FromStr for PayloadFilter {
+    type Err = PayloadFilterError;
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(value)?;
+        if tokens.is_empty() {
+            return Err(PayloadFilterError::Empty);
+        }
+        let mut parser = Parser {
+            tokens: tokens.into_iter().peekable(),
+        };
+        let mut predicates = vec![parser.predicate()?];
+        while let Some(token) = parser.tokens.next() {
+            match token {
+                Token::And => predicates.push(parser.predicate()?),
+                token => {
+                    return Err(PayloadFilterError::Unexpected {
+                        found: token.to_string(),
+                        expected: "'&&'",
+                    })
+                }
+            }
+        }
+        Ok(Self { predicates })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Text(String),
+    Op(FilterOp),
+    And,
+    LBracket,
+    RBracket,
+    Comma,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ident(value) => f.write_str(value),
+            Token::Text(value) => write!(f, "{value:?}"),
+            Token::Op(op) => write!(f, "{op}"),
+            Token::And => f.write_str("&&"),
+            Token::LBracket => f.write_str("["),
+            Token::RBracket => f.write_str("]"),
+            Token::Comma => f.write_str(","),
+        }
+    }
+}
+
+fn tokenize(value: &str) -> Result<Vec<Token>, PayloadFilterError> {
+    let mut tokens = vec![];
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            ',' => Token::Comma,
+            '"' | '\'' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some(next) if next == c => break,
+                        Some(next) => text.push(next),
+                        None => {
+                            return Err(PayloadFilterError::UnterminatedString)
+                        }
+                    }
+                }
+                Token::Text(text)
+            }
+            '=' | '!' | '>' | '<' | '&' => {
+                let next = chars.next_if(|next| matches!(next, '=' | '&'));
+                match (c, next) {
+                    ('=', Some('=')) | ('=', None) => Token::Op(FilterOp::Eq),
+                    ('!', Some('=')) => Token::Op(FilterOp::Ne),
+                    ('>', Some('=')) => Token::Op(FilterOp::Gte),
+                    ('>', None) => Token::Op(FilterOp::Gt),
+                    ('<', Some('=')) => Token::Op(FilterOp::Lte),
+                    ('<', None) => Token::Op(FilterOp::Lt),
+                    ('&', Some('&')) => Token::And,
+                    _ => {
+                        return Err(PayloadFilterError::Unexpected {
+                            found: c.to_string(),
+                            expected: "an operator",
+                        })
+                    }
+                }
+            }
+            c if c.is_alphanumeric() || matches!(c, '_' | '-' | '.') => {
+                let mut ident = c.to_string();
+                while let Some(next) = chars.next_if(|next| {
+                    next.is_alphanumeric() || matches!(next, '_' | '-' | '.')
+                }) {
+                    ident.push(next);
+                }
+                match ident.as_str() {
+                    "and" | "AND" => Token::And,
+                    "in" | "IN" => Token::Op(FilterOp::In),
+                    _ => Token::Ident(ident),
+                }
+            }
+            c => {
+                return Err(PayloadFilterError::Unexpected {
+                    found: c.to_string(),
+                    expected: "a field, operator or value",
+                })
+            }
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: std::iter::Peekable<std::vec::IntoIter<Token>>,
+}
+
+impl Parser {
+    fn next(
+        &mut self,
+        expected: &'static str,
+    ) -> Result<Token, PayloadFilterError> {
+        self.tokens
+            .next()
+            .ok_or(PayloadFilterError::UnexpectedEnd(expected))
+    }
+
+    fn predicate(&mut self) -> Result<FilterPredicate, PayloadFilterError> {
+        let field = match self.next("a field")? {
+            Token::Ident(field) => field,
+            token => {
+                return Err(PayloadFilterError::Unexpected {
+                    found: token.to_string(),
+                    expected: "a field",
+                })
+            }
+        };
+        let op = match self.next("an operator")? {
+            Token::Op(op) => op,
+            token => {
+                return Err(PayloadFilterError::Unexpected {
+                    found: token.to_string(),
+                    expected: "an operator",
+                })
+            }
+        };
+        let values = match op {
+            FilterOp::In => self.list()?,
+            _ => vec![self.value()?],
+        };
+        Ok(FilterPredicate { field, op, values })
+    }
+
+    fn list(&mut self) -> Result<Vec<FilterValue>, PayloadFilterError> {
+        match self.next("'['")? {
+            Token::LBracket => {}
+            token => {
+                return Err(PayloadFilterError::Unexpected {
+                    found: token.to_string(),
+                    expected: "'['",
+                })
+            }
+        }
+        let mut values = vec![self.value()?];
+        loop {
+            match self.next("']'")? {
+                Token::Comma => values.push(self.value()?),
+                Token::RBracket => return Ok(values),
+                token => {
+                    return Err(PayloadFilterError::Unexpected {
+                        found: token.to_string(),
+                        expected: "',' or ']'",
+                    })
+                }
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<FilterValue, PayloadFilterError> {
+        match self.next("a value")? {
+            Token::Text(text) => Ok(FilterValue::Text(text)),
+            Token::Ident(ident) => Ok(match ident.as_str() {
+                "true" => FilterValue::Bool(true),
+                "false" => FilterValue::Bool(false),
+                _ => match ident.parse::<i128>() {
+                    Ok(number) => FilterValue::Number(number),
+                    // Bare words such as hex ids are taken as text
+                    Err(_) => FilterValue::Text(ident),
+                },
+            }),
+            token => Err(PayloadFilterError::Unexpected {
+                found: token.to_string(),
+                expected: "a value",
+            }),
+        }
+    }
+}
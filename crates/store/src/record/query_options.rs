@@ -1,7 +1,9 @@
-use std::sync::LazyLock;
+use std::sync::{Arc, LazyLock};
 
 use pedronauck_streams_types::BlockHeight;
 
+use super::{PayloadFilter, RecordPointer};
+
 pub static STORE_PAGINATION_LIMIT: LazyLock<usize> = LazyLock::new(|| {
     dotenvy::var("STORE_PAGINATION_LIMIT")
         .ok()
@@ -11,25 +13,37 @@ pub static STORE_PAGINATION_LIMIT: LazyLock<usize> = LazyLock::new(|| {
 
 #[derive(Debug, Clone)]
 pub struct QueryOptions {
+    #[deprecated(note = "use `after_pointer` for keyset pagination instead")]
     pub offset: i64,
     pub limit: i64,
     pub from_block: Option<BlockHeight>,
+    pub to_block: Option<BlockHeight>,
+    pub after_pointer: Option<RecordPointer>,
     pub namespace: Option<String>,
     pub distinct: bool,
+    pub filter: Option<Arc<PayloadFilter>>,
 }
 impl Default for QueryOptions {
+    #[allow(deprecated)]
     fn default() -> Self {
         Self {
             offset: 0,
             limit: *STORE_PAGINATION_LIMIT as i64,
             from_block: None,
+            to_block: None,
+            after_pointer: None,
             namespace: None,
             distinct: false,
+            filter: None,
         }
     }
 }
 
 impl QueryOptions {
+    #[deprecated(
+        note = "use `with_after_pointer` for keyset pagination instead"
+    )]
+    #[allow(deprecated)]
     pub fn with_offset(mut self, offset: i64) -> Self {
         self.offset = offset.max(0);
         self
@@ -42,6 +56,17 @@ impl QueryOptions {
         self.from_block = from_block;
         self
     }
+    pub fn with_to_block(mut self, to_block: Option<BlockHeight>) -> Self {
+        self.to_block = to_block;
+        self
+    }
+    pub fn with_after_pointer(
+        mut self,
+        after_pointer: Option<RecordPointer>,
+    ) -> Self {
+        self.after_pointer = after_pointer;
+        self
+    }
     pub fn with_namespace(mut self, namespace: Option<String>) -> Self {
         self.namespace = namespace;
         self
@@ -50,6 +75,17 @@ impl QueryOptions {
         self.distinct = distinct;
         self
     }
+    pub fn with_filter(mut self, filter: Option<Arc<PayloadFilter>>) -> Self {
+        self.filter = filter;
+        self
+    }
+    /// Moves the keyset cursor so the next page starts right after the
+    /// given pointer.
+    pub fn seek_after(&mut self, pointer: RecordPointer) {
+        self.after_pointer = Some(pointer);
+    }
+    #[deprecated(note = "use `seek_after` for keyset pagination instead")]
+    #[allow(deprecated)]
     pub fn increment_offset(&mut self) {
         self.offset += self.limit;
     }
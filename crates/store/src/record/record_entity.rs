@@ -15,8 +15,18 @@ pub enum RecordEntity {
     Output,
     Receipt,
     Utxo,
+    Bundle,
+    Account,
+    ContractTransaction,
+    Coin,
+    BalanceChange,
+    Message,
+    Contract,
 }
 
+/// ID of the subject of contracts deployed by `Create` transactions
+const CONTRACTS_CREATED: &str = "contracts_created";
+
 impl std::fmt::Display for RecordEntity {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(self.as_str())
@@ -32,6 +42,13 @@ impl RecordEntity {
             Self::Output => "output",
             Self::Receipt => "receipt",
             Self::Utxo => "utxo",
+            Self::Bundle => "bundle",
+            Self::Account => "account",
+            Self::ContractTransaction => "contract_transaction",
+            Self::Coin => "coin",
+            Self::BalanceChange => "balance_change",
+            Self::Message => "message",
+            Self::Contract => "contract",
         }
     }
 
@@ -43,7 +60,11 @@ impl RecordEntity {
         subject: &str,
     ) -> Result<RecordEntity, RecordEntityError> {
         let subject = subject.to_lowercase();
-        let subject_entity = if subject.contains("_") {
+        // Created contracts are an entity of their own, rather than records
+        // of the one named by the start of their subject
+        let subject_entity = if subject.contains("_")
+            && !subject.starts_with(CONTRACTS_CREATED)
+        {
             subject
                 .split("_")
                 .next()
@@ -66,6 +87,13 @@ impl TryFrom<&str> for RecordEntity {
             s if s.contains("output") => Ok(Self::Output),
             s if s.contains("receipt") => Ok(Self::Receipt),
             s if s.contains("utxo") => Ok(Self::Utxo),
+            s if s.contains("bundle") => Ok(Self::Bundle),
+            s if s.contains("account") => Ok(Self::Account),
+            s if s.contains(CONTRACTS_CREATED) => Ok(Self::Contract),
+            s if s.contains("contract") => Ok(Self::ContractTransaction),
+            s if s.contains("coin") => Ok(Self::Coin),
+            s if s.contains("balance") => Ok(Self::BalanceChange),
+            s if s.contains("message") => Ok(Self::Message),
             _ => Err(RecordEntityError::UnknownSubject(s.to_string())),
         }
     }
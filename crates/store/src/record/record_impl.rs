@@ -5,11 +5,24 @@ pub use pedronauck_data_parser::{
     DataEncoder,
     DataParserError as EncoderError,
 };
-use pedronauck_streams_subject::subject::IntoSubject;
+use pedronauck_streams_subject::subject::{
+    IntoSubject,
+    SqlCondition,
+    SqlValue,
+};
 use pedronauck_streams_types::BlockTimestamp;
 use sqlx::{PgConnection, PgExecutor, Postgres, QueryBuilder};
 
-use super::{QueryOptions, RecordEntity, RecordPacket, RecordPointer};
+use super::{
+    FilterColumn,
+    FilterOp,
+    FilterSqlCondition,
+    FilterSqlValue,
+    QueryOptions,
+    RecordEntity,
+    RecordPacket,
+    RecordPointer,
+};
 use crate::db::{DbError, DbItem, DbResult};
 
 pub trait RecordEncoder: DataEncoder<Err = DbError> {}
@@ -24,6 +37,9 @@ pub trait Record: RecordEncoder + 'static {
 
     const ENTITY: RecordEntity;
     const ORDER_PROPS: &'static [&'static str];
+    /// Payload fields that payload filters can push down to the record's
+    /// table
+    const FILTER_COLUMNS: &'static [FilterColumn] = &[];
 
     async fn insert<'e, 'c: 'e, E>(
         executor: E,
@@ -60,55 +76,153 @@ pub trait Record: RecordEncoder + 'static {
         Self::decode_json(record.encoded_value())
     }
 
-    // TODO: remove complexity here; make each record type have its own query builder
     fn build_find_many_query(
         subject: Arc<dyn IntoSubject>,
         options: QueryOptions,
     ) -> QueryBuilder<'static, Postgres> {
         let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::default();
-        let order_props = Self::ORDER_PROPS.join(", ");
-
-        if order_props != "block_height" {
-            query_builder.push("WITH items AS (");
-        }
+        let order_columns = order_columns(Self::ORDER_PROPS);
 
-        // Internal select statement
         query_builder.push("SELECT ");
         if options.distinct {
             query_builder.push("DISTINCT ON (block_height) ");
         }
         query_builder.push("* FROM ");
         query_builder.push(Self::ENTITY.table_name());
-        let mut conditions = Vec::new();
-        if let Some(where_clause) = subject.to_sql_where() {
-            conditions.push(where_clause);
+        let mut separator = " WHERE ";
+        for condition in subject.to_sql_conditions() {
+            query_builder.push(separator);
+            push_subject_condition(&mut query_builder, condition);
+            separator = " AND ";
+        }
+        if let Some(filter) = &options.filter {
+            for condition in filter.sql_conditions(Self::FILTER_COLUMNS) {
+                query_builder.push(separator);
+                push_filter_condition(&mut query_builder, condition);
+                separator = " AND ";
+            }
         }
         if let Some(block) = options.from_block {
-            conditions.push(format!("block_height >= {}", block));
+            query_builder
+                .push(separator)
+                .push("block_height >= ")
+                .push_bind(block);
+            separator = " AND ";
+        }
+        if let Some(block) = options.to_block {
+            query_builder
+                .push(separator)
+                .push("block_height <= ")
+                .push_bind(block);
+            separator = " AND ";
+        }
+        // Keyset pagination: seek past the last record of the previous page
+        // using the same columns as the ordering indexes
+        if let Some(pointer) = options.after_pointer {
+            query_builder.push(separator);
+            push_after_pointer(&mut query_builder, &pointer, &order_columns);
+            separator = " AND ";
         }
         if cfg!(any(test, feature = "test-helpers")) {
             if let Some(ns) = options.namespace {
-                conditions.push(format!("subject LIKE '{ns}%'"));
+                query_builder
+                    .push(separator)
+                    .push("subject LIKE ")
+                    .push_bind(format!("{ns}%"));
             }
         }
-        if !conditions.is_empty() {
-            query_builder.push(" WHERE ");
-            query_builder.push(conditions.join(" AND "));
-        }
-        query_builder.push(" ORDER BY block_height ASC");
+        query_builder.push(" ORDER BY ");
+        query_builder.push(order_columns.join(", "));
+        query_builder.push(" ASC");
         query_builder.push(" LIMIT ");
         query_builder.push_bind(options.limit);
-        query_builder.push(" OFFSET ");
-        query_builder.push_bind(options.offset);
-
-        if order_props != "block_height" {
-            query_builder.push(") SELECT * FROM items ");
-            query_builder.push("ORDER BY ");
-            query_builder.push(Self::ORDER_PROPS.join(", "));
-            query_builder.push(" ASC");
+        // Kept for callers still paging by offset, which skips or repeats
+        // records when rows are inserted between pages
+        #[allow(deprecated)]
+        let offset = options.offset;
+        if offset > 0 {
+            query_builder.push(" OFFSET ");
+            query_builder.push_bind(offset);
         }
 
         tracing::info!("Query built: {}", &query_builder.sql());
         query_builder
     }
 }
+
+/// Columns records are ordered by: the block height followed by the
+/// record's `ORDER_PROPS`, matching the `idx_*_ordering` indexes.
+fn order_columns(order_props: &[&'static str]) -> Vec<&'static str> {
+    let mut columns = vec!["block_height"];
+    columns.extend(
+        order_props
+            .iter()
+            .filter(|column| **column != "block_height"),
+    );
+    columns
+}
+
+fn push_subject_condition(
+    query_builder: &mut QueryBuilder<'static, Postgres>,
+    condition: SqlCondition,
+) {
+    match condition {
+        SqlCondition::Eq(column, value) => {
+            query_builder.push(column).push(" = ");
+            match value {
+                SqlValue::Int(value) => query_builder.push_bind(value),
+                SqlValue::Text(value) => query_builder.push_bind(value),
+            };
+        }
+        SqlCondition::Raw(sql) => {
+            query_builder.push(sql);
+        }
+    }
+}
+
+fn push_filter_condition(
+    query_builder: &mut QueryBuilder<'static, Postgres>,
+    condition: FilterSqlCondition,
+) {
+    query_builder
+        .push(condition.column)
+        .push(condition.op.as_sql());
+    if condition.op == FilterOp::In {
+        query_builder.push("(");
+    }
+    let mut values_list = query_builder.separated(", ");
+    for value in condition.values {
+        match value {
+            FilterSqlValue::Int(value) => values_list.push_bind(value),
+            FilterSqlValue::Text(value) => values_list.push_bind(value),
+        };
+    }
+    if condition.op == FilterOp::In {
+        query_builder.push(")");
+    }
+}
+
+/// Row comparison selecting the records strictly after a pointer, using the
+/// order columns up to the last one the pointer has a value for. Columns
+/// the pointer leaves unset before that one are compared against `-1`, the
+/// value tables store for indexes a record doesn't have.
+fn push_after_pointer(
+    query_builder: &mut QueryBuilder<'static, Postgres>,
+    pointer: &RecordPointer,
+    order_columns: &[&'static str],
+) {
+    let len = order_columns
+        .iter()
+        .rposition(|column| pointer.column_value(column).is_some())
+        .map_or(0, |position| position + 1);
+    let columns = &order_columns[..len];
+    query_builder
+        .push("(")
+        .push(columns.join(", "))
+        .push(") > (");
+    let mut values_list = query_builder.separated(", ");
+    for column in columns {
+        values_list.push_bind(pointer.column_value(column).unwrap_or(-1));
+    }
+    query_builder.push(")");
+}
@@ -13,6 +13,8 @@ pub enum RecordPacketError {
     SubjectMismatch,
     #[error(transparent)]
     EncodeError(#[from] EncoderError),
+    #[error("Invalid record pointer: {0}")]
+    InvalidPointer(String),
 }
 
 pub trait PacketBuilder: Send + Sync + 'static {
@@ -20,7 +22,22 @@ pub trait PacketBuilder: Send + Sync + 'static {
     fn build_packets(opts: &Self::Opts) -> Vec<RecordPacket>;
 }
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+/// Position of a record in the chain. Pointers are ordered by block height
+/// first and then by each index, matching the order records are stored in.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    utoipa::ToSchema,
+)]
 pub struct RecordPointer {
     pub block_height: BlockHeight,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -31,6 +48,80 @@ pub struct RecordPointer {
     pub output_index: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub receipt_index: Option<u32>,
+    /// Position of the record among the rows a single input, output or
+    /// receipt fans out to, such as both sides of a transfer, so each row
+    /// has a pointer of its own
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fanout_index: Option<u32>,
+}
+
+impl RecordPointer {
+    /// Value of the pointer for one of the ordering columns of a record
+    /// table, if the pointer has it.
+    pub fn column_value(&self, column: &str) -> Option<i64> {
+        match column {
+            "block_height" => Some(self.block_height.into_inner() as i64),
+            "tx_index" => self.tx_index.map(i64::from),
+            "input_index" => self.input_index.map(i64::from),
+            "output_index" => self.output_index.map(i64::from),
+            "receipt_index" => self.receipt_index.map(i64::from),
+            "fanout_index" => self.fanout_index.map(i64::from),
+            _ => None,
+        }
+    }
+}
+
+/// Compact string form of a pointer: the block height followed by each
+/// index that is set, e.g. `120,tx_index=3,receipt_index=7`.
+impl std::fmt::Display for RecordPointer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.block_height)?;
+        let indexes = [
+            ("tx_index", self.tx_index),
+            ("input_index", self.input_index),
+            ("output_index", self.output_index),
+            ("receipt_index", self.receipt_index),
+            ("fanout_index", self.fanout_index),
+        ];
+        for (name, value) in indexes {
+            if let Some(value) = value {
+                write!(f, ",{}={}", name, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for RecordPointer {
+    type Err = RecordPacketError;
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || RecordPacketError::InvalidPointer(value.to_string());
+        let mut parts = value.split(',').map(str::trim);
+        let block_height = parts
+            .next()
+            .filter(|height| !height.is_empty())
+            .ok_or_else(invalid)?
+            .parse::<BlockHeight>()
+            .map_err(|_| invalid())?;
+        let mut pointer = RecordPointer {
+            block_height,
+            ..Default::default()
+        };
+        for part in parts {
+            let (name, index) = part.split_once('=').ok_or_else(invalid)?;
+            let index =
+                Some(index.trim().parse::<u32>().map_err(|_| invalid())?);
+            match name.trim() {
+                "tx_index" => pointer.tx_index = index,
+                "input_index" => pointer.input_index = index,
+                "output_index" => pointer.output_index = index,
+                "receipt_index" => pointer.receipt_index = index,
+                "fanout_index" => pointer.fanout_index = index,
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(pointer)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -79,6 +170,26 @@ impl RecordPacket {
         self
     }
 
+    /// Position of the packet's record within the given block, read from
+    /// the indexes carried by its subject params
+    pub fn pointer(&self, block_height: BlockHeight) -> RecordPointer {
+        let params = &self.subject_payload.params;
+        let index = |name: &str| {
+            params
+                .get(name)
+                .and_then(|value| value.as_u64())
+                .map(|value| value as u32)
+        };
+        RecordPointer {
+            block_height,
+            tx_index: index("tx_index"),
+            input_index: index("input_index"),
+            output_index: index("output_index"),
+            receipt_index: index("receipt_index"),
+            fanout_index: None,
+        }
+    }
+
     pub fn subject_id(&self) -> String {
         self.subject_payload.subject.to_string()
     }
@@ -8,4 +8,6 @@ pub enum StoreError {
     Stream(#[from] sqlx::Error),
     #[error("Failed to find last block height: {0}")]
     FindLastBlockHeight(#[source] sqlx::Error),
+    #[error("Failed to find block height by timestamp: {0}")]
+    FindBlockHeightByTimestamp(#[source] sqlx::Error),
 }
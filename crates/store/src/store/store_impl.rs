@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use pedronauck_data_parser::DataEncoder;
 use pedronauck_streams_subject::subject::IntoSubject;
-use pedronauck_streams_types::BlockHeight;
+use pedronauck_streams_types::{BlockHeight, BlockTimestamp};
 
 use super::StoreError;
 use crate::{
@@ -76,10 +76,19 @@ pub async fn find_last_block_height(
 ) -> StoreResult<BlockHeight> {
     let select = "SELECT block_height FROM blocks".to_string();
     let mut query_builder = sqlx::QueryBuilder::new(select);
+    let mut separator = " WHERE ";
     if let Some(ns) = options.namespace {
         query_builder
-            .push(" WHERE subject LIKE ")
+            .push(separator)
+            .push("subject LIKE ")
             .push_bind(format!("{}%", ns));
+        separator = " AND ";
+    }
+    if let Some(to_block) = options.to_block {
+        query_builder
+            .push(separator)
+            .push("block_height <= ")
+            .push_bind(to_block);
     }
 
     query_builder.push(" ORDER BY block_height DESC LIMIT 1");
@@ -92,6 +101,30 @@ pub async fn find_last_block_height(
     Ok(record.map(|(height,)| height.into()).unwrap_or_default())
 }
 
+pub async fn find_block_height_by_timestamp(
+    db: &Db,
+    timestamp: BlockTimestamp,
+    options: QueryOptions,
+) -> StoreResult<Option<BlockHeight>> {
+    let select = "SELECT block_height FROM blocks WHERE created_at >= ";
+    let mut query_builder = sqlx::QueryBuilder::new(select);
+    query_builder.push_bind(timestamp);
+    if let Some(ns) = options.namespace {
+        query_builder
+            .push(" AND subject LIKE ")
+            .push_bind(format!("{}%", ns));
+    }
+
+    query_builder.push(" ORDER BY block_height ASC LIMIT 1");
+    let query = query_builder.build_query_as::<(i64,)>();
+    let record: Option<(i64,)> = query
+        .fetch_optional(&db.pool)
+        .await
+        .map_err(StoreError::FindBlockHeightByTimestamp)?;
+
+    Ok(record.map(|(height,)| height.into()))
+}
+
 #[derive(Debug, Clone)]
 pub struct BlockHeightGap {
     pub start: BlockHeight,
@@ -1,6 +1,7 @@
 #![doc = include_str!("../README.md")]
 mod payload;
 mod schema;
+mod sql;
 
 pub mod subject {
     pub use std::fmt::Debug;
@@ -8,16 +9,16 @@ pub mod subject {
     use downcast_rs::{impl_downcast, Downcast};
     pub use indexmap::IndexMap;
     pub use serde_json;
-    pub use subject_derive::*;
+    pub use pedronauck_subject_derive::*;
 
     #[allow(unused_imports)]
-    pub use crate::{payload::*, schema::*};
+    pub use crate::{payload::*, schema::*, sql::*};
 
     pub trait IntoSubject: Debug + Downcast + Send + Sync + 'static {
         fn id(&self) -> &'static str;
         fn parse(&self) -> String;
         fn query_all(&self) -> &'static str;
-        fn to_sql_where(&self) -> Option<String>;
+        fn to_sql_conditions(&self) -> Vec<SqlCondition>;
         fn to_sql_select(&self) -> Option<String>;
         fn schema(&self) -> Schema;
         fn to_payload(&self) -> SubjectPayload;
@@ -0,0 +1,25 @@
+/// Value of a subject field as it reaches Postgres, always through a bound
+/// parameter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SqlValue {
+    Int(i64),
+    Text(String),
+}
+
+impl std::fmt::Display for SqlValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SqlValue::Int(value) => write!(f, "{value}"),
+            SqlValue::Text(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// A single `WHERE` condition produced by a subject.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SqlCondition {
+    /// `column = $n`, where the value is bound as a parameter
+    Eq(&'static str, SqlValue),
+    /// Static SQL declared on the subject itself through `custom_where`
+    Raw(&'static str),
+}
@@ -114,9 +114,55 @@ impl FieldAttributes {
 #[derive(Debug)]
 pub struct FieldInfo<'a> {
     pub ident: &'a Ident,
+    pub ty: &'a Type,
     pub attributes: FieldAttributes,
 }
 
+/// Types stored in integer columns, so their values are bound as `i64`
+/// instead of text
+const INTEGER_TYPES: &[&str] = &[
+    "u8",
+    "u16",
+    "u32",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "BlockHeight",
+    "DaBlockHeight",
+];
+
+impl FieldInfo<'_> {
+    pub fn sql_column(&self) -> String {
+        match &self.attributes.sql_column {
+            Some(val) => val.clone(),
+            None => self.ident.to_string(),
+        }
+    }
+
+    pub fn is_integer(&self) -> bool {
+        option_inner_ident(self.ty)
+            .is_some_and(|ident| INTEGER_TYPES.iter().any(|name| ident == name))
+    }
+}
+
+fn option_inner_ident(ty: &Type) -> Option<&Ident> {
+    let Type::Path(TypePath { path, .. }) = ty else {
+        return None;
+    };
+    let syn::PathArguments::AngleBracketed(args) =
+        &path.segments.last()?.arguments
+    else {
+        return None;
+    };
+    match args.args.first()? {
+        syn::GenericArgument::Type(Type::Path(TypePath { path, .. })) => {
+            path.segments.last().map(|segment| &segment.ident)
+        }
+        _ => None,
+    }
+}
+
 fn is_option_type(ty: &Type) -> bool {
     if let Type::Path(TypePath {
         path: syn::Path { segments, .. },
@@ -175,9 +221,11 @@ pub fn names_from_fields(fields: &Punctuated<Field, Comma>) -> Vec<FieldInfo> {
         .iter()
         .filter_map(|f| {
             let attributes = FieldAttributes::from_field(f);
-            f.ident
-                .as_ref()
-                .map(|ident| FieldInfo { ident, attributes })
+            f.ident.as_ref().map(|ident| FieldInfo {
+                ident,
+                ty: &f.ty,
+                attributes,
+            })
         })
         .collect()
 }
@@ -31,40 +31,35 @@ pub fn query_all_fn() -> TokenStream {
     }
 }
 
-pub fn to_sql_where_fn(fields: &[FieldInfo]) -> TokenStream {
+pub fn to_sql_conditions_fn(fields: &[FieldInfo]) -> TokenStream {
+    let crate_path = quote!(pedronauck_streams_subject::subject);
     let conditions: Vec<TokenStream> = fields
         .iter()
         .map(|field| {
             let name = field.ident;
-            let column_name = match &field.attributes.sql_column {
-                Some(val) => val.clone(),
-                None => name.to_string(),
+            let column_name = field.sql_column();
+            let value = if field.is_integer() {
+                quote!(#crate_path::SqlValue::Int(i64::from(*val)))
+            } else {
+                quote!(#crate_path::SqlValue::Text(val.to_string()))
             };
 
             quote! {
                 if let Some(val) = &self.#name {
-                    conditions.push(format!("{} = '{}'", #column_name, val));
+                    conditions.push(#crate_path::SqlCondition::Eq(#column_name, #value));
                 }
             }
         })
         .collect();
 
     quote! {
-        fn to_sql_where(&self) -> Option<String> {
+        fn to_sql_conditions(&self) -> Vec<#crate_path::SqlCondition> {
             let mut conditions = Vec::new();
             #(#conditions)*
-
             if let Some(extra) = Self::EXTRA_WHERE {
-                if conditions.is_empty() {
-                    Some(extra.to_string())
-                } else {
-                    Some(format!("{} AND {}", conditions.join(" AND "), extra))
-                }
-            } else if conditions.is_empty() {
-                None
-            } else {
-                Some(conditions.join(" AND "))
+                conditions.push(#crate_path::SqlCondition::Raw(extra));
             }
+            conditions
         }
     }
 }
@@ -74,10 +69,7 @@ pub fn to_sql_select_fn(fields: &[FieldInfo]) -> TokenStream {
         .iter()
         .map(|field| {
             let name = field.ident;
-            let column_name = match &field.attributes.sql_column {
-                Some(val) => val.clone(),
-                None => name.to_string(),
-            };
+            let column_name = field.sql_column();
 
             quote! {
                 if self.#name.is_some() {
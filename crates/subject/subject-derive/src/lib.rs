@@ -20,7 +20,7 @@ pub fn subject_derive(input: TokenStream) -> TokenStream {
     let id_fn = into_subject::id_fn();
     let query_all_fn = into_subject::query_all_fn();
     let parse_fn = into_subject::parse_fn(&input, &field_names);
-    let to_sql_where_fn = into_subject::to_sql_where_fn(&field_infos);
+    let to_sql_conditions_fn = into_subject::to_sql_conditions_fn(&field_infos);
     let to_sql_select_fn = into_subject::to_sql_select_fn(&field_infos);
     let to_payload_fn = into_subject::to_payload_fn(&input);
     let schema_fn = into_subject::schema_fn(
@@ -53,7 +53,7 @@ pub fn subject_derive(input: TokenStream) -> TokenStream {
             #id_fn
             #parse_fn
             #query_all_fn
-            #to_sql_where_fn
+            #to_sql_conditions_fn
             #to_sql_select_fn
             #schema_fn
             #to_payload_fn
@@ -60,13 +60,11 @@ fn subject_derive_sql_where_exact_match() {
     };
 
     assert_eq!(subject.parse(), "test.foo.55.bar");
-    assert_eq!(
-        subject.to_sql_where(),
-        Some(
-            "field_id1 = 'foo' AND field_id2 = '55' AND field_id3 = 'bar'"
-                .to_string()
-        )
-    );
+    assert_eq!(subject.to_sql_conditions(), vec![
+        SqlCondition::Eq("field_id1", SqlValue::Text("foo".to_string())),
+        SqlCondition::Eq("field_id2", SqlValue::Int(55)),
+        SqlCondition::Eq("field_id3", SqlValue::Text("bar".to_string())),
+    ]);
 }
 
 #[test]
@@ -78,10 +76,10 @@ fn subject_derive_sql_where_subject_string() {
     };
 
     assert_eq!(subject.parse(), "test.*.55.bar");
-    assert_eq!(
-        subject.to_sql_where(),
-        Some("field_id2 = '55' AND field_id3 = 'bar'".to_string())
-    );
+    assert_eq!(subject.to_sql_conditions(), vec![
+        SqlCondition::Eq("field_id2", SqlValue::Int(55)),
+        SqlCondition::Eq("field_id3", SqlValue::Text("bar".to_string())),
+    ]);
 }
 
 #[test]
@@ -92,10 +90,10 @@ fn subject_derive_sql_where_greater_than() {
         field3: Some("bar".to_string()),
     };
 
-    assert_eq!(
-        subject.to_sql_where(),
-        Some("field_id1 = 'foo' AND field_id3 = 'bar'".to_string())
-    );
+    assert_eq!(subject.to_sql_conditions(), vec![
+        SqlCondition::Eq("field_id1", SqlValue::Text("foo".to_string())),
+        SqlCondition::Eq("field_id3", SqlValue::Text("bar".to_string())),
+    ]);
 }
 
 #[test]
@@ -107,15 +105,15 @@ fn subject_derive_sql_where_table_only() {
     };
 
     assert_eq!(subject.parse(), "test.>");
-    assert_eq!(subject.to_sql_where(), None);
+    assert!(subject.to_sql_conditions().is_empty());
 
     let subject2 = TestSubject::default();
     assert_eq!(subject2.parse(), "test.>");
-    assert_eq!(subject2.to_sql_where(), None);
+    assert!(subject2.to_sql_conditions().is_empty());
 
     let subject3 = TestSubject::new();
     assert_eq!(subject3.parse(), "test.>");
-    assert_eq!(subject3.to_sql_where(), None);
+    assert!(subject3.to_sql_conditions().is_empty());
 }
 
 #[test]
@@ -277,10 +275,12 @@ fn subject_derive_sql_where_with_custom_where() {
         field2: Some(55),
         field3: Some("bar".to_string()),
     };
-    assert_eq!(
-        subject.to_sql_where(),
-        Some("field_id1 = 'foo' AND field_id2 = '55' AND field_id3 = 'bar' AND deleted_at IS NULL".to_string())
-    );
+    assert_eq!(subject.to_sql_conditions(), vec![
+        SqlCondition::Eq("field_id1", SqlValue::Text("foo".to_string())),
+        SqlCondition::Eq("field_id2", SqlValue::Int(55)),
+        SqlCondition::Eq("field_id3", SqlValue::Text("bar".to_string())),
+        SqlCondition::Raw("deleted_at IS NULL"),
+    ]);
 
     // Test with partial fields
     let subject = TestSubjectWithExtra {
@@ -288,17 +288,16 @@ fn subject_derive_sql_where_with_custom_where() {
         field2: None,
         field3: None,
     };
-    assert_eq!(
-        subject.to_sql_where(),
-        Some("field_id1 = 'foo' AND deleted_at IS NULL".to_string())
-    );
+    assert_eq!(subject.to_sql_conditions(), vec![
+        SqlCondition::Eq("field_id1", SqlValue::Text("foo".to_string())),
+        SqlCondition::Raw("deleted_at IS NULL"),
+    ]);
 
     // Test with no fields
     let subject = TestSubjectWithExtra::default();
-    assert_eq!(
-        subject.to_sql_where(),
-        Some("deleted_at IS NULL".to_string())
-    );
+    assert_eq!(subject.to_sql_conditions(), vec![SqlCondition::Raw(
+        "deleted_at IS NULL"
+    ),]);
 }
 
 #[test]
@@ -394,13 +393,11 @@ fn subject_derive_with_alias() {
     };
     let subject1 = TestSubjectWithAlias::try_from(payload1).unwrap();
     assert_eq!(subject1.parse(), "test.foo.55.bar");
-    assert_eq!(
-        subject1.to_sql_where(),
-        Some(
-            "field_id1 = 'foo' AND field_id2 = '55' AND field_id3 = 'bar'"
-                .to_string()
-        )
-    );
+    assert_eq!(subject1.to_sql_conditions(), vec![
+        SqlCondition::Eq("field_id1", SqlValue::Text("foo".to_string())),
+        SqlCondition::Eq("field_id2", SqlValue::Int(55)),
+        SqlCondition::Eq("field_id3", SqlValue::Text("bar".to_string())),
+    ]);
 
     // Test creating subject using alias names
     let payload2 = SubjectPayload {
@@ -412,10 +409,10 @@ fn subject_derive_with_alias() {
     };
     let subject2 = TestSubjectWithAlias::try_from(payload2).unwrap();
     assert_eq!(subject2.parse(), "test.foo.55.*");
-    assert_eq!(
-        subject2.to_sql_where(),
-        Some("field_id1 = 'foo' AND field_id2 = '55'".to_string())
-    );
+    assert_eq!(subject2.to_sql_conditions(), vec![
+        SqlCondition::Eq("field_id1", SqlValue::Text("foo".to_string())),
+        SqlCondition::Eq("field_id2", SqlValue::Int(55)),
+    ]);
 
     // Test that both regular field names and aliases work together
     let payload3 = SubjectPayload {
@@ -427,8 +424,8 @@ fn subject_derive_with_alias() {
     };
     let subject3 = TestSubjectWithAlias::try_from(payload3).unwrap();
     assert_eq!(subject3.parse(), "test.foo.55.*");
-    assert_eq!(
-        subject3.to_sql_where(),
-        Some("field_id1 = 'foo' AND field_id2 = '55'".to_string())
-    );
+    assert_eq!(subject3.to_sql_conditions(), vec![
+        SqlCondition::Eq("field_id1", SqlValue::Text("foo".to_string())),
+        SqlCondition::Eq("field_id2", SqlValue::Int(55)),
+    ]);
 }
@@ -21,7 +21,7 @@ pub enum TimeUnit {
     Days,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Copy)]
 pub struct BlockTimestamp(pub DateTime<Utc>);
 
 impl BlockTimestamp {
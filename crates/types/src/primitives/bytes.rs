@@ -81,6 +81,12 @@ impl_utoipa_for_byte_type_detailed!(
     "A 32-byte Fuel nonce with 0x prefix"
 );
 
+impl_utoipa_for_byte_type_detailed!(
+    MessageId,
+    32,
+    "A 32-byte message identifier with 0x prefix"
+);
+
 impl TxId {
     pub fn random() -> Self {
         let mut rng = rand::rng();
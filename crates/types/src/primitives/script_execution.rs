@@ -1,4 +1,7 @@
-use fuel_core_types::{fuel_asm::RawInstruction, fuel_tx::PanicReason};
+use fuel_core_types::{
+    fuel_asm::{Instruction, RawInstruction},
+    fuel_tx::PanicReason,
+};
 
 use crate::fuel_core::*;
 
@@ -112,6 +115,22 @@ impl utoipa::PartialSchema for PanicInstruction {
     }
 }
 
+impl PanicInstruction {
+    /// Name of the panic reason, e.g. `NotEnoughBalance`
+    pub fn reason_name(&self) -> String {
+        self.reason.to_string()
+    }
+
+    /// The instruction that panicked, e.g. `TR { contract_id_addr: 0x10,
+    /// amount: 0x11, asset_id_addr: 0x12 }`, or `None` when it isn't a
+    /// valid instruction
+    pub fn decoded_instruction(&self) -> Option<String> {
+        Instruction::try_from(self.instruction)
+            .ok()
+            .map(|instruction| format!("{instruction:?}"))
+    }
+}
+
 impl From<FuelCorePanicInstruction> for PanicInstruction {
     fn from(value: FuelCorePanicInstruction) -> Self {
         Self {
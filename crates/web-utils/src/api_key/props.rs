@@ -30,10 +30,24 @@ pub enum HistoricalLimitError {
     InvalidFormat(String),
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum MessagesPerSecondError {
+    #[error("Failed to parse to stream_msgs_per_sec: {0}")]
+    InvalidFormat(String),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum BytesPerSecondError {
+    #[error("Failed to parse to stream_bytes_per_sec: {0}")]
+    InvalidFormat(String),
+}
+
 declare_integer_wrapper!(ApiKeyId, u32, ApiKeyIdError);
 declare_integer_wrapper!(ApiKeyRoleId, u32, ApiKeyRoleIdError);
 declare_integer_wrapper!(SubscriptionCount, u32, SubscriptionCountError);
 declare_integer_wrapper!(RateLimitPerMinute, u32, RateLimitPerMinuteError);
 declare_integer_wrapper!(HistoricalLimit, u32, HistoricalLimitError);
+declare_integer_wrapper!(MessagesPerSecond, u32, MessagesPerSecondError);
+declare_integer_wrapper!(BytesPerSecond, u32, BytesPerSecondError);
 declare_string_wrapper!(ApiKeyUserName);
 declare_string_wrapper!(ApiKeyValue);
@@ -6,7 +6,9 @@ use crate::api_key::{
     ApiKeyRoleId,
     ApiKeyRoleName,
     ApiKeyRoleScope,
+    BytesPerSecond,
     HistoricalLimit,
+    MessagesPerSecond,
     RateLimitPerMinute,
     SubscriptionCount,
 };
@@ -21,6 +23,8 @@ pub struct ApiKeyRole {
     subscription_limit: Option<SubscriptionCount>,
     rate_limit_per_minute: Option<RateLimitPerMinute>,
     historical_limit: Option<HistoricalLimit>,
+    stream_msgs_per_sec: Option<MessagesPerSecond>,
+    stream_bytes_per_sec: Option<BytesPerSecond>,
 }
 
 impl ApiKeyRole {
@@ -39,9 +43,22 @@ impl ApiKeyRole {
             subscription_limit,
             rate_limit_per_minute,
             historical_limit,
+            stream_msgs_per_sec: None,
+            stream_bytes_per_sec: None,
         }
     }
 
+    /// Caps how fast each subscription of the role can be streamed
+    pub fn with_stream_throughput(
+        mut self,
+        msgs_per_sec: Option<MessagesPerSecond>,
+        bytes_per_sec: Option<BytesPerSecond>,
+    ) -> Self {
+        self.stream_msgs_per_sec = msgs_per_sec;
+        self.stream_bytes_per_sec = bytes_per_sec;
+        self
+    }
+
     pub fn id(&self) -> &ApiKeyRoleId {
         &self.id
     }
@@ -66,6 +83,14 @@ impl ApiKeyRole {
         self.historical_limit
     }
 
+    pub fn stream_msgs_per_sec(&self) -> Option<MessagesPerSecond> {
+        self.stream_msgs_per_sec
+    }
+
+    pub fn stream_bytes_per_sec(&self) -> Option<BytesPerSecond> {
+        self.stream_bytes_per_sec
+    }
+
     pub fn has_scopes(
         &self,
         scopes: &[ApiKeyRoleScope],
@@ -91,7 +116,7 @@ impl ApiKeyRole {
         E: sqlx::PgExecutor<'c>,
     {
         sqlx::query_as::<_, Self>(
-            "SELECT id, name, scopes, subscription_limit, rate_limit_per_minute, historical_limit
+            "SELECT id, name, scopes, subscription_limit, rate_limit_per_minute, historical_limit, stream_msgs_per_sec, stream_bytes_per_sec
              FROM api_key_roles
              ORDER BY name",
         )
@@ -108,7 +133,7 @@ impl ApiKeyRole {
         E: sqlx::PgExecutor<'c>,
     {
         sqlx::query_as::<_, Self>(
-            "SELECT id, name, scopes, subscription_limit, rate_limit_per_minute, historical_limit
+            "SELECT id, name, scopes, subscription_limit, rate_limit_per_minute, historical_limit, stream_msgs_per_sec, stream_bytes_per_sec
              FROM api_key_roles
              WHERE name = $1::api_role",
         )
@@ -126,7 +151,7 @@ impl ApiKeyRole {
         E: sqlx::PgExecutor<'c>,
     {
         sqlx::query_as::<_, Self>(
-            "SELECT id, name, scopes, subscription_limit, rate_limit_per_minute, historical_limit
+            "SELECT id, name, scopes, subscription_limit, rate_limit_per_minute, historical_limit, stream_msgs_per_sec, stream_bytes_per_sec
              FROM api_key_roles
              WHERE id = $1",
         )
@@ -195,6 +220,8 @@ impl<'r> sqlx::Decode<'r, sqlx::Postgres> for ApiKeyRole {
             subscription_limit,
             rate_limit_per_minute,
             historical_limit,
+            stream_msgs_per_sec,
+            stream_bytes_per_sec,
         ) = <(
             ApiKeyRoleId,
             ApiKeyRoleName,
@@ -202,6 +229,8 @@ impl<'r> sqlx::Decode<'r, sqlx::Postgres> for ApiKeyRole {
             Option<SubscriptionCount>,
             Option<RateLimitPerMinute>,
             Option<HistoricalLimit>,
+            Option<MessagesPerSecond>,
+            Option<BytesPerSecond>,
         )>::decode(value)?;
         Ok(Self {
             id,
@@ -210,6 +239,8 @@ impl<'r> sqlx::Decode<'r, sqlx::Postgres> for ApiKeyRole {
             subscription_limit,
             rate_limit_per_minute,
             historical_limit,
+            stream_msgs_per_sec,
+            stream_bytes_per_sec,
         })
     }
 }
@@ -258,29 +289,41 @@ impl MockApiKeyRole {
     }
 
     pub fn builder() -> Self {
-        Self(ApiKeyRole::new(
-            ApiKeyRoleId::from(3),
-            ApiKeyRoleName::Builder,
-            vec![
-                ApiKeyRoleScope::HistoricalData,
-                ApiKeyRoleScope::LiveData,
-                ApiKeyRoleScope::RestApi,
-            ],
-            Some(SubscriptionCount::from(50)),
-            Some(RateLimitPerMinute::from(7)),
-            Some(HistoricalLimit::from(600)),
-        ))
+        Self(
+            ApiKeyRole::new(
+                ApiKeyRoleId::from(3),
+                ApiKeyRoleName::Builder,
+                vec![
+                    ApiKeyRoleScope::HistoricalData,
+                    ApiKeyRoleScope::LiveData,
+                    ApiKeyRoleScope::RestApi,
+                ],
+                Some(SubscriptionCount::from(50)),
+                Some(RateLimitPerMinute::from(7)),
+                Some(HistoricalLimit::from(600)),
+            )
+            .with_stream_throughput(
+                Some(MessagesPerSecond::from(50)),
+                Some(BytesPerSecond::from(1_048_576)),
+            ),
+        )
     }
 
     pub fn web_client() -> Self {
-        Self(ApiKeyRole::new(
-            ApiKeyRoleId::from(4),
-            ApiKeyRoleName::WebClient,
-            vec![ApiKeyRoleScope::LiveData, ApiKeyRoleScope::RestApi],
-            None,
-            Some(RateLimitPerMinute::from(1000)),
-            None,
-        ))
+        Self(
+            ApiKeyRole::new(
+                ApiKeyRoleId::from(4),
+                ApiKeyRoleName::WebClient,
+                vec![ApiKeyRoleScope::LiveData, ApiKeyRoleScope::RestApi],
+                None,
+                Some(RateLimitPerMinute::from(1000)),
+                None,
+            )
+            .with_stream_throughput(
+                Some(MessagesPerSecond::from(20)),
+                Some(BytesPerSecond::from(262_144)),
+            ),
+        )
     }
 
     pub fn no_scopes() -> Self {
@@ -1,11 +1,11 @@
 use std::{sync::Arc, time::Duration};
 
-use pedronauck_message_broker::NatsMessageBroker;
+use pedronauck_message_broker::MessageBroker;
 use tokio_util::sync::CancellationToken;
 
 pub const GRACEFUL_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(90);
 
-pub async fn shutdown_broker_with_timeout(broker: &Arc<NatsMessageBroker>) {
+pub async fn shutdown_broker_with_timeout(broker: &Arc<dyn MessageBroker>) {
     let _ = tokio::time::timeout(GRACEFUL_SHUTDOWN_TIMEOUT, async {
         tracing::info!("Flushing in-flight messages to broker ...");
         match broker.flush().await {
@@ -522,7 +522,7 @@ export const schema = {
                 id: "receipts_panic",
                 entity: "Receipt",
                 subject: "ReceiptsPanicSubject",
-                format: "receipts.panic.{block_height}.{tx_id}.{tx_index}.{receipt_index}.{contract}",
+                format: "receipts.panic.{block_height}.{tx_id}.{tx_index}.{receipt_index}.{contract}.{reason}",
                 wildcard: "receipts.panic.>",
                 fields: {
                     block_height: {
@@ -547,13 +547,17 @@ export const schema = {
                         description:
                             "The ID of the contract that panicked (32 byte string prefixed by 0x)",
                     },
+                    reason: {
+                        type: "String",
+                        description: "The name of the panic reason, e.g. NotEnoughBalance",
+                    },
                 },
             },
             revert: {
                 id: "receipts_revert",
                 entity: "Receipt",
                 subject: "ReceiptsRevertSubject",
-                format: "receipts.revert.{block_height}.{tx_id}.{tx_index}.{receipt_index}.{contract}",
+                format: "receipts.revert.{block_height}.{tx_id}.{tx_index}.{receipt_index}.{contract}.{signal}",
                 wildcard: "receipts.revert.>",
                 fields: {
                     block_height: {
@@ -578,6 +582,11 @@ export const schema = {
                         description:
                             "The ID of the contract that reverted (32 byte string prefixed by 0x)",
                     },
+                    signal: {
+                        type: "RevertSignal",
+                        description:
+                            "The Sway revert signal, e.g. failed_require, or custom for codes the contract reverted with itself",
+                    },
                 },
             },
             log: {
@@ -615,7 +624,7 @@ export const schema = {
                 id: "receipts_log_data",
                 entity: "Receipt",
                 subject: "ReceiptsLogDataSubject",
-                format: "receipts.log_data.{block_height}.{tx_id}.{tx_index}.{receipt_index}.{contract}",
+                format: "receipts.log_data.{block_height}.{tx_id}.{tx_index}.{receipt_index}.{contract}.{log_id}",
                 wildcard: "receipts.log_data.>",
                 fields: {
                     block_height: {
@@ -640,6 +649,11 @@ export const schema = {
                         description:
                             "The ID of the contract that emitted the log data (32 byte string prefixed by 0x)",
                     },
+                    log_id: {
+                        type: "u64",
+                        description:
+                            "The ID the value was logged under, matching the `logId` of the contract's ABI",
+                    },
                 },
             },
             transfer: {
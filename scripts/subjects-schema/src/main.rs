@@ -1,8 +1,14 @@
 use std::{env, fs, path::Path};
 
 use pedronauck_streams_domains::{
+    accounts::subjects::*,
+    balances::subjects::*,
     blocks::subjects::*,
+    bundles::subjects::*,
+    coins::subjects::*,
+    contracts::subjects::*,
     inputs::subjects::*,
+    messages::subjects::*,
     outputs::subjects::*,
     receipts::subjects::*,
     transactions::subjects::*,
@@ -14,6 +20,13 @@ fn main() {
     let block_schema = BlocksSubject::new().schema();
     let transaction_schema = TransactionsSubject::new().schema();
     let utxos_schema = UtxosSubject::new().schema();
+    let bundles_schema = BundlesSubject::new().schema();
+    let accounts_schema = AccountsSubject::new().schema();
+    let contracts_schema = ContractsSubject::new().schema();
+    let contracts_created_schema = ContractsCreatedSubject::new().schema();
+    let coins_schema = CoinsSubject::new().schema();
+    let balances_schema = BalancesSubject::new().schema();
+    let messages_schema = MessagesSubject::new().schema();
 
     let mut inputs_schema = InputsSubject::new().schema();
     let inputs_coin_schema = InputsCoinSubject::new().schema();
@@ -84,6 +97,13 @@ fn main() {
         ("outputs".to_string(), outputs_schema),
         ("receipts".to_string(), receipts_schema),
         ("utxos".to_string(), utxos_schema),
+        ("bundles".to_string(), bundles_schema),
+        ("accounts".to_string(), accounts_schema),
+        ("contracts".to_string(), contracts_schema),
+        ("contracts_created".to_string(), contracts_created_schema),
+        ("coins".to_string(), coins_schema),
+        ("balances".to_string(), balances_schema),
+        ("messages".to_string(), messages_schema),
     ]);
 
     let schema_json = serde_json::to_string_pretty(&final_schema).unwrap();
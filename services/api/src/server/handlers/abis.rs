@@ -0,0 +1,86 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use pedronauck_streams_core::types::ContractId;
+use pedronauck_streams_domains::abis::ContractAbi;
+use pedronauck_web_utils::api_key::{ApiKey, ApiKeyRoleScope};
+
+use super::Error;
+use crate::server::state::ServerState;
+
+fn parse_contract_id(contract_id: &str) -> actix_web::Result<ContractId> {
+    contract_id
+        .parse::<ContractId>()
+        .map_err(actix_web::error::ErrorBadRequest)
+}
+
+#[utoipa::path(
+    get,
+    path = "/abis/{contractId}",
+    tag = "abis",
+    params(
+        ("contractId" = String, Path, description = "Contract ID")
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the contract ABI", body = ContractAbi),
+        (status = 400, description = "Invalid contract ID", body = String),
+        (status = 404, description = "No ABI registered for the contract", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    ),
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn get_contract_abi(
+    req: HttpRequest,
+    contract_id: web::Path<String>,
+    state: web::Data<ServerState>,
+) -> actix_web::Result<HttpResponse> {
+    let _api_key = ApiKey::from_req(&req)?;
+    let contract_id = parse_contract_id(&contract_id)?;
+    match ContractAbi::find(&state.db.pool, &contract_id)
+        .await
+        .map_err(Error::Abi)?
+    {
+        Some(abi) => Ok(HttpResponse::Ok().json(abi)),
+        None => Ok(HttpResponse::NotFound()
+            .body(format!("No ABI registered for contract {contract_id}"))),
+    }
+}
+
+/// Registers the Sway JSON ABI of a contract, so the consumer decodes the
+/// values it logs from then on. Restricted to keys allowed to manage API
+/// keys.
+#[utoipa::path(
+    put,
+    path = "/abis/{contractId}",
+    tag = "abis",
+    params(
+        ("contractId" = String, Path, description = "Contract ID")
+    ),
+    request_body(content = Object, description = "Sway JSON ABI of the contract, as emitted by `forc build`"),
+    responses(
+        (status = 200, description = "Successfully registered the contract ABI", body = ContractAbi),
+        (status = 400, description = "Invalid contract ID or ABI", body = String),
+        (status = 401, description = "API key is not allowed to register ABIs", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    ),
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn put_contract_abi(
+    req: HttpRequest,
+    contract_id: web::Path<String>,
+    abi: web::Json<serde_json::Value>,
+    state: web::Data<ServerState>,
+) -> actix_web::Result<HttpResponse> {
+    let api_key = ApiKey::from_req(&req)?;
+    api_key
+        .role()
+        .has_scopes(&[ApiKeyRoleScope::ManageApiKeys])?;
+    let contract_id = parse_contract_id(&contract_id)?;
+    let abi = ContractAbi::upsert(&state.db.pool, &contract_id, &abi)
+        .await
+        .map_err(Error::Abi)?;
+    tracing::info!("Registered ABI of contract {}", contract_id);
+    Ok(HttpResponse::Ok().json(abi))
+}
@@ -12,15 +12,16 @@ use pedronauck_streams_core::types::{
     TxId,
 };
 use pedronauck_streams_domains::{
+    balances::queryable::BalancesQuery,
+    coins::queryable::CoinsQuery,
     inputs::queryable::InputsQuery,
     outputs::queryable::OutputsQuery,
     queryable::{Queryable, ValidatedQuery},
     transactions::queryable::TransactionsQuery,
-    utxos::queryable::UtxosQuery,
 };
 use pedronauck_web_utils::api_key::ApiKey;
 
-use super::{Error, GetDataResponse};
+use super::{Error, GetBalancesResponse, GetDataResponse};
 use crate::server::state::ServerState;
 
 #[utoipa::path(
@@ -40,7 +41,8 @@ use crate::server::state::ServerState;
         ("after" = Option<i32>, Query, description = "Return transactions after this height"),
         ("before" = Option<i32>, Query, description = "Return transactions before this height"),
         ("first" = Option<i32>, Query, description = "Limit results, sorted by ascending block height", maximum = 100),
-        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100)
+        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100),
+        ("projection" = Option<String>, Query, description = "Payload fields to return, either `compact` or a comma separated list of fields")
     ),
     responses(
         (status = 200, description = "Successfully retrieved account transactions", body = GetDataResponse),
@@ -67,7 +69,7 @@ pub async fn get_accounts_transactions(
         .await
         .map_err(Error::Sqlx)?
         .try_into()?;
-    Ok(HttpResponse::Ok().json(response))
+    Ok(HttpResponse::Ok().json(response.project(&req)?))
 }
 
 #[utoipa::path(
@@ -92,7 +94,8 @@ pub async fn get_accounts_transactions(
         ("after" = Option<i32>, Query, description = "Return inputs after this height"),
         ("before" = Option<i32>, Query, description = "Return inputs before this height"),
         ("first" = Option<i32>, Query, description = "Limit results, sorted by ascending block height", maximum = 100),
-        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100)
+        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100),
+        ("projection" = Option<String>, Query, description = "Payload fields to return, either `compact` or a comma separated list of fields")
     ),
     responses(
         (status = 200, description = "Successfully retrieved account inputs", body = GetDataResponse),
@@ -119,7 +122,7 @@ pub async fn get_accounts_inputs(
         .await
         .map_err(Error::Sqlx)?
         .try_into()?;
-    Ok(HttpResponse::Ok().json(response))
+    Ok(HttpResponse::Ok().json(response.project(&req)?))
 }
 
 #[utoipa::path(
@@ -142,7 +145,8 @@ pub async fn get_accounts_inputs(
         ("after" = Option<i32>, Query, description = "Return outputs after this height"),
         ("before" = Option<i32>, Query, description = "Return outputs before this height"),
         ("first" = Option<i32>, Query, description = "Limit results, sorted by ascending block height", maximum = 100),
-        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100)
+        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100),
+        ("projection" = Option<String>, Query, description = "Payload fields to return, either `compact` or a comma separated list of fields")
     ),
     responses(
         (status = 200, description = "Successfully retrieved account outputs", body = GetDataResponse),
@@ -169,7 +173,7 @@ pub async fn get_accounts_outputs(
         .await
         .map_err(Error::Sqlx)?
         .try_into()?;
-    Ok(HttpResponse::Ok().json(response))
+    Ok(HttpResponse::Ok().json(response.project(&req)?))
 }
 
 #[utoipa::path(
@@ -179,19 +183,18 @@ pub async fn get_accounts_outputs(
     params(
         // Path parameter
         ("address" = String, Path, description = "Account address"),
-        // UtxosQuery fields
-        ("txId" = Option<TxId>, Query, description = "Filter by transaction ID"),
-        ("txIndex" = Option<u32>, Query, description = "Filter by transaction index"),
-        ("inputIndex" = Option<i32>, Query, description = "Filter by input index"),
-        ("utxoType" = Option<InputType>, Query, description = "Filter by UTXO type"),
-        ("blockHeight" = Option<BlockHeight>, Query, description = "Filter by block height"),
+        // CoinsQuery fields
+        ("unspent" = Option<bool>, Query, description = "Only return unspent coins when true, or only spent ones when false"),
+        ("assetId" = Option<AssetId>, Query, description = "Filter by asset ID"),
         ("utxoId" = Option<HexData>, Query, description = "Filter by UTXO ID"),
-        ("contractId" = Option<ContractId>, Query, description = "Filter by contract ID (for contract UTXOs)"),
+        ("spentInTx" = Option<TxId>, Query, description = "Filter by the ID of the transaction spending the coin"),
+        ("blockHeight" = Option<BlockHeight>, Query, description = "Filter by the height where the coin was created or, once spent, spent"),
         // Flattened QueryPagination fields
         ("after" = Option<i32>, Query, description = "Return UTXOs after this height"),
         ("before" = Option<i32>, Query, description = "Return UTXOs before this height"),
         ("first" = Option<i32>, Query, description = "Limit results, sorted by ascending block height", maximum = 100),
-        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100)
+        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100),
+        ("projection" = Option<String>, Query, description = "Payload fields to return, either `compact` or a comma separated list of fields")
     ),
     responses(
         (status = 200, description = "Successfully retrieved account UTXOs", body = GetDataResponse),
@@ -206,17 +209,60 @@ pub async fn get_accounts_outputs(
 pub async fn get_accounts_utxos(
     req: HttpRequest,
     address: web::Path<String>,
-    req_query: ValidatedQuery<UtxosQuery>,
+    req_query: ValidatedQuery<CoinsQuery>,
     state: web::Data<ServerState>,
 ) -> actix_web::Result<HttpResponse> {
     let _api_key = ApiKey::from_req(&req)?;
     let mut query = req_query.into_inner();
     let address = address.into_inner();
-    query.set_address(&address);
+    query.set_owner(&address);
     let response: GetDataResponse = query
         .execute(&state.db.pool)
         .await
         .map_err(Error::Sqlx)?
         .try_into()?;
+    Ok(HttpResponse::Ok().json(response.project(&req)?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/accounts/{address}/balances",
+    tag = "accounts",
+    params(
+        // Path parameter
+        ("address" = String, Path, description = "Account address"),
+        // BalancesQuery fields
+        ("assetId" = Option<AssetId>, Query, description = "Filter by asset ID"),
+        // Flattened QueryPagination fields
+        ("after" = Option<i32>, Query, description = "Return balances last changed after this height"),
+        ("before" = Option<i32>, Query, description = "Return balances last changed before this height"),
+        ("first" = Option<i32>, Query, description = "Limit results, sorted by ascending height of the last change", maximum = 100),
+        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending height of the last change", maximum = 100)
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved account balances", body = GetBalancesResponse),
+        (status = 400, description = "Invalid query parameters", body = String),
+        (status = 404, description = "Account not found", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    ),
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn get_accounts_balances(
+    req: HttpRequest,
+    address: web::Path<String>,
+    req_query: ValidatedQuery<BalancesQuery>,
+    state: web::Data<ServerState>,
+) -> actix_web::Result<HttpResponse> {
+    let _api_key = ApiKey::from_req(&req)?;
+    let mut query = req_query.into_inner();
+    let address = address.into_inner();
+    query.set_owner(&address);
+    let response: GetBalancesResponse = query
+        .execute(&state.db.pool)
+        .await
+        .map_err(Error::Sqlx)?
+        .into();
     Ok(HttpResponse::Ok().json(response))
 }
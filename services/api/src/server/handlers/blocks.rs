@@ -9,6 +9,7 @@ use pedronauck_streams_core::types::{
     InputType,
     OutputType,
     ReceiptType,
+    RevertSignal,
     TransactionStatus,
     TransactionType,
     TxId,
@@ -41,6 +42,7 @@ use crate::server::state::ServerState;
         ("before" = Option<i32>, Query, description = "Return blocks before this height"),
         ("first" = Option<i32>, Query, description = "Limit results, sorted by ascending block height", maximum = 100),
         ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100),
+        ("projection" = Option<String>, Query, description = "Payload fields to return, either `compact` or a comma separated list of fields"),
     ),
     responses(
         (status = 200, description = "Successfully retrieved blocks", body = GetDataResponse),
@@ -63,7 +65,7 @@ pub async fn get_blocks(
         .await
         .map_err(Error::Sqlx)?
         .try_into()?;
-    Ok(HttpResponse::Ok().json(response))
+    Ok(HttpResponse::Ok().json(response.project(&req)?))
 }
 
 #[utoipa::path(
@@ -82,7 +84,8 @@ pub async fn get_blocks(
         ("after" = Option<i32>, Query, description = "Return transactions after this height"),
         ("before" = Option<i32>, Query, description = "Return transactions before this height"),
         ("first" = Option<i32>, Query, description = "Limit results, sorted by ascending block height", maximum = 100),
-        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100)
+        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100),
+        ("projection" = Option<String>, Query, description = "Payload fields to return, either `compact` or a comma separated list of fields")
     ),
     responses(
         (status = 200, description = "Successfully retrieved block transactions", body = GetDataResponse),
@@ -109,7 +112,7 @@ pub async fn get_block_transactions(
         .await
         .map_err(Error::Sqlx)?
         .try_into()?;
-    Ok(HttpResponse::Ok().json(response))
+    Ok(HttpResponse::Ok().json(response.project(&req)?))
 }
 
 #[utoipa::path(
@@ -131,12 +134,17 @@ pub async fn get_block_transactions(
         ("sender" = Option<Address>, Query, description = "Filter by sender address"),
         ("recipient" = Option<Address>, Query, description = "Filter by recipient address"),
         ("subId" = Option<Bytes32>, Query, description = "Filter by sub ID"),
+        ("logId" = Option<u64>, Query, description = "Filter log data receipts by the ID the value was logged under"),
+        ("logType" = Option<String>, Query, description = "Filter log data receipts by the ABI type of the decoded value, e.g. `struct Transfer`"),
+        ("panicReason" = Option<String>, Query, description = "Filter panic receipts by the name of the panic reason, e.g. `NotEnoughBalance`"),
+        ("revertSignal" = Option<RevertSignal>, Query, description = "Filter revert receipts by the Sway revert signal"),
         ("address" = Option<Address>, Query, description = "Filter by address (for accounts)"),
         // Flattened QueryPagination fields
         ("after" = Option<i32>, Query, description = "Return receipts after this height"),
         ("before" = Option<i32>, Query, description = "Return receipts before this height"),
         ("first" = Option<i32>, Query, description = "Limit results, sorted by ascending block height", maximum = 100),
-        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100)
+        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100),
+        ("projection" = Option<String>, Query, description = "Payload fields to return, either `compact` or a comma separated list of fields")
     ),
     responses(
         (status = 200, description = "Successfully retrieved block receipts", body = GetDataResponse),
@@ -163,7 +171,7 @@ pub async fn get_block_receipts(
         .await
         .map_err(Error::Sqlx)?
         .try_into()?;
-    Ok(HttpResponse::Ok().json(response))
+    Ok(HttpResponse::Ok().json(response.project(&req)?))
 }
 
 #[utoipa::path(
@@ -187,7 +195,8 @@ pub async fn get_block_receipts(
         ("after" = Option<i32>, Query, description = "Return inputs after this height"),
         ("before" = Option<i32>, Query, description = "Return inputs before this height"),
         ("first" = Option<i32>, Query, description = "Limit results, sorted by ascending block height", maximum = 100),
-        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100)
+        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100),
+        ("projection" = Option<String>, Query, description = "Payload fields to return, either `compact` or a comma separated list of fields")
     ),
     responses(
         (status = 200, description = "Successfully retrieved block inputs", body = GetDataResponse),
@@ -214,7 +223,7 @@ pub async fn get_block_inputs(
         .await
         .map_err(Error::Sqlx)?
         .try_into()?;
-    Ok(HttpResponse::Ok().json(response))
+    Ok(HttpResponse::Ok().json(response.project(&req)?))
 }
 
 #[utoipa::path(
@@ -237,7 +246,8 @@ pub async fn get_block_inputs(
         ("after" = Option<i32>, Query, description = "Return outputs after this height"),
         ("before" = Option<i32>, Query, description = "Return outputs before this height"),
         ("first" = Option<i32>, Query, description = "Limit results, sorted by ascending block height", maximum = 100),
-        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100)
+        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100),
+        ("projection" = Option<String>, Query, description = "Payload fields to return, either `compact` or a comma separated list of fields")
     ),
     responses(
         (status = 200, description = "Successfully retrieved block outputs", body = GetDataResponse),
@@ -264,5 +274,5 @@ pub async fn get_block_outputs(
         .await
         .map_err(Error::Sqlx)?
         .try_into()?;
-    Ok(HttpResponse::Ok().json(response))
+    Ok(HttpResponse::Ok().json(response.project(&req)?))
 }
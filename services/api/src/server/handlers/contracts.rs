@@ -3,25 +3,79 @@ use pedronauck_streams_core::types::{
     Address,
     AssetId,
     BlockHeight,
+    ContractId,
     HexData,
     InputType,
     OutputType,
+    StreamResponse,
     TransactionStatus,
     TransactionType,
     TxId,
 };
 use pedronauck_streams_domains::{
+    balances::queryable::BalancesQuery,
+    contracts::queryable::ContractsQuery,
     inputs::queryable::InputsQuery,
     outputs::queryable::OutputsQuery,
     queryable::{Queryable, ValidatedQuery},
     transactions::queryable::TransactionsQuery,
     utxos::queryable::UtxosQuery,
 };
+use pedronauck_streams_store::db::DbItem;
 use pedronauck_web_utils::api_key::ApiKey;
 
-use super::{Error, GetDataResponse};
+use super::{Error, GetBalancesResponse, GetDataResponse};
 use crate::server::state::ServerState;
 
+/// Deployment of a contract: its deployer, bytecode root and size, salt
+/// and initial storage slots
+#[utoipa::path(
+    get,
+    path = "/contracts/{contractId}",
+    tag = "contracts",
+    params(
+        ("contractId" = String, Path, description = "Contract ID")
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the contract deployment", body = StreamResponse),
+        (status = 400, description = "Invalid contract ID", body = String),
+        (status = 404, description = "Contract not found", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    ),
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn get_contract(
+    req: HttpRequest,
+    contract_id: web::Path<String>,
+    state: web::Data<ServerState>,
+) -> actix_web::Result<HttpResponse> {
+    let _api_key = ApiKey::from_req(&req)?;
+    let contract_id = contract_id
+        .parse::<ContractId>()
+        .map_err(actix_web::error::ErrorBadRequest)?;
+    let query = ContractsQuery {
+        contract_id: Some(contract_id.to_owned()),
+        ..Default::default()
+    };
+    let item = query
+        .execute(&state.db.pool)
+        .await
+        .map_err(Error::Sqlx)?
+        .into_iter()
+        .next();
+    match item {
+        Some(item) => {
+            let response = StreamResponse::try_from((item.subject_id(), item))
+                .map_err(Error::Stream)?;
+            Ok(HttpResponse::Ok().json(response))
+        }
+        None => Ok(HttpResponse::NotFound()
+            .body(format!("Contract {contract_id} not found"))),
+    }
+}
+
 #[utoipa::path(
     get,
     path = "/contracts/{contractId}/transactions",
@@ -39,7 +93,8 @@ use crate::server::state::ServerState;
         ("after" = Option<i32>, Query, description = "Return transactions after this height"),
         ("before" = Option<i32>, Query, description = "Return transactions before this height"),
         ("first" = Option<i32>, Query, description = "Limit results, sorted by ascending block height", maximum = 100),
-        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100)
+        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100),
+        ("projection" = Option<String>, Query, description = "Payload fields to return, either `compact` or a comma separated list of fields")
     ),
     responses(
         (status = 200, description = "Successfully retrieved contract transactions", body = GetDataResponse),
@@ -66,7 +121,7 @@ pub async fn get_contracts_transactions(
         .await
         .map_err(Error::Sqlx)?
         .try_into()?;
-    Ok(HttpResponse::Ok().json(response))
+    Ok(HttpResponse::Ok().json(response.project(&req)?))
 }
 
 #[utoipa::path(
@@ -90,7 +145,8 @@ pub async fn get_contracts_transactions(
         ("after" = Option<i32>, Query, description = "Return inputs after this height"),
         ("before" = Option<i32>, Query, description = "Return inputs before this height"),
         ("first" = Option<i32>, Query, description = "Limit results, sorted by ascending block height", maximum = 100),
-        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100)
+        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100),
+        ("projection" = Option<String>, Query, description = "Payload fields to return, either `compact` or a comma separated list of fields")
     ),
     responses(
         (status = 200, description = "Successfully retrieved contract inputs", body = GetDataResponse),
@@ -117,7 +173,7 @@ pub async fn get_contracts_inputs(
         .await
         .map_err(Error::Sqlx)?
         .try_into()?;
-    Ok(HttpResponse::Ok().json(response))
+    Ok(HttpResponse::Ok().json(response.project(&req)?))
 }
 
 #[utoipa::path(
@@ -139,7 +195,8 @@ pub async fn get_contracts_inputs(
         ("after" = Option<i32>, Query, description = "Return outputs after this height"),
         ("before" = Option<i32>, Query, description = "Return outputs before this height"),
         ("first" = Option<i32>, Query, description = "Limit results, sorted by ascending block height", maximum = 100),
-        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100)
+        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100),
+        ("projection" = Option<String>, Query, description = "Payload fields to return, either `compact` or a comma separated list of fields")
     ),
     responses(
         (status = 200, description = "Successfully retrieved contract outputs", body = GetDataResponse),
@@ -166,7 +223,7 @@ pub async fn get_contracts_outputs(
         .await
         .map_err(Error::Sqlx)?
         .try_into()?;
-    Ok(HttpResponse::Ok().json(response))
+    Ok(HttpResponse::Ok().json(response.project(&req)?))
 }
 
 #[utoipa::path(
@@ -187,7 +244,8 @@ pub async fn get_contracts_outputs(
         ("after" = Option<i32>, Query, description = "Return UTXOs after this height"),
         ("before" = Option<i32>, Query, description = "Return UTXOs before this height"),
         ("first" = Option<i32>, Query, description = "Limit results, sorted by ascending block height", maximum = 100),
-        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100)
+        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100),
+        ("projection" = Option<String>, Query, description = "Payload fields to return, either `compact` or a comma separated list of fields")
     ),
     responses(
         (status = 200, description = "Successfully retrieved contract UTXOs", body = GetDataResponse),
@@ -214,5 +272,49 @@ pub async fn get_contracts_utxos(
         .await
         .map_err(Error::Sqlx)?
         .try_into()?;
+    Ok(HttpResponse::Ok().json(response.project(&req)?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/contracts/{contractId}/balances",
+    tag = "contracts",
+    params(
+        // Path parameter
+        ("contractId" = String, Path, description = "Contract ID"),
+        // BalancesQuery fields
+        ("assetId" = Option<AssetId>, Query, description = "Filter by asset ID"),
+        // Flattened QueryPagination fields
+        ("after" = Option<i32>, Query, description = "Return balances last changed after this height"),
+        ("before" = Option<i32>, Query, description = "Return balances last changed before this height"),
+        ("first" = Option<i32>, Query, description = "Limit results, sorted by ascending height of the last change", maximum = 100),
+        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending height of the last change", maximum = 100)
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved contract balances", body = GetBalancesResponse),
+        (status = 400, description = "Invalid query parameters", body = String),
+        (status = 404, description = "Contract not found", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    ),
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn get_contracts_balances(
+    req: HttpRequest,
+    contract_id: web::Path<String>,
+    req_query: ValidatedQuery<BalancesQuery>,
+    state: web::Data<ServerState>,
+) -> actix_web::Result<HttpResponse> {
+    let _api_key = ApiKey::from_req(&req)?;
+    let mut query = req_query.into_inner();
+    let contract_id = contract_id.into_inner();
+    // Contracts hold balances under their ID
+    query.set_owner(&contract_id);
+    let response: GetBalancesResponse = query
+        .execute(&state.db.pool)
+        .await
+        .map_err(Error::Sqlx)?
+        .into();
     Ok(HttpResponse::Ok().json(response))
 }
@@ -0,0 +1,114 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use pedronauck_streams_core::types::{
+    Address,
+    BlockHeight,
+    MessageDirection,
+    MessageId,
+    MessageStatus,
+    Nonce,
+    StreamResponse,
+    TxId,
+};
+use pedronauck_streams_domains::{
+    messages::queryable::MessagesQuery,
+    queryable::{Queryable, ValidatedQuery},
+};
+use pedronauck_streams_store::db::DbItem;
+use pedronauck_web_utils::api_key::ApiKey;
+
+use super::{Error, GetDataResponse};
+use crate::server::state::ServerState;
+
+#[utoipa::path(
+    get,
+    path = "/messages",
+    tag = "messages",
+    params(
+        // MessagesQuery fields
+        ("messageId" = Option<MessageId>, Query, description = "Filter by message ID"),
+        ("nonce" = Option<Nonce>, Query, description = "Filter by message nonce"),
+        ("direction" = Option<MessageDirection>, Query, description = "Filter by direction, either `l1_to_l2` or `l2_to_l1`"),
+        ("status" = Option<MessageStatus>, Query, description = "Filter by status, either `sent`, `relayed` or `spent`"),
+        ("sender" = Option<Address>, Query, description = "Filter by sender address"),
+        ("recipient" = Option<Address>, Query, description = "Filter by recipient address"),
+        ("txId" = Option<TxId>, Query, description = "Filter by the ID of the transaction creating or last using the message"),
+        ("blockHeight" = Option<BlockHeight>, Query, description = "Filter by the height where the message was created or last used"),
+        // Flattened QueryPagination fields
+        ("after" = Option<i32>, Query, description = "Return messages after this height"),
+        ("before" = Option<i32>, Query, description = "Return messages before this height"),
+        ("first" = Option<i32>, Query, description = "Limit results, sorted by ascending block height", maximum = 100),
+        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100),
+        ("projection" = Option<String>, Query, description = "Payload fields to return, either `compact` or a comma separated list of fields")
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved messages", body = GetDataResponse),
+        (status = 400, description = "Invalid query parameters", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    ),
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn get_messages(
+    req: HttpRequest,
+    req_query: ValidatedQuery<MessagesQuery>,
+    state: web::Data<ServerState>,
+) -> actix_web::Result<HttpResponse> {
+    let _api_key = ApiKey::from_req(&req)?;
+    let query = req_query.into_inner();
+    let response: GetDataResponse = query
+        .execute(&state.db.pool)
+        .await
+        .map_err(Error::Sqlx)?
+        .try_into()?;
+    Ok(HttpResponse::Ok().json(response.project(&req)?))
+}
+
+/// Current state of a bridge message, e.g. to follow a withdrawal from the
+/// ID of its message
+#[utoipa::path(
+    get,
+    path = "/messages/{messageId}",
+    tag = "messages",
+    params(
+        ("messageId" = String, Path, description = "Message ID")
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved the message", body = StreamResponse),
+        (status = 400, description = "Invalid message ID", body = String),
+        (status = 404, description = "Message not found", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    ),
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn get_message(
+    req: HttpRequest,
+    message_id: web::Path<String>,
+    state: web::Data<ServerState>,
+) -> actix_web::Result<HttpResponse> {
+    let _api_key = ApiKey::from_req(&req)?;
+    let message_id = message_id
+        .parse::<MessageId>()
+        .map_err(actix_web::error::ErrorBadRequest)?;
+    let query = MessagesQuery {
+        message_id: Some(message_id.to_owned()),
+        ..Default::default()
+    };
+    let item = query
+        .execute(&state.db.pool)
+        .await
+        .map_err(Error::Sqlx)?
+        .into_iter()
+        .next();
+    match item {
+        Some(item) => {
+            let response = StreamResponse::try_from((item.subject_id(), item))
+                .map_err(Error::Stream)?;
+            Ok(HttpResponse::Ok().json(response))
+        }
+        None => Ok(HttpResponse::NotFound()
+            .body(format!("Message {message_id} not found"))),
+    }
+}
@@ -1,27 +1,38 @@
+pub mod abis;
 pub mod accounts;
 pub mod blocks;
 pub mod contracts;
 pub mod inputs;
 pub mod macros;
+pub mod messages;
 pub mod open_api;
 pub mod outputs;
 pub mod receipts;
 pub mod transactions;
 pub mod utxos;
-use actix_web::{http::StatusCode, web};
+use actix_web::{http::StatusCode, web, HttpRequest};
 use open_api::ApiDoc;
-use pedronauck_streams_core::types::{StreamResponse, StreamResponseError};
+use pedronauck_streams_core::types::{
+    PayloadProjection,
+    StreamResponse,
+    StreamResponseError,
+};
 use pedronauck_streams_domains::{
+    abis::AbiError,
+    balances::Balance,
     inputs::InputType,
     outputs::OutputType,
     receipts::ReceiptType,
 };
-use pedronauck_streams_store::{db::DbItem, record::RecordPointer};
+use pedronauck_streams_store::{
+    db::{DbError, DbItem},
+    record::RecordPointer,
+};
 use pedronauck_web_utils::{
     api_key::middleware::ApiKeyAuth,
     server::api::with_prefixed_route,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
@@ -42,6 +53,10 @@ pub enum Error {
     Validation(#[from] validator::ValidationErrors),
     #[error("Stream response error {0}")]
     Stream(#[from] StreamResponseError),
+    #[error("Database error {0}")]
+    Db(#[from] DbError),
+    #[error("ABI error {0}")]
+    Abi(#[from] AbiError),
 }
 
 impl From<Error> for actix_web::Error {
@@ -61,6 +76,22 @@ impl From<Error> for actix_web::Error {
                 StatusCode::INTERNAL_SERVER_ERROR,
             )
             .into(),
+            Error::Db(e) => actix_web::error::InternalError::new(
+                e,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+            .into(),
+            Error::Abi(e @ AbiError::Database(_)) => {
+                actix_web::error::InternalError::new(
+                    e,
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )
+                .into()
+            }
+            Error::Abi(e) => {
+                actix_web::error::InternalError::new(e, StatusCode::BAD_REQUEST)
+                    .into()
+            }
         }
     }
 }
@@ -89,6 +120,46 @@ where
     }
 }
 
+/// Current balances, which are summed from the stored changes rather than
+/// being records themselves
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBalancesResponse {
+    data: Vec<Balance>,
+}
+
+impl From<Vec<Balance>> for GetBalancesResponse {
+    fn from(data: Vec<Balance>) -> Self {
+        Self { data }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ProjectionQuery {
+    projection: Option<String>,
+}
+
+impl GetDataResponse {
+    /// Narrows down the payloads with the `projection` query param, e.g.
+    /// `?projection=compact` or `?projection=id,status,txPointer.blockHeight`
+    pub fn project(self, req: &HttpRequest) -> Result<Self, Error> {
+        let query =
+            web::Query::<ProjectionQuery>::from_query(req.query_string())
+                .map(web::Query::into_inner)
+                .unwrap_or_default();
+        let Some(projection) = query.projection else {
+            return Ok(self);
+        };
+        let Ok(projection) = projection.parse::<PayloadProjection>();
+        let data = self
+            .data
+            .into_iter()
+            .map(|response| response.with_projection(&projection))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { data })
+    }
+}
+
 pub fn create_services(
     state: ServerState,
 ) -> impl Fn(&mut web::ServiceConfig) + Send + Sync + 'static {
@@ -112,7 +183,18 @@ pub fn create_services(
             ]
         );
 
-        // transactions
+        // transactions, with the trace registered first as the scope below
+        // answers every route under its prefix
+        cfg.service(
+            web::resource(format!(
+                "{}/{{tx_id}}/trace",
+                with_prefixed_route("transactions")
+            ))
+            .wrap(api_key_middleware.clone())
+            .route(
+                web::get().to(handlers::transactions::get_transaction_trace),
+            ),
+        );
         resource_with_related_endpoints!(
             cfg,
             api_key_middleware,
@@ -182,20 +264,30 @@ pub fn create_services(
             ("/call", Call)
         );
 
-        // contracts
+        // contracts, with the deployment registered first as the scope below
+        // answers every route under its prefix
+        cfg.service(
+            web::resource(format!(
+                "{}/{{contract_id}}",
+                with_prefixed_route("contracts")
+            ))
+            .wrap(api_key_middleware.clone())
+            .route(web::get().to(handlers::contracts::get_contract)),
+        );
         related_resource_endpoint!(
             cfg,
             api_key_middleware,
             "contracts",
             "contract_id",
             [
-                // ( // TODO: need to extend db with normalized values
-                //     "transactions",
-                //     handlers::contracts::get_contracts_transactions
-                // ),
+                (
+                    "transactions",
+                    handlers::contracts::get_contracts_transactions
+                ),
                 ("inputs", handlers::contracts::get_contracts_inputs),
                 ("outputs", handlers::contracts::get_contracts_outputs),
-                ("utxos", handlers::contracts::get_contracts_utxos)
+                ("utxos", handlers::contracts::get_contracts_utxos),
+                ("balances", handlers::contracts::get_contracts_balances)
             ]
         );
 
@@ -206,16 +298,42 @@ pub fn create_services(
             "accounts",
             "address",
             [
-                // ( // TODO: need to extend db with normalized values
-                //     "transactions",
-                //     handlers::accounts::get_accounts_transactions
-                // ),
+                (
+                    "transactions",
+                    handlers::accounts::get_accounts_transactions
+                ),
                 ("inputs", handlers::accounts::get_accounts_inputs),
                 ("outputs", handlers::accounts::get_accounts_outputs),
-                ("utxos", handlers::accounts::get_accounts_utxos)
+                ("utxos", handlers::accounts::get_accounts_utxos),
+                ("balances", handlers::accounts::get_accounts_balances)
             ]
         );
 
+        // messages
+        cfg.service(
+            web::scope(&with_prefixed_route("messages"))
+                .wrap(api_key_middleware.clone())
+                .route("", web::get().to(handlers::messages::get_messages))
+                .route(
+                    "/{message_id}",
+                    web::get().to(handlers::messages::get_message),
+                ),
+        );
+
+        // abis
+        cfg.service(
+            web::scope(&with_prefixed_route("abis"))
+                .wrap(api_key_middleware.clone())
+                .route(
+                    "/{contract_id}",
+                    web::get().to(handlers::abis::get_contract_abi),
+                )
+                .route(
+                    "/{contract_id}",
+                    web::put().to(handlers::abis::put_contract_abi),
+                ),
+        );
+
         // Serve the OpenAPI specification as JSON
         cfg.service(
             SwaggerUi::new("/swagger-ui/{_:.*}")
@@ -9,6 +9,7 @@ use pedronauck_streams_core::types::{
     BurnReceipt,
     CallReceipt,
     Consensus,
+    DecodedLog,
     FuelCoreUpgradePurposeWrapper,
     GasAmount,
     HexData,
@@ -18,6 +19,7 @@ use pedronauck_streams_core::types::{
     InputMessage,
     LogDataReceipt,
     LogReceipt,
+    MessageId,
     MessageOutReceipt,
     MintReceipt,
     Nonce,
@@ -33,6 +35,7 @@ use pedronauck_streams_core::types::{
     ReturnDataReceipt,
     ReturnReceipt,
     RevertReceipt,
+    RevertSignal,
     Salt,
     ScriptResultReceipt,
     StorageSlot,
@@ -42,10 +45,20 @@ use pedronauck_streams_core::types::{
     UtxoId,
 };
 use pedronauck_streams_domains::{
+    abis::ContractAbi,
+    balances::{queryable::BalancesQuery, Balance},
     blocks::queryable::BlocksQuery,
+    coins::{queryable::CoinsQuery, Coin, CoinStatus},
+    contracts::{queryable::ContractsQuery, Contract},
     inputs::queryable::InputsQuery,
+    messages::{
+        queryable::MessagesQuery,
+        Message,
+        MessageDirection,
+        MessageStatus,
+    },
     outputs::queryable::OutputsQuery,
-    receipts::queryable::ReceiptsQuery,
+    receipts::{queryable::ReceiptsQuery, CallFrame, CallResult, CallTrace},
     transactions::queryable::TransactionsQuery,
 };
 use utoipa::{
@@ -67,10 +80,12 @@ impl Modify for SecurityAddon {
 }
 
 use super::{
+    abis::*,
     accounts::*,
     blocks::*,
     contracts::*,
     inputs::*,
+    messages::*,
     outputs::*,
     receipts::*,
     transactions::*,
@@ -89,10 +104,13 @@ use super::{
         get_accounts_inputs,
         get_accounts_outputs,
         get_accounts_utxos,
+        get_accounts_balances,
+        get_contract,
         get_contracts_transactions,
         get_contracts_inputs,
         get_contracts_outputs,
         get_contracts_utxos,
+        get_contracts_balances,
         get_inputs,
         get_outputs,
         get_receipts,
@@ -100,7 +118,12 @@ use super::{
         get_transaction_receipts,
         get_transaction_inputs,
         get_transaction_outputs,
+        get_transaction_trace,
         get_utxos,
+        get_messages,
+        get_message,
+        get_contract_abi,
+        put_contract_abi,
     ),
     components(schemas(
         BlocksQuery,
@@ -108,6 +131,17 @@ use super::{
         ReceiptsQuery,
         InputsQuery,
         OutputsQuery,
+        CoinsQuery,
+        Coin,
+        CoinStatus,
+        BalancesQuery,
+        Balance,
+        ContractsQuery,
+        Contract,
+        MessagesQuery,
+        Message,
+        MessageDirection,
+        MessageStatus,
         Consensus,
         BlockHeader,
         BlockId,
@@ -137,8 +171,14 @@ use super::{
         ReturnDataReceipt,
         PanicReceipt,
         RevertReceipt,
+        RevertSignal,
         LogReceipt,
         LogDataReceipt,
+        DecodedLog,
+        ContractAbi,
+        CallTrace,
+        CallFrame,
+        CallResult,
         TransferReceipt,
         TransferOutReceipt,
         ScriptResultReceipt,
@@ -146,16 +186,19 @@ use super::{
         MintReceipt,
         BurnReceipt,
         Nonce,
+        MessageId,
         UtxoId,
     )),
     tags(
         (name = "Blocks", description = "Block retrieval endpoints"),
         (name = "Accounts", description = "Accounts retrieval endpoints"),
         (name = "Contracts", description = "Contracts retrieval endpoints"),
+        (name = "Abis", description = "Contract ABI registration endpoints"),
         (name = "Inputs", description = "Inputs retrieval endpoints"),
         (name = "Outputs", description = "Outputs retrieval endpoints"),
         (name = "Receipts", description = "Receipts retrieval endpoints"),
         (name = "Transactions", description = "Transactions retrieval endpoints"),
+        (name = "Messages", description = "Bridge messages retrieval endpoints"),
     ),
     modifiers(&SecurityAddon)
 )]
@@ -9,7 +9,7 @@ use pedronauck_streams_core::types::{
 };
 use pedronauck_streams_domains::{
     queryable::{Queryable, ValidatedQuery},
-    receipts::{queryable::ReceiptsQuery, ReceiptType},
+    receipts::{queryable::ReceiptsQuery, ReceiptType, RevertSignal},
 };
 use pedronauck_web_utils::api_key::ApiKey;
 
@@ -34,12 +34,17 @@ use crate::server::state::ServerState;
         ("sender" = Option<Address>, Query, description = "Filter by sender address"),
         ("recipient" = Option<Address>, Query, description = "Filter by recipient address"),
         ("subId" = Option<Bytes32>, Query, description = "Filter by sub ID"),
+        ("logId" = Option<u64>, Query, description = "Filter log data receipts by the ID the value was logged under"),
+        ("logType" = Option<String>, Query, description = "Filter log data receipts by the ABI type of the decoded value, e.g. `struct Transfer`"),
+        ("panicReason" = Option<String>, Query, description = "Filter panic receipts by the name of the panic reason, e.g. `NotEnoughBalance`"),
+        ("revertSignal" = Option<RevertSignal>, Query, description = "Filter revert receipts by the Sway revert signal"),
         ("address" = Option<Address>, Query, description = "Filter by address"),
         // Flattened QueryPagination fields
         ("after" = Option<i32>, Query, description = "Return receipts after this height"),
         ("before" = Option<i32>, Query, description = "Return receipts before this height"),
         ("first" = Option<i32>, Query, description = "Limit results, sorted by ascending block height", maximum = 100),
-        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100)
+        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100),
+        ("projection" = Option<String>, Query, description = "Payload fields to return, either `compact` or a comma separated list of fields")
     ),
     responses(
         (status = 200, description = "Successfully retrieved receipts", body = GetDataResponse),
@@ -64,5 +69,5 @@ pub async fn get_receipts(
         .await
         .map_err(Error::Sqlx)?
         .try_into()?;
-    Ok(HttpResponse::Ok().json(response))
+    Ok(HttpResponse::Ok().json(response.project(&req)?))
 }
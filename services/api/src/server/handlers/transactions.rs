@@ -8,6 +8,7 @@ use pedronauck_streams_core::types::{
     InputType,
     OutputType,
     ReceiptType,
+    RevertSignal,
     TransactionStatus,
     TransactionType,
     TxId,
@@ -16,7 +17,7 @@ use pedronauck_streams_domains::{
     inputs::queryable::InputsQuery,
     outputs::queryable::OutputsQuery,
     queryable::{Queryable, ValidatedQuery},
-    receipts::queryable::ReceiptsQuery,
+    receipts::{queryable::ReceiptsQuery, CallTrace},
     transactions::queryable::TransactionsQuery,
 };
 use pedronauck_web_utils::api_key::ApiKey;
@@ -41,7 +42,8 @@ use crate::server::state::ServerState;
         ("after" = Option<i32>, Query, description = "Return transactions after this height"),
         ("before" = Option<i32>, Query, description = "Return transactions before this height"),
         ("first" = Option<i32>, Query, description = "Limit results, sorted by ascending block height", maximum = 100),
-        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100)
+        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100),
+        ("projection" = Option<String>, Query, description = "Payload fields to return, either `compact` or a comma separated list of fields")
     ),
     responses(
         (status = 200, description = "Successfully retrieved transactions", body = GetDataResponse),
@@ -64,7 +66,7 @@ pub async fn get_transactions(
         .await
         .map_err(Error::Sqlx)?
         .try_into()?;
-    Ok(HttpResponse::Ok().json(response))
+    Ok(HttpResponse::Ok().json(response.project(&req)?))
 }
 
 #[utoipa::path(
@@ -86,12 +88,17 @@ pub async fn get_transactions(
         ("sender" = Option<Address>, Query, description = "Filter by sender address"),
         ("recipient" = Option<Address>, Query, description = "Filter by recipient address"),
         ("subId" = Option<Bytes32>, Query, description = "Filter by sub ID"),
+        ("logId" = Option<u64>, Query, description = "Filter log data receipts by the ID the value was logged under"),
+        ("logType" = Option<String>, Query, description = "Filter log data receipts by the ABI type of the decoded value, e.g. `struct Transfer`"),
+        ("panicReason" = Option<String>, Query, description = "Filter panic receipts by the name of the panic reason, e.g. `NotEnoughBalance`"),
+        ("revertSignal" = Option<RevertSignal>, Query, description = "Filter revert receipts by the Sway revert signal"),
         ("address" = Option<Address>, Query, description = "Filter by address"),
         // Flattened QueryPagination fields
         ("after" = Option<i32>, Query, description = "Return receipts after this height"),
         ("before" = Option<i32>, Query, description = "Return receipts before this height"),
         ("first" = Option<i32>, Query, description = "Limit results, sorted by ascending block height", maximum = 100),
-        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100)
+        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100),
+        ("projection" = Option<String>, Query, description = "Payload fields to return, either `compact` or a comma separated list of fields")
     ),
     responses(
         (status = 200, description = "Successfully retrieved transaction receipts", body = GetDataResponse),
@@ -118,7 +125,7 @@ pub async fn get_transaction_receipts(
         .await
         .map_err(Error::Sqlx)?
         .try_into()?;
-    Ok(HttpResponse::Ok().json(response))
+    Ok(HttpResponse::Ok().json(response.project(&req)?))
 }
 
 #[utoipa::path(
@@ -143,7 +150,8 @@ pub async fn get_transaction_receipts(
         ("after" = Option<i32>, Query, description = "Return inputs after this height"),
         ("before" = Option<i32>, Query, description = "Return inputs before this height"),
         ("first" = Option<i32>, Query, description = "Limit results, sorted by ascending block height", maximum = 100),
-        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100)
+        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100),
+        ("projection" = Option<String>, Query, description = "Payload fields to return, either `compact` or a comma separated list of fields")
     ),
     responses(
         (status = 200, description = "Successfully retrieved transaction inputs", body = GetDataResponse),
@@ -170,7 +178,7 @@ pub async fn get_transaction_inputs(
         .await
         .map_err(Error::Sqlx)?
         .try_into()?;
-    Ok(HttpResponse::Ok().json(response))
+    Ok(HttpResponse::Ok().json(response.project(&req)?))
 }
 
 #[utoipa::path(
@@ -193,7 +201,8 @@ pub async fn get_transaction_inputs(
         ("after" = Option<i32>, Query, description = "Return outputs after this height"),
         ("before" = Option<i32>, Query, description = "Return outputs before this height"),
         ("first" = Option<i32>, Query, description = "Limit results, sorted by ascending block height", maximum = 100),
-        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100)
+        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100),
+        ("projection" = Option<String>, Query, description = "Payload fields to return, either `compact` or a comma separated list of fields")
     ),
     responses(
         (status = 200, description = "Successfully retrieved transaction outputs", body = GetDataResponse),
@@ -220,5 +229,41 @@ pub async fn get_transaction_outputs(
         .await
         .map_err(Error::Sqlx)?
         .try_into()?;
-    Ok(HttpResponse::Ok().json(response))
+    Ok(HttpResponse::Ok().json(response.project(&req)?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/transactions/{txId}/trace",
+    tag = "transactions",
+    params(
+        ("txId" = String, Path, description = "Transaction ID")
+    ),
+    responses(
+        (status = 200, description = "Successfully rebuilt the transaction call tree", body = CallTrace),
+        (status = 400, description = "Invalid transaction ID", body = String),
+        (status = 404, description = "Transaction not found", body = String),
+        (status = 500, description = "Internal server error", body = String)
+    ),
+    security(
+        ("api_key" = [])
+    )
+)]
+pub async fn get_transaction_trace(
+    req: HttpRequest,
+    tx_id: web::Path<String>,
+    state: web::Data<ServerState>,
+) -> actix_web::Result<HttpResponse> {
+    let _api_key = ApiKey::from_req(&req)?;
+    let tx_id = tx_id
+        .parse::<TxId>()
+        .map_err(actix_web::error::ErrorBadRequest)?;
+    match CallTrace::find(&state.db.pool, &tx_id)
+        .await
+        .map_err(Error::Db)?
+    {
+        Some(trace) => Ok(HttpResponse::Ok().json(trace)),
+        None => Ok(HttpResponse::NotFound()
+            .body(format!("No receipts stored for transaction {tx_id}"))),
+    }
 }
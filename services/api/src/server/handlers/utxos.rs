@@ -34,7 +34,8 @@ use crate::server::state::ServerState;
         ("after" = Option<i32>, Query, description = "Return UTXOs after this height"),
         ("before" = Option<i32>, Query, description = "Return UTXOs before this height"),
         ("first" = Option<i32>, Query, description = "Limit results, sorted by ascending block height", maximum = 100),
-        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100)
+        ("last" = Option<i32>, Query, description = "Limit results, sorted by descending block height", maximum = 100),
+        ("projection" = Option<String>, Query, description = "Payload fields to return, either `compact` or a comma separated list of fields")
     ),
     responses(
         (status = 200, description = "Successfully retrieved UTXOs", body = GetDataResponse),
@@ -57,5 +58,5 @@ pub async fn get_utxos(
         .await
         .map_err(Error::Sqlx)?
         .try_into()?;
-    Ok(HttpResponse::Ok().json(response))
+    Ok(HttpResponse::Ok().json(response.project(&req)?))
 }
@@ -40,3 +40,23 @@ pub enum ConsumerError {
     #[error("Database operation timed out")]
     DatabaseTimeout,
 }
+
+impl ConsumerError {
+    /// Whether the block could go through on a later delivery, so the
+    /// message should be redelivered instead of acknowledged. Tasks that
+    /// panicked or were cancelled are retried as well.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ConsumerError::Timeout
+                | ConsumerError::JoinTasks(_)
+                | ConsumerError::Semaphore(_)
+                | ConsumerError::DatabaseTimeout
+                | ConsumerError::Db(_)
+                | ConsumerError::Store(_)
+                | ConsumerError::Sqlx(_)
+                | ConsumerError::Stream(_)
+                | ConsumerError::MessageBrokerClient(_)
+        )
+    }
+}
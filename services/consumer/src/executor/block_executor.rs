@@ -1,12 +1,33 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
-use futures::{future::try_join_all, StreamExt};
-use pedronauck_message_broker::{Message, NatsMessageBroker, NatsQueue};
+use futures::StreamExt;
+use pedronauck_message_broker::{
+    Message,
+    MessageBroker,
+    NatsQueue,
+    NatsSubject,
+};
 use pedronauck_streams_core::{
-    types::{Block, BlockTimestamp, Transaction},
+    types::{
+        AccountActivity,
+        BalanceChange,
+        Block,
+        BlockHeight,
+        BlockTimestamp,
+        Coin,
+        Contract,
+        ContractActivity,
+        Message as BridgeMessage,
+        Transaction,
+        TransactionBundle,
+    },
     FuelStreams,
 };
-use pedronauck_streams_domains::MsgPayload;
+use pedronauck_streams_domains::{
+    abis::AbiRegistry,
+    accounts::AccountTransaction,
+    MsgPayload,
+};
 use pedronauck_streams_store::{
     db::Db,
     record::{DataEncoder, PacketBuilder, RecordPacket},
@@ -18,13 +39,15 @@ use pedronauck_web_utils::{
 };
 use tokio::{
     sync::Semaphore,
-    task::{JoinError, JoinSet},
+    task::{Id, JoinError, JoinSet},
 };
 use tokio_util::sync::CancellationToken;
 
 use super::{
+    block_publisher::{BlockEvent, BlockPublisher},
+    block_retrier::{ack, dead_letter},
     block_stats::{ActionType, BlockStats},
-    retry::RetryService,
+    retry::{redelivery_delay, RetryService},
 };
 use crate::{errors::ConsumerError, metrics::Metrics, FuelStores};
 
@@ -32,16 +55,30 @@ const MAX_CONCURRENT_TASKS: usize = 32;
 const BATCH_SIZE: usize = 100;
 
 #[derive(Debug)]
-enum ProcessResult {
-    Store(Result<BlockStats, ConsumerError>),
-    Stream(Result<BlockStats, ConsumerError>),
+pub(super) struct ProcessResult {
+    pub packets: Arc<Vec<RecordPacket>>,
+    pub msg_payload: Arc<MsgPayload>,
+    pub store: BlockStats,
 }
 
+/// What became of the message of a block
+#[derive(Debug, PartialEq)]
+pub(super) enum Settled {
+    Acked,
+    Redelivered,
+    HandedOver,
+}
+
+/// Messages of the blocks being processed, by task. They are kept outside
+/// the tasks so a block is still redelivered when its task fails or panics.
+type InFlight = HashMap<Id, (Box<dyn Message>, BlockHeight)>;
+
 pub struct BlockExecutor {
     db: Arc<Db>,
-    message_broker: Arc<NatsMessageBroker>,
+    message_broker: Arc<dyn MessageBroker>,
     pedronauck_streams: Arc<FuelStreams>,
     fuel_stores: Arc<FuelStores>,
+    abi_registry: Arc<AbiRegistry>,
     semaphore: Arc<Semaphore>,
     telemetry: Arc<Telemetry<Metrics>>,
 }
@@ -49,18 +86,20 @@ pub struct BlockExecutor {
 impl BlockExecutor {
     pub fn new(
         db: Arc<Db>,
-        message_broker: &Arc<NatsMessageBroker>,
+        message_broker: &Arc<dyn MessageBroker>,
         pedronauck_streams: &Arc<FuelStreams>,
         telemetry: Arc<Telemetry<Metrics>>,
     ) -> Self {
         let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_TASKS));
         let fuel_stores = FuelStores::new(&db).arc();
+        let abi_registry = AbiRegistry::new(&db).arc();
         Self {
             db,
             semaphore,
             message_broker: message_broker.clone(),
             pedronauck_streams: pedronauck_streams.clone(),
             fuel_stores: fuel_stores.clone(),
+            abi_registry,
             telemetry,
         }
     }
@@ -74,8 +113,13 @@ impl BlockExecutor {
             "Starting consumer with max concurrent tasks: {}",
             MAX_CONCURRENT_TASKS
         );
-        let telemetry = self.telemetry.clone();
         let queue = NatsQueue::BlockImporter(self.message_broker.clone());
+        let mut publisher = BlockPublisher::spawn(
+            &self.message_broker,
+            &self.pedronauck_streams,
+            &self.telemetry,
+        );
+        let mut in_flight = InFlight::new();
         while !token.is_cancelled() {
             tokio::select! {
                 msg_result = queue.subscribe(BATCH_SIZE) => {
@@ -85,20 +129,35 @@ impl BlockExecutor {
                         self.spawn_processing_tasks(
                             msg,
                             &mut join_set,
+                            &mut publisher,
+                            &mut in_flight,
                         )
                         .await?;
                     }
                 }
-                Some(result) = join_set.join_next() => {
-                    Self::handle_task_result(result, &telemetry).await?;
+                Some(result) = join_set.join_next_with_id() => {
+                    self.handle_task_result(
+                        result,
+                        &queue,
+                        &mut publisher,
+                        &mut in_flight,
+                    )
+                    .await?;
                 }
             }
         }
 
         // Wait for all tasks to finish
-        while let Some(result) = join_set.join_next().await {
-            Self::handle_task_result(result, &telemetry).await?;
+        while let Some(result) = join_set.join_next_with_id().await {
+            self.handle_task_result(
+                result,
+                &queue,
+                &mut publisher,
+                &mut in_flight,
+            )
+            .await?;
         }
+        publisher.close().await?;
 
         tracing::info!("Stopping broker ...");
         shutdown_broker_with_timeout(&self.message_broker).await;
@@ -110,91 +169,218 @@ impl BlockExecutor {
         &self,
         msg: Box<dyn Message>,
         join_set: &mut JoinSet<Result<ProcessResult, ConsumerError>>,
+        publisher: &mut BlockPublisher,
+        in_flight: &mut InFlight,
     ) -> Result<(), ConsumerError> {
         let db = self.db.clone();
         let semaphore = self.semaphore.clone();
-        let pedronauck_streams = self.pedronauck_streams.clone();
         let fuel_stores = self.fuel_stores.clone();
         let payload = msg.payload();
-        let msg_payload = MsgPayload::decode(&payload).await?.arc();
-        let packets = Self::build_packets(&msg_payload);
-        join_set.spawn({
-            let semaphore = semaphore.clone();
-            let packets = packets.clone();
-            let msg_payload = msg_payload.clone();
-            let fuel_stores = fuel_stores.clone();
-            async move {
-                let _permit = semaphore.acquire().await?;
-                let result =
-                    handle_stores(&db, &fuel_stores, &packets, &msg_payload)
-                        .await;
-                Ok::<_, ConsumerError>(ProcessResult::Store(result))
-            }
-        });
-
-        join_set.spawn({
-            let semaphore = semaphore.clone();
-            let packets = packets.clone();
-            let msg_payload = msg_payload.clone();
-            let pedronauck_streams = pedronauck_streams.clone();
-            async move {
-                let _permit = semaphore.acquire_owned().await?;
-                let result =
-                    handle_streams(&pedronauck_streams, &packets, &msg_payload)
-                        .await;
-                Ok(ProcessResult::Stream(result))
+        let mut msg_payload = match MsgPayload::decode(&payload).await {
+            Ok(msg_payload) => msg_payload,
+            Err(e) => {
+                // Redelivering won't make the payload decodable
+                tracing::error!("Failed to decode message {}: {}", msg.id(), e);
+                let Some(height) = NatsSubject::parse_height(&msg.id()) else {
+                    return ack(msg.as_ref()).await;
+                };
+                dead_letter(&self.message_broker, msg.as_ref(), height).await?;
+                let event = BlockEvent::HandedOver(BlockHeight::from(height));
+                return publisher.send(event).await;
             }
+        };
+        decode_logs(&self.abi_registry, &mut msg_payload).await;
+        let msg_payload = msg_payload.arc();
+        let block_height = msg_payload.block_height();
+        let packets = Self::build_packets(&msg_payload);
+        let task = join_set.spawn(async move {
+            let _permit = semaphore.acquire_owned().await?;
+            let store =
+                handle_stores(&db, &fuel_stores, &packets, &msg_payload)
+                    .await?;
+            Ok(ProcessResult {
+                packets,
+                msg_payload,
+                store,
+            })
         });
-
-        msg.ack().await.map_err(|e| {
-            tracing::error!("Failed to ack message: {:?}", e);
-            ConsumerError::MessageBrokerClient(e)
-        })?;
-
-        Ok(())
+        in_flight.insert(task.id(), (msg, block_height));
+        publisher
+            .send(BlockEvent::Spawned(task.id(), block_height))
+            .await
     }
 
     async fn handle_task_result(
-        result: Result<Result<ProcessResult, ConsumerError>, JoinError>,
-        telemetry: &Arc<Telemetry<Metrics>>,
+        &self,
+        result: Result<(Id, Result<ProcessResult, ConsumerError>), JoinError>,
+        queue: &NatsQueue,
+        publisher: &mut BlockPublisher,
+        in_flight: &mut InFlight,
     ) -> Result<(), ConsumerError> {
-        match result {
-            Ok(Ok(ProcessResult::Store(store_result))) => {
-                let store_stats = store_result?;
-                if let Some(metrics) = telemetry.base_metrics() {
-                    metrics.update_from_stats(&store_stats)
-                }
-
-                match &store_stats.error {
-                    Some(error) => store_stats.log_error(error),
-                    None => store_stats.log_success(),
-                }
+        let (id, result) = match result {
+            Ok((id, result)) => (id, result),
+            Err(e) => (e.id(), Err(ConsumerError::from(e))),
+        };
+        let Some((msg, block_height)) = in_flight.remove(&id) else {
+            return Ok(());
+        };
+        let settled = match result {
+            // Stored blocks are published by the publisher, in height order
+            Ok(result) if result.store.error.is_none() => {
+                return publisher
+                    .send(BlockEvent::Stored(id, msg, result))
+                    .await;
             }
-            Ok(Ok(ProcessResult::Stream(stream_result))) => {
-                let stream_stats = stream_result?;
-                match &stream_stats.error {
-                    Some(error) => stream_stats.log_error(error),
-                    None => stream_stats.log_success(),
-                }
+            Ok(result) => {
+                finish_block(
+                    &self.telemetry,
+                    &self.message_broker,
+                    queue,
+                    msg,
+                    result.store,
+                    None,
+                )
+                .await?
+            }
+            Err(e) => {
+                tracing::error!("Task of block {} failed: {}", block_height, e);
+                settle_block(
+                    &self.message_broker,
+                    queue,
+                    msg.as_ref(),
+                    block_height,
+                    Some(&e),
+                )
+                .await?
             }
-            Ok(Err(e)) => tracing::error!("Task error: {}", e),
-            Err(e) => tracing::error!("Task panicked: {}", e),
+        };
+
+        // Failed blocks are never published for this delivery, so they
+        // only hold back the blocks above them until they are redelivered
+        publisher.send(BlockEvent::Failed(id)).await?;
+        if settled == Settled::HandedOver {
+            publisher.send(BlockEvent::HandedOver(block_height)).await?;
         }
         Ok(())
     }
 
-    fn build_packets(msg_payload: &MsgPayload) -> Arc<Vec<RecordPacket>> {
+    pub(super) fn build_packets(
+        msg_payload: &MsgPayload,
+    ) -> Arc<Vec<RecordPacket>> {
         let block_packets = Block::build_packets(msg_payload);
         let tx_packets = Transaction::build_packets(msg_payload);
+        let bundle_packets = TransactionBundle::build_packets(msg_payload);
+        let account_packets = AccountActivity::build_packets(msg_payload);
+        let contract_packets = ContractActivity::build_packets(msg_payload);
+        let coin_packets = Coin::build_packets(msg_payload);
+        let balance_packets = BalanceChange::build_packets(msg_payload);
+        let message_packets = BridgeMessage::build_packets(msg_payload);
+        let contract_created_packets = Contract::build_packets(msg_payload);
         let packets = block_packets
             .into_iter()
             .chain(tx_packets)
+            .chain(bundle_packets)
+            .chain(account_packets)
+            .chain(contract_packets)
+            .chain(coin_packets)
+            .chain(balance_packets)
+            .chain(message_packets)
+            .chain(contract_created_packets)
             .collect::<Vec<_>>();
         Arc::new(packets)
     }
 }
 
-async fn handle_stores(
+/// Records the stats of a processed block and settles its message
+pub(super) async fn finish_block(
+    telemetry: &Telemetry<Metrics>,
+    message_broker: &Arc<dyn MessageBroker>,
+    queue: &NatsQueue,
+    msg: Box<dyn Message>,
+    store: BlockStats,
+    stream: Option<BlockStats>,
+) -> Result<Settled, ConsumerError> {
+    if let Some(metrics) = telemetry.base_metrics() {
+        metrics.update_from_stats(&store)
+    }
+    for stats in std::iter::once(&store).chain(stream.as_ref()) {
+        match &stats.error {
+            Some(error) => stats.log_error(error),
+            None => stats.log_success(),
+        }
+    }
+
+    let stream_error = stream.as_ref().and_then(|s| s.error.as_ref());
+    settle_block(
+        message_broker,
+        queue,
+        msg.as_ref(),
+        store.block_height,
+        store.error.as_ref().or(stream_error),
+    )
+    .await
+}
+
+/// Acks a processed block, or schedules it for redelivery when it failed.
+/// Blocks that ran out of deliveries, or failed in a way a later delivery
+/// can't fix, are handed over to the retrier.
+pub(super) async fn settle_block(
+    message_broker: &Arc<dyn MessageBroker>,
+    queue: &NatsQueue,
+    msg: &dyn Message,
+    block_height: BlockHeight,
+    error: Option<&ConsumerError>,
+) -> Result<Settled, ConsumerError> {
+    let delivery_count = msg.delivery_count();
+    match error {
+        None => {
+            ack(msg).await?;
+            Ok(Settled::Acked)
+        }
+        Some(error)
+            if error.is_retryable()
+                && (delivery_count as i64) < queue.max_deliver() =>
+        {
+            let delay = redelivery_delay(delivery_count);
+            tracing::warn!(
+                "Block {} failed on delivery {}, redelivering in {:?}",
+                block_height,
+                delivery_count,
+                delay
+            );
+            msg.nak(Some(delay)).await.map_err(|e| {
+                tracing::error!("Failed to nak message: {:?}", e);
+                ConsumerError::MessageBrokerClient(e)
+            })?;
+            Ok(Settled::Redelivered)
+        }
+        Some(_) => {
+            tracing::error!(
+                "Block {} failed for good, sending it to the retrier",
+                block_height
+            );
+            dead_letter(message_broker, msg, block_height.into()).await?;
+            Ok(Settled::HandedOver)
+        }
+    }
+}
+
+/// Decodes the logs of contracts with a registered ABI. Blocks are still
+/// processed when the ABIs can't be loaded, with their logs left raw.
+pub(super) async fn decode_logs(
+    abi_registry: &AbiRegistry,
+    msg_payload: &mut MsgPayload,
+) {
+    if let Err(e) = abi_registry.decode_logs(msg_payload).await {
+        tracing::warn!(
+            "Failed to decode logs of block {}: {}",
+            msg_payload.block_height(),
+            e
+        );
+    }
+}
+
+pub(super) async fn handle_stores(
     db: &Arc<Db>,
     fuel_stores: &Arc<FuelStores>,
     packets: &Arc<Vec<RecordPacket>>,
@@ -206,9 +392,13 @@ async fn handle_stores(
     let result = retry_service
         .with_retry("store_insertions", || async {
             let mut tx = db.pool.begin().await?;
+            // Balance changes update the `balances` table as they are
+            // inserted, so balances are committed along with the block
             for packet in packets.iter() {
                 fuel_stores.insert_by_entity(&mut tx, packet).await?;
             }
+            let account_txs = AccountTransaction::from_msg_payload(msg_payload);
+            AccountTransaction::insert_many(&mut tx, &account_txs).await?;
             let block_propagation_ms = stats.calculate_block_propagation_ms();
             update_block_propagation_ms(
                 &mut tx,
@@ -227,7 +417,10 @@ async fn handle_stores(
     }
 }
 
-async fn handle_streams(
+/// Publishes the packets of a block one at a time, in the order their
+/// records are stored, so live subscribers see a transaction before its
+/// inputs, outputs and receipts.
+pub(super) async fn handle_streams(
     pedronauck_streams: &Arc<FuelStreams>,
     packets: &Arc<Vec<RecordPacket>>,
     msg_payload: &Arc<MsgPayload>,
@@ -235,14 +428,168 @@ async fn handle_streams(
     let block_height = msg_payload.block_height();
     let stats = BlockStats::new(block_height.to_owned(), ActionType::Stream);
     let now = BlockTimestamp::now();
-    let publish_futures = packets.iter().map(|packet| {
-        let packet = packet.to_owned();
-        let packet = packet.with_start_time(now);
-        pedronauck_streams.publish_by_entity(packet.arc())
-    });
-
-    match try_join_all(publish_futures).await {
-        Ok(_) => Ok(stats.finish(packets.len())),
-        Err(e) => Ok(stats.finish_with_error(ConsumerError::from(e))),
+    let mut ordered = packets.iter().collect::<Vec<_>>();
+    ordered.sort_by_key(|packet| packet.pointer(block_height));
+    for packet in ordered {
+        let packet = packet.to_owned().with_start_time(now);
+        if let Err(e) = pedronauck_streams.publish_by_entity(packet.arc()).await
+        {
+            return Ok(stats.finish_with_error(ConsumerError::from(e)));
+        }
+    }
+    Ok(stats.finish(packets.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use pedronauck_message_broker::MemoryMessageBroker;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const MAX_DELIVER: i64 = 2;
+
+    fn setup_broker() -> Arc<dyn MessageBroker> {
+        MemoryMessageBroker::new()
+            .with_namespace("test")
+            .with_ack_wait(1)
+            .with_max_deliver(MAX_DELIVER)
+            .arc()
+    }
+
+    async fn fetch(queue: &NatsQueue) -> Vec<Box<dyn Message>> {
+        let messages = queue.subscribe(10).await.unwrap();
+        messages.map(|msg| msg.unwrap()).collect().await
+    }
+
+    async fn stored(queue: &NatsQueue) -> Vec<String> {
+        let filter = format!("{}.>", queue.queue_name());
+        let messages = queue.messages(&filter, 10).await.unwrap();
+        messages.into_iter().map(|msg| msg.subject).collect()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_stored_block_is_acked() -> Result<(), ConsumerError> {
+        let broker = setup_broker();
+        let importer = NatsQueue::BlockImporter(broker.clone());
+        let retrier = NatsQueue::BlockRetrier(broker.clone());
+        importer
+            .publish(&NatsSubject::BlockSubmitted(1), "block")
+            .await?;
+
+        let messages = fetch(&importer).await;
+        assert_eq!(messages.len(), 1);
+        let settled = settle_block(
+            &broker,
+            &importer,
+            messages[0].as_ref(),
+            BlockHeight::from(1u64),
+            None,
+        )
+        .await?;
+        assert_eq!(settled, Settled::Acked);
+
+        assert!(stored(&importer).await.is_empty());
+        assert!(stored(&retrier).await.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_failed_block_is_redelivered() -> Result<(), ConsumerError> {
+        let broker = setup_broker();
+        let importer = NatsQueue::BlockImporter(broker.clone());
+        let retrier = NatsQueue::BlockRetrier(broker.clone());
+        importer
+            .publish(&NatsSubject::BlockSubmitted(1), "block")
+            .await?;
+
+        let messages = fetch(&importer).await;
+        let error = ConsumerError::DatabaseTimeout;
+        let settled = settle_block(
+            &broker,
+            &importer,
+            messages[0].as_ref(),
+            BlockHeight::from(1u64),
+            Some(&error),
+        )
+        .await?;
+        assert_eq!(settled, Settled::Redelivered);
+        assert!(stored(&retrier).await.is_empty());
+
+        tokio::time::advance(redelivery_delay(1)).await;
+        let messages = fetch(&importer).await;
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].delivery_count(), 2);
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_panicked_task_is_redelivered() -> Result<(), ConsumerError> {
+        let broker = setup_broker();
+        let importer = NatsQueue::BlockImporter(broker.clone());
+        importer
+            .publish(&NatsSubject::BlockSubmitted(1), "block")
+            .await?;
+
+        let messages = fetch(&importer).await;
+        let panicked = tokio::spawn(async { panic!("task failed") })
+            .await
+            .unwrap_err();
+        let error = ConsumerError::from(panicked);
+        let settled = settle_block(
+            &broker,
+            &importer,
+            messages[0].as_ref(),
+            BlockHeight::from(1u64),
+            Some(&error),
+        )
+        .await?;
+        assert_eq!(settled, Settled::Redelivered);
+
+        tokio::time::advance(redelivery_delay(1)).await;
+        let messages = fetch(&importer).await;
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].delivery_count(), 2);
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_block_goes_to_retrier_at_max_deliver(
+    ) -> Result<(), ConsumerError> {
+        let broker = setup_broker();
+        let importer = NatsQueue::BlockImporter(broker.clone());
+        let retrier = NatsQueue::BlockRetrier(broker.clone());
+        importer
+            .publish(&NatsSubject::BlockSubmitted(1), "block")
+            .await?;
+
+        let error = ConsumerError::DatabaseTimeout;
+        for delivery_count in 1..=MAX_DELIVER as u64 {
+            let messages = fetch(&importer).await;
+            assert_eq!(messages.len(), 1);
+            assert_eq!(messages[0].delivery_count(), delivery_count);
+            let settled = settle_block(
+                &broker,
+                &importer,
+                messages[0].as_ref(),
+                BlockHeight::from(1u64),
+                Some(&error),
+            )
+            .await?;
+            let expected = match delivery_count as i64 {
+                MAX_DELIVER => Settled::HandedOver,
+                _ => Settled::Redelivered,
+            };
+            assert_eq!(settled, expected);
+            tokio::time::advance(redelivery_delay(delivery_count)).await;
+        }
+
+        assert!(stored(&importer).await.is_empty());
+        let failed = retrier
+            .find_message(&NatsSubject::BlockFailed(1))
+            .await?
+            .expect("block handed over to the retrier");
+        assert_eq!(failed.payload.to_vec(), b"block".to_vec());
+        Ok(())
     }
 }
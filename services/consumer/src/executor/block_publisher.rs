@@ -0,0 +1,163 @@
+use std::{sync::Arc, time::Duration};
+
+use pedronauck_message_broker::{Message, MessageBroker, NatsQueue};
+use pedronauck_streams_core::{types::BlockHeight, FuelStreams};
+use pedronauck_web_utils::telemetry::Telemetry;
+use tokio::{
+    sync::mpsc,
+    task::{Id, JoinHandle},
+    time::{sleep_until, Instant},
+};
+
+use super::{
+    block_executor::{finish_block, handle_streams, ProcessResult},
+    block_retrier::dead_letter,
+    block_sequencer::{BlockSequencer, Release},
+};
+use crate::{errors::ConsumerError, metrics::Metrics};
+
+/// How long a missing height holds back the blocks above it. It covers the
+/// first redeliveries of a failed block.
+const GAP_TIMEOUT: Duration = Duration::from_secs(60);
+const EVENTS_CAPACITY: usize = 1024;
+
+/// Progress of a block in the executor
+#[derive(Debug)]
+pub(super) enum BlockEvent {
+    /// The block's task was spawned
+    Spawned(Id, BlockHeight),
+    /// The block's records were stored, so it can be published
+    Stored(Id, Box<dyn Message>, ProcessResult),
+    /// The block's task failed, so it won't be published for this delivery
+    Failed(Id),
+    /// The block was handed over to the retrier, so it isn't waited for
+    HandedOver(BlockHeight),
+}
+
+/// Task releasing the stored blocks in height order and publishing them, so
+/// the executor keeps spawning and storing blocks while they are published
+pub(super) struct BlockPublisher {
+    sender: mpsc::Sender<BlockEvent>,
+    handle: JoinHandle<Result<(), ConsumerError>>,
+}
+
+impl BlockPublisher {
+    pub fn spawn(
+        message_broker: &Arc<dyn MessageBroker>,
+        pedronauck_streams: &Arc<FuelStreams>,
+        telemetry: &Arc<Telemetry<Metrics>>,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel(EVENTS_CAPACITY);
+        let task = PublishTask {
+            message_broker: message_broker.clone(),
+            pedronauck_streams: pedronauck_streams.clone(),
+            telemetry: telemetry.clone(),
+            sequencer: BlockSequencer::strict(GAP_TIMEOUT),
+        };
+        let handle = tokio::spawn(task.run(receiver));
+        Self { sender, handle }
+    }
+
+    /// Sends the progress of a block, returning the publisher's error if it
+    /// stopped
+    pub async fn send(
+        &mut self,
+        event: BlockEvent,
+    ) -> Result<(), ConsumerError> {
+        if self.sender.send(event).await.is_ok() {
+            return Ok(());
+        }
+        // The task only stops before the executor when publishing failed
+        (&mut self.handle).await?
+    }
+
+    /// Waits for the blocks sent so far to be published. Blocks still held
+    /// back by a missing height are left unacknowledged.
+    pub async fn close(self) -> Result<(), ConsumerError> {
+        drop(self.sender);
+        self.handle.await?
+    }
+}
+
+struct PublishTask {
+    message_broker: Arc<dyn MessageBroker>,
+    pedronauck_streams: Arc<FuelStreams>,
+    telemetry: Arc<Telemetry<Metrics>>,
+    sequencer: BlockSequencer<(Box<dyn Message>, ProcessResult)>,
+}
+
+impl PublishTask {
+    async fn run(
+        mut self,
+        mut receiver: mpsc::Receiver<BlockEvent>,
+    ) -> Result<(), ConsumerError> {
+        let queue = NatsQueue::BlockImporter(self.message_broker.clone());
+        loop {
+            let deadline = self.sequencer.hold_deadline();
+            tokio::select! {
+                event = receiver.recv() => match event {
+                    Some(event) => self.apply(event),
+                    None => break,
+                },
+                _ = sleep_until(deadline.unwrap_or_else(Instant::now)),
+                    if deadline.is_some() => {}
+            }
+            self.publish_ready(&queue).await?;
+        }
+        Ok(())
+    }
+
+    fn apply(&mut self, event: BlockEvent) {
+        match event {
+            BlockEvent::Spawned(id, height) => self.sequencer.track(id, height),
+            BlockEvent::Stored(id, msg, result) => {
+                self.sequencer.complete(id, (msg, result))
+            }
+            BlockEvent::Failed(id) => self.sequencer.discard(id),
+            BlockEvent::HandedOver(height) => self.sequencer.skip(height),
+        }
+    }
+
+    /// Publishes the released blocks one at a time. Late blocks are handed
+    /// over to the retrier, which is the only one publishing out of order.
+    async fn publish_ready(
+        &mut self,
+        queue: &NatsQueue,
+    ) -> Result<(), ConsumerError> {
+        while let Some(release) = self.sequencer.pop_ready() {
+            match release {
+                Release::InOrder((msg, result)) => {
+                    let stream = handle_streams(
+                        &self.pedronauck_streams,
+                        &result.packets,
+                        &result.msg_payload,
+                    )
+                    .await?;
+                    finish_block(
+                        &self.telemetry,
+                        &self.message_broker,
+                        queue,
+                        msg,
+                        result.store,
+                        Some(stream),
+                    )
+                    .await?;
+                }
+                Release::Late((msg, result)) => {
+                    let height = result.msg_payload.block_height();
+                    tracing::warn!(
+                        "Block {} arrived after a higher block was published, sending it to the retrier",
+                        height
+                    );
+                    dead_letter(
+                        &self.message_broker,
+                        msg.as_ref(),
+                        height.into(),
+                    )
+                    .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,386 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use futures::StreamExt;
+use pedronauck_message_broker::{
+    Message,
+    MessageBroker,
+    NatsQueue,
+    NatsSubject,
+};
+use pedronauck_streams_core::FuelStreams;
+use pedronauck_streams_domains::{abis::AbiRegistry, MsgPayload};
+use pedronauck_streams_store::{
+    db::Db,
+    record::{DataEncoder, RecordPacket},
+};
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+
+use super::{
+    block_executor::{
+        decode_logs,
+        handle_stores,
+        handle_streams,
+        BlockExecutor,
+    },
+    block_sequencer::{BlockSequencer, Release},
+    block_stats::BlockStats,
+    retry::retrier_delay,
+};
+use crate::{errors::ConsumerError, FuelStores};
+
+const BATCH_SIZE: usize = 10;
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+struct RetryResult {
+    packets: Arc<Vec<RecordPacket>>,
+    msg_payload: Arc<MsgPayload>,
+    store: BlockStats,
+}
+
+/// Re-processes blocks the consumer gave up on, which are published to the
+/// `block_failed` subject of the retrier queue. Blocks that keep failing
+/// are moved to the `block_dead` subject of the retrier stream once the
+/// queue's max deliver is reached.
+pub struct BlockRetrier {
+    db: Arc<Db>,
+    message_broker: Arc<dyn MessageBroker>,
+    pedronauck_streams: Arc<FuelStreams>,
+    fuel_stores: Arc<FuelStores>,
+    abi_registry: Arc<AbiRegistry>,
+}
+
+impl BlockRetrier {
+    pub fn new(
+        db: Arc<Db>,
+        message_broker: &Arc<dyn MessageBroker>,
+        pedronauck_streams: &Arc<FuelStreams>,
+    ) -> Self {
+        let fuel_stores = FuelStores::new(&db).arc();
+        let abi_registry = AbiRegistry::new(&db).arc();
+        Self {
+            db,
+            message_broker: message_broker.clone(),
+            pedronauck_streams: pedronauck_streams.clone(),
+            fuel_stores,
+            abi_registry,
+        }
+    }
+
+    pub async fn start(
+        &self,
+        token: &CancellationToken,
+    ) -> Result<(), ConsumerError> {
+        tracing::info!("Starting block retrier");
+        let queue = NatsQueue::BlockRetrier(self.message_broker.clone());
+        while !token.is_cancelled() {
+            let mut messages = queue.subscribe(BATCH_SIZE).await?;
+            let mut batch = Vec::new();
+            while let Some(msg) = messages.next().await {
+                batch.push(msg?);
+            }
+            if batch.is_empty() {
+                tokio::select! {
+                    _ = tokio::time::sleep(POLL_INTERVAL) => {}
+                    _ = token.cancelled() => {}
+                }
+                continue;
+            }
+            self.retry_batch(batch, &queue).await?;
+        }
+        tracing::info!("Block retrier stopped");
+        Ok(())
+    }
+
+    /// Stores the blocks of a batch concurrently, then publishes the stored
+    /// ones in height order. Blocks are only published once their records
+    /// are committed, so subscribers never see data the store doesn't have.
+    async fn retry_batch(
+        &self,
+        batch: Vec<Box<dyn Message>>,
+        queue: &NatsQueue,
+    ) -> Result<(), ConsumerError> {
+        let mut join_set = JoinSet::new();
+        let mut sequencer = BlockSequencer::default();
+        // Messages are kept outside the tasks, so a block is still retried
+        // when its task fails or panics
+        let mut in_flight = HashMap::new();
+        for msg in batch {
+            if self.was_published_again(msg.as_ref()).await? {
+                tracing::info!(
+                    "Block {} was published again, leaving it to the importer",
+                    msg.id()
+                );
+                ack(msg.as_ref()).await?;
+                continue;
+            }
+            let mut msg_payload = match MsgPayload::decode(&msg.payload()).await
+            {
+                Ok(msg_payload) => msg_payload,
+                Err(e) => {
+                    let error = ConsumerError::from(e);
+                    settle_retry(
+                        &self.message_broker,
+                        queue,
+                        msg.as_ref(),
+                        Some(&error),
+                    )
+                    .await?;
+                    continue;
+                }
+            };
+            decode_logs(&self.abi_registry, &mut msg_payload).await;
+            let msg_payload = msg_payload.arc();
+            let block_height = msg_payload.block_height();
+            let packets = BlockExecutor::build_packets(&msg_payload);
+            let db = self.db.clone();
+            let fuel_stores = self.fuel_stores.clone();
+            let task = join_set.spawn(async move {
+                let store =
+                    handle_stores(&db, &fuel_stores, &packets, &msg_payload)
+                        .await?;
+                Ok::<_, ConsumerError>(RetryResult {
+                    packets,
+                    msg_payload,
+                    store,
+                })
+            });
+            sequencer.track(task.id(), block_height);
+            in_flight.insert(task.id(), msg);
+        }
+
+        while let Some(result) = join_set.join_next_with_id().await {
+            let (id, result) = match result {
+                Ok((id, result)) => (id, result),
+                Err(e) => (e.id(), Err(ConsumerError::from(e))),
+            };
+            let Some(msg) = in_flight.remove(&id) else {
+                continue;
+            };
+            match result {
+                Ok(result) if result.store.error.is_none() => {
+                    sequencer.complete(id, (msg, result))
+                }
+                Ok(result) => {
+                    sequencer.discard(id);
+                    settle_retry(
+                        &self.message_broker,
+                        queue,
+                        msg.as_ref(),
+                        result.store.error.as_ref(),
+                    )
+                    .await?;
+                }
+                Err(e) => {
+                    tracing::error!("Retry task of {} failed: {}", msg.id(), e);
+                    sequencer.discard(id);
+                    settle_retry(
+                        &self.message_broker,
+                        queue,
+                        msg.as_ref(),
+                        Some(&e),
+                    )
+                    .await?;
+                }
+            }
+
+            while let Some((msg, result)) =
+                sequencer.pop_ready().map(Release::into_inner)
+            {
+                let stream = handle_streams(
+                    &self.pedronauck_streams,
+                    &result.packets,
+                    &result.msg_payload,
+                )
+                .await?;
+                settle_retry(
+                    &self.message_broker,
+                    queue,
+                    msg.as_ref(),
+                    stream.error.as_ref(),
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the publisher submitted the block to the block importer
+    /// again, as its gap scan does for heights missing from the store. The
+    /// fresh copy supersedes the payload held by the retrier.
+    async fn was_published_again(
+        &self,
+        msg: &dyn Message,
+    ) -> Result<bool, ConsumerError> {
+        let Some(height) = NatsSubject::parse_height(&msg.id()) else {
+            return Ok(false);
+        };
+        let importer = NatsQueue::BlockImporter(self.message_broker.clone());
+        let fresh = importer
+            .find_message(&NatsSubject::BlockSubmitted(height))
+            .await?;
+        Ok(fresh.is_some())
+    }
+}
+
+/// Acks a recovered block, or schedules another attempt. Blocks that can't
+/// succeed on a later attempt, or ran out of deliveries, are moved to the
+/// dead letters.
+async fn settle_retry(
+    message_broker: &Arc<dyn MessageBroker>,
+    queue: &NatsQueue,
+    msg: &dyn Message,
+    error: Option<&ConsumerError>,
+) -> Result<(), ConsumerError> {
+    let delivery_count = msg.delivery_count();
+    let Some(error) = error else {
+        tracing::info!("Recovered failed block {}", msg.id());
+        return ack(msg).await;
+    };
+    if !error.is_retryable() || (delivery_count as i64) >= queue.max_deliver() {
+        tracing::error!(
+            "Block {} failed {} times, moving it to the dead letters: {}",
+            msg.id(),
+            delivery_count,
+            error
+        );
+        return bury(message_broker, msg).await;
+    }
+
+    let delay = retrier_delay(delivery_count);
+    tracing::warn!(
+        "Retry {} of block {} failed, trying again in {:?}: {}",
+        delivery_count,
+        msg.id(),
+        delay,
+        error
+    );
+    msg.nak(Some(delay))
+        .await
+        .map_err(ConsumerError::MessageBrokerClient)
+}
+
+/// Hands a block the consumer couldn't process over to the retrier queue
+/// and acks the original message.
+pub(super) async fn dead_letter(
+    message_broker: &Arc<dyn MessageBroker>,
+    msg: &dyn Message,
+    block_height: u64,
+) -> Result<(), ConsumerError> {
+    let queue = NatsQueue::BlockRetrier(message_broker.clone());
+    queue
+        .publish(&NatsSubject::BlockFailed(block_height), msg.payload())
+        .await?;
+    ack(msg).await
+}
+
+/// Moves a block the retrier gave up on to the dead letters of the retrier
+/// stream, where it waits to be replayed or dropped
+async fn bury(
+    message_broker: &Arc<dyn MessageBroker>,
+    msg: &dyn Message,
+) -> Result<(), ConsumerError> {
+    let Some(block_height) = NatsSubject::parse_height(&msg.id()) else {
+        tracing::error!("Dropping failed block with invalid id {}", msg.id());
+        return ack(msg).await;
+    };
+    let queue = NatsQueue::BlockRetrier(message_broker.clone());
+    queue
+        .publish(&NatsSubject::BlockDead(block_height), msg.payload())
+        .await?;
+    ack(msg).await
+}
+
+pub(super) async fn ack(msg: &dyn Message) -> Result<(), ConsumerError> {
+    msg.ack().await.map_err(|e| {
+        tracing::error!("Failed to ack message: {:?}", e);
+        ConsumerError::MessageBrokerClient(e)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pedronauck_message_broker::{
+        MemoryMessageBroker,
+        BLOCK_RETRIER_MAX_DELIVER,
+    };
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn setup_broker() -> Arc<dyn MessageBroker> {
+        MemoryMessageBroker::new()
+            .with_namespace("test")
+            .with_ack_wait(1)
+            .arc()
+    }
+
+    async fn fetch(queue: &NatsQueue) -> Vec<Box<dyn Message>> {
+        let messages = queue.subscribe(BATCH_SIZE).await.unwrap();
+        messages.map(|msg| msg.unwrap()).collect().await
+    }
+
+    async fn stored(queue: &NatsQueue, filter: &str) -> Vec<String> {
+        let messages = queue.messages(filter, BATCH_SIZE).await.unwrap();
+        messages.into_iter().map(|msg| msg.subject).collect()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_recovered_block_is_acked() -> Result<(), ConsumerError> {
+        let broker = setup_broker();
+        let retrier = NatsQueue::BlockRetrier(broker.clone());
+        retrier
+            .publish(&NatsSubject::BlockFailed(1), "block")
+            .await?;
+
+        let messages = fetch(&retrier).await;
+        assert_eq!(messages.len(), 1);
+        settle_retry(&broker, &retrier, messages[0].as_ref(), None).await?;
+
+        assert!(stored(&retrier, &retrier.consumer_subject())
+            .await
+            .is_empty());
+        assert!(stored(&retrier, &retrier.dead_letters_subject())
+            .await
+            .is_empty());
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_block_is_buried_at_max_deliver() -> Result<(), ConsumerError>
+    {
+        let broker = setup_broker();
+        let retrier = NatsQueue::BlockRetrier(broker.clone());
+        retrier
+            .publish(&NatsSubject::BlockFailed(1), "block")
+            .await?;
+
+        let error = ConsumerError::DatabaseTimeout;
+        for delivery_count in 1..=BLOCK_RETRIER_MAX_DELIVER as u64 {
+            let messages = fetch(&retrier).await;
+            assert_eq!(messages.len(), 1);
+            assert_eq!(messages[0].delivery_count(), delivery_count);
+            settle_retry(&broker, &retrier, messages[0].as_ref(), Some(&error))
+                .await?;
+            let buried =
+                retrier.find_message(&NatsSubject::BlockDead(1)).await?;
+            assert_eq!(
+                buried.is_some(),
+                delivery_count as i64 == BLOCK_RETRIER_MAX_DELIVER
+            );
+            tokio::time::advance(retrier_delay(delivery_count)).await;
+        }
+
+        let buried = retrier
+            .find_message(&NatsSubject::BlockDead(1))
+            .await?
+            .expect("block should be in the dead letters");
+        assert_eq!(buried.payload.to_vec(), b"block".to_vec());
+        assert!(stored(&retrier, &retrier.consumer_subject())
+            .await
+            .is_empty());
+        assert!(fetch(&retrier).await.is_empty());
+        Ok(())
+    }
+}
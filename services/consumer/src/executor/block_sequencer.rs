@@ -0,0 +1,302 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    time::Duration,
+};
+
+use pedronauck_streams_core::types::BlockHeight;
+use tokio::{task::Id, time::Instant};
+
+/// Releases concurrently processed blocks in height order, so live
+/// subscribers don't see block N+1 before block N.
+///
+/// Every block is tracked as soon as its task is spawned. A stored block is
+/// only released once no lower block is still being processed, and blocks
+/// whose task failed are dropped so they don't hold back the ones above.
+///
+/// A strict sequencer also waits for heights it isn't tracking, such as a
+/// block being redelivered after a failure, so heights are released one
+/// after the other. A missing height holds back the blocks above it until
+/// it comes back, it is handed over to the retrier with [`skip`], or the
+/// gap timeout expires. Blocks showing up below a released height are
+/// returned as [`Release::Late`], for the caller to hand them over to the
+/// retrier instead of publishing them out of order.
+///
+/// [`skip`]: BlockSequencer::skip
+#[derive(Debug)]
+pub(super) struct BlockSequencer<T> {
+    tasks: HashMap<Id, (BlockHeight, u64)>,
+    pending: BTreeMap<(BlockHeight, u64), Option<T>>,
+    next_seq: u64,
+    /// How long a missing height can hold back the blocks above it. Only
+    /// the blocks being tracked are ordered without it.
+    gap_timeout: Option<Duration>,
+    /// Lowest height not released yet
+    next_height: Option<u64>,
+    /// Missing heights that won't be released, as the retrier owns them
+    skipped: BTreeSet<u64>,
+    held_since: Option<Instant>,
+}
+
+/// Block leaving the sequencer
+#[derive(Debug, PartialEq)]
+pub(super) enum Release<T> {
+    /// Next block in height order
+    InOrder(T),
+    /// Block below a height that was already released
+    Late(T),
+}
+
+impl<T> Release<T> {
+    pub fn into_inner(self) -> T {
+        match self {
+            Release::InOrder(item) | Release::Late(item) => item,
+        }
+    }
+}
+
+impl<T> Default for BlockSequencer<T> {
+    fn default() -> Self {
+        Self {
+            tasks: HashMap::new(),
+            pending: BTreeMap::new(),
+            next_seq: 0,
+            gap_timeout: None,
+            next_height: None,
+            skipped: BTreeSet::new(),
+            held_since: None,
+        }
+    }
+}
+
+impl<T> BlockSequencer<T> {
+    /// Sequencer releasing heights one after the other, waiting up to
+    /// `gap_timeout` for a missing height
+    pub fn strict(gap_timeout: Duration) -> Self {
+        Self {
+            gap_timeout: Some(gap_timeout),
+            ..Self::default()
+        }
+    }
+
+    /// Starts tracking the block processed by the given task
+    pub fn track(&mut self, task_id: Id, height: BlockHeight) {
+        // The same height can be in flight twice when a message is
+        // redelivered, so entries are keyed by arrival as well
+        let key = (height, self.next_seq);
+        self.next_seq += 1;
+        self.tasks.insert(task_id, key);
+        self.pending.insert(key, None);
+    }
+
+    /// Marks the block of the given task as ready to be released
+    pub fn complete(&mut self, task_id: Id, item: T) {
+        if let Some(key) = self.tasks.remove(&task_id) {
+            self.pending.insert(key, Some(item));
+        }
+    }
+
+    /// Stops tracking a block that won't be released by this sequencer
+    pub fn discard(&mut self, task_id: Id) {
+        if let Some(key) = self.tasks.remove(&task_id) {
+            self.pending.remove(&key);
+        }
+    }
+
+    /// Stops waiting for a height that was handed over to the retrier
+    pub fn skip(&mut self, height: BlockHeight) {
+        let height = height.into_inner();
+        if self.next_height.is_some_and(|next| height >= next) {
+            self.skipped.insert(height);
+        }
+    }
+
+    /// When the gap holding back the next block times out, if any
+    pub fn hold_deadline(&self) -> Option<Instant> {
+        Some(self.held_since? + self.gap_timeout?)
+    }
+
+    /// Next block in height order, if it is ready and no missing height
+    /// holds it back
+    pub fn pop_ready(&mut self) -> Option<Release<T>> {
+        let entry = self.pending.first_entry()?;
+        entry.get().as_ref()?;
+        let height = entry.key().0.into_inner();
+        let next = self.next_height.unwrap_or(height);
+        if height < next {
+            let (_, item) = entry.remove_entry();
+            return item.map(Release::Late);
+        }
+        if height > next && !self.gap_settled(next, height) {
+            return None;
+        }
+
+        let (_, item) = self.pending.pop_first()?;
+        self.held_since = None;
+        self.skipped = self.skipped.split_off(&(height + 1));
+        if self.gap_timeout.is_some() {
+            self.next_height = Some(height + 1);
+        }
+        item.map(Release::InOrder)
+    }
+
+    /// Whether the heights from `next` up to `height` are settled, either
+    /// handed over to the retrier or missing for longer than the timeout
+    fn gap_settled(&mut self, next: u64, height: u64) -> bool {
+        let Some(gap_timeout) = self.gap_timeout else {
+            return true;
+        };
+        if self.skipped.range(next..height).count() as u64 == height - next {
+            return true;
+        }
+        let held_since = *self.held_since.get_or_insert_with(Instant::now);
+        if held_since.elapsed() < gap_timeout {
+            return false;
+        }
+        tracing::warn!(
+            "Blocks {} to {} didn't arrive within {:?}, releasing block {}",
+            next,
+            height - 1,
+            gap_timeout,
+            height
+        );
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAP_TIMEOUT: Duration = Duration::from_secs(60);
+
+    fn task_id() -> Id {
+        tokio::spawn(async {}).id()
+    }
+
+    fn track(sequencer: &mut BlockSequencer<u64>, heights: &[u64]) -> Vec<Id> {
+        heights
+            .iter()
+            .map(|height| {
+                let id = task_id();
+                sequencer.track(id, BlockHeight::from(*height));
+                id
+            })
+            .collect()
+    }
+
+    fn drain(sequencer: &mut BlockSequencer<u64>) -> Vec<u64> {
+        std::iter::from_fn(|| sequencer.pop_ready())
+            .map(Release::into_inner)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_out_of_order_completion() {
+        let mut sequencer = BlockSequencer::default();
+        let ids = track(&mut sequencer, &[1, 2, 3]);
+
+        sequencer.complete(ids[2], 3);
+        sequencer.complete(ids[1], 2);
+        assert!(drain(&mut sequencer).is_empty());
+
+        sequencer.complete(ids[0], 1);
+        assert_eq!(drain(&mut sequencer), vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_discarded_block_releases_higher_blocks() {
+        let mut sequencer = BlockSequencer::default();
+        let ids = track(&mut sequencer, &[1, 2]);
+
+        sequencer.complete(ids[1], 2);
+        assert!(drain(&mut sequencer).is_empty());
+
+        sequencer.discard(ids[0]);
+        assert_eq!(drain(&mut sequencer), vec![2]);
+    }
+
+    #[tokio::test]
+    async fn test_gap_across_batches() {
+        let mut sequencer = BlockSequencer::default();
+        let first = track(&mut sequencer, &[1, 3]);
+        sequencer.complete(first[1], 3);
+        sequencer.complete(first[0], 1);
+        assert_eq!(drain(&mut sequencer), vec![1, 3]);
+
+        // Without a gap timeout, block 2 wasn't tracked when block 3 went
+        // out, so it doesn't hold back block 4
+        let second = track(&mut sequencer, &[2, 4]);
+        sequencer.complete(second[1], 4);
+        assert!(drain(&mut sequencer).is_empty());
+        sequencer.complete(second[0], 2);
+        assert_eq!(drain(&mut sequencer), vec![2, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_redelivered_block() {
+        let mut sequencer = BlockSequencer::default();
+        let ids = track(&mut sequencer, &[5, 6]);
+        let redelivered = track(&mut sequencer, &[5]);
+
+        // Both deliveries of block 5 are tracked apart, so finishing one
+        // leaves the other in place
+        sequencer.complete(ids[1], 6);
+        sequencer.discard(ids[0]);
+        assert!(drain(&mut sequencer).is_empty());
+
+        sequencer.complete(redelivered[0], 5);
+        assert_eq!(drain(&mut sequencer), vec![5, 6]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_strict_waits_for_missing_height() {
+        let mut sequencer = BlockSequencer::strict(GAP_TIMEOUT);
+        let first = track(&mut sequencer, &[1, 2, 3]);
+        sequencer.complete(first[0], 1);
+        sequencer.complete(first[2], 3);
+        // Block 2 failed and waits to be redelivered
+        sequencer.discard(first[1]);
+        assert_eq!(drain(&mut sequencer), vec![1]);
+        assert!(sequencer.hold_deadline().is_some());
+
+        let redelivered = track(&mut sequencer, &[2]);
+        sequencer.complete(redelivered[0], 2);
+        assert_eq!(drain(&mut sequencer), vec![2, 3]);
+        assert_eq!(sequencer.hold_deadline(), None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_strict_skips_heights_handed_to_retrier() {
+        let mut sequencer = BlockSequencer::strict(GAP_TIMEOUT);
+        let ids = track(&mut sequencer, &[1, 2, 3, 4]);
+        sequencer.complete(ids[0], 1);
+        sequencer.discard(ids[1]);
+        sequencer.discard(ids[2]);
+        sequencer.complete(ids[3], 4);
+        assert_eq!(drain(&mut sequencer), vec![1]);
+
+        sequencer.skip(BlockHeight::from(2u64));
+        assert!(drain(&mut sequencer).is_empty());
+        sequencer.skip(BlockHeight::from(3u64));
+        assert_eq!(drain(&mut sequencer), vec![4]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_strict_gap_timeout() {
+        let mut sequencer = BlockSequencer::strict(GAP_TIMEOUT);
+        let first = track(&mut sequencer, &[1, 3]);
+        sequencer.complete(first[0], 1);
+        sequencer.complete(first[1], 3);
+        assert_eq!(drain(&mut sequencer), vec![1]);
+
+        tokio::time::advance(GAP_TIMEOUT - Duration::from_secs(1)).await;
+        assert!(drain(&mut sequencer).is_empty());
+        tokio::time::advance(Duration::from_secs(1)).await;
+        assert_eq!(drain(&mut sequencer), vec![3]);
+
+        // Block 2 shows up once block 3 went out, so it is late
+        let late = track(&mut sequencer, &[2]);
+        sequencer.complete(late[0], 2);
+        assert_eq!(sequencer.pop_ready(), Some(Release::Late(2)));
+    }
+}
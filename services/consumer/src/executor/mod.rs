@@ -1,5 +1,9 @@
 mod block_executor;
+mod block_publisher;
+mod block_retrier;
+mod block_sequencer;
 pub(crate) mod block_stats;
 mod retry;
 
 pub use block_executor::*;
+pub use block_retrier::BlockRetrier;
@@ -11,6 +11,28 @@ static DB_TIMEOUT: LazyLock<Duration> = LazyLock::new(|| {
     )
 });
 
+const NAK_BASE_DELAY: Duration = Duration::from_secs(1);
+const NAK_MAX_DELAY: Duration = Duration::from_secs(60);
+const RETRIER_BASE_DELAY: Duration = Duration::from_secs(30);
+const RETRIER_MAX_DELAY: Duration = Duration::from_secs(30 * 60);
+
+fn backoff(base: Duration, max: Duration, delivery_count: u64) -> Duration {
+    let exponent = delivery_count.saturating_sub(1).min(16) as u32;
+    base.saturating_mul(2u32.pow(exponent)).min(max)
+}
+
+/// Backoff before a failed block message is redelivered, doubling with each
+/// delivery up to a minute
+pub fn redelivery_delay(delivery_count: u64) -> Duration {
+    backoff(NAK_BASE_DELAY, NAK_MAX_DELAY, delivery_count)
+}
+
+/// Backoff between retrier attempts of a dead-lettered block, doubling with
+/// each delivery up to half an hour
+pub fn retrier_delay(delivery_count: u64) -> Duration {
+    backoff(RETRIER_BASE_DELAY, RETRIER_MAX_DELAY, delivery_count)
+}
+
 #[derive(Debug)]
 pub struct RetryService {
     pub max_retries: u32,
@@ -1,16 +1,29 @@
 use std::sync::Arc;
 
 use pedronauck_streams_core::types::{
+    AccountActivity,
+    BalanceChange,
     Block,
+    Coin,
+    Contract,
+    ContractActivity,
     Input,
+    Message,
     Output,
     Receipt,
     Transaction,
+    TransactionBundle,
     Utxo,
 };
 use pedronauck_streams_domains::{
+    accounts::AccountDbItem,
+    balances::BalanceChangeDbItem,
     blocks::BlockDbItem,
+    bundles::BundleDbItem,
+    coins::CoinDbItem,
+    contracts::{ContractDbItem, ContractTransactionDbItem},
     inputs::InputDbItem,
+    messages::MessageDbItem,
     outputs::OutputDbItem,
     receipts::ReceiptDbItem,
     transactions::TransactionDbItem,
@@ -32,6 +45,13 @@ pub struct FuelStores {
     pub outputs: Store<Output>,
     pub receipts: Store<Receipt>,
     pub utxos: Store<Utxo>,
+    pub bundles: Store<TransactionBundle>,
+    pub accounts: Store<AccountActivity>,
+    pub contract_transactions: Store<ContractActivity>,
+    pub coins: Store<Coin>,
+    pub balance_changes: Store<BalanceChange>,
+    pub messages: Store<Message>,
+    pub contracts: Store<Contract>,
 }
 
 impl FuelStores {
@@ -43,6 +63,13 @@ impl FuelStores {
             outputs: Store::new(db),
             receipts: Store::new(db),
             utxos: Store::new(db),
+            bundles: Store::new(db),
+            accounts: Store::new(db),
+            contract_transactions: Store::new(db),
+            coins: Store::new(db),
+            balance_changes: Store::new(db),
+            messages: Store::new(db),
+            contracts: Store::new(db),
         }
     }
 
@@ -55,6 +82,13 @@ impl FuelStores {
         self.outputs.with_namespace(namespace);
         self.receipts.with_namespace(namespace);
         self.utxos.with_namespace(namespace);
+        self.bundles.with_namespace(namespace);
+        self.accounts.with_namespace(namespace);
+        self.contract_transactions.with_namespace(namespace);
+        self.coins.with_namespace(namespace);
+        self.balance_changes.with_namespace(namespace);
+        self.messages.with_namespace(namespace);
+        self.contracts.with_namespace(namespace);
         self
     }
 
@@ -106,6 +140,48 @@ impl FuelStores {
                     .insert_record_with_transaction(db_tx, &db_item)
                     .await?;
             }
+            RecordEntity::Bundle => {
+                let db_item: BundleDbItem = packet.try_into()?;
+                self.bundles
+                    .insert_record_with_transaction(db_tx, &db_item)
+                    .await?;
+            }
+            RecordEntity::Account => {
+                let db_item: AccountDbItem = packet.try_into()?;
+                self.accounts
+                    .insert_record_with_transaction(db_tx, &db_item)
+                    .await?;
+            }
+            RecordEntity::ContractTransaction => {
+                let db_item: ContractTransactionDbItem = packet.try_into()?;
+                self.contract_transactions
+                    .insert_record_with_transaction(db_tx, &db_item)
+                    .await?;
+            }
+            RecordEntity::Coin => {
+                let db_item: CoinDbItem = packet.try_into()?;
+                self.coins
+                    .insert_record_with_transaction(db_tx, &db_item)
+                    .await?;
+            }
+            RecordEntity::BalanceChange => {
+                let db_item: BalanceChangeDbItem = packet.try_into()?;
+                self.balance_changes
+                    .insert_record_with_transaction(db_tx, &db_item)
+                    .await?;
+            }
+            RecordEntity::Message => {
+                let db_item: MessageDbItem = packet.try_into()?;
+                self.messages
+                    .insert_record_with_transaction(db_tx, &db_item)
+                    .await?;
+            }
+            RecordEntity::Contract => {
+                let db_item: ContractDbItem = packet.try_into()?;
+                self.contracts
+                    .insert_record_with_transaction(db_tx, &db_item)
+                    .await?;
+            }
         };
         Ok(())
     }
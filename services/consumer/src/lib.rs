@@ -7,3 +7,8 @@ pub mod server;
 pub use executor::*;
 pub use fuel_stores::*;
 pub use server::*;
+
+use std::sync::LazyLock;
+
+pub static API_PASSWORD: LazyLock<String> =
+    LazyLock::new(|| dotenvy::var("API_PASSWORD").ok().unwrap_or_default());
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use clap::Parser;
-use pedronauck_message_broker::NatsMessageBroker;
+use pedronauck_message_broker::{MessageBroker, NatsMessageBroker};
 use pedronauck_streams_core::FuelStreams;
 use pedronauck_streams_store::db::{Db, DbConnectionOpts};
 use pedronauck_web_utils::{shutdown::ShutdownController, telemetry::Telemetry};
@@ -10,6 +10,7 @@ use sv_consumer::{
     errors::ConsumerError,
     metrics::Metrics,
     BlockExecutor,
+    BlockRetrier,
     Server,
 };
 
@@ -26,11 +27,14 @@ async fn main() -> anyhow::Result<()> {
 
     // Initialize shared resources
     let db = setup_db(&cli.db_url).await?;
-    let message_broker = NatsMessageBroker::setup(&cli.nats_url, None).await?;
+    let message_broker: Arc<dyn MessageBroker> =
+        NatsMessageBroker::setup(&cli.nats_url, None).await?;
     let metrics = Metrics::new(None)?;
     let telemetry = Telemetry::new(Some(metrics)).await?;
     telemetry.start().await?;
     let pedronauck_streams = FuelStreams::new(&message_broker, &db).await.arc();
+    let block_retrier =
+        BlockRetrier::new(db.clone(), &message_broker, &pedronauck_streams);
     let block_executor = BlockExecutor::new(
         db,
         &message_broker,
@@ -44,11 +48,13 @@ async fn main() -> anyhow::Result<()> {
         result = async {
             tokio::join!(
                 block_executor.start(shutdown.token()),
+                block_retrier.start(shutdown.token()),
                 server.start()
             )
         } => {
             result.0?;
             result.1?;
+            result.2?;
             tracing::info!("Processing complete");
         }
         _ = shutdown.wait_for_shutdown() => {
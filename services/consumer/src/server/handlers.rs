@@ -0,0 +1,352 @@
+use actix_web::{http::StatusCode, web, HttpResponse};
+use pedronauck_message_broker::{
+    MessageBrokerError,
+    NatsQueue,
+    NatsSubject,
+    QueuedMessage,
+};
+use pedronauck_web_utils::server::{
+    api::with_prefixed_route,
+    middlewares::password::middleware::PasswordAuth,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::state::ServerState;
+
+const DEFAULT_LIST_LIMIT: usize = 100;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Message broker error {0}")]
+    MessageBroker(#[from] MessageBrokerError),
+    #[error("No dead-lettered block at height {0}")]
+    NotFound(u64),
+}
+
+impl From<Error> for actix_web::Error {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::MessageBroker(e) => actix_web::error::InternalError::new(
+                e,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+            .into(),
+            Error::NotFound(_) => {
+                actix_web::error::InternalError::new(err, StatusCode::NOT_FOUND)
+                    .into()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadLetter {
+    pub sequence: u64,
+    pub block_height: Option<u64>,
+    pub subject: String,
+    pub published_at: i64,
+}
+
+impl From<QueuedMessage> for DeadLetter {
+    fn from(msg: QueuedMessage) -> Self {
+        Self {
+            sequence: msg.sequence,
+            block_height: msg.block_height(),
+            subject: msg.subject,
+            published_at: msg.published_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListDeadLettersQuery {
+    pub limit: Option<usize>,
+}
+
+pub fn create_services(
+    state: ServerState,
+) -> impl Fn(&mut web::ServiceConfig) + Send + Sync + 'static {
+    move |cfg: &mut web::ServiceConfig| {
+        let password_manager = &state.password_manager;
+        cfg.app_data(web::Data::new(state.clone()));
+        let route = with_prefixed_route("dead_letters");
+        cfg.service(
+            web::resource(&route)
+                .wrap(PasswordAuth::new(password_manager))
+                .route(web::get().to(list_dead_letters)),
+        );
+        cfg.service(
+            web::resource(format!("{route}/{{height}}/replay"))
+                .wrap(PasswordAuth::new(password_manager))
+                .route(web::post().to(replay_dead_letter)),
+        );
+        cfg.service(
+            web::resource(format!("{route}/{{height}}"))
+                .wrap(PasswordAuth::new(password_manager))
+                .route(web::delete().to(drop_dead_letter)),
+        );
+    }
+}
+
+async fn find_dead_letter(
+    queue: &NatsQueue,
+    height: u64,
+) -> Result<QueuedMessage, Error> {
+    queue
+        .find_message(&NatsSubject::BlockDead(height))
+        .await?
+        .ok_or(Error::NotFound(height))
+}
+
+/// Lists the blocks the retrier gave up on. Blocks still waiting for another
+/// retrier attempt are not dead letters yet, so they are left out.
+pub async fn list_dead_letters(
+    query: web::Query<ListDeadLettersQuery>,
+    state: web::Data<ServerState>,
+) -> actix_web::Result<HttpResponse> {
+    let limit = query.limit.unwrap_or(DEFAULT_LIST_LIMIT);
+    let queue = NatsQueue::BlockRetrier(state.msg_broker.clone());
+    let dead_letters = queue
+        .messages(&queue.dead_letters_subject(), limit)
+        .await
+        .map_err(Error::from)?
+        .into_iter()
+        .map(DeadLetter::from)
+        .collect::<Vec<_>>();
+    Ok(HttpResponse::Ok().json(dead_letters))
+}
+
+/// Sends a dead-lettered block back to the block importer queue and removes
+/// it from the retrier
+pub async fn replay_dead_letter(
+    path: web::Path<u64>,
+    state: web::Data<ServerState>,
+) -> actix_web::Result<HttpResponse> {
+    let height = path.into_inner();
+    let retrier = NatsQueue::BlockRetrier(state.msg_broker.clone());
+    let dead_letter = find_dead_letter(&retrier, height).await?;
+    let importer = NatsQueue::BlockImporter(state.msg_broker.clone());
+    importer
+        .publish(
+            &NatsSubject::BlockSubmitted(height),
+            dead_letter.payload.clone(),
+        )
+        .await
+        .map_err(Error::from)?;
+    retrier
+        .delete_message(dead_letter.sequence)
+        .await
+        .map_err(Error::from)?;
+    tracing::info!("Replayed dead-lettered block {}", height);
+    Ok(HttpResponse::Ok().json(DeadLetter::from(dead_letter)))
+}
+
+pub async fn drop_dead_letter(
+    path: web::Path<u64>,
+    state: web::Data<ServerState>,
+) -> actix_web::Result<HttpResponse> {
+    let height = path.into_inner();
+    let retrier = NatsQueue::BlockRetrier(state.msg_broker.clone());
+    let dead_letter = find_dead_letter(&retrier, height).await?;
+    retrier
+        .delete_message(dead_letter.sequence)
+        .await
+        .map_err(Error::from)?;
+    tracing::info!("Dropped dead-lettered block {}", height);
+    Ok(HttpResponse::Ok().json(DeadLetter::from(dead_letter)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Instant};
+
+    use actix_web::{
+        dev::ServiceResponse,
+        http::header::AUTHORIZATION,
+        test,
+        App,
+    };
+    use pedronauck_message_broker::{MemoryMessageBroker, MessageBroker};
+    use pedronauck_web_utils::{
+        server::middlewares::password::PasswordManager,
+        telemetry::Telemetry,
+    };
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::metrics::Metrics;
+
+    const PASSWORD: &str = "secret";
+
+    async fn setup_state() -> ServerState {
+        let msg_broker: Arc<dyn MessageBroker> =
+            MemoryMessageBroker::new().with_namespace("test").arc();
+        ServerState {
+            start_time: Instant::now(),
+            msg_broker,
+            telemetry: Telemetry::<Metrics>::new(None).await.unwrap(),
+            password_manager: Arc::new(PasswordManager::new(
+                PASSWORD.to_string(),
+            )),
+        }
+    }
+
+    async fn bury(state: &ServerState, height: u64) {
+        let retrier = NatsQueue::BlockRetrier(state.msg_broker.clone());
+        retrier
+            .publish(&NatsSubject::BlockDead(height), "block")
+            .await
+            .unwrap();
+    }
+
+    fn authorized(req: test::TestRequest) -> test::TestRequest {
+        req.insert_header((AUTHORIZATION, format!("Bearer {PASSWORD}")))
+    }
+
+    fn status(result: Result<ServiceResponse, actix_web::Error>) -> StatusCode {
+        match result {
+            Ok(resp) => resp.status(),
+            Err(err) => err.as_response_error().status_code(),
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_dead_letters_require_password() {
+        let state = setup_state().await;
+        let app = test::init_service(
+            App::new().configure(create_services(state.clone())),
+        )
+        .await;
+        let route = with_prefixed_route("dead_letters");
+
+        let req = test::TestRequest::get().uri(&route).to_request();
+        let err = test::try_call_service(&app, req).await.unwrap_err();
+        assert_eq!(
+            err.as_response_error().status_code(),
+            StatusCode::UNAUTHORIZED
+        );
+
+        bury(&state, 1).await;
+        let req = test::TestRequest::post()
+            .uri(&format!("{route}/1/replay?password=wrong"))
+            .to_request();
+        let err = test::try_call_service(&app, req).await.unwrap_err();
+        assert_eq!(
+            err.as_response_error().status_code(),
+            StatusCode::UNAUTHORIZED
+        );
+
+        let retrier = NatsQueue::BlockRetrier(state.msg_broker.clone());
+        let dead_letter = retrier
+            .find_message(&NatsSubject::BlockDead(1))
+            .await
+            .unwrap();
+        assert!(dead_letter.is_some());
+    }
+
+    #[actix_web::test]
+    async fn test_list_dead_letters() {
+        let state = setup_state().await;
+        let app = test::init_service(
+            App::new().configure(create_services(state.clone())),
+        )
+        .await;
+        let route = with_prefixed_route("dead_letters");
+        bury(&state, 1).await;
+        bury(&state, 2).await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!("{route}?password={PASSWORD}"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        let heights = body
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|dead_letter| dead_letter["blockHeight"].as_u64())
+            .collect::<Vec<_>>();
+        assert_eq!(heights, vec![Some(1), Some(2)]);
+    }
+
+    #[actix_web::test]
+    async fn test_replay_dead_letter() {
+        let state = setup_state().await;
+        let app = test::init_service(
+            App::new().configure(create_services(state.clone())),
+        )
+        .await;
+        let route = with_prefixed_route("dead_letters");
+        bury(&state, 1).await;
+
+        let replay = || {
+            authorized(
+                test::TestRequest::post().uri(&format!("{route}/1/replay")),
+            )
+            .to_request()
+        };
+        assert_eq!(
+            status(test::try_call_service(&app, replay()).await),
+            StatusCode::OK
+        );
+
+        let importer = NatsQueue::BlockImporter(state.msg_broker.clone());
+        let replayed = importer
+            .find_message(&NatsSubject::BlockSubmitted(1))
+            .await
+            .unwrap()
+            .expect("block should be back in the importer queue");
+        assert_eq!(replayed.payload.to_vec(), b"block".to_vec());
+
+        let retrier = NatsQueue::BlockRetrier(state.msg_broker.clone());
+        let dead_letter = retrier
+            .find_message(&NatsSubject::BlockDead(1))
+            .await
+            .unwrap();
+        assert!(dead_letter.is_none());
+        assert_eq!(
+            status(test::try_call_service(&app, replay()).await),
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_drop_dead_letter() {
+        let state = setup_state().await;
+        let app = test::init_service(
+            App::new().configure(create_services(state.clone())),
+        )
+        .await;
+        let route = with_prefixed_route("dead_letters");
+        bury(&state, 1).await;
+
+        let delete = || {
+            authorized(test::TestRequest::delete().uri(&format!("{route}/1")))
+                .to_request()
+        };
+        assert_eq!(
+            status(test::try_call_service(&app, delete()).await),
+            StatusCode::OK
+        );
+
+        let retrier = NatsQueue::BlockRetrier(state.msg_broker.clone());
+        let dead_letter = retrier
+            .find_message(&NatsSubject::BlockDead(1))
+            .await
+            .unwrap();
+        assert!(dead_letter.is_none());
+        let importer = NatsQueue::BlockImporter(state.msg_broker.clone());
+        let replayed = importer
+            .find_message(&NatsSubject::BlockSubmitted(1))
+            .await
+            .unwrap();
+        assert!(replayed.is_none());
+        assert_eq!(
+            status(test::try_call_service(&app, delete()).await),
+            StatusCode::NOT_FOUND
+        );
+    }
+}
@@ -1,12 +1,13 @@
+pub mod handlers;
 pub mod metrics;
 pub(crate) mod state;
 
 use std::sync::Arc;
 
 use metrics::Metrics;
-use pedronauck_message_broker::NatsMessageBroker;
+use pedronauck_message_broker::MessageBroker;
 use pedronauck_web_utils::{
-    server::api::build_and_spawn_web_server,
+    server::api::{spawn_web_server, ApiServerBuilder},
     telemetry::Telemetry,
 };
 
@@ -14,14 +15,14 @@ use crate::{errors::ConsumerError, state::ServerState};
 
 pub struct Server {
     port: u16,
-    message_broker: Arc<NatsMessageBroker>,
+    message_broker: Arc<dyn MessageBroker>,
     telemetry: Arc<Telemetry<Metrics>>,
 }
 
 impl Server {
     pub fn new(
         port: u16,
-        message_broker: Arc<NatsMessageBroker>,
+        message_broker: Arc<dyn MessageBroker>,
         telemetry: Arc<Telemetry<Metrics>>,
     ) -> Self {
         Self {
@@ -35,9 +36,11 @@ impl Server {
         let server_state =
             ServerState::new(self.message_broker, Arc::clone(&self.telemetry));
 
-        build_and_spawn_web_server(self.port, server_state)
-            .await
+        let server = ApiServerBuilder::new(self.port, server_state.clone())
+            .with_dynamic_routes(handlers::create_services(server_state))
+            .build()
             .map_err(|_| ConsumerError::WebServerStart)?;
+        spawn_web_server(server).await;
         Ok(())
     }
 }
@@ -4,26 +4,34 @@ use std::{
 };
 
 use async_trait::async_trait;
-use pedronauck_message_broker::NatsMessageBroker;
-use pedronauck_web_utils::{server::state::StateProvider, telemetry::Telemetry};
+use pedronauck_message_broker::MessageBroker;
+use pedronauck_web_utils::{
+    server::{middlewares::password::PasswordManager, state::StateProvider},
+    telemetry::Telemetry,
+};
 
-use crate::metrics::Metrics;
+use crate::{metrics::Metrics, API_PASSWORD};
 
+#[derive(Clone)]
 pub struct ServerState {
     pub start_time: Instant,
-    pub msg_broker: Arc<NatsMessageBroker>,
+    pub msg_broker: Arc<dyn MessageBroker>,
     pub telemetry: Arc<Telemetry<Metrics>>,
+    pub password_manager: Arc<PasswordManager>,
 }
 
 impl ServerState {
     pub fn new(
-        msg_broker: Arc<NatsMessageBroker>,
+        msg_broker: Arc<dyn MessageBroker>,
         telemetry: Arc<Telemetry<Metrics>>,
     ) -> Self {
+        let password_manager =
+            Arc::new(PasswordManager::new(API_PASSWORD.clone()));
         Self {
             start_time: Instant::now(),
             msg_broker,
             telemetry,
+            password_manager,
         }
     }
 
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use clap::Parser;
-use pedronauck_message_broker::NatsMessageBroker;
+use pedronauck_message_broker::{MessageBroker, NatsMessageBroker};
 use pedronauck_streams_core::types::*;
 use pedronauck_streams_store::{
     db::{Db, DbConnectionOpts},
@@ -30,7 +30,8 @@ async fn main() -> anyhow::Result<()> {
     fuel_core.start().await?;
 
     let db = setup_db(&cli.db_url).await?;
-    let message_broker = NatsMessageBroker::setup(&cli.nats_url, None).await?;
+    let message_broker: Arc<dyn MessageBroker> =
+        NatsMessageBroker::setup(&cli.nats_url, None).await?;
     let last_block_height = Arc::new(fuel_core.get_latest_block_height()?);
     let gaps =
         Arc::new(find_next_block_to_save(&db, *last_block_height).await?);
@@ -98,7 +99,7 @@ async fn setup_db(db_url: &str) -> Result<Arc<Db>, PublishError> {
 }
 
 async fn process_live_blocks(
-    message_broker: &Arc<NatsMessageBroker>,
+    message_broker: &Arc<dyn MessageBroker>,
     fuel_core: &Arc<dyn FuelCoreLike>,
     token: CancellationToken,
     telemetry: &Arc<Telemetry<Metrics>>,
@@ -129,7 +130,7 @@ async fn process_live_blocks(
 
 fn process_historical_blocks(
     from_height: BlockHeight,
-    message_broker: &Arc<NatsMessageBroker>,
+    message_broker: &Arc<dyn MessageBroker>,
     fuel_core: &Arc<dyn FuelCoreLike>,
     last_block_height: &Arc<BlockHeight>,
     gaps: &Arc<Vec<BlockHeightGap>>,
@@ -169,7 +170,7 @@ fn process_historical_blocks(
 
 async fn process_blocks_with_join_set(
     processed_gaps: Vec<BlockHeightGap>,
-    message_broker: Arc<NatsMessageBroker>,
+    message_broker: Arc<dyn MessageBroker>,
     fuel_core: Arc<dyn FuelCoreLike>,
     telemetry: Arc<Telemetry<Metrics>>,
     token: CancellationToken,
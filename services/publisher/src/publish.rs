@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use fuel_core_types::blockchain::SealedBlock;
-use pedronauck_message_broker::{NatsMessageBroker, NatsQueue, NatsSubject};
+use pedronauck_message_broker::{MessageBroker, NatsQueue, NatsSubject};
 use pedronauck_streams_core::types::FuelCoreLike;
 use pedronauck_streams_domains::{Metadata, MsgPayload};
 use pedronauck_streams_store::record::DataEncoder;
@@ -10,7 +10,7 @@ use pedronauck_web_utils::telemetry::Telemetry;
 use crate::{error::PublishError, metrics::Metrics};
 
 pub async fn publish_block(
-    message_broker: &Arc<NatsMessageBroker>,
+    message_broker: &Arc<dyn MessageBroker>,
     fuel_core: &Arc<dyn FuelCoreLike>,
     sealed_block: &Arc<SealedBlock>,
     telemetry: &Arc<Telemetry<Metrics>>,
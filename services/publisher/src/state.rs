@@ -4,7 +4,7 @@ use std::{
 };
 
 use async_trait::async_trait;
-use pedronauck_message_broker::NatsMessageBroker;
+use pedronauck_message_broker::MessageBroker;
 use pedronauck_web_utils::{
     server::state::StateProvider,
     telemetry::Telemetry,
@@ -21,13 +21,13 @@ pub struct HealthResponse {
 
 pub struct ServerState {
     pub start_time: Instant,
-    pub msg_broker: Arc<NatsMessageBroker>,
+    pub msg_broker: Arc<dyn MessageBroker>,
     pub telemetry: Arc<Telemetry<Metrics>>,
 }
 
 impl ServerState {
     pub fn new(
-        msg_broker: Arc<NatsMessageBroker>,
+        msg_broker: Arc<dyn MessageBroker>,
         telemetry: Arc<Telemetry<Metrics>>,
     ) -> Self {
         Self {
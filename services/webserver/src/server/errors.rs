@@ -7,7 +7,7 @@ use pedronauck_streams_core::{
 use pedronauck_streams_domains::SubjectsError;
 use pedronauck_streams_store::{
     db::DbError,
-    record::{EncoderError, RecordEntityError},
+    record::{EncoderError, PayloadFilterError, RecordEntityError},
     store::StoreError,
 };
 use tokio::task::JoinError;
@@ -54,6 +54,8 @@ pub enum WebsocketError {
     Subjects(#[from] SubjectsError),
     #[error(transparent)]
     RecordEntity(#[from] RecordEntityError),
+    #[error(transparent)]
+    PayloadFilter(#[from] PayloadFilterError),
 }
 
 impl From<WebsocketError> for CloseReason {
@@ -72,7 +74,8 @@ impl From<WebsocketError> for CloseReason {
                 // Invalid type
                 WebsocketError::Encoder(_)
                 | WebsocketError::SubjectPayload(_)
-                | WebsocketError::MessagePayload(_) => CloseCode::Invalid,
+                | WebsocketError::MessagePayload(_)
+                | WebsocketError::PayloadFilter(_) => CloseCode::Invalid,
 
                 // Unsupported type
                 WebsocketError::Serde(_)
@@ -4,7 +4,7 @@ use std::{
 };
 
 use async_trait::async_trait;
-use pedronauck_message_broker::NatsMessageBroker;
+use pedronauck_message_broker::{MessageBroker, NatsMessageBroker};
 use pedronauck_streams_core::FuelStreams;
 use pedronauck_streams_store::db::{Db, DbConnectionOpts};
 use pedronauck_web_utils::{
@@ -24,7 +24,7 @@ use crate::{
 pub struct ServerState {
     pub db: Arc<Db>,
     pub start_time: Instant,
-    pub msg_broker: Arc<NatsMessageBroker>,
+    pub msg_broker: Arc<dyn MessageBroker>,
     pub pedronauck_streams: Arc<FuelStreams>,
     pub telemetry: Arc<Telemetry<Metrics>>,
     pub api_keys_manager: Arc<ApiKeysManager>,
@@ -35,7 +35,8 @@ pub struct ServerState {
 impl ServerState {
     pub async fn new(config: &Config) -> anyhow::Result<Self> {
         let url = &config.broker.url;
-        let msg_broker = NatsMessageBroker::setup(url, None).await?;
+        let msg_broker: Arc<dyn MessageBroker> =
+            NatsMessageBroker::setup(url, None).await?;
         let db = Db::new(DbConnectionOpts {
             connection_str: config.db.url.clone(),
             ..Default::default()
@@ -0,0 +1,134 @@
+use pedronauck_streams_core::{
+    server::ServerResponse,
+    types::{BlockHeight, StreamResponse},
+};
+
+/// Buffers the responses of the current block so they can be sent between
+/// `BlockStart` and `BlockEnd` markers. A block is closed by the first item
+/// of a later block, or by an explicit end of block: the end of the
+/// historical replay or the end of the streams. The responses are expected
+/// in chain order, as sent by [`merge_ordered`].
+///
+/// [`merge_ordered`]: pedronauck_streams_core::merge_ordered
+#[derive(Debug, Default)]
+pub struct BlockFrame {
+    height: Option<BlockHeight>,
+    items: Vec<StreamResponse>,
+}
+
+impl BlockFrame {
+    /// Buffers a response, returning the frame of the previous block when
+    /// the response starts a new one
+    pub fn push(&mut self, response: StreamResponse) -> Vec<ServerResponse> {
+        let height = response.pointer.block_height;
+        let closed = match self.height {
+            Some(current) if current != height => self.flush(),
+            _ => vec![],
+        };
+        self.height = Some(height);
+        self.items.push(response);
+        closed
+    }
+
+    /// Closes the current block, returning its items wrapped by the block
+    /// markers
+    pub fn flush(&mut self) -> Vec<ServerResponse> {
+        let Some(height) = self.height.take() else {
+            return vec![];
+        };
+        let items = std::mem::take(&mut self.items);
+        let mut responses = Vec::with_capacity(items.len() + 2);
+        responses.push(ServerResponse::BlockStart {
+            height,
+            item_count: items.len(),
+        });
+        responses.extend(items.into_iter().map(ServerResponse::Response));
+        responses.push(ServerResponse::BlockEnd { height });
+        responses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use pedronauck_streams_core::types::{MessagePayload, Utxo};
+    use pedronauck_streams_store::record::RecordPointer;
+
+    use super::*;
+
+    fn response(height: u32, tx_index: u32) -> StreamResponse {
+        StreamResponse {
+            version: "1".to_string(),
+            ty: "utxos".to_string(),
+            subject: format!("utxos.{height}.{tx_index}"),
+            pointer: RecordPointer {
+                block_height: height.into(),
+                tx_index: Some(tx_index),
+                ..Default::default()
+            },
+            payload: MessagePayload::Utxo(Arc::new(Utxo::default())),
+            propagation_time_ms: None,
+            payload_size: 0,
+        }
+    }
+
+    fn heights(responses: &[ServerResponse]) -> Vec<String> {
+        responses
+            .iter()
+            .map(|response| match response {
+                ServerResponse::BlockStart { height, item_count } => {
+                    format!("start:{height}:{item_count}")
+                }
+                ServerResponse::Response(response) => {
+                    format!("item:{}", response.pointer.block_height)
+                }
+                ServerResponse::BlockEnd { height } => format!("end:{height}"),
+                _ => "other".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_push_buffers_items_of_the_same_block() {
+        let mut frame = BlockFrame::default();
+        assert!(frame.push(response(1, 0)).is_empty());
+        assert!(frame.push(response(1, 1)).is_empty());
+    }
+
+    #[test]
+    fn test_push_closes_block_on_later_height() {
+        let mut frame = BlockFrame::default();
+        frame.push(response(1, 0));
+        frame.push(response(1, 1));
+        let closed = frame.push(response(2, 0));
+        assert_eq!(heights(&closed), vec![
+            "start:1:2",
+            "item:1",
+            "item:1",
+            "end:1"
+        ]);
+        assert_eq!(heights(&frame.flush()), vec![
+            "start:2:1",
+            "item:2",
+            "end:2"
+        ]);
+    }
+
+    #[test]
+    fn test_push_closes_block_right_away_on_skipped_heights() {
+        let mut frame = BlockFrame::default();
+        frame.push(response(1, 0));
+        let closed = frame.push(response(5, 0));
+        assert_eq!(heights(&closed), vec!["start:1:1", "item:1", "end:1"]);
+    }
+
+    #[test]
+    fn test_flush_empty_frame() {
+        let mut frame = BlockFrame::default();
+        assert!(frame.flush().is_empty());
+        frame.push(response(1, 0));
+        assert_eq!(frame.flush().len(), 3);
+        assert!(frame.flush().is_empty());
+    }
+}
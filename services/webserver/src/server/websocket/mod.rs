@@ -1,3 +1,4 @@
+mod block_frame;
 mod checker;
 mod session;
 mod subscribe;
@@ -1,29 +1,27 @@
 use std::sync::Arc;
 
 use actix_ws::Session;
-use futures::stream::{SelectAll, StreamExt};
+use futures::stream::{self, BoxStream, StreamExt};
 use pedronauck_streams_core::{
+    merge_ordered,
     prelude::IntoSubject,
     server::{ServerResponse, Subscription},
-    types::{ServerRequest, StreamResponse},
-    BoxedStream,
+    types::ServerRequest,
+    BoxedEventStream,
     FuelStreams,
     StreamError,
+    StreamEvent,
+    SubscribeOpts,
 };
 use pedronauck_streams_domains::Subjects;
-use pedronauck_streams_store::record::RecordEntity;
+use pedronauck_streams_store::record::{PayloadFilter, RecordEntity};
 use pedronauck_web_utils::api_key::{ApiKey, ApiKeyRole};
 use smallvec::SmallVec;
 
+use super::block_frame::BlockFrame;
 use crate::server::{errors::WebsocketError, websocket::WsSession};
 
-type CombinedStream = SelectAll<
-    Box<
-        dyn futures::Stream<Item = Result<StreamResponse, StreamError>>
-            + Send
-            + Unpin,
-    >,
->;
+type CombinedStream = BoxStream<'static, Result<StreamEvent, StreamError>>;
 
 pub async fn subscribe_mult(
     session: &mut Session,
@@ -32,7 +30,8 @@ pub async fn subscribe_mult(
 ) -> Result<(), WebsocketError> {
     let api_key = ctx.api_key();
     let subscriptions = server_request.subscriptions(api_key);
-    let mut streams = SmallVec::<[BoxedStream; 20]>::new();
+    let block_framing = server_request.block_framing();
+    let mut streams = SmallVec::<[BoxedEventStream; 20]>::new();
     let mut subscribed_msgs: SmallVec<[ServerResponse; 20]> = SmallVec::new();
 
     for subscription in subscriptions {
@@ -49,7 +48,8 @@ pub async fn subscribe_mult(
         streams.push(sub);
     }
 
-    let combined_stream = futures::stream::select_all(streams);
+    let combined_stream =
+        combine_streams(streams.into_vec(), server_request.merge_ordered());
     actix_web::rt::spawn({
         let ctx = ctx.to_owned();
         let api_key = api_key.to_owned();
@@ -64,6 +64,7 @@ pub async fn subscribe_mult(
                     &ctx,
                     &api_key,
                     combined_stream,
+                    block_framing,
                 )
                 .await;
             }
@@ -75,39 +76,66 @@ pub async fn subscribe_mult(
     Ok(())
 }
 
+/// Combines the subscription streams, merging their historical replays in
+/// chain order when asked to, or otherwise interleaving them as they come.
+/// A single [`StreamEvent::ReplayEnd`] is sent once every replay is done.
+fn combine_streams(
+    streams: Vec<BoxedEventStream>,
+    merge: bool,
+) -> CombinedStream {
+    if merge {
+        return merge_ordered(streams);
+    }
+    let mut replaying = streams.len();
+    stream::select_all(streams)
+        .filter_map(move |result| {
+            let event = match result {
+                Ok(StreamEvent::ReplayEnd) => {
+                    replaying = replaying.saturating_sub(1);
+                    (replaying == 0).then_some(Ok(StreamEvent::ReplayEnd))
+                }
+                result => Some(result),
+            };
+            futures::future::ready(event)
+        })
+        .boxed()
+}
+
 async fn process_subscription(
     session: &mut Session,
     ctx: &WsSession,
     api_key: &ApiKey,
     mut stream: CombinedStream,
+    block_framing: bool,
 ) {
     let mut shutdown_rx = ctx.receiver();
+    let mut frame = BlockFrame::default();
     loop {
-        tokio::select! {
-            Some(stream_result) = stream.next() => {
+        let responses = tokio::select! {
+            stream_result = stream.next() => {
                 match stream_result {
-                    Ok(result) => {
+                    Some(Ok(StreamEvent::Response(result))) => {
                         tracing::debug!("Received message from stream: {:?}", result);
-                        let payload = ServerResponse::Response(result);
-                        if let Err(err) = ctx.send_message(session, payload).await {
-                            match err {
-                                WebsocketError::Closed(_) => {
-                                    tracing::info!(%api_key, "Session closed, exiting subscription task");
-                                    break;
-                                }
-                                err => {
-                                    tracing::error!(%api_key, "Failed to send message: {}", err);
-                                    ctx.shutdown().await;
-                                    break;
-                                }
-                            }
+                        if block_framing {
+                            frame.push(result)
+                        } else {
+                            vec![ServerResponse::Response(result)]
                         }
                     }
-                    Err(err) => {
+                    // Replayed blocks are stored whole, so the last one is
+                    // complete once the replay is over
+                    Some(Ok(StreamEvent::ReplayEnd)) => frame.flush(),
+                    Some(Err(err)) => {
                         tracing::error!(%api_key, "Stream error: {}", err);
                         ctx.shutdown().await;
                         break;
                     }
+                    None => {
+                        tracing::info!(%api_key, "All streams ended, cleaning up subscriptions");
+                        send_responses(session, ctx, api_key, frame.flush()).await;
+                        ctx.shutdown().await;
+                        break;
+                    }
                 }
             }
             _ = shutdown_rx.changed() => {
@@ -116,12 +144,12 @@ async fn process_subscription(
                     ctx.shutdown().await;
                     break;
                 }
+                continue;
             }
-            else => {
-                tracing::info!(%api_key, "All streams ended, cleaning up subscriptions");
-                ctx.shutdown().await;
-                break;
-            }
+        };
+
+        if !send_responses(session, ctx, api_key, responses).await {
+            break;
         }
     }
 
@@ -129,54 +157,139 @@ async fn process_subscription(
     ctx.shutdown().await;
 }
 
+/// Sends the responses in order, returning whether the subscription should
+/// keep running
+async fn send_responses(
+    session: &mut Session,
+    ctx: &WsSession,
+    api_key: &ApiKey,
+    responses: Vec<ServerResponse>,
+) -> bool {
+    for payload in responses {
+        if let Err(err) = ctx.send_message(session, payload).await {
+            match err {
+                WebsocketError::Closed(_) => {
+                    tracing::info!(%api_key, "Session closed, exiting subscription task");
+                }
+                err => {
+                    tracing::error!(%api_key, "Failed to send message: {}", err);
+                    ctx.shutdown().await;
+                }
+            }
+            return false;
+        }
+    }
+    true
+}
+
 async fn create_subscriber(
     api_key_role: &ApiKeyRole,
     streams: &Arc<FuelStreams>,
     subscription: &Subscription,
-) -> Result<BoxedStream, WebsocketError> {
+) -> Result<BoxedEventStream, WebsocketError> {
     let subject_payload = subscription.payload.clone();
     let deliver_policy = subscription.deliver_policy;
     let subject: Subjects = subject_payload.clone().try_into()?;
     let subject: Arc<dyn IntoSubject> = subject.into();
     let subject_id = subject_payload.subject.as_str();
     let record_entity = RecordEntity::try_from(subject_id)?;
+    let filter = subscription
+        .filter
+        .as_deref()
+        .map(str::parse::<PayloadFilter>)
+        .transpose()?
+        .map(Arc::new);
+    let opts = SubscribeOpts {
+        filter,
+        rate_limit: subscription.rate_limit,
+    };
     let stream = match record_entity {
         RecordEntity::Block => {
             streams
                 .blocks
-                .subscribe_dynamic(subject, deliver_policy, api_key_role)
+                .subscribe_events(subject, deliver_policy, opts, api_key_role)
                 .await
         }
         RecordEntity::Transaction => {
             streams
                 .transactions
-                .subscribe_dynamic(subject, deliver_policy, api_key_role)
+                .subscribe_events(subject, deliver_policy, opts, api_key_role)
                 .await
         }
         RecordEntity::Input => {
             streams
                 .inputs
-                .subscribe_dynamic(subject, deliver_policy, api_key_role)
+                .subscribe_events(subject, deliver_policy, opts, api_key_role)
                 .await
         }
         RecordEntity::Output => {
             streams
                 .outputs
-                .subscribe_dynamic(subject, deliver_policy, api_key_role)
+                .subscribe_events(subject, deliver_policy, opts, api_key_role)
                 .await
         }
         RecordEntity::Receipt => {
             streams
                 .receipts
-                .subscribe_dynamic(subject, deliver_policy, api_key_role)
+                .subscribe_events(subject, deliver_policy, opts, api_key_role)
                 .await
         }
         RecordEntity::Utxo => {
             streams
                 .utxos
-                .subscribe_dynamic(subject, deliver_policy, api_key_role)
+                .subscribe_events(subject, deliver_policy, opts, api_key_role)
+                .await
+        }
+        RecordEntity::Bundle => {
+            streams
+                .bundles
+                .subscribe_events(subject, deliver_policy, opts, api_key_role)
+                .await
+        }
+        RecordEntity::Account => {
+            streams
+                .accounts
+                .subscribe_events(subject, deliver_policy, opts, api_key_role)
+                .await
+        }
+        RecordEntity::ContractTransaction => {
+            streams
+                .contracts
+                .subscribe_events(subject, deliver_policy, opts, api_key_role)
+                .await
+        }
+        RecordEntity::Coin => {
+            streams
+                .coins
+                .subscribe_events(subject, deliver_policy, opts, api_key_role)
+                .await
+        }
+        RecordEntity::BalanceChange => {
+            streams
+                .balances
+                .subscribe_events(subject, deliver_policy, opts, api_key_role)
+                .await
+        }
+        RecordEntity::Message => {
+            streams
+                .messages
+                .subscribe_events(subject, deliver_policy, opts, api_key_role)
+                .await
+        }
+        RecordEntity::Contract => {
+            streams
+                .contracts_created
+                .subscribe_events(subject, deliver_policy, opts, api_key_role)
                 .await
         }
     };
+    let projection = subscription.projection.clone();
+    let stream = stream.map(move |result| match (&projection, result) {
+        (Some(projection), Ok(StreamEvent::Response(response))) => response
+            .with_projection(projection)
+            .map(StreamEvent::Response)
+            .map_err(StreamError::from),
+        (_, result) => result,
+    });
     Ok(Box::new(stream))
 }
@@ -3,7 +3,7 @@ mod fuel_core_helpers;
 use std::sync::Arc;
 
 pub use fuel_core_helpers::*;
-use pedronauck_message_broker::NatsMessageBroker;
+use pedronauck_message_broker::{MessageBroker, NatsMessageBroker};
 use pedronauck_streams_core::{stream::*, subjects::IntoSubject, types::Block};
 use pedronauck_streams_domains::{
     blocks::{subjects::BlocksSubject, types::MockBlock, BlockDbItem},
@@ -40,7 +40,8 @@ pub async fn setup_stream(
     prefix: &str,
 ) -> anyhow::Result<Stream<Block>> {
     let db = setup_db().await?;
-    let broker = NatsMessageBroker::setup(nats_url, Some(prefix)).await?;
+    let broker: Arc<dyn MessageBroker> =
+        NatsMessageBroker::setup(nats_url, Some(prefix)).await?;
     let stream =
         Stream::<Block>::with_namespace(&broker, &db, prefix.to_string());
     Ok(stream)
@@ -1,6 +1,11 @@
 use std::sync::Arc;
 
-use pedronauck_message_broker::{NatsMessageBroker, NatsQueue, NatsSubject};
+use pedronauck_message_broker::{
+    MessageBroker,
+    NatsMessageBroker,
+    NatsQueue,
+    NatsSubject,
+};
 use pedronauck_streams_core::{
     inputs::InputsSubject,
     outputs::OutputsSubject,
@@ -198,7 +203,7 @@ async fn test_consumer_inserting_records() -> anyhow::Result<()> {
     let db = setup_db().await?;
     let shutdown = Arc::new(ShutdownController::new());
     shutdown.clone().spawn_signal_handler();
-    let message_broker =
+    let message_broker: Arc<dyn MessageBroker> =
         NatsMessageBroker::setup("nats://localhost:4222", Some(&prefix))
             .await?;
 
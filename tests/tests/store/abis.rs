@@ -0,0 +1,139 @@
+use pedronauck_streams_core::{
+    subjects::{ReceiptsLogDataSubject, SubjectBuildable},
+    types::{
+        Bytes32,
+        ContractAbi,
+        ContractId,
+        LogDataReceipt,
+        MockReceipt,
+        Receipt,
+        TxId,
+    },
+};
+use pedronauck_streams_domains::{
+    abis::{AbiError, AbiRegistry},
+    receipts::ReceiptDbItem,
+    transactions::types::MockTransaction,
+    MockMsgPayload,
+};
+use pedronauck_streams_store::record::{PacketBuilder, QueryOptions, Record};
+use pedronauck_streams_test::{close_db, create_random_db_name, setup_store};
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+const AMOUNT_LOG_ID: u64 = 8;
+
+/// ABI of a contract logging a single `u64`
+fn abi() -> serde_json::Value {
+    json!({
+        "programType": "contract",
+        "specVersion": "1",
+        "encodingVersion": "1",
+        "concreteTypes": [
+            { "type": "u64", "concreteTypeId": "u64" }
+        ],
+        "metadataTypes": [],
+        "loggedTypes": [
+            { "logId": AMOUNT_LOG_ID.to_string(), "concreteTypeId": "u64" }
+        ]
+    })
+}
+
+fn log_data(contract_id: &ContractId, log_id: u64, data: Vec<u8>) -> Receipt {
+    let Receipt::LogData(receipt) = MockReceipt::log_data() else {
+        panic!("expected a log data receipt");
+    };
+    Receipt::LogData(LogDataReceipt {
+        id: contract_id.to_owned(),
+        rb: log_id.into(),
+        len: (data.len() as u64).into(),
+        data: Some(data.into()),
+        ..receipt
+    })
+}
+
+#[tokio::test]
+async fn test_store_contract_abi() -> anyhow::Result<()> {
+    let store = setup_store::<Receipt>().await?;
+    let contract_id = ContractId::from(Bytes32::from(&TxId::random()));
+    assert_eq!(ContractAbi::find(&store.db.pool, &contract_id).await?, None);
+
+    let created =
+        ContractAbi::upsert(&store.db.pool, &contract_id, &abi()).await?;
+    assert_eq!(created.contract_id, contract_id);
+    assert_eq!(created.abi, abi());
+    let found = ContractAbi::find(&store.db.pool, &contract_id).await?;
+    assert_eq!(found, Some(created.to_owned()));
+
+    // Uploading again replaces the ABI in place
+    let mut updated_abi = abi();
+    updated_abi["programType"] = json!("library");
+    let updated =
+        ContractAbi::upsert(&store.db.pool, &contract_id, &updated_abi).await?;
+    assert_eq!(updated.abi, updated_abi);
+    assert_eq!(updated.created_at, created.created_at);
+
+    // ABIs whose logged types can't be decoded are rejected
+    let mut invalid_abi = abi();
+    invalid_abi["loggedTypes"][0]["concreteTypeId"] = json!("missing");
+    let result =
+        ContractAbi::upsert(&store.db.pool, &contract_id, &invalid_abi).await;
+    assert!(matches!(result, Err(AbiError::UnknownType(_))));
+
+    close_db(&store.db).await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_store_decoded_log_data() -> anyhow::Result<()> {
+    let prefix = create_random_db_name();
+    let mut store = setup_store::<Receipt>().await?;
+    store.with_namespace(&prefix);
+
+    let contract_id = ContractId::from(Bytes32::from(&TxId::random()));
+    ContractAbi::upsert(&store.db.pool, &contract_id, &abi()).await?;
+    let tx = MockTransaction::script(vec![], vec![], vec![
+        log_data(&contract_id, AMOUNT_LOG_ID, 42u64.to_be_bytes().to_vec()),
+        // Logs that don't match the ABI are kept undecoded
+        log_data(&contract_id, 1, vec![1, 2, 3]),
+    ]);
+    let mut msg_payload =
+        MockMsgPayload::with_transactions(1, vec![tx]).into_inner();
+    msg_payload.namespace = Some(prefix.to_string());
+
+    let registry = AbiRegistry::new(&store.db);
+    assert_eq!(registry.decode_logs(&mut msg_payload).await?, 1);
+    let receipts = &msg_payload.transactions[0].receipts;
+    let Receipt::LogData(receipt) = &receipts[0] else {
+        panic!("expected a log data receipt");
+    };
+    let decoded = receipt.decoded.as_ref().expect("log should be decoded");
+    assert_eq!(decoded.log_id, AMOUNT_LOG_ID.to_string());
+    assert_eq!(decoded.type_name, "u64");
+    assert_eq!(decoded.value, json!("42"));
+    let Receipt::LogData(receipt) = &receipts[1] else {
+        panic!("expected a log data receipt");
+    };
+    assert_eq!(receipt.decoded, None);
+
+    let packets = Receipt::build_packets(&msg_payload);
+    assert_eq!(packets.len(), 2);
+    for packet in &packets {
+        let db_item = ReceiptDbItem::try_from(packet)?;
+        store.insert_record(&db_item).await?;
+    }
+
+    let subject = ReceiptsLogDataSubject::new()
+        .with_contract(Some(contract_id))
+        .with_log_id(Some(AMOUNT_LOG_ID))
+        .dyn_arc();
+    let records = store
+        .find_many_by_subject(&subject, QueryOptions::default())
+        .await?;
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].log_type, Some("u64".to_string()));
+    assert_eq!(Receipt::from_db_item(&records[0])?, receipts[0]);
+
+    close_db(&store.db).await;
+    Ok(())
+}
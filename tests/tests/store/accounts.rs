@@ -0,0 +1,200 @@
+use pedronauck_streams_core::{
+    subjects::{AccountsSubject, SubjectBuildable, TransactionsSubject},
+    types::{
+        AccountActivity,
+        AccountActivityType,
+        AccountRole,
+        Address,
+        Bytes32,
+        Input,
+        InputMessage,
+        MockInput,
+        MockTransaction,
+        Output,
+        OutputCoin,
+        Transaction,
+        TxId,
+    },
+};
+use pedronauck_streams_domains::{
+    accounts::{AccountDbItem, AccountTransaction},
+    queryable::Queryable,
+    transactions::{queryable::TransactionsQuery, TransactionDbItem},
+    MockMsgPayload,
+    Subjects,
+};
+use pedronauck_streams_store::record::{
+    PacketBuilder,
+    QueryOptions,
+    Record,
+    RecordPointer,
+};
+use pedronauck_streams_test::{close_db, create_random_db_name, setup_store};
+use pretty_assertions::assert_eq;
+
+#[tokio::test]
+async fn test_store_account_activity() -> anyhow::Result<()> {
+    let prefix = create_random_db_name();
+    let mut store = setup_store::<AccountActivity>().await?;
+    store.with_namespace(&prefix);
+
+    let msg_payload = MockMsgPayload::build(1, &prefix);
+    let packets = AccountActivity::build_packets(&msg_payload);
+    assert!(!packets.is_empty());
+
+    for packet in packets.iter() {
+        let db_item = AccountDbItem::try_from(packet)?;
+        let inserted = store.insert_record(&db_item).await?;
+        assert_eq!(inserted.subject, packet.subject_str());
+
+        let subject: Subjects = packet.subject_payload.clone().try_into()?;
+        assert!(matches!(subject, Subjects::Accounts(_)));
+
+        let activity = AccountActivity::from_db_item(&inserted)?;
+        assert_eq!(activity.address.to_string(), inserted.address);
+        assert!(activity.record.addresses().contains(&activity.address));
+    }
+
+    let first = AccountDbItem::try_from(&packets[0])?;
+    let activity = AccountActivity::from_db_item(&first)?;
+    let subject = AccountsSubject::new()
+        .with_address(Some(activity.address.clone()))
+        .dyn_arc();
+    let records = store
+        .find_many_by_subject(&subject, QueryOptions::default())
+        .await?;
+    assert!(!records.is_empty());
+    let pointers = records
+        .iter()
+        .map(|record| RecordPointer::from(record.to_owned()))
+        .collect::<Vec<_>>();
+    assert!(pointers.windows(2).all(|pair| pair[0] <= pair[1]));
+    for record in records.iter() {
+        assert_eq!(record.address, activity.address.to_string());
+        let activity_type: AccountActivityType =
+            record.activity_type.parse().unwrap();
+        let pointer = RecordPointer::from(record.to_owned());
+        match activity_type {
+            AccountActivityType::Input | AccountActivityType::Utxo => {
+                assert!(pointer.input_index.is_some())
+            }
+            AccountActivityType::Output => {
+                assert!(pointer.output_index.is_some())
+            }
+            AccountActivityType::Receipt => {
+                assert!(pointer.receipt_index.is_some())
+            }
+        }
+    }
+
+    close_db(&store.db).await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_page_through_record_accounts() -> anyhow::Result<()> {
+    let prefix = create_random_db_name();
+    let mut store = setup_store::<AccountActivity>().await?;
+    store.with_namespace(&prefix);
+
+    // A message input touches its sender and recipient, and so does the
+    // UTXO it spends, all with the index of the input
+    let input = Input::Message(InputMessage {
+        sender: Address::from(Bytes32::from(&TxId::random())),
+        recipient: Address::from(Bytes32::from(&TxId::random())),
+        amount: 100.into(),
+        ..Default::default()
+    });
+    let tx = MockTransaction::script(vec![input], vec![], vec![]);
+    let mut msg_payload =
+        MockMsgPayload::with_transactions(1, vec![tx.to_owned()]).into_inner();
+    msg_payload.namespace = Some(prefix.to_string());
+    let packets = AccountActivity::build_packets(&msg_payload);
+    assert!(packets.len() > 2);
+    for packet in packets.iter() {
+        let db_item = AccountDbItem::try_from(packet)?;
+        store.insert_record(&db_item).await?;
+    }
+
+    // Paging one row at a time returns each of them once
+    let subject = AccountsSubject::new().with_tx_id(Some(tx.id)).dyn_arc();
+    let mut options = QueryOptions::default().with_limit(1);
+    let mut subjects = vec![];
+    loop {
+        let records = store
+            .find_many_by_subject(&subject, options.clone())
+            .await?;
+        let Some(record) = records.into_iter().next() else {
+            break;
+        };
+        subjects.push(record.subject.to_owned());
+        options.seek_after(record.into());
+    }
+    let mut expected = packets
+        .iter()
+        .map(|packet| packet.subject_str())
+        .collect::<Vec<_>>();
+    expected.sort();
+    subjects.sort();
+    assert_eq!(subjects, expected);
+
+    close_db(&store.db).await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_account_transactions_back_transactions_query(
+) -> anyhow::Result<()> {
+    let prefix = create_random_db_name();
+    let mut store = setup_store::<Transaction>().await?;
+    store.with_namespace(&prefix);
+
+    // The database is shared across runs, so each run uses a new address
+    let recipient = Address::from(Bytes32::from(&TxId::random()));
+    let output = Output::Coin(OutputCoin {
+        to: recipient.to_owned(),
+        amount: 100.into(),
+        ..Default::default()
+    });
+    let tx = MockTransaction::script(
+        vec![MockInput::coin_signed()],
+        vec![output],
+        vec![],
+    );
+    let msg_payload = MockMsgPayload::build(1, &prefix);
+    let subject = TransactionsSubject::new()
+        .with_block_height(Some(1.into()))
+        .with_tx_id(Some(tx.id.clone()))
+        .with_tx_index(Some(0))
+        .with_tx_status(Some(tx.status.clone()))
+        .with_tx_type(Some(tx.tx_type.clone()))
+        .dyn_arc();
+    let packet = tx
+        .to_packet(&subject, msg_payload.timestamp())
+        .with_namespace(&prefix);
+    let db_item = TransactionDbItem::try_from(&packet)?;
+    store.insert_record(&db_item).await?;
+
+    let links = AccountTransaction::from_transaction(1.into(), 0, &tx);
+    assert!(links.iter().any(|link| {
+        link.address == recipient && link.role == AccountRole::Recipient
+    }));
+    // Processing the block again keeps a single link per role
+    for _ in 0..2 {
+        let mut db_tx = store.db.pool.begin().await?;
+        AccountTransaction::insert_many(&mut db_tx, &links).await?;
+        db_tx.commit().await?;
+    }
+
+    let mut query = TransactionsQuery {
+        pagination: (None, None, Some(10), None).into(),
+        ..Default::default()
+    };
+    query.set_address(&recipient.to_string());
+    let records = query.execute(&store.db.pool).await?;
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].tx_id, tx.id.to_string());
+
+    close_db(&store.db).await;
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use pedronauck_streams_core::{
+    subjects::{BalancesSubject, SubjectBuildable},
+    types::{
+        Address,
+        BalanceChange,
+        BalanceChangeKind,
+        Bytes32,
+        CallReceipt,
+        ContractId,
+        Input,
+        InputCoin,
+        MockInput,
+        MockTransaction,
+        Output,
+        OutputChange,
+        OutputCoin,
+        Receipt,
+        Transaction,
+        TransferReceipt,
+        TxId,
+    },
+};
+use pedronauck_streams_domains::{
+    balances::{queryable::BalancesQuery, BalanceChangeDbItem},
+    MockMsgPayload,
+    Subjects,
+};
+use pedronauck_streams_store::{
+    record::{PacketBuilder, QueryOptions, Record, RecordPacket},
+    store::Store,
+};
+use pedronauck_streams_test::{close_db, create_random_db_name, setup_store};
+use pretty_assertions::assert_eq;
+
+fn build_packets(prefix: &str, tx: &Transaction) -> Vec<RecordPacket> {
+    let mut msg_payload =
+        MockMsgPayload::with_transactions(1, vec![tx.to_owned()]).into_inner();
+    msg_payload.namespace = Some(prefix.to_string());
+    BalanceChange::build_packets(&msg_payload)
+}
+
+async fn insert_packets(
+    store: &Store<BalanceChange>,
+    packets: &[RecordPacket],
+) -> anyhow::Result<()> {
+    for packet in packets {
+        let db_item = BalanceChangeDbItem::try_from(packet)?;
+        store.insert_record(&db_item).await?;
+    }
+    Ok(())
+}
+
+async fn balance_of(
+    store: &Store<BalanceChange>,
+    owner: &str,
+) -> anyhow::Result<Vec<String>> {
+    let mut query = BalancesQuery {
+        pagination: (None, None, Some(10), None).into(),
+        ..Default::default()
+    };
+    query.set_owner(owner);
+    let balances = query.execute(&store.db.pool).await?;
+    Ok(balances.into_iter().map(|balance| balance.amount).collect())
+}
+
+#[tokio::test]
+async fn test_store_balance_changes() -> anyhow::Result<()> {
+    let prefix = create_random_db_name();
+    let mut store = setup_store::<BalanceChange>().await?;
+    store.with_namespace(&prefix);
+
+    // The database is shared across runs, so each run uses a new owner
+    let owner = Address::from(Bytes32::from(&TxId::random()));
+    let contract_id = ContractId::from(Bytes32::from(&TxId::random()));
+    let create_tx = MockTransaction::script(
+        vec![],
+        vec![Output::Coin(OutputCoin {
+            to: owner.to_owned(),
+            amount: 100.into(),
+            ..Default::default()
+        })],
+        vec![],
+    );
+    let created = build_packets(&prefix, &create_tx);
+    assert_eq!(created.len(), 1);
+    let subject: Subjects = created[0].subject_payload.clone().try_into()?;
+    assert!(matches!(subject, Subjects::Balances(_)));
+    insert_packets(&store, &created).await?;
+    assert_eq!(balance_of(&store, &owner.to_string()).await?, vec!["100"]);
+
+    // Storing a block again doesn't apply its changes twice
+    insert_packets(&store, &created).await?;
+    assert_eq!(balance_of(&store, &owner.to_string()).await?, vec!["100"]);
+
+    // Spending the coin sends its change back and forwards part of it to
+    // a contract
+    let Input::Coin(input) = MockInput::coin_signed() else {
+        panic!("expected a coin input");
+    };
+    let spend_tx = MockTransaction::script(
+        vec![Input::Coin(InputCoin {
+            owner: owner.to_owned(),
+            amount: 100.into(),
+            asset_id: Default::default(),
+            ..input
+        })],
+        vec![Output::Change(OutputChange {
+            to: owner.to_owned(),
+            amount: 40.into(),
+            ..Default::default()
+        })],
+        vec![Receipt::Call(CallReceipt {
+            to: contract_id.to_owned(),
+            amount: 7.into(),
+            ..Default::default()
+        })],
+    );
+    let spent = build_packets(&prefix, &spend_tx);
+    assert_eq!(spent.len(), 3);
+    insert_packets(&store, &spent).await?;
+    assert_eq!(balance_of(&store, &owner.to_string()).await?, vec!["40"]);
+    assert_eq!(balance_of(&store, &contract_id.to_string()).await?, vec![
+        "7"
+    ]);
+
+    let subject = BalancesSubject::new()
+        .with_owner(Some(owner))
+        .with_kind(Some(BalanceChangeKind::Debit))
+        .dyn_arc();
+    let records = store
+        .find_many_by_subject(&subject, QueryOptions::default())
+        .await?;
+    assert_eq!(records.len(), 1);
+    let change = BalanceChange::from_db_item(&records[0])?;
+    assert_eq!(*change.amount, 100);
+    assert_eq!(change.tx_id, spend_tx.id);
+
+    close_db(&store.db).await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_page_through_transfer() -> anyhow::Result<()> {
+    let prefix = create_random_db_name();
+    let mut store = setup_store::<BalanceChange>().await?;
+    store.with_namespace(&prefix);
+
+    // Both sides of a transfer come from the same receipt, so they only
+    // differ by their fanout index
+    let from = ContractId::from(Bytes32::from(&TxId::random()));
+    let to = ContractId::from(Bytes32::from(&TxId::random()));
+    let tx = MockTransaction::script(vec![], vec![], vec![Receipt::Transfer(
+        TransferReceipt {
+            id: from,
+            to,
+            amount: 5.into(),
+            ..Default::default()
+        },
+    )]);
+    let packets = build_packets(&prefix, &tx);
+    assert_eq!(packets.len(), 2);
+    insert_packets(&store, &packets).await?;
+
+    // Paging one row at a time returns both sides
+    let subject = BalancesSubject::new().with_tx_id(Some(tx.id)).dyn_arc();
+    let mut options = QueryOptions::default().with_limit(1);
+    let mut kinds = vec![];
+    loop {
+        let records = store
+            .find_many_by_subject(&subject, options.clone())
+            .await?;
+        let Some(record) = records.into_iter().next() else {
+            break;
+        };
+        kinds.push(record.kind.to_owned());
+        options.seek_after(record.into());
+    }
+    assert_eq!(kinds, vec!["debit", "credit"]);
+
+    close_db(&store.db).await;
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use pedronauck_streams_core::{
+    subjects::{BundlesSubject, SubjectBuildable},
+    types::TransactionBundle,
+};
+use pedronauck_streams_domains::{
+    bundles::BundleDbItem,
+    MockMsgPayload,
+    Subjects,
+};
+use pedronauck_streams_store::record::{PacketBuilder, QueryOptions, Record};
+use pedronauck_streams_test::{close_db, create_random_db_name, setup_store};
+use pretty_assertions::assert_eq;
+
+#[tokio::test]
+async fn test_store_transaction_bundles() -> anyhow::Result<()> {
+    let prefix = create_random_db_name();
+    let mut store = setup_store::<TransactionBundle>().await?;
+    store.with_namespace(&prefix);
+
+    let msg_payload = MockMsgPayload::build(1, &prefix);
+    let packets = TransactionBundle::build_packets(&msg_payload);
+    assert_eq!(packets.len(), msg_payload.transactions.len());
+
+    for (packet, tx) in packets.iter().zip(msg_payload.transactions.iter()) {
+        let db_item = BundleDbItem::try_from(packet)?;
+        let inserted = store.insert_record(&db_item).await?;
+        assert_eq!(inserted.subject, packet.subject_str());
+        assert_eq!(inserted.tx_id, tx.id.to_string());
+
+        let subject: Subjects = packet.subject_payload.clone().try_into()?;
+        assert!(matches!(subject, Subjects::Bundles(_)));
+
+        let bundle = TransactionBundle::from_db_item(&inserted)?;
+        assert_eq!(bundle.transaction.id, tx.id);
+        assert_eq!(bundle.inputs.len(), tx.inputs.len());
+        assert_eq!(bundle.outputs.len(), tx.outputs.len());
+        assert_eq!(bundle.receipts.len(), tx.receipts.len());
+    }
+
+    let tx = msg_payload.transactions.last().unwrap();
+    let subject = BundlesSubject::new()
+        .with_tx_id(Some(tx.id.clone()))
+        .with_tx_status(Some(tx.status.clone()))
+        .dyn_arc();
+    let records = store
+        .find_many_by_subject(&subject, QueryOptions::default())
+        .await?;
+    assert_eq!(records.len(), 1);
+    let bundle = TransactionBundle::from_db_item(&records[0])?;
+    assert_eq!(bundle.transaction.id, tx.id);
+
+    close_db(&store.db).await;
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use pedronauck_streams_core::{
+    subjects::{CoinsSubject, SubjectBuildable},
+    types::{
+        Address,
+        Bytes32,
+        Coin,
+        CoinStatus,
+        Input,
+        InputCoin,
+        MockInput,
+        MockTransaction,
+        Output,
+        OutputCoin,
+        Transaction,
+        TxId,
+        UtxoId,
+    },
+};
+use pedronauck_streams_domains::{
+    coins::{queryable::CoinsQuery, CoinDbItem},
+    mocks::MockOutput,
+    queryable::Queryable,
+    MockMsgPayload,
+    Subjects,
+};
+use pedronauck_streams_store::record::{
+    PacketBuilder,
+    QueryOptions,
+    RecordPacket,
+};
+use pedronauck_streams_test::{close_db, create_random_db_name, setup_store};
+use pretty_assertions::assert_eq;
+
+fn build_packets(prefix: &str, tx: &Transaction) -> Vec<RecordPacket> {
+    let mut msg_payload =
+        MockMsgPayload::with_transactions(1, vec![tx.to_owned()]).into_inner();
+    msg_payload.namespace = Some(prefix.to_string());
+    Coin::build_packets(&msg_payload)
+}
+
+#[tokio::test]
+async fn test_store_coin_lifecycle() -> anyhow::Result<()> {
+    let prefix = create_random_db_name();
+    let mut store = setup_store::<Coin>().await?;
+    store.with_namespace(&prefix);
+
+    // The database is shared across runs, so each run uses a new owner
+    let owner = Address::from(Bytes32::from(&TxId::random()));
+    let create_tx = MockTransaction::script(
+        vec![],
+        vec![
+            Output::Coin(OutputCoin {
+                to: owner.to_owned(),
+                amount: 100.into(),
+                ..Default::default()
+            }),
+            MockOutput::variable(0),
+        ],
+        vec![],
+    );
+
+    // Only the coin output creates a coin, the variable one holds nothing
+    let created = build_packets(&prefix, &create_tx);
+    assert_eq!(created.len(), 1);
+    let subject: Subjects = created[0].subject_payload.clone().try_into()?;
+    assert!(matches!(subject, Subjects::Coins(_)));
+    let db_item = CoinDbItem::try_from(&created[0])?;
+    let inserted = store.insert_record(&db_item).await?;
+    assert_eq!(inserted.status, CoinStatus::Unspent.to_string());
+    assert_eq!(inserted.output_index, 0);
+    assert_eq!(inserted.spent_in_tx, None);
+
+    let Input::Coin(input) = MockInput::coin_signed() else {
+        panic!("expected a coin input");
+    };
+    let spend_tx = MockTransaction::script(
+        vec![Input::Coin(InputCoin {
+            owner: owner.to_owned(),
+            utxo_id: UtxoId {
+                tx_id: Bytes32::from(&create_tx.id),
+                output_index: 0,
+            },
+            ..input
+        })],
+        vec![],
+        vec![],
+    );
+    let spent = build_packets(&prefix, &spend_tx);
+    assert_eq!(spent.len(), 1);
+    let db_item = CoinDbItem::try_from(&spent[0])?;
+    let inserted = store.insert_record(&db_item).await?;
+    assert_eq!(inserted.utxo_id, CoinDbItem::try_from(&created[0])?.utxo_id);
+    assert_eq!(inserted.status, CoinStatus::Spent.to_string());
+    assert_eq!(inserted.spent_in_tx, Some(spend_tx.id.to_string()));
+    assert_eq!(inserted.spent_at_height, Some(inserted.block_height));
+
+    // Storing the creation again, as when blocks are stored out of order,
+    // keeps the coin spent
+    let db_item = CoinDbItem::try_from(&created[0])?;
+    let kept = store.insert_record(&db_item).await?;
+    assert_eq!(kept.status, CoinStatus::Spent.to_string());
+    assert_eq!(kept.spent_in_tx, Some(spend_tx.id.to_string()));
+
+    let mut query = CoinsQuery {
+        unspent: Some(true),
+        pagination: (None, None, Some(10), None).into(),
+        ..Default::default()
+    };
+    query.set_owner(&owner.to_string());
+    assert!(query.execute(&store.db.pool).await?.is_empty());
+    query.unspent = Some(false);
+    assert_eq!(query.execute(&store.db.pool).await?.len(), 1);
+
+    let subject = CoinsSubject::new()
+        .with_owner(Some(owner))
+        .with_status(Some(CoinStatus::Spent))
+        .dyn_arc();
+    let records = store
+        .find_many_by_subject(&subject, QueryOptions::default())
+        .await?;
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].subject, spent[0].subject_str());
+
+    close_db(&store.db).await;
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use pedronauck_streams_core::{
+    subjects::{ContractsSubject, SubjectBuildable},
+    types::{
+        Bytes32,
+        CallReceipt,
+        ContractActivity,
+        ContractActivityType,
+        ContractId,
+        Input,
+        InputContract,
+        MockTransaction,
+        Receipt,
+        TxId,
+    },
+};
+use pedronauck_streams_domains::{
+    contracts::ContractTransactionDbItem,
+    mocks::MockInput,
+    MockMsgPayload,
+    Subjects,
+};
+use pedronauck_streams_store::record::{
+    PacketBuilder,
+    QueryOptions,
+    Record,
+    RecordPointer,
+};
+use pedronauck_streams_test::{close_db, create_random_db_name, setup_store};
+use pretty_assertions::assert_eq;
+
+#[tokio::test]
+async fn test_store_contract_activity() -> anyhow::Result<()> {
+    let prefix = create_random_db_name();
+    let mut store = setup_store::<ContractActivity>().await?;
+    store.with_namespace(&prefix);
+
+    // The mocks only use the zero contract ID, which is left out
+    let contract_id = Bytes32::from([7u8; 32]);
+    let Input::Contract(input) = MockInput::contract() else {
+        panic!("expected a contract input");
+    };
+    let mut msg_payload = MockMsgPayload::build(1, &prefix);
+    for tx in msg_payload.transactions.iter_mut() {
+        tx.inputs = vec![Input::Contract(InputContract {
+            contract_id: contract_id.to_owned(),
+            ..input.to_owned()
+        })];
+    }
+    let packets = ContractActivity::build_packets(&msg_payload);
+    assert!(!packets.is_empty());
+
+    let contract_id = ContractId::from(&contract_id);
+    for packet in packets.iter() {
+        let db_item = ContractTransactionDbItem::try_from(packet)?;
+        let inserted = store.insert_record(&db_item).await?;
+        assert_eq!(inserted.subject, packet.subject_str());
+
+        let subject: Subjects = packet.subject_payload.clone().try_into()?;
+        assert!(matches!(subject, Subjects::Contracts(_)));
+
+        let activity = ContractActivity::from_db_item(&inserted)?;
+        assert_eq!(activity.contract_id.to_string(), inserted.contract_id);
+    }
+
+    let subject = ContractsSubject::new()
+        .with_contract_id(Some(contract_id.clone()))
+        .dyn_arc();
+    let records = store
+        .find_many_by_subject(&subject, QueryOptions::default())
+        .await?;
+    assert_eq!(records.len(), packets.len());
+    let pointers = records
+        .iter()
+        .map(|record| RecordPointer::from(record.to_owned()))
+        .collect::<Vec<_>>();
+    assert!(pointers.windows(2).all(|pair| pair[0] <= pair[1]));
+
+    // Contract outputs point to the contract input, so they're linked too
+    let mut input_tx_ids = vec![];
+    for (record, pointer) in records.iter().zip(pointers.iter()) {
+        assert_eq!(record.contract_id, contract_id.to_string());
+        let activity_type: ContractActivityType =
+            record.activity_type.parse().unwrap();
+        match activity_type {
+            ContractActivityType::Input => {
+                assert_eq!(pointer.input_index, Some(0));
+                input_tx_ids.push(record.tx_id.to_owned());
+            }
+            ContractActivityType::Output => {
+                assert!(pointer.output_index.is_some())
+            }
+            ContractActivityType::Receipt => {
+                assert!(pointer.receipt_index.is_some())
+            }
+        }
+    }
+    let tx_ids = msg_payload
+        .transactions
+        .iter()
+        .map(|tx| tx.id.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(input_tx_ids, tx_ids);
+
+    close_db(&store.db).await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_page_through_call_contracts() -> anyhow::Result<()> {
+    let prefix = create_random_db_name();
+    let mut store = setup_store::<ContractActivity>().await?;
+    store.with_namespace(&prefix);
+
+    // A call involves both the caller and the callee from the same receipt
+    let tx = MockTransaction::script(vec![], vec![], vec![Receipt::Call(
+        CallReceipt {
+            id: ContractId::from(Bytes32::from(&TxId::random())),
+            to: ContractId::from(Bytes32::from(&TxId::random())),
+            ..Default::default()
+        },
+    )]);
+    let mut msg_payload =
+        MockMsgPayload::with_transactions(1, vec![tx.to_owned()]).into_inner();
+    msg_payload.namespace = Some(prefix.to_string());
+    let packets = ContractActivity::build_packets(&msg_payload);
+    assert_eq!(packets.len(), 2);
+    for packet in packets.iter() {
+        let db_item = ContractTransactionDbItem::try_from(packet)?;
+        store.insert_record(&db_item).await?;
+    }
+
+    // Paging one row at a time returns both contracts
+    let subject = ContractsSubject::new().with_tx_id(Some(tx.id)).dyn_arc();
+    let mut options = QueryOptions::default().with_limit(1);
+    let mut subjects = vec![];
+    loop {
+        let records = store
+            .find_many_by_subject(&subject, options.clone())
+            .await?;
+        let Some(record) = records.into_iter().next() else {
+            break;
+        };
+        subjects.push(record.subject.to_owned());
+        options.seek_after(record.into());
+    }
+    let expected = packets
+        .iter()
+        .map(|packet| packet.subject_str())
+        .collect::<Vec<_>>();
+    assert_eq!(subjects, expected);
+
+    close_db(&store.db).await;
+    Ok(())
+}
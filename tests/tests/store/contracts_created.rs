@@ -0,0 +1,101 @@
+use pedronauck_streams_core::{
+    subjects::{ContractsCreatedSubject, SubjectBuildable},
+    types::{
+        Bytes32,
+        Contract,
+        ContractId,
+        HexData,
+        MockTransaction,
+        Output,
+        OutputContractCreated,
+        Transaction,
+        TransactionStatus,
+        TxId,
+    },
+};
+use pedronauck_streams_domains::{
+    contracts::{queryable::ContractsQuery, ContractDbItem},
+    mocks::{MockInput, MockOutput},
+    queryable::Queryable,
+    MockMsgPayload,
+    Subjects,
+};
+use pedronauck_streams_store::record::{
+    PacketBuilder,
+    QueryOptions,
+    RecordPacket,
+};
+use pedronauck_streams_test::{close_db, create_random_db_name, setup_store};
+use pretty_assertions::assert_eq;
+
+fn build_packets(prefix: &str, tx: &Transaction) -> Vec<RecordPacket> {
+    let mut msg_payload =
+        MockMsgPayload::with_transactions(1, vec![tx.to_owned()]).into_inner();
+    msg_payload.namespace = Some(prefix.to_string());
+    Contract::build_packets(&msg_payload)
+}
+
+#[tokio::test]
+async fn test_store_contract_created() -> anyhow::Result<()> {
+    let prefix = create_random_db_name();
+    let mut store = setup_store::<Contract>().await?;
+    store.with_namespace(&prefix);
+
+    // Random ID, as the database is shared across runs
+    let contract_id = ContractId::from(Bytes32::from(&TxId::random()));
+    let bytecode = vec![0x24, 0x40, 0x00, 0x00];
+    let mut tx = MockTransaction::create(
+        vec![MockInput::coin_signed()],
+        vec![
+            MockOutput::coin(100),
+            Output::ContractCreated(OutputContractCreated {
+                contract_id: contract_id.to_owned(),
+                state_root: Bytes32::default(),
+            }),
+        ],
+        vec![],
+    );
+    tx.bytecode_witness_index = Some(0);
+    tx.witnesses = vec![HexData(bytecode.into())];
+
+    let packets = build_packets(&prefix, &tx);
+    assert_eq!(packets.len(), 1);
+    let subject: Subjects = packets[0].subject_payload.clone().try_into()?;
+    assert!(matches!(subject, Subjects::ContractsCreated(_)));
+    let db_item = ContractDbItem::try_from(&packets[0])?;
+    let inserted = store.insert_record(&db_item).await?;
+    assert_eq!(inserted.contract_id, contract_id.to_string());
+    assert_eq!(inserted.output_index, 1);
+    assert_eq!(inserted.bytecode_size, 4);
+
+    // Storing the deployment again, as when blocks are reindexed, keeps a
+    // single row for the contract
+    let reinserted = store.insert_record(&db_item).await?;
+    assert_eq!(reinserted.contract_id, inserted.contract_id);
+
+    let query = ContractsQuery {
+        contract_id: Some(contract_id.to_owned()),
+        ..Default::default()
+    };
+    let found = query.execute(&store.db.pool).await?;
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].bytecode_root, inserted.bytecode_root);
+
+    let subject = ContractsCreatedSubject::new()
+        .with_contract_id(Some(contract_id))
+        .dyn_arc();
+    let records = store
+        .find_many_by_subject(&subject, QueryOptions::default())
+        .await?;
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].subject, packets[0].subject_str());
+
+    // Failed deployments don't create contracts
+    let mut failed_tx = tx.to_owned();
+    failed_tx.id = TxId::random();
+    failed_tx.status = TransactionStatus::Failed;
+    assert!(build_packets(&prefix, &failed_tx).is_empty());
+
+    close_db(&store.db).await;
+    Ok(())
+}
@@ -0,0 +1,199 @@
+use pedronauck_streams_core::{
+    subjects::{MessagesSubject, SubjectBuildable},
+    types::{
+        Address,
+        Bytes32,
+        HexData,
+        Input,
+        InputMessage,
+        Message,
+        MessageDirection,
+        MessageOutReceipt,
+        MessageStatus,
+        MockTransaction,
+        Nonce,
+        Receipt,
+        Transaction,
+        TransactionStatus,
+        TxId,
+    },
+};
+use pedronauck_streams_domains::{
+    messages::{queryable::MessagesQuery, MessageDbItem},
+    queryable::Queryable,
+    MockMsgPayload,
+    Subjects,
+};
+use pedronauck_streams_store::record::{
+    PacketBuilder,
+    QueryOptions,
+    RecordPacket,
+};
+use pedronauck_streams_test::{close_db, create_random_db_name, setup_store};
+use pretty_assertions::assert_eq;
+
+fn build_packets(prefix: &str, tx: &Transaction) -> Vec<RecordPacket> {
+    build_packets_at(prefix, 1, tx)
+}
+
+fn build_packets_at(
+    prefix: &str,
+    height: u32,
+    tx: &Transaction,
+) -> Vec<RecordPacket> {
+    let mut msg_payload =
+        MockMsgPayload::with_transactions(height, vec![tx.to_owned()])
+            .into_inner();
+    msg_payload.namespace = Some(prefix.to_string());
+    Message::build_packets(&msg_payload)
+}
+
+/// Random nonce, as the database is shared across runs
+fn random_nonce() -> Nonce {
+    Nonce::from(Bytes32::from(&TxId::random()))
+}
+
+#[tokio::test]
+async fn test_store_message_to_l1() -> anyhow::Result<()> {
+    let prefix = create_random_db_name();
+    let mut store = setup_store::<Message>().await?;
+    store.with_namespace(&prefix);
+
+    let sender = Address::from(Bytes32::from(&TxId::random()));
+    let tx =
+        MockTransaction::script(vec![], vec![], vec![Receipt::MessageOut(
+            MessageOutReceipt {
+                sender: sender.to_owned(),
+                recipient: Address::default(),
+                amount: 100.into(),
+                nonce: random_nonce(),
+                len: 0.into(),
+                digest: Bytes32::default(),
+                data: None,
+            },
+        )]);
+    let packets = build_packets(&prefix, &tx);
+    assert_eq!(packets.len(), 1);
+    let subject: Subjects = packets[0].subject_payload.clone().try_into()?;
+    assert!(matches!(subject, Subjects::Messages(_)));
+    let db_item = MessageDbItem::try_from(&packets[0])?;
+    let inserted = store.insert_record(&db_item).await?;
+    assert_eq!(inserted.direction, MessageDirection::L2ToL1.to_string());
+    assert_eq!(inserted.status, MessageStatus::Sent.to_string());
+    assert_eq!(inserted.receipt_index, 0);
+    assert_eq!(inserted.input_index, -1);
+
+    let query = MessagesQuery {
+        sender: Some(sender.to_owned()),
+        direction: Some(MessageDirection::L2ToL1),
+        pagination: (None, None, Some(10), None).into(),
+        ..Default::default()
+    };
+    let found = query.execute(&store.db.pool).await?;
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].message_id, inserted.message_id);
+
+    // Failed transactions don't send their messages
+    let mut failed_tx = tx.to_owned();
+    failed_tx.id = TxId::random();
+    failed_tx.status = TransactionStatus::Failed;
+    assert!(build_packets(&prefix, &failed_tx).is_empty());
+
+    close_db(&store.db).await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_store_message_from_l1() -> anyhow::Result<()> {
+    let prefix = create_random_db_name();
+    let mut store = setup_store::<Message>().await?;
+    store.with_namespace(&prefix);
+
+    let recipient = Address::from(Bytes32::from(&TxId::random()));
+    let input = Input::Message(InputMessage {
+        amount: 100.into(),
+        data: HexData(vec![1, 2, 3].into()),
+        nonce: random_nonce(),
+        recipient: recipient.to_owned(),
+        ..Default::default()
+    });
+    let mut retry_tx =
+        MockTransaction::script(vec![input.to_owned()], vec![], vec![]);
+    retry_tx.status = TransactionStatus::Failed;
+    let spend_tx = MockTransaction::script(vec![input], vec![], vec![]);
+
+    // A message with data used by a failed transaction can be retried
+    let retried = build_packets_at(&prefix, 2, &retry_tx);
+    assert_eq!(retried.len(), 1);
+    let db_item = MessageDbItem::try_from(&retried[0])?;
+    let inserted = store.insert_record(&db_item).await?;
+    assert_eq!(inserted.direction, MessageDirection::L1ToL2.to_string());
+    assert_eq!(inserted.status, MessageStatus::Relayed.to_string());
+    assert_eq!(inserted.input_index, 0);
+
+    // Spending it updates its state, while its position stays where it was
+    // first seen
+    let spent = build_packets_at(&prefix, 3, &spend_tx);
+    let db_item = MessageDbItem::try_from(&spent[0])?;
+    let updated = store.insert_record(&db_item).await?;
+    assert_eq!(updated.status, MessageStatus::Spent.to_string());
+    assert_eq!(updated.tx_id, spend_tx.id.to_string());
+    assert_eq!(updated.block_height, 2);
+    assert_eq!(updated.created_at, inserted.created_at);
+
+    // Storing the failed use again, as when blocks are stored out of order,
+    // keeps the message spent
+    let db_item = MessageDbItem::try_from(&retried[0])?;
+    let kept = store.insert_record(&db_item).await?;
+    assert_eq!(kept.status, MessageStatus::Spent.to_string());
+    assert_eq!(kept.tx_id, spend_tx.id.to_string());
+
+    let subject = MessagesSubject::new()
+        .with_recipient(Some(recipient))
+        .with_status(Some(MessageStatus::Spent))
+        .dyn_arc();
+    let records = store
+        .find_many_by_subject(&subject, QueryOptions::default())
+        .await?;
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].subject, spent[0].subject_str());
+
+    close_db(&store.db).await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_store_message_keeps_newest_use() -> anyhow::Result<()> {
+    let prefix = create_random_db_name();
+    let mut store = setup_store::<Message>().await?;
+    store.with_namespace(&prefix);
+
+    let input = Input::Message(InputMessage {
+        amount: 100.into(),
+        data: HexData(vec![1, 2, 3].into()),
+        nonce: random_nonce(),
+        ..Default::default()
+    });
+    let mut older_tx =
+        MockTransaction::script(vec![input.to_owned()], vec![], vec![]);
+    older_tx.status = TransactionStatus::Failed;
+    let mut newer_tx = MockTransaction::script(vec![input], vec![], vec![]);
+    newer_tx.status = TransactionStatus::Failed;
+
+    let newer = build_packets_at(&prefix, 5, &newer_tx);
+    let db_item = MessageDbItem::try_from(&newer[0])?;
+    let inserted = store.insert_record(&db_item).await?;
+    assert_eq!(inserted.tx_id, newer_tx.id.to_string());
+
+    // An older failed use stored late doesn't overwrite the newer one
+    let older = build_packets_at(&prefix, 4, &older_tx);
+    let db_item = MessageDbItem::try_from(&older[0])?;
+    let kept = store.insert_record(&db_item).await?;
+    assert_eq!(kept.status, MessageStatus::Relayed.to_string());
+    assert_eq!(kept.tx_id, newer_tx.id.to_string());
+    assert_eq!(kept.subject, newer[0].subject_str());
+    assert_eq!(kept.block_height, 5);
+
+    close_db(&store.db).await;
+    Ok(())
+}
@@ -1,7 +1,16 @@
+mod abis;
+mod accounts;
+mod balances;
 mod blocks;
+mod bundles;
+mod coins;
+mod contracts;
+mod contracts_created;
 mod inputs;
+mod messages;
 mod outputs;
 mod pattern_matching;
+mod payload_filter;
 mod query_builder;
 mod receipts;
 mod record;
@@ -0,0 +1,113 @@
+use pedronauck_streams_store::record::{
+    FilterColumn,
+    FilterOp,
+    FilterSqlCondition,
+    FilterSqlValue,
+    FilterValue,
+    PayloadFilter,
+    PayloadFilterError,
+};
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+#[test]
+fn test_payload_filter_parses_predicates() {
+    let filter: PayloadFilter =
+        r#"amount > 1000 and assetId in ['0x01', 0x02] && status == "failed""#
+            .parse()
+            .unwrap();
+    let predicates = filter.predicates();
+
+    assert_eq!(predicates.len(), 3);
+    assert_eq!(predicates[0].field, "amount");
+    assert_eq!(predicates[0].op, FilterOp::Gt);
+    assert_eq!(predicates[0].values, vec![FilterValue::Number(1000)]);
+    assert_eq!(predicates[1].op, FilterOp::In);
+    assert_eq!(predicates[1].values, vec![
+        FilterValue::Text("0x01".to_string()),
+        FilterValue::Text("0x02".to_string()),
+    ]);
+    assert_eq!(predicates[2].values, vec![FilterValue::Text(
+        "failed".to_string()
+    )]);
+    assert_eq!(
+        filter.to_string(),
+        r#"amount > 1000 && assetId in ["0x01", "0x02"] && status == "failed""#
+    );
+}
+
+#[test]
+fn test_payload_filter_rejects_invalid_expressions() {
+    let cases = [
+        ("", PayloadFilterError::Empty),
+        ("amount >", PayloadFilterError::UnexpectedEnd("a value")),
+        ("status == 'failed", PayloadFilterError::UnterminatedString),
+        ("amount 1000", PayloadFilterError::Unexpected {
+            found: "1000".to_string(),
+            expected: "an operator",
+        }),
+        ("assetId in [0x01", PayloadFilterError::UnexpectedEnd("']'")),
+    ];
+    for (expression, expected) in cases {
+        let err = expression.parse::<PayloadFilter>().unwrap_err();
+        assert_eq!(err, expected, "expression: {expression}");
+    }
+}
+
+#[test]
+fn test_payload_filter_matches_payload() {
+    let payload = json!({
+        "type": "Coin",
+        "amount": "1500",
+        "assetId": "0xabcd",
+        "isCreate": false,
+        "predicate": null,
+        "txPointer": { "blockHeight": 12, "txIndex": 0 },
+    });
+    let cases = [
+        ("amount > 1000", true),
+        ("amount >= 1500 && amount <= 1500", true),
+        ("amount < 1000", false),
+        ("assetId == '0xABCD'", true),
+        ("assetId in [0x01, 0xabcd]", true),
+        ("assetId != 0xabcd", false),
+        ("txPointer.blockHeight == 12", true),
+        ("isCreate == false", true),
+        ("owner == 0x01", false),
+        ("predicate != 0x01", false),
+    ];
+    for (expression, expected) in cases {
+        let filter: PayloadFilter = expression.parse().unwrap();
+        assert_eq!(
+            filter.matches(&payload),
+            expected,
+            "expression: {expression}"
+        );
+    }
+}
+
+#[test]
+fn test_payload_filter_sql_conditions() {
+    let columns = [
+        FilterColumn::int("height", "block_height"),
+        FilterColumn::text("producer", "producer_address"),
+    ];
+    let filter: PayloadFilter =
+        "height >= 10 && producer == 0xAB && producer > 0x01 && version == 1"
+            .parse()
+            .unwrap();
+
+    // Text columns only take equality checks and unknown fields are skipped
+    assert_eq!(filter.sql_conditions(&columns), vec![
+        FilterSqlCondition {
+            column: "block_height",
+            op: FilterOp::Gte,
+            values: vec![FilterSqlValue::Int(10)],
+        },
+        FilterSqlCondition {
+            column: "producer_address",
+            op: FilterOp::Eq,
+            values: vec![FilterSqlValue::Text("0xab".to_string())],
+        },
+    ]);
+}
@@ -3,10 +3,16 @@ use std::sync::Arc;
 use pedronauck_streams_core::{
     inputs::InputsCoinSubject,
     subjects::*,
-    types::{Block, Input},
+    types::{AccountActivity, Block, Input},
 };
 use pedronauck_streams_domains::blocks::subjects::BlocksSubject;
-use pedronauck_streams_store::record::{QueryOptions, Record};
+use pedronauck_streams_store::record::{
+    PayloadFilter,
+    QueryOptions,
+    Record,
+    RecordPointer,
+};
+use pedronauck_streams_subject::subject::{SqlCondition, SqlValue};
 use pedronauck_streams_types::{Address, TxId};
 use pretty_assertions::assert_eq;
 
@@ -19,40 +25,40 @@ fn test_query_builder_simple() {
     );
 
     let options = QueryOptions {
-        offset: 0,
         limit: 10,
         from_block: None,
+        to_block: None,
+        after_pointer: None,
         namespace: Some("test_ns".to_string()),
         distinct: false,
+        filter: None,
+        ..Default::default()
     };
 
     let sql_statement = subject.to_sql_select();
-    let sql_where = subject.to_sql_where();
+    let sql_conditions = subject.to_sql_conditions();
     assert_eq!(
         sql_statement,
         Some("producer_address, block_height".to_string())
     );
-    assert_eq!(
-        sql_where,
-        Some(format!(
-            "producer_address = '{}' AND block_height = '50'",
-            Address::default()
-        ))
-    );
+    assert_eq!(sql_conditions, vec![
+        SqlCondition::Eq(
+            "producer_address",
+            SqlValue::Text(Address::default().to_string())
+        ),
+        SqlCondition::Eq("block_height", SqlValue::Int(50)),
+    ]);
 
     let query = Block::build_find_many_query(subject, options);
     let sql = query.sql();
 
     assert_eq!(
         sql,
-        format!(
-            "SELECT * FROM blocks \
-            WHERE producer_address = '{}' AND block_height = '50' \
-            AND subject LIKE 'test_ns%' \
-            ORDER BY block_height ASC \
-            LIMIT $1 OFFSET $2",
-            Address::default()
-        )
+        "SELECT * FROM blocks \
+        WHERE producer_address = $1 AND block_height = $2 \
+        AND subject LIKE $3 \
+        ORDER BY block_height ASC \
+        LIMIT $4"
     );
 }
 
@@ -63,6 +69,22 @@ fn test_query_builder_with_no_subject_fields() {
     let query = Block::build_find_many_query(subject, options);
     let sql = query.sql();
 
+    assert_eq!(
+        sql,
+        "SELECT * FROM blocks \
+        ORDER BY block_height ASC \
+        LIMIT $1"
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_query_builder_with_deprecated_offset() {
+    let subject = Arc::new(BlocksSubject::new());
+    let options = QueryOptions::default().with_offset(20);
+    let query = Block::build_find_many_query(subject, options);
+    let sql = query.sql();
+
     assert_eq!(
         sql,
         "SELECT * FROM blocks \
@@ -84,11 +106,14 @@ fn test_query_builder_with_complex_ordering() {
     });
 
     let options = QueryOptions {
-        offset: 0,
         limit: 20,
         from_block: Some(50.into()),
+        to_block: None,
+        after_pointer: None,
         namespace: Some("test_ns".to_string()),
         distinct: false,
+        filter: None,
+        ..Default::default()
     };
 
     let query = Input::build_find_many_query(subject, options);
@@ -96,20 +121,123 @@ fn test_query_builder_with_complex_ordering() {
 
     assert_eq!(
         sql,
-        format!(
-            "WITH items AS (\
-            SELECT * FROM inputs \
-            WHERE tx_id = '{}' \
-            AND owner_id = '{}' \
-            AND input_type = 'coin' \
-            AND block_height >= 50 \
-            AND subject LIKE 'test_ns%' \
-            ORDER BY block_height ASC \
-            LIMIT $1 OFFSET $2) \
-            SELECT * FROM items \
-            ORDER BY tx_index, input_index ASC",
-            TxId::default(),
-            Address::default(),
-        )
+        "SELECT * FROM inputs \
+        WHERE tx_id = $1 \
+        AND owner_id = $2 \
+        AND input_type = 'coin' \
+        AND block_height >= $3 \
+        AND subject LIKE $4 \
+        ORDER BY block_height, tx_index, input_index ASC \
+        LIMIT $5"
+    );
+}
+
+#[test]
+fn test_query_builder_binds_subject_params() {
+    let subject =
+        Arc::new(BlocksSubject::new().with_producer(Some(Address::default())));
+    let query = Block::build_find_many_query(subject, QueryOptions::default());
+    let sql = query.sql();
+
+    assert!(!sql.contains(&Address::default().to_string()));
+    assert_eq!(
+        sql,
+        "SELECT * FROM blocks \
+        WHERE producer_address = $1 \
+        ORDER BY block_height ASC \
+        LIMIT $2"
+    );
+}
+
+#[test]
+fn test_query_builder_with_block_range() {
+    let subject = Arc::new(BlocksSubject::new());
+    let options = QueryOptions::default()
+        .with_from_block(Some(10.into()))
+        .with_to_block(Some(20.into()));
+    let query = Block::build_find_many_query(subject, options);
+    let sql = query.sql();
+
+    assert_eq!(
+        sql,
+        "SELECT * FROM blocks \
+        WHERE block_height >= $1 AND block_height <= $2 \
+        ORDER BY block_height ASC \
+        LIMIT $3"
+    );
+}
+
+#[test]
+fn test_query_builder_after_pointer() {
+    let subject = Arc::new(InputsCoinSubject::new());
+    let pointer = RecordPointer {
+        block_height: 120.into(),
+        tx_index: Some(3),
+        input_index: Some(7),
+        ..Default::default()
+    };
+    let options = QueryOptions::default()
+        .with_from_block(Some(pointer.block_height))
+        .with_after_pointer(Some(pointer));
+    let query = Input::build_find_many_query(subject, options);
+    let sql = query.sql();
+
+    assert_eq!(
+        sql,
+        "SELECT * FROM inputs \
+        WHERE input_type = 'coin' \
+        AND block_height >= $1 \
+        AND (block_height, tx_index, input_index) > ($2, $3, $4) \
+        ORDER BY block_height, tx_index, input_index ASC \
+        LIMIT $5"
+    );
+}
+
+#[test]
+fn test_query_builder_after_pointer_with_unset_indexes() {
+    let subject =
+        Arc::new(AccountsSubject::new().with_address(Some(Address::default())));
+    let pointer = RecordPointer {
+        block_height: 120.into(),
+        tx_index: Some(3),
+        output_index: Some(2),
+        ..Default::default()
+    };
+    let options = QueryOptions::default().with_after_pointer(Some(pointer));
+    let query = AccountActivity::build_find_many_query(subject, options);
+    let sql = query.sql();
+
+    // input_index isn't set, so it's compared against the -1 stored for
+    // records without an input index
+    assert_eq!(
+        sql,
+        "SELECT * FROM accounts \
+        WHERE address = $1 \
+        AND (block_height, tx_index, input_index, output_index) > ($2, $3, $4, $5) \
+        ORDER BY block_height, tx_index, input_index, output_index, receipt_index, fanout_index ASC \
+        LIMIT $6"
+    );
+}
+
+#[test]
+fn test_query_builder_pushes_down_payload_filter() {
+    let subject = Arc::new(InputsCoinSubject::new());
+    let filter: PayloadFilter =
+        r#"assetId in ["0xAA", "0xbb"] && owner != "0x01" && amount > 1000"#
+            .parse()
+            .unwrap();
+    let options = QueryOptions::default().with_filter(Some(Arc::new(filter)));
+    let query = Input::build_find_many_query(subject, options);
+    let sql = query.sql();
+
+    // amount has no column, so it's only evaluated on the decoded payload
+    assert_eq!(
+        sql,
+        "SELECT * FROM inputs \
+        WHERE input_type = 'coin' \
+        AND asset_id IN ($1, $2) \
+        AND owner_id <> $3 \
+        ORDER BY block_height, tx_index, input_index ASC \
+        LIMIT $4"
     );
 }
@@ -1,6 +1,9 @@
-use pedronauck_streams_core::types::{MockReceipt, Receipt, Transaction};
+use pedronauck_streams_core::{
+    subjects::{ReceiptsPanicSubject, ReceiptsRevertSubject, SubjectBuildable},
+    types::{MockReceipt, Receipt, RevertReceipt, RevertSignal, Transaction},
+};
 use pedronauck_streams_domains::{
-    receipts::{DynReceiptSubject, ReceiptDbItem},
+    receipts::{CallResult, CallTrace, DynReceiptSubject, ReceiptDbItem},
     transactions::types::MockTransaction,
     MockMsgPayload,
     Subjects,
@@ -434,3 +437,98 @@ async fn test_receipt_subject_to_db_item_conversion() -> anyhow::Result<()> {
     close_db(&store.db).await;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_transaction_call_trace() -> anyhow::Result<()> {
+    let prefix = create_random_db_name();
+    let mut store = setup_store::<Receipt>().await?;
+    store.with_namespace(&prefix);
+
+    let receipts = vec![
+        MockReceipt::call(),
+        MockReceipt::log(),
+        MockReceipt::call(),
+        MockReceipt::return_receipt(),
+        MockReceipt::revert(),
+        MockReceipt::script_result(),
+    ];
+    let (tx, tx_id) = create_tx(receipts);
+    assert_eq!(CallTrace::find(&store.db.pool, &tx_id).await?, None);
+
+    // Stored out of order, as receipts of a transaction may be
+    for packet in create_packets(&tx, &tx_id, &prefix).iter().rev() {
+        let db_item = ReceiptDbItem::try_from(packet)?;
+        store.insert_record(&db_item).await?;
+    }
+
+    let trace = CallTrace::find(&store.db.pool, &tx_id)
+        .await?
+        .expect("trace should be found");
+    assert_eq!(trace.tx_id, tx_id);
+    assert_eq!(trace.result, None);
+    assert_eq!(trace.failing_frame, Some(0));
+    assert_eq!(trace.calls.len(), 1);
+    let outer = &trace.calls[0];
+    assert!(matches!(outer.result, CallResult::Revert { .. }));
+    assert_eq!(outer.calls.len(), 1);
+    assert_eq!(outer.calls[0].receipt_index, 2);
+    assert!(matches!(outer.calls[0].result, CallResult::Return { .. }));
+
+    close_db(&store.db).await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_find_receipts_by_failure_reason() -> anyhow::Result<()> {
+    let prefix = create_random_db_name();
+    let mut store = setup_store::<Receipt>().await?;
+    store.with_namespace(&prefix);
+
+    let receipts = vec![
+        MockReceipt::panic(),
+        Receipt::Revert(RevertReceipt {
+            ra: 0xffff_ffff_ffff_0000.into(),
+            ..Default::default()
+        }),
+    ];
+    let (tx, tx_id) = create_tx(receipts);
+    for packet in create_packets(&tx, &tx_id, &prefix) {
+        let db_item = ReceiptDbItem::try_from(&packet)?;
+        store.insert_record(&db_item).await?;
+    }
+
+    let subject = ReceiptsRevertSubject::new()
+        .with_tx_id(Some(tx_id.to_owned()))
+        .with_signal(Some(RevertSignal::FailedRequire))
+        .dyn_arc();
+    let records = store
+        .find_many_by_subject(&subject, QueryOptions::default())
+        .await?;
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].revert_signal, Some("failed_require".to_string()));
+
+    let subject = ReceiptsRevertSubject::new()
+        .with_tx_id(Some(tx_id.to_owned()))
+        .with_signal(Some(RevertSignal::Custom))
+        .dyn_arc();
+    let records = store
+        .find_many_by_subject(&subject, QueryOptions::default())
+        .await?;
+    assert!(records.is_empty());
+
+    let subject = ReceiptsPanicSubject::new()
+        .with_tx_id(Some(tx_id.to_owned()))
+        .with_reason(Some("UnknownPanicReason".to_string()))
+        .dyn_arc();
+    let records = store
+        .find_many_by_subject(&subject, QueryOptions::default())
+        .await?;
+    assert_eq!(records.len(), 1);
+    assert_eq!(
+        records[0].panic_reason,
+        Some("UnknownPanicReason".to_string())
+    );
+
+    close_db(&store.db).await;
+    Ok(())
+}
@@ -1,14 +1,21 @@
 use std::{sync::Arc, time::Duration};
 
+use futures::StreamExt;
+use pedronauck_message_broker::{MessageBroker, NatsMessageBroker};
 use pedronauck_streams_core::{
     server::DeliverPolicy,
     subjects::*,
-    types::{Block, MockBlock},
+    types::{Block, Input, MockBlock, MockInput, MockTransaction},
     Stream,
     StreamError,
 };
-use pedronauck_streams_domains::MockMsgPayload;
-use pedronauck_streams_store::record::{Record, RecordPacket};
+use pedronauck_streams_domains::{inputs::InputDbItem, MockMsgPayload};
+use pedronauck_streams_store::record::{
+    PacketBuilder,
+    Record,
+    RecordPacket,
+    STORE_PAGINATION_LIMIT,
+};
 use pedronauck_streams_test::{
     close_db,
     create_multiple_records,
@@ -18,7 +25,6 @@ use pedronauck_streams_test::{
 };
 use pedronauck_streams_types::BlockHeight;
 use pedronauck_web_utils::api_key::{ApiKeyError, MockApiKeyRole};
-use futures::StreamExt;
 use pretty_assertions::assert_eq;
 use tokio::time::sleep;
 
@@ -238,3 +244,57 @@ async fn test_streaming_historical_with_no_limit() -> anyhow::Result<()> {
     close_db(&stream.store().db).await;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_streaming_historical_tip_block_past_page_limit(
+) -> anyhow::Result<()> {
+    let prefix = create_random_db_name();
+    let block_stream = setup_stream(NATS_URL, &prefix).await?;
+    let db = block_stream.store().db.clone();
+    let broker: Arc<dyn MessageBroker> =
+        NatsMessageBroker::setup(NATS_URL, Some(&prefix)).await?;
+    let stream = Stream::<Input>::with_namespace(&broker, &db, prefix.clone());
+
+    // The last block holds more inputs than a page. Its height is above the
+    // ones of the other tests, so it is the last block stored.
+    let height = BlockHeight::from(10_000_000);
+    insert_custom_block(&block_stream, &prefix, height).await?;
+    let input_count = *STORE_PAGINATION_LIMIT + 10;
+    let tx = MockTransaction::script(
+        vec![MockInput::coin_signed(); input_count],
+        vec![],
+        vec![],
+    );
+    let mut msg_payload =
+        MockMsgPayload::with_transactions(height.into(), vec![tx]).into_inner();
+    msg_payload.namespace = Some(prefix.to_string());
+    let packets = Input::build_packets(&msg_payload);
+    assert_eq!(packets.len(), input_count);
+    for packet in packets.iter() {
+        let db_item = InputDbItem::try_from(packet)?;
+        stream.store().insert_record(&db_item).await?;
+    }
+
+    let role = MockApiKeyRole::admin().into_inner();
+    let mut subscriber = stream
+        .subscribe(
+            InputsCoinSubject::new(),
+            DeliverPolicy::FromBlock {
+                block_height: height,
+            },
+            &role,
+        )
+        .await;
+
+    // Every input is replayed, not only the ones of the first page
+    for _ in 0..input_count {
+        let record =
+            tokio::time::timeout(Duration::from_secs(5), subscriber.next())
+                .await?
+                .expect("expected a replayed input")?;
+        assert_eq!(record.pointer.block_height, height);
+    }
+
+    close_db(&db).await;
+    Ok(())
+}
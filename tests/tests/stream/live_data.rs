@@ -10,8 +10,10 @@ use pedronauck_streams_test::{
     close_db,
     create_multiple_records,
     create_random_db_name,
+    insert_records,
     setup_stream,
 };
+use pedronauck_streams_types::BlockHeight;
 use pedronauck_web_utils::api_key::{ApiKeyError, MockApiKeyRole};
 use futures::StreamExt;
 use pretty_assertions::assert_eq;
@@ -58,6 +60,45 @@ async fn test_streaming_live_data() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_streaming_historical_to_live_handoff() -> anyhow::Result<()> {
+    let prefix = create_random_db_name();
+    let stream = setup_stream(NATS_URL, &prefix).await?;
+    let data = create_multiple_records(10, 0, &prefix);
+
+    // Blocks 0..5 are already stored, 3..10 are published live so the
+    // boundary blocks are seen by both the replay and the live subscription
+    insert_records(&stream.store(), &prefix, &data[..5]).await?;
+    let subject = BlocksSubject::new().with_height(None);
+    let role = MockApiKeyRole::admin().into_inner();
+    let deliver_policy = DeliverPolicy::FromBlock {
+        block_height: BlockHeight::from(0),
+    };
+    let mut subscriber = stream.subscribe(subject, deliver_policy, &role).await;
+
+    for record in &data[3..] {
+        let packet = record.2.to_owned().with_namespace(&prefix);
+        let subject = packet.subject_str();
+        let response = StreamResponse::try_from(&packet)?;
+        stream.publish(&subject, &Arc::new(response)).await?;
+    }
+
+    let mut received = vec![];
+    while let Some(record) = subscriber.next().await {
+        let block = record?.payload.as_block()?;
+        received.push(block.height.into_inner());
+        if received.len() == data.len() {
+            break;
+        }
+    }
+
+    let expected = (0..data.len() as u64).collect::<Vec<_>>();
+    assert_eq!(received, expected);
+
+    close_db(&stream.store().db).await;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_streaming_live_data_without_proper_role() -> anyhow::Result<()> {
     let prefix = create_random_db_name();
@@ -157,3 +157,30 @@ async fn test_validate_historical_days_limit() -> anyhow::Result<()> {
     close_db(&db).await;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_role_stream_throughput() {
+    let db = setup_test_db().await;
+    let pool = db.pool_ref();
+
+    let builder_role =
+        ApiKeyRole::fetch_by_name(pool, &ApiKeyRoleName::Builder)
+            .await
+            .expect("Failed to fetch builder role");
+    assert_eq!(
+        builder_role.stream_msgs_per_sec(),
+        Some(MessagesPerSecond::from(50))
+    );
+    assert_eq!(
+        builder_role.stream_bytes_per_sec(),
+        Some(BytesPerSecond::from(1_048_576))
+    );
+
+    // Admin keys backfilling history are never throttled
+    let admin_role = ApiKeyRole::fetch_by_name(pool, &ApiKeyRoleName::Admin)
+        .await
+        .expect("Failed to fetch admin role");
+    assert_eq!(admin_role.stream_msgs_per_sec(), None);
+    assert_eq!(admin_role.stream_bytes_per_sec(), None);
+    close_db(&db).await;
+}